@@ -68,6 +68,7 @@
               just --set prefix "$out" \
                 --set bin-src "target/release/dev-heppen-webapps" \
                 --set webview-src "target/release/dev-heppen-webapps-webview" \
+                --set extension-src "target/release/libwebapps_web_extension.so" \
                 install
 
               runHook postInstall
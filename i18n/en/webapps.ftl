@@ -7,6 +7,22 @@ delete=Delete
 yes=Yes
 no=No
 confirm-delete=Are you sure you want to delete { $app }?
+confirm-delete-apps=Are you sure you want to delete { $count ->
+    [one] 1 app
+   *[other] { $count } apps
+}?
+delete-removes=The menu entry, settings and usage statistics are removed.
+delete-profile=Also delete website data, like logins and cookies ({ $size })
+delete-icons=Also delete the icon files
+unsaved-changes=Unsaved Changes
+unsaved-changes-body=The changes to this app haven't been saved and will be lost.
+discard-changes=Discard
+keep-editing=Keep Editing
+duplicate-apps=Already Installed?
+duplicate-apps-body=These apps are for the same site or have the same name. Open one of them instead, or create the new app anyway.
+create-anyway=Create Anyway
+undo=Undo
+redo=Redo
 cancel=Cancel
 downloader-canceled=Installing stopped.
 help=Help
@@ -16,8 +32,55 @@ support-body=If you find this app useful, please consider to support author, by
 settings=Settings
 import-theme=Import theme
 imported-themes=Imported themes
+grid-density=Size of apps in the grid
+grid-density-compact=Compact
+grid-density-comfortable=Comfortable
+grid-density-large=Large
 run-app=Run app
 reset-settings=Reset settings
+warm-pool=Keep a renderer ready for faster launches
+favicon-refresh=Refresh favicons of installed apps
+favicon-refresh-off=Never
+favicon-refresh-launch=On every launch
+favicon-refresh-weekly=Weekly
+refresh-now=Refresh now
+favicon-changes=Updated favicons
+pinned-apps=Pinned
+recent-apps=Recent
+pin-app=Pin
+unpin-app=Unpin
+launch-pinned=Launch { $name }
+app-groups=Groups
+new-group-placeholder=New group, e.g. Work
+add-group=Add
+no-group=No group
+group-header={ $name } ({ $count })
+group-apps={ $count ->
+    [one] 1 app
+   *[other] { $count } apps
+}
+custom-categories=Your categories
+new-category-placeholder=New category, shown in menus under
+add-category=Add
+move-apps=Move all apps from
+move-apps-to=to
+move=Move
+toast-apps-moved={ $count ->
+    [one] Moved 1 app
+   *[other] Moved { $count } apps
+}
+refetch-all-icons=Download the icons of all apps again
+refetch=Download
+refetch-icons=Downloading icons
+refetch-progress={ $done } of { $total } apps done
+refetch-finished={ $total ->
+    [one] Finished 1 app
+   *[other] Finished { $total } apps
+}
+refetch-updated=New icon
+refetch-unchanged=Unchanged
+refetch-no-icon=No icon found
+refetch-failed=Failed: { $error }
 reset=Reset
 
 # header
@@ -28,11 +91,45 @@ new-app=Create new
 edit=Edit
 close=Close
 create-new-webapp=Create new Web App
+wizard-offer=New to web apps? Set one up step by step.
+wizard-start=Guided Setup
+wizard-step=Step { $number } of { $count }
+wizard-url=Which site should become an app?
+wizard-url-description=Enter the address of the site, as you would open it in a browser.
+wizard-details=Name and Icon
+wizard-details-description=The name and icon were taken from the site. Change them if you like, and choose where the app appears in the app library.
+wizard-icon-missing=No icon was found on the site. Choose one to continue.
+wizard-presets=Window and Privacy
+wizard-window=Window
+wizard-privacy=Privacy
+wizard-confirm=Ready to Create
+wizard-next=Next
+wizard-back=Back
+wizard-create=Create App
+wizard-full-form=Use the Full Form
+window-preset-standard=Standard
+window-preset-standard-description=A regular window that opens at the usual size.
+window-preset-compact=Compact
+window-preset-compact-description=A narrow window to keep beside others, like a chat or music app.
+window-preset-maximized=Maximized
+window-preset-maximized-description=Fills the screen, for apps you work in all day.
+privacy-preset-standard=Standard
+privacy-preset-standard-description=Stays signed in between launches, like a browser tab.
+privacy-preset-private=Private
+privacy-preset-private-description=Forgets everything, including sign-ins, when closed.
+privacy-preset-strict=Strict
+privacy-preset-strict-description=Stays signed in, and blocks trackers, third-party cookies and fingerprinting.
 icon-selector=Icon selector
 icon-installer=Papirus Icons Installer
 
 # common.rs
 select-category=Select Category
+tags=Tags
+tags-placeholder=Add a tag
+app-id=App ID
+app-id-description=Names the app's desktop file and window class. Website data and usage statistics move along. Save other changes first.
+app-id-taken=Another app has this ID
+rename=Rename
 select-browser=Select Browser
 
 # home_screen.rs
@@ -60,13 +157,47 @@ new-webapp-title=New Quick Web App
 title=Title
 url=URL
 download-favicon=Download favicon
+choose-site-icon=Choose icon
+site-icons=Icons from the site
+site-icons-loading=Looking for icons…
+site-icons-empty=The site offers no icons large enough to use.
+site-icon-size={ $width }×{ $height }
+site-icon-scalable=Scalable
+edit-icon=Edit icon
+icon-editing=Icon editing
+icon-fit=Make square by
+icon-fit-pad=Padding
+icon-fit-crop=Cropping
+icon-padding=Padding (%)
+icon-corner-radius=Corner rounding (%)
+icon-background=Background color
+icon-background-placeholder=Transparent, or e.g. #ffffff
+apply-icon-edit=Apply
 non-standard-arguments=Non-standard arguments
 # keep navbar, isolated profile nad private mode small count of characters
 navbar=Nav Bar
 persistent-profile=Persistent Profile
 private-mode=Private Mode
 window-size=Window Size
+device-preset=Device Emulation
+device-preset-off=Off
+device-preset-phone=Phone
+device-preset-tablet=Tablet
+device-preset-custom=Custom
+device-pixel-ratio=Pixel ratio
+window-mode=Start As
+window-mode-normal=Window
+window-mode-maximized=Maximized
+window-mode-fullscreen=Fullscreen
+window-position=Window Position
+window-position-x=X
+window-position-y=Y
+monitor=Monitor
+monitor-placeholder=e.g. DP-1 (empty uses the current monitor)
 decorations=Window Decorations
+swipe-navigation=Swipe to Go Back and Forward
+sticky=Show on All Workspaces
+skip-taskbar=Hide from Taskbar
 simulate-mobile=Try to simulate mobile device
 
 # iconpicker.rs
@@ -74,6 +205,15 @@ icon-name-to-find=Icon name to find
 my-icons=My icons
 download=Download
 search=Search
+search-apps=Search name, URL, category or tag
+all-apps=All apps
+tag-filter=Tag: { $tag }
+sort-category=By category
+sort-name=By name
+sort-recently-launched=Recently launched
+sort-most-launched=Most launched
+sort-recently-added=Recently added
+sort-manual=Custom order
 no-icons-found=No icons found. Try a different search term or upload a custom icon.
 
 # icons_installator.rs
@@ -86,10 +226,22 @@ warning=You don't meet requirements
     .success=You can create new Web App
     .duplicate=  - Web App invalid. Maybe you already have this Web App?
 warning-app-name = App name must be at least 3 characters
-warning-app-url = Please enter a valid HTTP or HTTPS URL
+warning-app-url = Please enter a valid HTTP or HTTPS URL, localhost address or local file path
+url-checking=Checking the site…
+url-reachable=The site answered (HTTP { $status })
+url-error-status=The site answered with an error (HTTP { $status })
+url-unreachable=The site can't be reached: { $reason }
+url-redirects=It redirects to { $url }
+url-use-redirect=Use this address
+validation-title-missing=Enter a name for the app.
+validation-duplicate-name=Another app already has this name, which makes them hard to tell apart.
+validation-url-missing=Enter the address of the site.
+validation-icon-missing=Choose an icon: download the site's favicon, pick one of its icons or use the icon selector.
+validation-category-missing=Choose a category for the app.
+validation-summary=Fix the problems marked above to create the app.
     .wrong-icon =  - Selected icon is invalid. Select another one.
     .app-name=  - App name must be longer than 3 characters
-    .app-url=  - You must provide valid URL starting with http:// or https://
+    .app-url=  - You must provide valid URL starting with http://, https:// or file://
     .app-icon=  - You must select an Icon for your launcher
     .app-browser=  - Please select a browser. Make sure at least one is installed system-wide or via Flatpak
 
@@ -99,6 +251,15 @@ import-apps=Import apps
 toast-export-success=Apps exported successfully
 toast-import-success=Apps imported successfully
 toast-export-error=Failed to export apps
+toast-export-cookies-success={ $count ->
+    [one] 1 cookie exported
+   *[other] { $count } cookies exported
+}
+toast-export-cookies-error=Failed to export cookies
+toast-favicons-refreshed={ $count ->
+    [one] The favicon of 1 app was updated
+   *[other] The favicons of { $count } apps were updated
+}
 toast-import-error=Failed to import apps
 duplicate=Duplicate
 
@@ -108,16 +269,24 @@ file-dialog-import-title=Import Web Apps
 file-dialog-open-theme=Open Theme
 file-dialog-open-icons=Open multiple images
 file-dialog-save=Save
+file-dialog-export-cookies-title=Export Cookies
 file-dialog-import=Import
+file-dialog-open-css=Open CSS File
+file-dialog-open-js=Open JavaScript File
 file-filter-ron=RON export
 file-filter-ron-theme=Ron Theme
 file-filter-png=PNG Image
 file-filter-svg=SVG Images
+file-filter-css=CSS files
+file-filter-js=JavaScript files
 
 # toast notifications
 toast-app-saved=Web app saved successfully
 toast-app-deleted=Web app deleted
 toast-save-error=Failed to save web app
+toast-app-renamed=App ID changed
+toast-rename-error=Failed to change the app ID
+toast-rename-running=Close the app before changing its ID
 
 # custom CSS/JS injection
 custom-css=Custom CSS
@@ -125,6 +294,16 @@ custom-css-placeholder=body {"{"} background: #1a1a2e; {"}"}
 custom-js=Custom JavaScript
 custom-js-placeholder=console.log('Hello from custom script');
 custom-js-warning=Scripts run with full page access. Only use trusted code.
+load-from-file=Load from file…
+link-file=Link file…
+linked-file=Read from { $path } at every launch
+unlink-file=Unlink
+code-scope=Only on
+code-scope-placeholder=/mail/*, *.example.com (empty runs on every page)
+site-styles=Styles
+apply-style=Apply
+site-styles-update=Check for new styles
+site-styles-fetch-failed=The styles couldn't be downloaded
 
 # user agent
 user-agent=User Agent
@@ -137,6 +316,7 @@ user-agent-custom-placeholder=Mozilla/5.0 ...
 # permissions
 permission-camera=Allow Camera
 permission-microphone=Allow Microphone
+permission-screen-sharing=Allow Screen Sharing
 permission-geolocation=Allow Location
 permission-notifications=Allow Notifications
 
@@ -144,11 +324,16 @@ permission-notifications=Allow Notifications
 clear-data=Clear Data
 toast-data-cleared=Website data cleared successfully
 toast-data-clear-error=Failed to clear website data
+toast-warm-pool-error=Failed to change the renderer pool
 profile-data-size=Profile Data Size
 
 # URL scheme handlers
 url-schemes=URL Schemes
 url-schemes-placeholder=mailto, webcal, slack
+allowed-hosts=Allowed Sites
+allowed-hosts-placeholder=*.example.com (empty allows all)
+blocked-hosts=Blocked Sites
+blocked-hosts-placeholder=*.doubleclick.net, ads.example.com/track*
 
 # editor sections (#49)
 advanced-settings=Advanced Settings
@@ -165,6 +350,8 @@ toggle-view=Toggle View
 
 # thumbnails (#48)
 fetch-thumbnail=Load Preview
+preview=Preview
+preview-window-title=Preview: { $title }
 
 # privacy features (#53, #60, #61)
 content-blocking=Block Ads & Trackers
@@ -174,6 +361,16 @@ block-webrtc=Block WebRTC IP Leak
 # proxy (#54)
 proxy-url=Proxy URL
 proxy-url-placeholder=http://proxy.example.com:8080
+network-isolation=Network Access
+network-isolation-off=Unrestricted
+network-isolation-localhost=Localhost only
+network-isolation-none=No network
+doh-url=DNS-over-HTTPS Resolver
+doh-url-placeholder=System DNS (e.g. https://cloudflare-dns.com/dns-query)
+doh-url-invalid=The resolver address must start with https://
+host-overrides=Host Overrides
+host-overrides-placeholder=staging.example.com=10.0.0.5, api.example.com=10.0.0.6
+host-overrides-invalid=Use host=address pairs with IP addresses
 
 # zoom & session (#55, #56)
 zoom-level=Zoom Level
@@ -184,6 +381,12 @@ restore-session=Restore Last Session
 launch-count=Launch Count
 last-launched=Last Launched
 never-launched=Never
+usage-sessions=Sessions
+usage-total-time=Total Time
+usage-total-time-value={ $open } open, { $focused } focused
+usage-last-session=Last Session
+usage-time=Time Used
+usage-time-value={ $minutes } min on { $day }
 
 # minimize to background (#59)
 minimize-to-background=Minimize to Background on Close
@@ -198,3 +401,247 @@ bulk-delete=Delete Selected
 bulk-export=Export Selected
 toast-bulk-deleted=Selected apps deleted
 toast-bulk-exported=Selected apps exported
+bulk-refetch-icons=Download Icons Again
+bulk-move-to-group=Move to group
+bulk-category=Category
+bulk-persistent-on=Keep Website Data
+bulk-persistent-off=Forget Website Data
+bulk-launch=Launch Selected
+bulk-select-all=Select all
+toast-bulk-saved={ $count ->
+    [one] Updated 1 app
+   *[other] Updated { $count } apps
+}
+
+# auto reload
+auto-reload=Auto Reload Interval (minutes)
+auto-reload-placeholder=Disabled
+daily-limit=Daily Time Limit (minutes)
+daily-limit-placeholder=No limit
+suspend-after=Suspend When Hidden (minutes)
+suspend-after-placeholder=Never
+
+# load error page
+error-page-heading=Can't reach this page
+error-page-message=Check your internet connection. The page will reload automatically when you're back online.
+error-page-retry=Retry
+
+# splash screen
+splash-screen=Show Splash Screen While Loading
+theme-color=Theme Color
+theme-color-placeholder=#1e1e2e
+warning-theme-color=Use a hex color like #1e1e2e
+
+# navigation toolbar
+navigation-toolbar=Navigation Toolbar
+toolbar-back=Back
+toolbar-forward=Forward
+toolbar-reload=Reload
+toolbar-home=Home
+
+# granular data clearing
+clear=Clear
+data-cookies=Cookies
+data-cache=Cache
+data-local-storage=Local Storage
+data-indexeddb=IndexedDB
+data-history=Browsing History
+data-other=Other Site Data
+data-total=Total
+app-with-storage={ $name } ({ $size })
+cache-limit=Disk Cache Limit (MB)
+cache-limit-placeholder=Unlimited
+
+# ephemeral profile
+ephemeral-profile=Fresh Profile on Every Launch
+sandbox=Sandbox (bubblewrap, no access to your home folder)
+
+# webkit features
+enable-javascript=Enable JavaScript
+enable-webgl=Enable WebGL
+autoplay-policy=Media Autoplay
+autoplay-allow=Allow
+autoplay-allow-muted=Allow muted only
+autoplay-block=Block
+smooth-scrolling=Smooth Scrolling
+page-cache=Back/Forward Page Cache
+
+# geolocation
+geolocation-accuracy=Location Accuracy
+geolocation-accuracy-country=Country
+geolocation-accuracy-city=City
+geolocation-accuracy-neighborhood=Neighborhood
+geolocation-accuracy-street=Street
+geolocation-accuracy-exact=Exact
+location-in-use=This app is using your location
+
+# password autofill
+password-autofill=Save and Autofill Passwords
+password-save-prompt=Save the password for { $username } on { $origin }?
+password-save=Save
+password-not-now=Not Now
+
+# tls errors
+tls-error-heading=This connection is not secure
+tls-error-message=The certificate presented by { $host } could not be verified. Someone may be trying to impersonate the site.
+tls-error-unknown-ca=The certificate is not signed by a trusted authority.
+tls-error-bad-identity=The certificate does not belong to this site.
+tls-error-not-activated=The certificate is not valid yet.
+tls-error-expired=The certificate has expired.
+tls-error-revoked=The certificate has been revoked.
+tls-error-insecure=The certificate uses an insecure algorithm.
+tls-error-generic=The certificate could not be validated.
+tls-error-back=Go Back
+tls-error-proceed=Proceed anyway (this app only)
+tls-exceptions=Certificate Exceptions
+remove=Remove
+
+# custom ca
+ca-certificate=Custom CA Certificate
+ca-certificate-placeholder=/path/to/ca-bundle.pem
+warning-ca-certificate=File not found; the certificate will not be trusted
+
+# privacy signals
+do-not-track=Send Do Not Track Signals
+referrer-policy=Referrer
+referrer-policy-default=Website default
+referrer-policy-trim=Trim to origin on other sites
+referrer-policy-same-origin=Send to same site only
+referrer-policy-none=Never send
+
+# fingerprinting
+fingerprint-resistance=Resist Fingerprinting
+
+# mute
+muted=Mute Audio
+
+# data saver
+data-saver=Data Saver (block images and fonts)
+
+# Running app control
+reload-app=Reload
+quit-app=Quit
+focus-app=Focus
+kill-app=Kill
+running-apps=Running Apps
+running-apps-empty=No web apps are running.
+running-app-usage={ $memory } memory, { $cpu } CPU, process { $pid }
+
+# Global shortcut
+global-shortcut=Show/Hide Shortcut
+global-shortcut-placeholder=CTRL+ALT+S
+
+# Start hidden
+start-hidden=Start Hidden
+autostart=Start at Login
+autostart-hidden=Hidden
+
+# Tray
+tray-show=Show
+tray-reload=Reload
+tray-mute=Mute / Unmute
+tray-quit=Quit
+
+# Notifications
+notification-open=Open
+
+# Notification quiet hours
+quiet-hours=Notification Quiet Hours
+quiet-hours-placeholder=22:00-08:00
+quiet-hours-invalid=Use the form HH:MM-HH:MM
+quiet-while-locked=Quiet While Screen Is Locked
+quiet-hours-action=During Quiet Hours
+quiet-hours-suppress=Suppress notifications
+quiet-hours-low-urgency=Show with low urgency
+
+# Spell checking
+spellcheck=Spell Checking
+spellcheck-languages=Spell Checking Languages
+spellcheck-languages-placeholder=en_US, de_DE (empty uses system language)
+
+# File uploads
+upload-directory=Upload Folder
+upload-directory-placeholder=~/Documents
+upload-files=Upload Files
+upload-accepted-files=Accepted files
+
+# Rendering
+disable-hardware-acceleration=Disable Hardware Acceleration (fixes black windows)
+
+context-menu=Context Menu
+context-menu-default=Default
+context-menu-extended=With app actions
+context-menu-disabled=Disabled
+context-menu-open-in-browser=Open Link in Default Browser
+context-menu-copy-url=Copy Page URL
+context-menu-reload=Reload
+context-menu-reader-mode=Toggle Reader Mode
+
+page-url-copied=Page URL copied
+
+history=History
+clear-history=Clear History
+history-search=Search history
+history-empty=No pages visited yet
+
+bookmarks=Bookmarks
+bookmarks-empty=No bookmarks yet. Press Ctrl+D to bookmark a page.
+bookmark-added=Bookmark added
+bookmark-exists=Already bookmarked
+
+screenshot-saved=Screenshot saved
+
+navigation-log=Navigation Log
+navigation-allowed=Allowed
+navigation-blocked=Blocked
+clear-navigation-log=Clear Navigation Log
+
+app-lock=Lock App
+app-lock-method=Unlock With
+app-lock-method-pin=PIN
+app-lock-method-system=System password
+app-lock-pin=PIN
+app-lock-pin-placeholder=Leave empty to keep the current PIN
+app-lock-pin-missing=Set a PIN to lock this app
+app-lock-idle=Lock Again After Idle (minutes)
+app-lock-idle-placeholder=0 never locks again
+app-lock-unlock=Unlock
+app-lock-wrong-pin=Wrong PIN
+app-lock-denied=Authentication failed
+app-lock-unavailable=System authentication is unavailable
+
+time-limit-warning={ $minutes ->
+    [one] 1 minute left today
+   *[other] { $minutes } minutes left today
+}
+time-limit-reached=You've used this app for { $minutes } minutes today. It will be available again tomorrow.
+
+cookies=Cookies
+cookies-empty=This site has no cookies
+cookie-session=Until the app closes
+cookie-expires-today=Expires today
+cookie-expires-days=Expires in { $days ->
+    [one] 1 day
+   *[other] { $days } days
+}
+export-cookies=Export as cookies.txt
+export=Export
+
+sandbox-unavailable=This app is set to run sandboxed, but bubblewrap (bwrap) is not installed.
+sandbox-failed=Failed to start the sandbox: { $error }
+
+# statistics dashboard
+statistics=Statistics
+statistics-apps=Web Apps
+statistics-sort=Sort By
+statistics-sort-usage=Time used
+statistics-sort-launches=Launches
+statistics-sort-last-used=Last used
+statistics-sort-storage=Storage
+statistics-sort-name=Name
+statistics-per-app=Per App
+statistics-entry={ $launches } launches, { $time } used, last { $last }, { $storage }
+statistics-loading=Loading statistics…
+statistics-unused=Unused Apps
+statistics-unused-hint=Not used in the last { $days } days
+statistics-select-unused=Select for removal
@@ -0,0 +1,90 @@
+//! What the webview records for an app while it runs: bookmarks, today's usage time,
+//! certificates accepted from the TLS error page and the theme color detected from the
+//! site.
+//!
+//! Kept in `state/<app_id>/state.ron` apart from the app's settings, so saving those in
+//! the manager never overwrites it. Each app gets a directory of its own that the sandbox
+//! can mount, and the file is replaced atomically under a lock, so the webview and the
+//! manager don't lose each other's changes.
+
+use std::{os::fd::AsRawFd as _, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::browser::{Bookmark, TlsException};
+
+const FILE_NAME: &str = "state.ron";
+const LOCK_NAME: &str = "state.lock";
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct AppState {
+    /// Pages bookmarked from inside the app
+    pub bookmarks: Vec<Bookmark>,
+    /// Local date (`YYYY-MM-DD`) `usage_seconds` were counted on
+    pub usage_day: Option<String>,
+    /// Seconds the window was in use on `usage_day`
+    pub usage_seconds: u64,
+    /// Certificates accepted from the TLS error page
+    pub tls_exceptions: Vec<TlsException>,
+    /// Theme color (`#rrggbb`) from the site's manifest, used when none is configured
+    pub theme_color: Option<String>,
+}
+
+impl AppState {
+    /// Seconds used on `day`, which is zero once the recorded day has passed.
+    pub fn usage_on(&self, day: &str) -> u64 {
+        if self.usage_day.as_deref() == Some(day) {
+            self.usage_seconds
+        } else {
+            0
+        }
+    }
+}
+
+/// The app's state directory.
+pub fn dir(app_id: &str) -> Option<PathBuf> {
+    let safe_id = crate::browser::sanitize_app_id(app_id);
+    Some(
+        dirs::data_dir()?
+            .join(crate::APP_ID)
+            .join("state")
+            .join(safe_id),
+    )
+}
+
+pub fn load(app_id: &str) -> AppState {
+    dir(app_id)
+        .and_then(|dir| std::fs::read_to_string(dir.join(FILE_NAME)).ok())
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Apply `update` to the app's state and save it.
+pub fn update(app_id: &str, update: impl FnOnce(&mut AppState)) -> std::io::Result<()> {
+    let dir = dir(app_id).ok_or_else(|| std::io::Error::other("no data directory"))?;
+    std::fs::create_dir_all(&dir)?;
+
+    // Held until the new state is in place; closing the file releases it
+    let lock = std::fs::File::create(dir.join(LOCK_NAME))?;
+    // SAFETY: `flock` only takes the descriptor, which `lock` keeps open
+    if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let mut state = load(app_id);
+    update(&mut state);
+    let serialized = ron::ser::to_string_pretty(&state, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    let tmp = dir.join(format!("{FILE_NAME}.tmp"));
+    std::fs::write(&tmp, serialized)?;
+    std::fs::rename(tmp, dir.join(FILE_NAME))
+}
+
+/// Remove the app's state, e.g. when the app is deleted.
+pub fn clear(app_id: &str) -> std::io::Result<()> {
+    match dir(app_id).map(std::fs::remove_dir_all) {
+        Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
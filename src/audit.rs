@@ -0,0 +1,95 @@
+//! Opt-in navigation audit log: one line per top-level navigation, kept outside the app's
+//! profile so clearing website data does not erase it.
+//!
+//! Lines are tab-separated: Unix timestamp, `allowed` or `blocked`, URL.
+
+use std::{
+    io::Write as _,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// The log is rotated to `<app_id>.log.1` once it grows past this.
+const MAX_LOG_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    pub timestamp: u64,
+    pub allowed: bool,
+    pub url: String,
+}
+
+pub fn log_path(app_id: &str) -> Option<PathBuf> {
+    let safe_id = crate::browser::sanitize_app_id(app_id);
+    Some(
+        dirs::data_dir()?
+            .join(crate::APP_ID)
+            .join("logs")
+            .join(format!("{safe_id}.log")),
+    )
+}
+
+/// Append a navigation to the app's log.
+pub fn append(app_id: &str, url: &str, allowed: bool) -> Result<(), std::io::Error> {
+    let Some(path) = log_path(app_id) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    if std::fs::metadata(&path).is_ok_and(|m| m.len() > MAX_LOG_SIZE)
+        && std::fs::rename(&path, path.with_extension("log.1")).is_err()
+    {
+        // The sandbox mounts only the log itself, which can't be moved, so start it over
+        std::fs::File::create(&path)?;
+    }
+
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let verdict = if allowed { "allowed" } else { "blocked" };
+    // Tabs and newlines can't appear in a parsed URL, but keep the format intact regardless
+    let url = url.replace(['\t', '\n', '\r'], " ");
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)?;
+    writeln!(file, "{timestamp}\t{verdict}\t{url}")
+}
+
+/// The most recent `limit` entries, newest first.
+pub fn recent(app_id: &str, limit: usize) -> Vec<AuditEntry> {
+    let Some(content) = log_path(app_id).and_then(|path| std::fs::read_to_string(path).ok())
+    else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .rev()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, '\t');
+            Some(AuditEntry {
+                timestamp: fields.next()?.parse().ok()?,
+                allowed: fields.next()? == "allowed",
+                url: fields.next()?.to_string(),
+            })
+        })
+        .take(limit)
+        .collect()
+}
+
+/// Remove the app's log, including the rotated copy.
+pub fn clear(app_id: &str) -> Result<(), std::io::Error> {
+    let Some(path) = log_path(app_id) else {
+        return Ok(());
+    };
+    for file in [path.with_extension("log.1"), path] {
+        match std::fs::remove_file(file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,56 @@
+//! Starting apps at login through entries in the XDG autostart directory, optionally
+//! with the window hidden until the app is opened.
+
+use std::path::PathBuf;
+
+use crate::{APP_ID, browser::Browser, in_flatpak};
+
+/// Autostart entry `<APP_ID>.<name>.desktop`. Always under the real home directory:
+/// inside Flatpak the XDG config dir points into the sandbox, where the session would
+/// never see it.
+pub(crate) fn entry_path(name: &str) -> Option<PathBuf> {
+    Some(
+        dirs::home_dir()?
+            .join(".config/autostart")
+            .join(format!("{APP_ID}.{name}.desktop")),
+    )
+}
+
+/// Write or remove the app's autostart entry, as its settings ask for. `name` must
+/// already be safe for a desktop file.
+pub fn update(browser: &Browser, name: &str) -> std::io::Result<()> {
+    if !browser.autostart.unwrap_or(false) {
+        return disable(&browser.app_id.id);
+    }
+    let app_id = &browser.app_id.id;
+    let path = entry_path(app_id).ok_or_else(|| std::io::Error::other("no home directory"))?;
+    let binary = format!("{APP_ID}.webview");
+    let mut exec = if in_flatpak() {
+        format!("/usr/bin/flatpak run --command={binary} {APP_ID} {app_id}")
+    } else {
+        format!("{binary} {app_id}")
+    };
+    if browser.autostart_hidden.unwrap_or(false) {
+        exec.push_str(" --hidden");
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("desktop.tmp");
+    std::fs::write(
+        &tmp,
+        format!(
+            "[Desktop Entry]\nType=Application\nName={name}\nExec={exec}\n\
+             NoDisplay=true\nX-GNOME-Autostart-enabled=true\n"
+        ),
+    )?;
+    std::fs::rename(tmp, path)
+}
+
+/// Stop starting the app at login.
+pub fn disable(app_id: &str) -> std::io::Result<()> {
+    match entry_path(app_id).map(std::fs::remove_file) {
+        Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -9,11 +9,97 @@ pub enum ViewMode {
     Grid,
 }
 
+/// Size of the cards in the grid view.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum GridDensity {
+    /// Small cards without storage sizes, to see many apps at once
+    Compact,
+    #[default]
+    Comfortable,
+    Large,
+}
+
+impl GridDensity {
+    pub const ALL: [GridDensity; 3] = [
+        GridDensity::Compact,
+        GridDensity::Comfortable,
+        GridDensity::Large,
+    ];
+
+    pub fn icon_size(self) -> u16 {
+        match self {
+            GridDensity::Compact => 48,
+            GridDensity::Comfortable => 64,
+            GridDensity::Large => 96,
+        }
+    }
+
+    /// Width and height of a card.
+    pub fn card_size(self) -> f32 {
+        match self {
+            GridDensity::Compact => 96.0,
+            GridDensity::Comfortable => 120.0,
+            GridDensity::Large => 160.0,
+        }
+    }
+}
+
+/// Order of the installed apps in the list and grid.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SortMode {
+    /// By category, then by name
+    #[default]
+    Category,
+    Name,
+    RecentlyLaunched,
+    MostLaunched,
+    RecentlyAdded,
+    /// In the order the apps were dragged into
+    Manual,
+}
+
+impl SortMode {
+    pub const ALL: [SortMode; 6] = [
+        SortMode::Category,
+        SortMode::Name,
+        SortMode::RecentlyLaunched,
+        SortMode::MostLaunched,
+        SortMode::RecentlyAdded,
+        SortMode::Manual,
+    ];
+
+    /// Whether the order depends on when the apps were launched or added.
+    pub fn uses_activity(self) -> bool {
+        matches!(
+            self,
+            SortMode::RecentlyLaunched | SortMode::MostLaunched | SortMode::RecentlyAdded
+        )
+    }
+}
+
+/// A group of apps, shown as a section in the list and a folder in the grid.
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AppGroup {
+    pub name: String,
+    pub app_ids: Vec<String>,
+    /// Whether the list shows only the group's header
+    pub collapsed: bool,
+}
+
 #[derive(Debug, Default, Clone, CosmicConfigEntry, Eq, PartialEq)]
 #[version = 2]
 pub struct AppConfig {
     pub app_theme: String,
     pub view_mode: ViewMode,
+    pub grid_density: GridDensity,
+    pub sort_mode: SortMode,
+    /// App ids in the order set by dragging them
+    pub app_order: Vec<String>,
+    pub custom_categories: Vec<webapps::CustomCategory>,
+    pub app_groups: Vec<AppGroup>,
+    /// App ids shown above the list for launching them quickly, in the order pinned
+    pub pinned_apps: Vec<String>,
+    pub favicon_refresh: webapps::favicon_refresh::Schedule,
 }
 
 impl AppConfig {
@@ -0,0 +1,55 @@
+//! The order users give the apps by dragging them, kept as a list of app ids in the
+//! config. Apps missing from it, like newly created ones, come after the ordered ones.
+
+use std::borrow::Cow;
+
+use cosmic::iced::clipboard::mime::{AllowedMimeTypes, AsMimeTypes};
+
+/// Only apps dragged within the manager are accepted.
+const MIME_TYPE: &str = "application/x-dev.heppen.webapps-app-id";
+
+/// The id of an app being dragged.
+#[derive(Debug, Clone)]
+pub struct DraggedApp(pub String);
+
+impl AllowedMimeTypes for DraggedApp {
+    fn allowed() -> Cow<'static, [String]> {
+        Cow::Owned(vec![MIME_TYPE.to_string()])
+    }
+}
+
+impl TryFrom<(Vec<u8>, String)> for DraggedApp {
+    type Error = std::string::FromUtf8Error;
+
+    fn try_from((data, _mime_type): (Vec<u8>, String)) -> Result<Self, Self::Error> {
+        String::from_utf8(data).map(DraggedApp)
+    }
+}
+
+impl AsMimeTypes for DraggedApp {
+    fn available(&self) -> Cow<'static, [String]> {
+        Cow::Owned(vec![MIME_TYPE.to_string()])
+    }
+
+    fn as_bytes(&self, mime_type: &str) -> Option<Cow<'static, [u8]>> {
+        (mime_type == MIME_TYPE).then(|| Cow::Owned(self.0.clone().into_bytes()))
+    }
+}
+
+/// Place of `app_id` in `order`, with unordered apps last.
+pub fn position(order: &[String], app_id: &str) -> usize {
+    order
+        .iter()
+        .position(|id| id == app_id)
+        .unwrap_or(order.len())
+}
+
+/// Move `app_id` in `order` to just before `target`.
+pub fn move_before(order: &mut Vec<String>, app_id: &str, target: &str) {
+    if app_id == target {
+        return;
+    }
+    order.retain(|id| id != app_id);
+    let idx = position(order, target);
+    order.insert(idx, app_id.to_string());
+}
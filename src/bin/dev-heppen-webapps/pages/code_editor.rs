@@ -0,0 +1,340 @@
+//! Multi-line editor for an app's custom CSS and JavaScript, with line numbers and
+//! highlighting of comments, strings, numbers and keywords.
+
+use std::ops::Range;
+
+use cosmic::{
+    Element, Theme,
+    iced::{Color, Length, Padding, widget::text_editor},
+    iced_core::text::{LineHeight, highlighter},
+    widget,
+};
+
+/// Text size of the code and its line numbers, which must line up.
+const TEXT_SIZE: f32 = 13.0;
+
+const LINE_HEIGHT: f32 = 1.3;
+
+/// Height of the editor before it scrolls.
+const MAX_HEIGHT: f32 = 240.0;
+
+const JS_KEYWORDS: &[&str] = &[
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    Css,
+    JavaScript,
+}
+
+/// The text being edited. Cloning copies the text but not the cursor.
+pub struct CodeEditor {
+    content: text_editor::Content,
+    language: Language,
+}
+
+impl std::fmt::Debug for CodeEditor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CodeEditor")
+            .field("language", &self.language)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Clone for CodeEditor {
+    fn clone(&self) -> Self {
+        Self::new(self.language, &self.text())
+    }
+}
+
+impl CodeEditor {
+    pub fn new(language: Language, text: &str) -> Self {
+        Self {
+            content: text_editor::Content::with_text(text),
+            language,
+        }
+    }
+
+    /// The code, without the trailing newline the editor keeps.
+    pub fn text(&self) -> String {
+        let mut text = self.content.text();
+        if text.ends_with('\n') {
+            text.pop();
+        }
+        text
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text().trim().is_empty()
+    }
+
+    pub fn perform(&mut self, action: text_editor::Action) {
+        self.content.perform(action);
+    }
+
+    pub fn view<'a, Message: Clone + 'a>(
+        &'a self,
+        placeholder: String,
+        on_action: impl Fn(text_editor::Action) -> Message + 'a,
+    ) -> Element<'a, Message> {
+        let numbers = (1..=self.content.line_count().max(1))
+            .map(|line| line.to_string())
+            .collect::<Vec<_>>()
+            .join("\n");
+        // Padded like the editor's text, so every number sits next to its line
+        let gutter = widget::container(
+            widget::text(numbers)
+                .font(cosmic::font::mono())
+                .size(TEXT_SIZE)
+                .line_height(LineHeight::Relative(LINE_HEIGHT))
+                .class(cosmic::style::Text::Color(Color::from_rgba(
+                    0.5, 0.5, 0.5, 1.0,
+                ))),
+        )
+        .padding(Padding::from([5, 8]));
+
+        // The editor grows with the code and scrolls together with the line numbers;
+        // wrapping is off, as wrapped lines would lose their number
+        let editor = text_editor(&self.content)
+            .placeholder(placeholder)
+            .on_action(on_action)
+            .font(cosmic::font::mono())
+            .size(TEXT_SIZE)
+            .line_height(LineHeight::Relative(LINE_HEIGHT))
+            .wrapping(cosmic::iced::widget::text::Wrapping::None)
+            .height(Length::Shrink)
+            .highlight_with::<Highlighter>(self.language, format);
+
+        widget::container(widget::scrollable(
+            widget::row().push(gutter).push(editor).width(Length::Fill),
+        ))
+        .max_height(MAX_HEIGHT)
+        .into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Highlight {
+    Comment,
+    String,
+    Number,
+    Keyword,
+    /// CSS property names
+    Property,
+}
+
+fn format(highlight: &Highlight, theme: &Theme) -> highlighter::Format<cosmic::iced::Font> {
+    let palette = &theme.cosmic().palette;
+    let color = match highlight {
+        Highlight::Comment => palette.neutral_6,
+        Highlight::String => palette.accent_green,
+        Highlight::Number => palette.accent_orange,
+        Highlight::Keyword => palette.accent_purple,
+        Highlight::Property => palette.accent_blue,
+    };
+    highlighter::Format {
+        color: Some(Color::from(color)),
+        font: None,
+    }
+}
+
+/// What a line starts inside of, carried over from the lines before.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct State {
+    in_comment: bool,
+    /// Open braces, to tell CSS properties from selectors
+    depth: u32,
+}
+
+pub struct Highlighter {
+    language: Language,
+    /// The state at the start of every line highlighted so far
+    states: Vec<State>,
+    current_line: usize,
+}
+
+impl highlighter::Highlighter for Highlighter {
+    type Settings = Language;
+    type Highlight = Highlight;
+    type Iterator<'a> = std::vec::IntoIter<(Range<usize>, Highlight)>;
+
+    fn new(language: &Language) -> Self {
+        Self {
+            language: *language,
+            states: vec![State::default()],
+            current_line: 0,
+        }
+    }
+
+    fn update(&mut self, language: &Language) {
+        self.language = *language;
+        self.change_line(0);
+    }
+
+    fn change_line(&mut self, line: usize) {
+        self.current_line = line.min(self.states.len() - 1);
+        self.states.truncate(self.current_line + 1);
+    }
+
+    fn highlight_line(&mut self, line: &str) -> Self::Iterator<'_> {
+        let state = self.states[self.current_line];
+        let (spans, next) = tokenize(self.language, line, state);
+        self.states.truncate(self.current_line + 1);
+        self.states.push(next);
+        self.current_line += 1;
+        spans.into_iter()
+    }
+
+    fn current_line(&self) -> usize {
+        self.current_line
+    }
+}
+
+fn is_ident(byte: u8, language: Language) -> bool {
+    byte.is_ascii_alphanumeric()
+        || byte == b'_'
+        || match language {
+            Language::Css => byte == b'-',
+            Language::JavaScript => byte == b'$',
+        }
+}
+
+/// Split `line` into highlighted spans, starting in `state`. Returns the spans and the
+/// state the next line starts in.
+fn tokenize(
+    language: Language,
+    line: &str,
+    mut state: State,
+) -> (Vec<(Range<usize>, Highlight)>, State) {
+    let bytes = line.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let start = i;
+        let rest = &bytes[i..];
+
+        if state.in_comment || rest.starts_with(b"/*") {
+            let from = if state.in_comment { i } else { i + 2 };
+            match line[from..].find("*/") {
+                Some(end) => {
+                    i = from + end + 2;
+                    state.in_comment = false;
+                }
+                None => {
+                    i = bytes.len();
+                    state.in_comment = true;
+                }
+            }
+            spans.push((start..i, Highlight::Comment));
+            continue;
+        }
+
+        let byte = bytes[i];
+        match byte {
+            b'/' if language == Language::JavaScript && rest.starts_with(b"//") => {
+                spans.push((start..bytes.len(), Highlight::Comment));
+                break;
+            }
+            b'"' | b'\'' | b'`' => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != byte {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(bytes.len());
+                spans.push((start..i, Highlight::String));
+            }
+            b'0'..=b'9' => {
+                // Also takes units and percentages, as in 1.5em or 50%
+                while i < bytes.len()
+                    && (is_ident(bytes[i], language) || bytes[i] == b'.' || bytes[i] == b'%')
+                {
+                    i += 1;
+                }
+                spans.push((start..i, Highlight::Number));
+            }
+            b'#' if language == Language::Css && state.depth > 0 => {
+                // A hex color, selectors only come before the braces
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_hexdigit() {
+                    i += 1;
+                }
+                spans.push((start..i, Highlight::Number));
+            }
+            b'@' if language == Language::Css => {
+                i += 1;
+                while i < bytes.len() && is_ident(bytes[i], language) {
+                    i += 1;
+                }
+                spans.push((start..i, Highlight::Keyword));
+            }
+            b'{' => {
+                state.depth += 1;
+                i += 1;
+            }
+            b'}' => {
+                state.depth = state.depth.saturating_sub(1);
+                i += 1;
+            }
+            _ if is_ident(byte, language) => {
+                while i < bytes.len() && is_ident(bytes[i], language) {
+                    i += 1;
+                }
+                let word = &line[start..i];
+                let highlight = match language {
+                    Language::JavaScript => {
+                        JS_KEYWORDS.contains(&word).then_some(Highlight::Keyword)
+                    }
+                    Language::Css => (state.depth > 0 && line[i..].trim_start().starts_with(':'))
+                        .then_some(Highlight::Property),
+                };
+                if let Some(highlight) = highlight {
+                    spans.push((start..i, highlight));
+                }
+            }
+            _ => {
+                i += line[i..].chars().next().map_or(1, char::len_utf8);
+            }
+        }
+    }
+    (spans, state)
+}
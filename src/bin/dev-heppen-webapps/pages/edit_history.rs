@@ -0,0 +1,63 @@
+//! Undo and redo of the changes made in the editor.
+
+use std::time::{Duration, Instant};
+
+/// Changes of the same kind made within this time, like typing into a field, are undone
+/// together.
+const MERGE_WINDOW: Duration = Duration::from_secs(1);
+
+/// Steps kept for undo.
+const MAX_STEPS: usize = 100;
+
+/// Earlier and undone states of `T`, each taken before a change.
+#[derive(Debug, Clone)]
+pub struct EditHistory<T, K> {
+    undo: Vec<T>,
+    redo: Vec<T>,
+    /// Kind and time of the last change
+    last: Option<(K, Instant)>,
+}
+
+impl<T, K> Default for EditHistory<T, K> {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            last: None,
+        }
+    }
+}
+
+impl<T, K: PartialEq> EditHistory<T, K> {
+    /// Record a change of `kind`, made to the state `before`.
+    pub fn record(&mut self, before: T, kind: K) {
+        let now = Instant::now();
+        let merged = self.last.as_ref().is_some_and(|(last, time)| {
+            *last == kind && now.duration_since(*time) < MERGE_WINDOW
+        });
+        if !merged || self.undo.is_empty() {
+            self.undo.push(before);
+            if self.undo.len() > MAX_STEPS {
+                self.undo.remove(0);
+            }
+        }
+        self.redo.clear();
+        self.last = Some((kind, now));
+    }
+
+    /// The state before the last change, which `current` is kept for redo.
+    pub fn undo(&mut self, current: T) -> Option<T> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        self.last = None;
+        Some(previous)
+    }
+
+    /// The state before the last undo, which `current` is kept for undo.
+    pub fn redo(&mut self, current: T) -> Option<T> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        self.last = None;
+        Some(next)
+    }
+}
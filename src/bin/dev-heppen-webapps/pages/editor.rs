@@ -1,15 +1,26 @@
 use cosmic::{
     Element, Task,
     action::Action,
-    iced::{Length, alignment::Vertical},
+    iced::{
+        Length,
+        alignment::{Horizontal, Vertical},
+        widget::text_editor,
+    },
     style, task,
     widget::{self},
 };
+use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
 use rand::{Rng, rng};
+use std::{path::PathBuf, time::Duration};
 use strum::IntoEnumIterator as _;
 use webapps::fl;
 
-use crate::pages;
+use crate::pages::{
+    self,
+    code_editor::{CodeEditor, Language},
+    edit_history::EditHistory,
+    wizard::{PrivacyPreset, Step, WindowPreset, Wizard},
+};
 
 /// Filter a string to only contain digits and dots (for numeric input fields).
 fn filter_numeric(input: String) -> String {
@@ -19,6 +30,291 @@ fn filter_numeric(input: String) -> String {
         .collect()
 }
 
+/// Recent visits listed in the editor's history section.
+const HISTORY_LIMIT: usize = 20;
+
+/// Navigation log entries listed in the editor.
+const NAVIGATION_LOG_LIMIT: usize = 100;
+
+/// Time after the last change to the URL before it is checked.
+const URL_CHECK_DELAY: Duration = Duration::from_millis(800);
+
+/// When a cookie expires, relative to now.
+fn cookie_expiry(expires: u64) -> String {
+    if expires == 0 {
+        return fl!("cookie-session");
+    }
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match expires.saturating_sub(now) / 86400 {
+        0 => fl!("cookie-expires-today"),
+        days => fl!("cookie-expires-days", days = days),
+    }
+}
+
+/// Localized label for a profile data category.
+fn profile_data_name(category: webapps::ProfileData) -> String {
+    match category {
+        webapps::ProfileData::Cookies => fl!("data-cookies"),
+        webapps::ProfileData::Cache => fl!("data-cache"),
+        webapps::ProfileData::LocalStorage => fl!("data-local-storage"),
+        webapps::ProfileData::IndexedDb => fl!("data-indexeddb"),
+        webapps::ProfileData::History => fl!("data-history"),
+        webapps::ProfileData::Other => fl!("data-other"),
+    }
+}
+
+/// Position of a geolocation accuracy in the editor dropdown.
+fn geolocation_accuracy_index(accuracy: webapps::browser::GeolocationAccuracy) -> usize {
+    webapps::browser::GeolocationAccuracy::iter()
+        .position(|a| a == accuracy)
+        .unwrap_or_default()
+}
+
+/// Position of a referrer policy in the editor dropdown.
+fn referrer_policy_index(policy: webapps::browser::ReferrerPolicy) -> usize {
+    webapps::browser::ReferrerPolicy::iter()
+        .position(|p| p == policy)
+        .unwrap_or_default()
+}
+
+/// Position of a quiet hours action in the editor dropdown.
+fn quiet_hours_action_index(action: webapps::browser::QuietHoursAction) -> usize {
+    webapps::browser::QuietHoursAction::iter()
+        .position(|a| a == action)
+        .unwrap_or_default()
+}
+
+/// Position of a window mode in the editor dropdown.
+pub(super) fn window_mode_index(value: webapps::browser::WindowMode) -> usize {
+    webapps::browser::WindowMode::iter()
+        .position(|v| v == value)
+        .unwrap_or_default()
+}
+
+/// Position of a device preset in the editor dropdown.
+fn device_preset_index(preset: webapps::browser::DevicePreset) -> usize {
+    webapps::browser::DevicePreset::iter()
+        .position(|p| p == preset)
+        .unwrap_or_default()
+}
+
+/// Position of a context menu mode in the editor dropdown.
+fn context_menu_index(value: webapps::browser::ContextMenu) -> usize {
+    webapps::browser::ContextMenu::iter()
+        .position(|v| v == value)
+        .unwrap_or_default()
+}
+
+/// Position of a lock method in the editor dropdown.
+fn lock_method_index(value: webapps::browser::LockMethod) -> usize {
+    webapps::browser::LockMethod::iter()
+        .position(|v| v == value)
+        .unwrap_or_default()
+}
+
+/// Dropdown index of a network isolation mode.
+fn network_isolation_index(value: webapps::browser::NetworkIsolation) -> usize {
+    webapps::browser::NetworkIsolation::iter()
+        .position(|v| v == value)
+        .unwrap_or_default()
+}
+
+/// What to do with a CSS or JavaScript file picked in the editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CodeFile {
+    /// Copy its contents into the app
+    Copy,
+    /// Read it again at every launch
+    Link,
+}
+
+/// Ask for a CSS or JavaScript file and read it.
+async fn pick_code_file(language: Language) -> Option<(PathBuf, String)> {
+    let (title, filter_name, glob) = match language {
+        Language::Css => (fl!("file-dialog-open-css"), fl!("file-filter-css"), "*.css"),
+        Language::JavaScript => (fl!("file-dialog-open-js"), fl!("file-filter-js"), "*.js"),
+    };
+    let label = fl!("open");
+    let response = match SelectedFiles::open_file()
+        .title(title.as_str())
+        .accept_label(label.as_str())
+        .modal(true)
+        .multiple(false)
+        .filter(FileFilter::new(&filter_name).glob(glob))
+        .send()
+        .await
+    {
+        Ok(r) => r.response(),
+        Err(e) => {
+            tracing::error!("Failed to open code file picker: {e}");
+            return None;
+        }
+    };
+    let path = response.ok()?.uris().first()?.to_file_path().ok()?;
+    match tokio::fs::read_to_string(&path).await {
+        Ok(code) => Some((path, code)),
+        Err(e) => {
+            tracing::error!("Failed to read {}: {e}", path.display());
+            None
+        }
+    }
+}
+
+/// Replace the lock's saved PIN hash with one of `pin`, the PIN entered in the editor.
+/// Hashing is slow on purpose, so it runs on a blocking thread.
+///
+/// Kept out of `apply_settings` since every hash is salted differently, which would
+/// make the app look modified on each comparison.
+async fn hash_entered_pin(browser: &mut webapps::browser::Browser, pin: String) {
+    let Some(lock) = browser.app_lock.as_mut().filter(|_| !pin.is_empty()) else {
+        return;
+    };
+    match tokio::task::spawn_blocking(move || webapps::browser::AppLock::hash_pin(&pin)).await {
+        Ok(hash) => lock.pin_hash = hash,
+        Err(e) => tracing::error!("Failed to hash the app lock PIN: {e}"),
+    }
+}
+
+/// Filter a string to only contain digits (for whole-number input fields).
+fn filter_digits(input: String) -> String {
+    input.chars().filter(|c| c.is_ascii_digit()).collect()
+}
+
+/// The problems with `field`, shown below it.
+fn field_problems(problems: &[Problem], field: Field) -> Option<Element<'static, Message>> {
+    let mut column = widget::column().spacing(2);
+    let mut any = false;
+    for problem in problems.iter().filter(|problem| problem.field() == field) {
+        column = column.push(widget::text::caption(problem.message()).class(style::Text::Accent));
+        any = true;
+    }
+    any.then(|| column.into())
+}
+
+/// Fields checked before an app can be created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Title,
+    Url,
+    Icon,
+    Category,
+}
+
+/// What keeps the app from being created, or looks like a mistake.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Problem {
+    TitleMissing,
+    TitleTooShort,
+    /// Another installed app has the same name
+    DuplicateName,
+    UrlMissing,
+    UrlInvalid,
+    IconMissing,
+    CategoryMissing,
+}
+
+impl Problem {
+    fn field(&self) -> Field {
+        match self {
+            Problem::TitleMissing | Problem::TitleTooShort | Problem::DuplicateName => {
+                Field::Title
+            }
+            Problem::UrlMissing | Problem::UrlInvalid => Field::Url,
+            Problem::IconMissing => Field::Icon,
+            Problem::CategoryMissing => Field::Category,
+        }
+    }
+
+    fn is_missing(&self) -> bool {
+        matches!(
+            self,
+            Problem::TitleMissing
+                | Problem::UrlMissing
+                | Problem::IconMissing
+                | Problem::CategoryMissing
+        )
+    }
+
+    /// Whether the app can't be created until it is fixed, rather than being a warning.
+    fn blocks_saving(&self) -> bool {
+        !matches!(self, Problem::TitleTooShort | Problem::DuplicateName)
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Problem::TitleMissing => fl!("validation-title-missing"),
+            Problem::TitleTooShort => fl!("warning-app-name"),
+            Problem::DuplicateName => fl!("validation-duplicate-name"),
+            Problem::UrlMissing => fl!("validation-url-missing"),
+            Problem::UrlInvalid => fl!("warning-app-url"),
+            Problem::IconMissing => fl!("validation-icon-missing"),
+            Problem::CategoryMissing => fl!("validation-category-missing"),
+        }
+    }
+}
+
+/// A category to put an app in: one of the XDG categories, or one made by the user,
+/// which menus show within its XDG category.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CategoryChoice {
+    pub category: webapps::Category,
+    pub custom: Option<String>,
+}
+
+/// The categories offered for apps, the XDG ones first.
+#[derive(Debug, Clone, Default)]
+pub struct CategoryChoices {
+    names: Vec<String>,
+    choices: Vec<CategoryChoice>,
+}
+
+impl CategoryChoices {
+    pub fn new(custom: &[webapps::CustomCategory]) -> Self {
+        let xdg = webapps::Category::iter().map(|category| CategoryChoice {
+            category,
+            custom: None,
+        });
+        let custom = custom.iter().map(|custom| CategoryChoice {
+            category: custom.xdg.clone(),
+            custom: Some(custom.name.clone()),
+        });
+        let choices: Vec<CategoryChoice> = xdg.chain(custom).collect();
+        let names = choices
+            .iter()
+            .map(|choice| match &choice.custom {
+                Some(name) => name.clone(),
+                None => choice.category.name(),
+            })
+            .collect();
+        Self { names, choices }
+    }
+
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    pub fn position(&self, choice: &CategoryChoice) -> Option<usize> {
+        self.choices.iter().position(|c| c == choice)
+    }
+
+    /// A dropdown to pick one of the categories, with `selected` picked.
+    pub fn dropdown<M: Clone + 'static>(
+        &self,
+        selected: Option<&CategoryChoice>,
+        on_select: impl Fn(CategoryChoice) -> M + 'static,
+    ) -> Element<'_, M> {
+        let choices = self.choices.clone();
+        let selected = selected.and_then(|selected| self.position(selected));
+        widget::dropdown(&self.names, selected, move |idx| {
+            on_select(choices.get(idx).cloned().unwrap_or_default())
+        })
+        .into()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AppEditor {
     pub app_browser: Option<webapps::browser::Browser>,
@@ -26,6 +322,9 @@ pub struct AppEditor {
     pub app_url: String,
     pub app_icon: String,
     pub app_category: webapps::Category,
+    // Tags as chips, and the one being typed
+    pub app_tags: Vec<String>,
+    pub tag_input: String,
     pub app_persistent: bool,
     pub app_window_width: String,
     pub app_window_height: String,
@@ -33,23 +332,56 @@ pub struct AppEditor {
     pub app_window_decorations: bool,
     pub app_private_mode: bool,
     pub app_simulate_mobile: bool,
-    pub app_custom_css: String,
-    pub app_custom_js: String,
+    pub app_custom_css: CodeEditor,
+    pub app_custom_js: CodeEditor,
+    // Linked files read at launch instead of the CSS and JavaScript above
+    pub app_custom_css_file: Option<PathBuf>,
+    pub app_custom_js_file: Option<PathBuf>,
+    // Pages the CSS and JavaScript run on, as a comma-separated list of patterns
+    pub app_custom_css_scope: String,
+    pub app_custom_js_scope: String,
+    // Ready-made CSS offered next to the custom CSS, loaded when first shown
+    pub show_styles: bool,
+    pub site_styles: Vec<webapps::site_styles::SiteStyle>,
+    pub styles_fetching: bool,
+    pub styles_fetch_failed: bool,
+    // A preview window follows the editor once it was opened
+    pub previewing: bool,
     pub selected_icon: Option<webapps::Icon>,
-    pub categories: Vec<String>,
-    pub category_idx: Option<usize>,
+    // The user's own category the app is in, if any
+    pub app_custom_category: Option<String>,
+    /// New id being typed for an installed app
+    pub app_id_input: String,
     pub is_installed: bool,
     pub app_user_agent: usize,
     pub app_custom_ua: String,
     pub user_agent_options: Vec<String>,
     pub app_allow_camera: bool,
     pub app_allow_microphone: bool,
+    pub app_allow_screen_sharing: bool,
     pub app_allow_geolocation: bool,
     pub app_allow_notifications: bool,
     pub app_url_schemes: String,
+    // Navigation allow/deny rules, comma-separated host patterns
+    pub app_allowed_hosts: String,
+    pub app_blocked_hosts: String,
     pub show_advanced: bool,
     pub thumbnail_handle: Option<widget::image::Handle>,
     pub thumbnail_loading: bool,
+    // Cancels favicon, title and thumbnail fetches for the previous URL
+    fetch_cancel: webapps::http::Cancel,
+    // Whether the URL can be reached, checked a moment after it was entered
+    pub url_checking: bool,
+    pub url_check: Option<Result<webapps::http::Head, String>>,
+    // Icon editing: fit, padding and radius in percent, and a hex background color
+    pub show_icon_edit: bool,
+    pub icon_edit_fit: usize,
+    pub icon_fit_options: Vec<String>,
+    pub icon_edit_padding: String,
+    pub icon_edit_radius: String,
+    pub icon_edit_background: String,
+    // The icon as chosen, which every edit starts from
+    icon_edit_source: Option<String>,
     // #53, #60, #61: Privacy features
     pub app_content_blocking: bool,
     pub app_block_cookies: bool,
@@ -63,24 +395,133 @@ pub struct AppEditor {
     // #57: Usage statistics (read-only display)
     pub app_launch_count: u64,
     pub app_last_launched: Option<u64>,
+    pub app_stats: webapps::stats::UsageStats,
+    // Bookmarks, today's usage and accepted certificates recorded by the webview
+    pub app_state: webapps::app_state::AppState,
     // #59: Minimize to background
     pub app_minimize_to_background: bool,
     // #62: Auto dark mode
     pub app_auto_dark_mode: bool,
+    // Auto-reload interval (minutes)
+    pub app_auto_reload: String,
+    // Daily usage limit (minutes)
+    pub app_daily_limit: String,
+    // Memory saver: suspend after hidden (minutes)
+    pub app_suspend_after: String,
+    // Splash screen and theme color
+    pub app_splash_screen: bool,
+    pub app_theme_color: String,
+    // Navigation toolbar
+    pub app_navigation_toolbar: bool,
+    // Profile storage usage per category and cache limit
+    pub storage_usage: Vec<(webapps::ProfileData, u64)>,
+    pub app_cache_limit: String,
+    // Recently visited pages, newest first
+    pub history: Vec<webapps::history::HistoryEntry>,
+    // Latest navigation audit log entries, newest first
+    pub navigation_log: Vec<webapps::audit::AuditEntry>,
+    // Cookies in the persistent profile, by domain
+    pub cookies: Vec<webapps::cookies::Cookie>,
+    // Ephemeral per-launch profile
+    pub app_ephemeral_profile: bool,
+    // WebKit feature toggles
+    pub app_web_features: webapps::browser::WebFeatures,
+    // Geolocation accuracy
+    pub app_geolocation_accuracy: usize,
+    pub geolocation_accuracy_options: Vec<String>,
+    // Secret Service password autofill
+    pub app_password_autofill: bool,
+    // Extra CA bundle path
+    pub app_ca_certificate: String,
+    // Do Not Track / Global Privacy Control
+    pub app_do_not_track: bool,
+    // Referrer policy
+    pub app_referrer_policy: usize,
+    pub referrer_policy_options: Vec<String>,
+    // Fingerprinting resistance
+    pub app_fingerprint_resistance: bool,
+    // Muted audio
+    pub app_muted: bool,
+    pub autoplay_options: Vec<String>,
+    // Data-saver mode
+    pub app_data_saver: bool,
+    // Preferred trigger for the show/hide global shortcut
+    pub app_global_shortcut: String,
+    // Start hidden, only appearing via tray or shortcut
+    pub app_start_hidden: bool,
+    // Start at login, optionally hidden only then
+    pub app_autostart: bool,
+    pub app_autostart_hidden: bool,
+    // Notification quiet hours, written as HH:MM-HH:MM
+    pub app_quiet_hours: String,
+    pub app_quiet_while_locked: bool,
+    pub app_quiet_action: usize,
+    pub quiet_action_options: Vec<String>,
+    // Spell checking
+    pub app_spellcheck: bool,
+    // Comma-separated spell checking languages
+    pub app_spellcheck_languages: String,
+    // Folder the upload file chooser starts in
+    pub app_upload_directory: String,
+    // Start maximized or fullscreen
+    pub app_window_mode: usize,
+    pub window_mode_options: Vec<String>,
+    // Window position and target monitor
+    pub app_window_x: String,
+    pub app_window_y: String,
+    pub app_monitor: String,
+    // Show on all workspaces
+    pub app_sticky: bool,
+    // Hide from the taskbar and dock (for tray-only apps)
+    pub app_skip_taskbar: bool,
+    // Software rendering fallback
+    pub app_disable_hardware_acceleration: bool,
+    // Device emulation preset and its pixel ratio
+    pub app_device_preset: usize,
+    pub device_preset_options: Vec<String>,
+    pub app_device_pixel_ratio: String,
+    // Swipe gestures for back/forward
+    pub app_swipe_navigation: bool,
+    // Context menu
+    pub app_context_menu: usize,
+    pub context_menu_options: Vec<String>,
+    // Navigation audit log
+    pub app_navigation_log: bool,
+    // App lock: unlock method, new PIN (never loaded back), hash of the saved PIN and idle
+    // re-lock minutes
+    pub app_lock_enabled: bool,
+    pub app_lock_method: usize,
+    pub lock_method_options: Vec<String>,
+    pub app_lock_pin: String,
+    pub app_lock_pin_hash: String,
+    pub app_lock_idle: String,
+    // Bubblewrap sandbox
+    pub app_sandbox: bool,
+    // Network isolation
+    pub app_network_isolation: usize,
+    pub network_isolation_options: Vec<String>,
+    // DNS-over-HTTPS resolver
+    pub app_doh_url: String,
+    // Host overrides (`host=address` pairs)
+    pub app_host_overrides: String,
+    // Earlier states for undo and redo, by the kind of message that changed them
+    edits: EditHistory<AppEditor, std::mem::Discriminant<Message>>,
+    // The app as last saved, to tell whether there are unsaved changes
+    saved_state: String,
+    // Guided creation shown in place of the form
+    pub wizard: Option<Wizard>,
 }
 
 impl Default for AppEditor {
     fn default() -> Self {
-        let categories = webapps::Category::iter()
-            .map(|c| c.name())
-            .collect::<Vec<String>>();
-
-        AppEditor {
+        let mut editor = AppEditor {
             app_browser: None,
             app_title: String::new(),
             app_url: String::new(),
             app_icon: String::new(),
             app_category: webapps::Category::default(),
+            app_tags: Vec::new(),
+            tag_input: String::new(),
             app_persistent: false,
             app_window_width: webapps::DEFAULT_WINDOW_WIDTH.to_string(),
             app_window_height: webapps::DEFAULT_WINDOW_HEIGHT.to_string(),
@@ -88,11 +529,20 @@ impl Default for AppEditor {
             app_window_decorations: true,
             app_private_mode: false,
             app_simulate_mobile: false,
-            app_custom_css: String::new(),
-            app_custom_js: String::new(),
+            app_custom_css: CodeEditor::new(Language::Css, ""),
+            app_custom_js: CodeEditor::new(Language::JavaScript, ""),
+            app_custom_css_file: None,
+            app_custom_js_file: None,
+            app_custom_css_scope: String::new(),
+            app_custom_js_scope: String::new(),
+            show_styles: false,
+            site_styles: Vec::new(),
+            styles_fetching: false,
+            styles_fetch_failed: false,
+            previewing: false,
             selected_icon: None,
-            categories,
-            category_idx: Some(0),
+            app_custom_category: None,
+            app_id_input: String::new(),
             is_installed: false,
             app_user_agent: 0,
             app_custom_ua: String::new(),
@@ -103,12 +553,23 @@ impl Default for AppEditor {
             ],
             app_allow_camera: false,
             app_allow_microphone: false,
+            app_allow_screen_sharing: false,
             app_allow_geolocation: false,
             app_allow_notifications: false,
             app_url_schemes: String::new(),
+            app_allowed_hosts: String::new(),
+            app_blocked_hosts: String::new(),
             show_advanced: false,
             thumbnail_handle: None,
             thumbnail_loading: false,
+            fetch_cancel: webapps::http::Cancel::default(),
+            show_icon_edit: false,
+            icon_edit_fit: 0,
+            icon_fit_options: vec![fl!("icon-fit-pad"), fl!("icon-fit-crop")],
+            icon_edit_padding: String::new(),
+            icon_edit_radius: String::new(),
+            icon_edit_background: String::new(),
+            icon_edit_source: None,
             app_content_blocking: false,
             app_block_cookies: false,
             app_block_webrtc: false,
@@ -117,19 +578,145 @@ impl Default for AppEditor {
             app_restore_session: false,
             app_launch_count: 0,
             app_last_launched: None,
+            app_stats: webapps::stats::UsageStats::default(),
+            app_state: webapps::app_state::AppState::default(),
             app_minimize_to_background: false,
             app_auto_dark_mode: false,
-        }
+            app_auto_reload: String::new(),
+            app_daily_limit: String::new(),
+            app_suspend_after: String::new(),
+            app_splash_screen: true,
+            app_theme_color: String::new(),
+            app_navigation_toolbar: false,
+            storage_usage: Vec::new(),
+            history: Vec::new(),
+            navigation_log: Vec::new(),
+            cookies: Vec::new(),
+            app_cache_limit: String::new(),
+            app_ephemeral_profile: false,
+            app_web_features: webapps::browser::WebFeatures::default(),
+            app_geolocation_accuracy: geolocation_accuracy_index(
+                webapps::browser::GeolocationAccuracy::default(),
+            ),
+            geolocation_accuracy_options: vec![
+                fl!("geolocation-accuracy-country"),
+                fl!("geolocation-accuracy-city"),
+                fl!("geolocation-accuracy-neighborhood"),
+                fl!("geolocation-accuracy-street"),
+                fl!("geolocation-accuracy-exact"),
+            ],
+            app_password_autofill: false,
+            app_ca_certificate: String::new(),
+            app_do_not_track: false,
+            app_referrer_policy: 0,
+            referrer_policy_options: vec![
+                fl!("referrer-policy-default"),
+                fl!("referrer-policy-trim"),
+                fl!("referrer-policy-same-origin"),
+                fl!("referrer-policy-none"),
+            ],
+            app_fingerprint_resistance: false,
+            app_muted: false,
+            autoplay_options: vec![
+                fl!("autoplay-allow"),
+                fl!("autoplay-allow-muted"),
+                fl!("autoplay-block"),
+            ],
+            app_data_saver: false,
+            app_global_shortcut: String::new(),
+            app_start_hidden: false,
+            app_autostart: false,
+            app_autostart_hidden: false,
+            app_quiet_hours: String::new(),
+            app_quiet_while_locked: false,
+            app_quiet_action: 0,
+            quiet_action_options: vec![
+                fl!("quiet-hours-suppress"),
+                fl!("quiet-hours-low-urgency"),
+            ],
+            app_spellcheck: false,
+            app_spellcheck_languages: String::new(),
+            app_upload_directory: String::new(),
+            app_window_mode: 0,
+            window_mode_options: vec![
+                fl!("window-mode-normal"),
+                fl!("window-mode-maximized"),
+                fl!("window-mode-fullscreen"),
+            ],
+            app_window_x: String::new(),
+            app_window_y: String::new(),
+            app_monitor: String::new(),
+            app_sticky: false,
+            app_skip_taskbar: false,
+            app_disable_hardware_acceleration: false,
+            app_device_preset: 0,
+            device_preset_options: vec![
+                fl!("device-preset-off"),
+                fl!("device-preset-phone"),
+                fl!("device-preset-tablet"),
+                fl!("device-preset-custom"),
+            ],
+            app_device_pixel_ratio: String::from("1"),
+            app_swipe_navigation: true,
+            app_context_menu: 0,
+            context_menu_options: vec![
+                fl!("context-menu-default"),
+                fl!("context-menu-extended"),
+                fl!("context-menu-disabled"),
+            ],
+            app_navigation_log: false,
+            app_lock_enabled: false,
+            app_lock_method: 0,
+            // The polkit action can't be installed from inside Flatpak
+            lock_method_options: [
+                Some(fl!("app-lock-method-pin")),
+                (!webapps::in_flatpak()).then(|| fl!("app-lock-method-system")),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            app_lock_pin: String::new(),
+            app_lock_pin_hash: String::new(),
+            app_lock_idle: String::new(),
+            app_sandbox: false,
+            app_network_isolation: 0,
+            network_isolation_options: vec![
+                fl!("network-isolation-off"),
+                fl!("network-isolation-localhost"),
+                fl!("network-isolation-none"),
+            ],
+            app_doh_url: String::new(),
+            app_host_overrides: String::new(),
+            edits: EditHistory::default(),
+            saved_state: String::new(),
+            wizard: None,
+        };
+        editor.saved_state = editor.state();
+        editor
     }
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
-    Category(usize),
+    ApplyIconEdit,
+    Category(CategoryChoice),
+    /// Tag being typed; a comma adds it
+    TagInput(String),
+    AddTag,
+    RemoveTag(usize),
+    AppIdInput(String),
+    RenameAppId,
+    ChooseSiteIcon,
     Done,
     DownloadFavicon,
     Duplicate,
     FaviconResult(Option<String>),
+    IconEditBackground(String),
+    IconEdited(Option<webapps::Icon>),
+    IconEditFit(usize),
+    IconEditPadding(String),
+    IconEditRadius(String),
+    ToggleIconEdit,
     PersistentProfile(bool),
     LaunchApp,
     OpenIconPicker,
@@ -140,16 +727,43 @@ pub enum Message {
     WindowDecorations(bool),
     AppIncognito(bool),
     AppSimulateMobile(bool),
-    CustomCss(String),
-    CustomJs(String),
+    CustomCss(text_editor::Action),
+    CustomJs(text_editor::Action),
+    PickCodeFile(Language, CodeFile),
+    CodeFilePicked(Language, CodeFile, PathBuf, String),
+    UnlinkCodeFile(Language),
+    CodeScope(Language, String),
+    ToggleStyles,
+    FetchStyles,
+    StylesFetched(Result<Vec<webapps::site_styles::SiteStyle>, String>),
+    ApplyStyle(usize),
+    Preview,
     UserAgentSelect(usize),
     CustomUserAgent(String),
     AllowCamera(bool),
     AllowMicrophone(bool),
+    AllowScreenSharing(bool),
     AllowGeolocation(bool),
     AllowNotifications(bool),
     ClearAppData,
+    ClearAppDataCategory(webapps::ProfileData),
+    LoadStorageUsage,
+    StorageUsage(Vec<(webapps::ProfileData, u64)>),
+    History(Vec<webapps::history::HistoryEntry>),
+    Cookies(Vec<webapps::cookies::Cookie>),
+    DeleteCookie(usize),
+    ExportCookies,
+    CacheLimit(String),
+    EphemeralProfile(bool),
+    EnableJavaScript(bool),
+    EnableWebGl(bool),
+    AutoplayPolicy(usize),
+    SmoothScrolling(bool),
+    PageCache(bool),
+    GeolocationAccuracy(usize),
     UrlSchemes(String),
+    AllowedHosts(String),
+    BlockedHosts(String),
     SiteTitleResult(Option<String>),
     ToggleAdvanced(bool),
     FetchThumbnail,
@@ -163,9 +777,833 @@ pub enum Message {
     RestoreSession(bool),
     MinimizeToBackground(bool),
     AutoDarkMode(bool),
+    AutoReload(String),
+    DailyLimit(String),
+    SuspendAfter(String),
+    SplashScreen(bool),
+    ThemeColor(String),
+    NavigationToolbar(bool),
+    PasswordAutofill(bool),
+    RemoveTlsException(usize),
+    RemoveBookmark(usize),
+    CaCertificate(String),
+    DoNotTrack(bool),
+    ReferrerPolicy(usize),
+    FingerprintResistance(bool),
+    Muted(bool),
+    DataSaver(bool),
+    GlobalShortcut(String),
+    StartHidden(bool),
+    Autostart(bool),
+    AutostartHidden(bool),
+    QuietHours(String),
+    QuietWhileLocked(bool),
+    QuietAction(usize),
+    Spellcheck(bool),
+    SpellcheckLanguages(String),
+    UploadDirectory(String),
+    WindowMode(usize),
+    WindowX(String),
+    WindowY(String),
+    Monitor(String),
+    Sticky(bool),
+    SkipTaskbar(bool),
+    DisableHardwareAcceleration(bool),
+    DevicePreset(usize),
+    DevicePixelRatio(String),
+    SwipeNavigation(bool),
+    ContextMenu(usize),
+    NavigationLog(bool),
+    ClearNavigationLog,
+    AppLock(bool),
+    AppLockMethod(usize),
+    AppLockPin(String),
+    AppLockIdle(String),
+    Sandbox(bool),
+    NetworkIsolation(usize),
+    DohUrl(String),
+    HostOverrides(String),
+    Undo,
+    Redo,
+    UrlChecked(String, Result<webapps::http::Head, String>),
+    UseRedirectTarget,
+    StartWizard,
+    WizardStep(Step),
+    WindowPreset(WindowPreset),
+    PrivacyPreset(PrivacyPreset),
+    CloseWizard,
 }
 
 impl AppEditor {
+    /// Show the app as entered in the editor in the preview, once it was opened. Returns
+    /// the file the preview reads.
+    pub fn update_preview(&self) -> Option<PathBuf> {
+        if !self.previewing || !webapps::url_valid(&self.app_url) {
+            return None;
+        }
+        webapps::preview::write(&self.launcher())
+            .inspect_err(|e| tracing::error!("Failed to write the preview: {e}"))
+            .ok()
+    }
+
+    /// The app as entered in the editor, under its own id or a placeholder for new apps.
+    fn launcher(&self) -> webapps::launcher::WebAppLauncher {
+        webapps::launcher::WebAppLauncher {
+            browser: self.edited_browser(),
+            name: self.app_title.clone(),
+            icon: self.app_icon.clone(),
+            category: self.app_category.clone(),
+            custom_category: self.app_custom_category.clone(),
+            tags: self.app_tags.clone(),
+        }
+    }
+
+    fn category_choice(&self) -> CategoryChoice {
+        CategoryChoice {
+            category: self.app_category.clone(),
+            custom: self.app_custom_category.clone(),
+        }
+    }
+
+    fn category_name(&self) -> String {
+        self.app_custom_category
+            .clone()
+            .unwrap_or_else(|| self.app_category.name())
+    }
+
+    /// The app as entered, serialized to compare it with how it was saved. A PIN typed
+    /// into the lock settings counts as a change without being hashed.
+    fn state(&self) -> String {
+        ron::to_string(&(self.launcher(), self.app_lock_pin.trim())).unwrap_or_default()
+    }
+
+    /// Whether the app was changed since it was saved, or since the editor was opened for
+    /// a new app.
+    pub fn is_modified(&self) -> bool {
+        self.state() != self.saved_state
+    }
+
+    /// A copy of the editor for the edit history, without the history itself.
+    fn snapshot(&mut self) -> AppEditor {
+        let edits = std::mem::take(&mut self.edits);
+        let snapshot = self.clone();
+        self.edits = edits;
+        snapshot
+    }
+
+    /// Go back to the fields of `snapshot`, keeping what was loaded or opened since.
+    fn restore(&mut self, snapshot: AppEditor) {
+        let current = std::mem::replace(self, snapshot);
+        self.edits = current.edits;
+        self.saved_state = current.saved_state;
+        self.wizard = current.wizard;
+        self.fetch_cancel = current.fetch_cancel;
+        self.previewing = current.previewing;
+        self.show_styles = current.show_styles;
+        self.site_styles = current.site_styles;
+        self.styles_fetching = current.styles_fetching;
+        self.styles_fetch_failed = current.styles_fetch_failed;
+        self.show_advanced = current.show_advanced;
+        self.show_icon_edit = current.show_icon_edit;
+        self.thumbnail_handle = current.thumbnail_handle;
+        self.thumbnail_loading = current.thumbnail_loading;
+        self.storage_usage = current.storage_usage;
+        self.history = current.history;
+        self.navigation_log = current.navigation_log;
+        self.cookies = current.cookies;
+        self.app_state = current.app_state;
+    }
+
+    /// A new app's settings as entered in the editor.
+    fn build_browser(&self, app_id: &str) -> webapps::browser::Browser {
+        self.apply_settings(webapps::browser::Browser::new(app_id, self.app_persistent))
+    }
+
+    /// The app's settings as entered in the editor: an installed app's browser with the
+    /// fields changed, or a new one.
+    fn edited_browser(&self) -> webapps::browser::Browser {
+        match &self.app_browser {
+            Some(browser) => self.apply_settings(browser.clone()),
+            None => self.build_browser("preview"),
+        }
+    }
+
+    /// `browser` with every setting in the editor applied, keeping what is only shown
+    /// here, like launch counts.
+    fn apply_settings(
+        &self,
+        mut browser: webapps::browser::Browser,
+    ) -> webapps::browser::Browser {
+        if browser.profile.is_some() != self.app_persistent {
+            browser.set_persistent(self.app_persistent);
+        }
+        browser.window_title = Some(self.app_title.clone());
+        browser.url = Some(webapps::normalize_app_url(&self.app_url));
+        browser.window_size = Some(self.app_window_size.clone());
+        browser.window_decorations = Some(self.app_window_decorations);
+        browser.private_mode = Some(self.app_private_mode);
+        browser.try_simulate_mobile = Some(self.app_simulate_mobile);
+        browser.custom_css =
+            (!self.app_custom_css.is_empty()).then(|| self.app_custom_css.text());
+        browser.custom_js = (!self.app_custom_js.is_empty()).then(|| self.app_custom_js.text());
+        browser.custom_css_file = self.app_custom_css_file.clone();
+        browser.custom_js_file = self.app_custom_js_file.clone();
+        let scope = |text: &str| {
+            Some(webapps::browser::NavigationRules::parse_patterns(text))
+                .filter(|scope| !scope.is_empty())
+        };
+        browser.custom_css_scope = scope(&self.app_custom_css_scope);
+        browser.custom_js_scope = scope(&self.app_custom_js_scope);
+        browser.user_agent = Some(match self.app_user_agent {
+            1 => webapps::browser::UserAgent::Mobile,
+            2 => webapps::browser::UserAgent::Custom(self.app_custom_ua.clone()),
+            _ => webapps::browser::UserAgent::Default,
+        });
+        browser.permissions = Some(webapps::browser::PermissionPolicy {
+            allow_camera: self.app_allow_camera,
+            allow_microphone: self.app_allow_microphone,
+            allow_geolocation: self.app_allow_geolocation,
+            allow_notifications: self.app_allow_notifications,
+            allow_screen_sharing: self.app_allow_screen_sharing,
+        });
+        // Only names the webview will register are kept
+        browser.url_schemes = Some(self.app_url_schemes.split(',').map(String::from).collect());
+        let schemes = browser.valid_url_schemes();
+        browser.url_schemes = (!schemes.is_empty()).then_some(schemes);
+        let rules = webapps::browser::NavigationRules {
+            allow: webapps::browser::NavigationRules::parse_patterns(
+                &self.app_allowed_hosts,
+            ),
+            deny: webapps::browser::NavigationRules::parse_patterns(
+                &self.app_blocked_hosts,
+            ),
+        };
+        browser.navigation_rules = (!rules.is_empty()).then_some(rules);
+        browser.content_blocking = Some(self.app_content_blocking);
+        browser.block_third_party_cookies = Some(self.app_block_cookies);
+        browser.block_webrtc = Some(self.app_block_webrtc);
+        browser.proxy_url = (!self.app_proxy_url.is_empty()).then(|| self.app_proxy_url.clone());
+        let zoom: f64 = self.app_zoom_level.parse().unwrap_or(1.0);
+        browser.zoom_level = Some(zoom.clamp(0.25, 5.0));
+        browser.restore_session = Some(self.app_restore_session);
+        browser.minimize_to_background = Some(self.app_minimize_to_background);
+        browser.auto_dark_mode = Some(self.app_auto_dark_mode);
+        let reload_minutes: u32 = self.app_auto_reload.parse().unwrap_or(0);
+        browser.auto_reload_minutes = (reload_minutes > 0).then(|| reload_minutes.min(24 * 60));
+        let limit_minutes: u32 = self.app_daily_limit.parse().unwrap_or(0);
+        browser.daily_limit_minutes = (limit_minutes > 0).then(|| limit_minutes.min(24 * 60));
+        let suspend_minutes: u32 = self.app_suspend_after.parse().unwrap_or(0);
+        browser.suspend_after_minutes =
+            (suspend_minutes > 0).then(|| suspend_minutes.min(24 * 60));
+        browser.splash_screen = Some(self.app_splash_screen);
+        browser.theme_color = Some(self.app_theme_color.trim().to_string())
+            .filter(|color| webapps::parse_hex_color(color).is_some());
+        browser.navigation_toolbar = Some(self.app_navigation_toolbar);
+        browser.cache_limit_mb = self.app_cache_limit.parse::<u32>().ok();
+        browser.ephemeral_profile = Some(self.app_ephemeral_profile);
+        browser.web_features = Some(self.app_web_features.clone());
+        browser.geolocation_accuracy = self
+            .app_allow_geolocation
+            .then(|| {
+                webapps::browser::GeolocationAccuracy::iter().nth(self.app_geolocation_accuracy)
+            })
+            .flatten();
+        browser.password_autofill = Some(self.app_password_autofill);
+        browser.ca_certificate = Some(self.app_ca_certificate.trim().to_string())
+            .filter(|path| !path.is_empty());
+        browser.do_not_track = Some(self.app_do_not_track);
+        browser.referrer_policy =
+            webapps::browser::ReferrerPolicy::iter().nth(self.app_referrer_policy);
+        browser.fingerprint_resistance = Some(self.app_fingerprint_resistance);
+        browser.muted = Some(self.app_muted);
+        browser.data_saver = Some(self.app_data_saver);
+        browser.global_shortcut = Some(self.app_global_shortcut.trim().to_string())
+            .filter(|shortcut| !shortcut.is_empty());
+        browser.start_hidden = Some(self.app_start_hidden);
+        browser.autostart = Some(self.app_autostart);
+        browser.autostart_hidden = Some(self.app_autostart_hidden);
+        let quiet_window =
+            webapps::browser::QuietHours::parse_window(&self.app_quiet_hours);
+        browser.quiet_hours = (quiet_window.is_some() || self.app_quiet_while_locked).then(|| {
+            webapps::browser::QuietHours {
+                window: quiet_window,
+                while_locked: self.app_quiet_while_locked,
+                action: webapps::browser::QuietHoursAction::iter()
+                    .nth(self.app_quiet_action)
+                    .unwrap_or_default(),
+            }
+        });
+        browser.spellcheck = Some(self.app_spellcheck);
+        let languages: Vec<String> = self
+            .app_spellcheck_languages
+            .split(',')
+            .map(|l| l.trim().to_string())
+            .filter(|l| {
+                !l.is_empty()
+                    && l
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-'))
+            })
+            .collect();
+        browser.spellcheck_languages = (!languages.is_empty()).then_some(languages);
+        browser.upload_directory = Some(self.app_upload_directory.trim().to_string())
+            .filter(|directory| !directory.is_empty());
+        browser.window_mode =
+            webapps::browser::WindowMode::iter().nth(self.app_window_mode);
+        browser.window_position = self.app_window_x.parse().ok().zip(self.app_window_y.parse().ok());
+        browser.monitor =
+            Some(self.app_monitor.trim().to_string()).filter(|monitor| !monitor.is_empty());
+        browser.sticky = Some(self.app_sticky);
+        browser.skip_taskbar = Some(self.app_skip_taskbar);
+        browser.disable_hardware_acceleration = Some(self.app_disable_hardware_acceleration);
+        let device_preset = webapps::browser::DevicePreset::iter()
+            .nth(self.app_device_preset)
+            .unwrap_or_default();
+        browser.device_emulation = (device_preset != webapps::browser::DevicePreset::Off).then(|| {
+            webapps::browser::DeviceEmulation {
+                preset: device_preset,
+                device_pixel_ratio: self
+                    .app_device_pixel_ratio
+                    .parse::<f64>()
+                    .map_or(1.0, |ratio| ratio.clamp(0.5, 5.0)),
+            }
+        });
+        browser.swipe_navigation = Some(self.app_swipe_navigation);
+        browser.context_menu =
+            webapps::browser::ContextMenu::iter().nth(self.app_context_menu);
+        browser.navigation_log = Some(self.app_navigation_log);
+        // The PIN field starts empty, so an unchanged PIN keeps the saved hash. A newly
+        // entered one is only hashed on saving, see `hash_entered_pin`.
+        browser.app_lock = None;
+        if self.app_lock_enabled {
+            let method = webapps::browser::LockMethod::iter()
+                .nth(self.app_lock_method)
+                .unwrap_or_default();
+            // A PIN lock without a PIN could never be opened
+            if method == webapps::browser::LockMethod::System
+                || !self.app_lock_pin_hash.is_empty()
+                || !self.app_lock_pin.trim().is_empty()
+            {
+                browser.app_lock = Some(webapps::browser::AppLock {
+                    method,
+                    pin_hash: self.app_lock_pin_hash.clone(),
+                    idle_minutes: self.app_lock_idle.parse().unwrap_or(0),
+                });
+            }
+        }
+        browser.sandbox = Some(self.app_sandbox);
+        browser.network_isolation = webapps::browser::NetworkIsolation::iter()
+            .nth(self.app_network_isolation);
+        browser.doh_url = Some(self.app_doh_url.trim().to_string())
+            .filter(|url| url.starts_with("https://"));
+        browser.host_overrides =
+            webapps::browser::HostOverride::parse_list(&self.app_host_overrides)
+                .filter(|overrides| !overrides.is_empty());
+        browser
+    }
+
+    /// The editor and linked file of the custom CSS or JavaScript.
+    fn code_mut(&mut self, language: Language) -> (&mut CodeEditor, &mut Option<PathBuf>) {
+        match language {
+            Language::Css => (&mut self.app_custom_css, &mut self.app_custom_css_file),
+            Language::JavaScript => (&mut self.app_custom_js, &mut self.app_custom_js_file),
+        }
+    }
+
+    /// The custom CSS or JavaScript with buttons to load it from a file, or the linked
+    /// file in place of the editor.
+    fn code_field(&self, language: Language) -> Element<'_, Message> {
+        let on_action: fn(text_editor::Action) -> Message = match language {
+            Language::Css => Message::CustomCss,
+            Language::JavaScript => Message::CustomJs,
+        };
+        let (title, placeholder, editor, file, scope) = match language {
+            Language::Css => (
+                fl!("custom-css"),
+                fl!("custom-css-placeholder"),
+                &self.app_custom_css,
+                &self.app_custom_css_file,
+                &self.app_custom_css_scope,
+            ),
+            Language::JavaScript => (
+                fl!("custom-js"),
+                fl!("custom-js-placeholder"),
+                &self.app_custom_js,
+                &self.app_custom_js_file,
+                &self.app_custom_js_scope,
+            ),
+        };
+
+        let header = widget::row()
+            .spacing(8)
+            .align_y(Vertical::Center)
+            .push(widget::text::body(title))
+            .push(widget::horizontal_space())
+            .push_maybe((language == Language::Css).then(|| {
+                widget::button::standard(fl!("site-styles")).on_press(Message::ToggleStyles)
+            }))
+            .push(
+                widget::button::standard(fl!("load-from-file"))
+                    .on_press(Message::PickCodeFile(language, CodeFile::Copy)),
+            )
+            .push(
+                widget::button::standard(fl!("link-file"))
+                    .on_press(Message::PickCodeFile(language, CodeFile::Link)),
+            );
+        let body = match file {
+            Some(file) => widget::row()
+                .spacing(8)
+                .align_y(Vertical::Center)
+                .push(
+                    widget::text::caption(fl!(
+                        "linked-file",
+                        path = file.display().to_string()
+                    ))
+                    .width(Length::Fill),
+                )
+                .push(
+                    widget::button::standard(fl!("unlink-file"))
+                        .on_press(Message::UnlinkCodeFile(language)),
+                )
+                .into(),
+            None => editor.view(placeholder, on_action),
+        };
+
+        widget::column()
+            .spacing(4)
+            .push(header)
+            .push_maybe((language == Language::Css && self.show_styles).then(|| self.styles()))
+            .push(body)
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .align_y(Vertical::Center)
+                    .push(widget::text::caption(fl!("code-scope")))
+                    .push(
+                        widget::text_input(fl!("code-scope-placeholder"), scope)
+                            .on_input(move |scope| Message::CodeScope(language, scope)),
+                    ),
+            )
+            .into()
+    }
+
+    /// Check the URL in the background once the user stopped typing it. The check is
+    /// cancelled with the other fetches when the URL changes again.
+    fn check_url(&mut self) -> Task<Action<crate::pages::Message>> {
+        self.url_check = None;
+        let url = webapps::normalize_app_url(&self.app_url);
+        self.url_checking = webapps::url_valid(&url) && !url.starts_with("file:");
+        if !self.url_checking {
+            return Task::none();
+        }
+        let cancel = self.fetch_cancel.clone();
+        Task::perform(
+            async move {
+                tokio::time::sleep(URL_CHECK_DELAY).await;
+                let options = webapps::http::Options {
+                    retries: 0,
+                    ..Default::default()
+                };
+                let result = webapps::http::head(&url, &options, &cancel).await;
+                (url, result)
+            },
+            |(url, result)| match result {
+                Err(webapps::http::Error::Cancelled) => cosmic::Action::None,
+                result => cosmic::Action::App(crate::pages::Message::Editor(
+                    Message::UrlChecked(url, result.map_err(|e| e.to_string())),
+                )),
+            },
+        )
+    }
+
+    /// Whether the URL can be reached and where it redirects to, below the URL.
+    fn url_status(&self) -> Option<Element<'_, Message>> {
+        let theme = cosmic::theme::active();
+        let (text, color) = match self.url_check.as_ref() {
+            None if self.url_checking => {
+                return Some(widget::text::caption(fl!("url-checking")).into());
+            }
+            None => return None,
+            Some(Ok(head)) if head.is_ok() => (
+                fl!("url-reachable", status = head.status),
+                theme.cosmic().success_color(),
+            ),
+            Some(Ok(head)) => (
+                fl!("url-error-status", status = head.status),
+                theme.cosmic().destructive_color(),
+            ),
+            Some(Err(e)) => (
+                fl!("url-unreachable", reason = e.as_str()),
+                theme.cosmic().destructive_color(),
+            ),
+        };
+        let status = widget::text::caption(text)
+            .class(cosmic::style::Text::Color(cosmic::iced::Color::from(color)));
+
+        // A trailing slash added by the server doesn't count as a redirect
+        let url = webapps::normalize_app_url(&self.app_url);
+        let redirect = self
+            .url_check
+            .as_ref()
+            .and_then(|check| check.as_ref().ok())
+            .filter(|head| head.url.trim_end_matches('/') != url.trim_end_matches('/'));
+        Some(match redirect {
+            Some(head) => widget::row()
+                .spacing(8)
+                .align_y(Vertical::Center)
+                .push(status)
+                .push(widget::text::caption(fl!("url-redirects", url = head.url.as_str())))
+                .push(
+                    widget::button::link(fl!("url-use-redirect"))
+                        .on_press(Message::UseRedirectTarget),
+                )
+                .into(),
+            None => status.into(),
+        })
+    }
+
+    /// The app's id, which can be changed while the app is saved and closed.
+    fn app_id_field(
+        &self,
+        installed: &[webapps::launcher::WebAppLauncher],
+    ) -> Element<'_, Message> {
+        let current = self
+            .app_browser
+            .as_ref()
+            .map(|browser| browser.app_id.id.as_str())
+            .unwrap_or_default();
+        let new_id = webapps::browser::sanitize_app_id(&self.app_id_input);
+        let taken = installed
+            .iter()
+            .any(|app| app.browser.app_id.id == new_id && new_id != current);
+        let can_rename = !new_id.is_empty() && new_id != current && !taken && !self.is_modified();
+        widget::settings::item_row(vec![
+            widget::column()
+                .width(Length::Fill)
+                .push(widget::text::body(fl!("app-id")))
+                .push(widget::text::caption(if taken {
+                    fl!("app-id-taken")
+                } else {
+                    fl!("app-id-description")
+                }))
+                .into(),
+            widget::text_input(current, &self.app_id_input)
+                .on_input(Message::AppIdInput)
+                .on_submit(|_| Message::RenameAppId)
+                .width(Length::Fixed(200.0))
+                .into(),
+            widget::button::standard(fl!("rename"))
+                .on_press_maybe(can_rename.then_some(Message::RenameAppId))
+                .into(),
+        ])
+        .into()
+    }
+
+    /// The app's tags as removable chips, followed by an input for more.
+    fn tags_field(&self) -> Element<'_, Message> {
+        let chips = self.app_tags.iter().enumerate().map(|(idx, tag)| {
+            widget::button::custom(
+                widget::row()
+                    .spacing(4)
+                    .align_y(Vertical::Center)
+                    .push(widget::text::caption(tag.as_str()))
+                    .push(widget::icon::from_name("window-close-symbolic").size(12)),
+            )
+            .padding([2, 8])
+            .class(cosmic::style::Button::Standard)
+            .on_press(Message::RemoveTag(idx))
+            .into()
+        });
+        widget::row::with_children(chips.collect::<Vec<Element<'_, Message>>>())
+            .spacing(4)
+            .align_y(Vertical::Center)
+            .push(
+                widget::text_input(fl!("tags-placeholder"), &self.tag_input)
+                    .on_input(Message::TagInput)
+                    .on_submit(|_| Message::AddTag)
+                    .width(Length::Fixed(140.0)),
+            )
+            .wrap()
+            .into()
+    }
+
+    /// Installed apps that look like the new app being created, as they are for the same
+    /// page or have the same name.
+    pub fn duplicates(
+        &self,
+        installed: &[webapps::launcher::WebAppLauncher],
+    ) -> Vec<webapps::launcher::WebAppLauncher> {
+        if self.app_browser.is_some() {
+            return Vec::new();
+        }
+        let title = self.app_title.trim();
+        installed
+            .iter()
+            .filter(|app| {
+                app.name.trim().eq_ignore_ascii_case(title)
+                    || app
+                        .browser
+                        .url
+                        .as_deref()
+                        .is_some_and(|url| webapps::same_app_url(url, &self.app_url))
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Problems with the app as entered.
+    fn problems(&self, installed: &[webapps::launcher::WebAppLauncher]) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        let title = self.app_title.trim();
+        if title.is_empty() {
+            problems.push(Problem::TitleMissing);
+        } else if title.chars().count() < 3 {
+            problems.push(Problem::TitleTooShort);
+        }
+        let app_id = self.app_browser.as_ref().map(|browser| browser.app_id.as_ref());
+        if !title.is_empty()
+            && installed.iter().any(|app| {
+                Some(app.browser.app_id.as_ref()) != app_id
+                    && app.name.trim().eq_ignore_ascii_case(title)
+            })
+        {
+            problems.push(Problem::DuplicateName);
+        }
+        if self.app_url.trim().is_empty() {
+            problems.push(Problem::UrlMissing);
+        } else if !webapps::app_url_valid(&webapps::normalize_app_url(&self.app_url)) {
+            problems.push(Problem::UrlInvalid);
+        }
+        if self.app_icon.is_empty() {
+            problems.push(Problem::IconMissing);
+        }
+        if self.app_category == webapps::Category::None {
+            problems.push(Problem::CategoryMissing);
+        }
+        problems
+    }
+
+    /// The current step of the guided creation, with buttons to move between steps.
+    fn wizard<'a>(
+        &'a self,
+        wizard: &Wizard,
+        categories: &'a CategoryChoices,
+    ) -> Element<'a, Message> {
+        let url_valid = webapps::app_url_valid(&webapps::normalize_app_url(&self.app_url));
+        let (body, can_continue): (Element<'_, Message>, bool) = match wizard.step {
+            Step::Url => (
+                widget::column()
+                    .spacing(8)
+                    .push(widget::text::body(fl!("wizard-url-description")))
+                    .push(
+                        widget::text_input(fl!("url"), &self.app_url)
+                            .on_input(Message::Url)
+                            .on_submit(move |_| {
+                                let step = if url_valid { Step::Details } else { Step::Url };
+                                Message::WizardStep(step)
+                            }),
+                    )
+                    .push_maybe(self.url_status())
+                    .push_maybe((!self.app_url.is_empty() && !url_valid).then(|| {
+                        widget::text::caption(fl!("warning-app-url")).class(style::Text::Accent)
+                    }))
+                    .into(),
+                url_valid,
+            ),
+            Step::Details => (
+                widget::row()
+                    .spacing(12)
+                    .push(
+                        widget::container(self.icon_element(self.selected_icon.clone()))
+                            .width(96.)
+                            .height(96.),
+                    )
+                    .push(
+                        widget::column()
+                            .spacing(8)
+                            .push(widget::text::body(fl!("wizard-details-description")))
+                            .push(
+                                widget::text_input(fl!("title"), &self.app_title)
+                                    .on_input(Message::Title),
+                            )
+                            .push(
+                                categories
+                                    .dropdown(Some(&self.category_choice()), Message::Category),
+                            )
+                            .push(
+                                widget::row()
+                                    .spacing(8)
+                                    .push(
+                                        widget::button::standard(fl!("choose-site-icon"))
+                                            .on_press_maybe(
+                                                (!webapps::is_local_url(&self.app_url))
+                                                    .then_some(Message::ChooseSiteIcon),
+                                            ),
+                                    )
+                                    .push(
+                                        widget::button::standard(fl!("icon-selector"))
+                                            .on_press(Message::OpenIconPicker),
+                                    ),
+                            )
+                            .push_maybe(self.app_icon.is_empty().then(|| {
+                                widget::text::caption(fl!("wizard-icon-missing"))
+                            })),
+                    )
+                    .into(),
+                !self.app_title.is_empty()
+                    && !self.app_icon.is_empty()
+                    && self.app_category != webapps::Category::None,
+            ),
+            Step::Presets => {
+                let choice = |name: String, description: String, selected: bool, message| {
+                    widget::button::custom(
+                        widget::column()
+                            .spacing(2)
+                            .push(widget::text::body(name))
+                            .push(widget::text::caption(description)),
+                    )
+                    .padding(12)
+                    .width(Length::Fill)
+                    .on_press(message)
+                    .class(if selected {
+                        style::Button::Suggested
+                    } else {
+                        style::Button::Standard
+                    })
+                };
+                let mut column = widget::column()
+                    .spacing(8)
+                    .push(widget::text::heading(fl!("wizard-window")));
+                for preset in WindowPreset::ALL {
+                    column = column.push(choice(
+                        preset.name(),
+                        preset.description(),
+                        preset == wizard.window,
+                        Message::WindowPreset(preset),
+                    ));
+                }
+                column = column.push(widget::text::heading(fl!("wizard-privacy")));
+                for preset in PrivacyPreset::ALL {
+                    column = column.push(choice(
+                        preset.name(),
+                        preset.description(),
+                        preset == wizard.privacy,
+                        Message::PrivacyPreset(preset),
+                    ));
+                }
+                (column.into(), true)
+            }
+            Step::Confirm => (
+                widget::settings::section()
+                    .add(widget::settings::item(
+                        fl!("title"),
+                        widget::text::body(self.app_title.clone()),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("url"),
+                        widget::text::body(webapps::normalize_app_url(&self.app_url)),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("category"),
+                        widget::text::body(self.category_name()),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("wizard-window"),
+                        widget::text::body(wizard.window.name()),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("wizard-privacy"),
+                        widget::text::body(wizard.privacy.name()),
+                    ))
+                    .into(),
+                webapps::launcher::webapplauncher_is_valid(
+                    &self.app_icon,
+                    &self.app_title,
+                    &Some(webapps::normalize_app_url(&self.app_url)),
+                    &self.app_category,
+                ),
+            ),
+        };
+
+        let forward = match wizard.step.next() {
+            Some(next) => widget::button::suggested(fl!("wizard-next"))
+                .on_press_maybe(can_continue.then_some(Message::WizardStep(next))),
+            None => widget::button::suggested(fl!("wizard-create"))
+                .on_press_maybe(can_continue.then_some(Message::Done)),
+        };
+        let footer = widget::row()
+            .spacing(8)
+            .push(widget::button::text(fl!("wizard-full-form")).on_press(Message::CloseWizard))
+            .push(widget::horizontal_space())
+            .push_maybe(wizard.step.previous().map(|previous| {
+                widget::button::standard(fl!("wizard-back"))
+                    .on_press(Message::WizardStep(previous))
+            }))
+            .push(forward);
+
+        widget::container(
+            widget::column()
+                .spacing(24)
+                .push(
+                    widget::column()
+                        .spacing(4)
+                        .push(widget::text::caption(fl!(
+                            "wizard-step",
+                            number = wizard.step.number(),
+                            count = Step::COUNT
+                        )))
+                        .push(widget::text::title3(wizard.step.title())),
+                )
+                .push(body)
+                .push(footer),
+        )
+        .padding(cosmic::iced::Padding::new(0.).left(30.0).right(30.0))
+        .max_width(700)
+        .into()
+    }
+
+    /// The gallery of ready-made CSS for the app's site.
+    fn styles(&self) -> Element<'_, Message> {
+        // Applied to the CSS in the editor, which a linked file would replace
+        let can_apply = self.app_custom_css_file.is_none();
+        let section = self
+            .site_styles
+            .iter()
+            .enumerate()
+            .filter(|(_, style)| style.suits(&self.app_url))
+            .fold(
+                widget::settings::section().title(fl!("site-styles")),
+                |section, (idx, style)| {
+                    section.add(widget::settings::item_row(vec![
+                        widget::column()
+                            .push(widget::text::body(style.name.as_str()))
+                            .push(widget::text::caption(style.description.as_str()))
+                            .width(Length::Fill)
+                            .into(),
+                        widget::button::standard(fl!("apply-style"))
+                            .on_press_maybe(can_apply.then_some(Message::ApplyStyle(idx)))
+                            .into(),
+                    ]))
+                },
+            );
+
+        let status = if self.styles_fetching {
+            fl!("loading")
+        } else if self.styles_fetch_failed {
+            fl!("site-styles-fetch-failed")
+        } else {
+            String::new()
+        };
+        section
+            .add(widget::settings::item_row(vec![
+                widget::text::caption(status).width(Length::Fill).into(),
+                widget::button::standard(fl!("site-styles-update"))
+                    .on_press_maybe((!self.styles_fetching).then_some(Message::FetchStyles))
+                    .into(),
+            ]))
+            .into()
+    }
+
     pub fn from(launcher: webapps::launcher::WebAppLauncher) -> Self {
         let window_size = launcher.browser.window_size.clone().unwrap_or_default();
         let window_decorations = launcher.browser.window_decorations.unwrap_or_default();
@@ -179,6 +1617,7 @@ impl AppEditor {
         editor.app_url = launcher.browser.url.clone().unwrap_or_default();
         editor.app_icon = launcher.icon.clone();
         editor.app_category = launcher.category.clone();
+        editor.app_tags = launcher.tags.clone();
         editor.app_persistent = launcher.browser.profile.is_some();
         editor.app_window_width = window_size.0.to_string();
         editor.app_window_height = window_size.1.to_string();
@@ -186,12 +1625,26 @@ impl AppEditor {
         editor.app_window_decorations = window_decorations;
         editor.app_private_mode = incognito;
         editor.app_simulate_mobile = simulate_mobile;
-        editor.app_custom_css = launcher.browser.custom_css.clone().unwrap_or_default();
-        editor.app_custom_js = launcher.browser.custom_js.clone().unwrap_or_default();
-        editor.category_idx = editor
-            .categories
-            .iter()
-            .position(|c| c == &launcher.category.name());
+        editor.app_custom_css = CodeEditor::new(
+            Language::Css,
+            launcher.browser.custom_css.as_deref().unwrap_or_default(),
+        );
+        editor.app_custom_js = CodeEditor::new(
+            Language::JavaScript,
+            launcher.browser.custom_js.as_deref().unwrap_or_default(),
+        );
+        editor.app_custom_css_file = launcher.browser.custom_css_file.clone();
+        editor.app_custom_js_file = launcher.browser.custom_js_file.clone();
+        editor.app_custom_css_scope = launcher.browser.custom_css_scope
+            .as_ref()
+            .map(|scope| scope.join(", "))
+            .unwrap_or_default();
+        editor.app_custom_js_scope = launcher.browser.custom_js_scope
+            .as_ref()
+            .map(|scope| scope.join(", "))
+            .unwrap_or_default();
+        editor.app_custom_category = launcher.custom_category.clone();
+        editor.app_id_input = launcher.browser.app_id.id.clone();
         editor.is_installed = true;
 
         editor.app_user_agent = match &launcher.browser.user_agent {
@@ -207,6 +1660,7 @@ impl AppEditor {
         let perms = launcher.browser.permissions.clone().unwrap_or_default();
         editor.app_allow_camera = perms.allow_camera;
         editor.app_allow_microphone = perms.allow_microphone;
+        editor.app_allow_screen_sharing = perms.allow_screen_sharing;
         editor.app_allow_geolocation = perms.allow_geolocation;
         editor.app_allow_notifications = perms.allow_notifications;
 
@@ -214,6 +1668,9 @@ impl AppEditor {
             .as_ref()
             .map(|schemes| schemes.join(", "))
             .unwrap_or_default();
+        let rules = launcher.browser.navigation_rules.clone().unwrap_or_default();
+        editor.app_allowed_hosts = rules.allow.join(", ");
+        editor.app_blocked_hosts = rules.deny.join(", ");
 
         editor.app_content_blocking = launcher.browser.content_blocking.unwrap_or(false);
         editor.app_block_cookies = launcher.browser.block_third_party_cookies.unwrap_or(false);
@@ -223,13 +1680,151 @@ impl AppEditor {
         editor.app_restore_session = launcher.browser.restore_session.unwrap_or(false);
         editor.app_launch_count = launcher.browser.launch_count.unwrap_or(0);
         editor.app_last_launched = launcher.browser.last_launched;
+        editor.app_stats = webapps::stats::load(launcher.browser.app_id.as_ref());
+        editor.app_state = webapps::app_state::load(launcher.browser.app_id.as_ref());
         editor.app_minimize_to_background = launcher.browser.minimize_to_background.unwrap_or(false);
         editor.app_auto_dark_mode = launcher.browser.auto_dark_mode.unwrap_or(false);
+        editor.app_auto_reload = launcher
+            .browser
+            .auto_reload_minutes
+            .filter(|m| *m > 0)
+            .map(|m| m.to_string())
+            .unwrap_or_default();
+        editor.app_daily_limit = launcher
+            .browser
+            .daily_limit_minutes
+            .filter(|m| *m > 0)
+            .map(|m| m.to_string())
+            .unwrap_or_default();
+        editor.app_suspend_after = launcher
+            .browser
+            .suspend_after_minutes
+            .filter(|m| *m > 0)
+            .map(|m| m.to_string())
+            .unwrap_or_default();
+        editor.app_splash_screen = launcher.browser.splash_screen.unwrap_or(true);
+        editor.app_theme_color = launcher.browser.theme_color.clone().unwrap_or_default();
+        editor.app_navigation_toolbar = launcher.browser.navigation_toolbar.unwrap_or(false);
+        editor.app_cache_limit = launcher
+            .browser
+            .cache_limit_mb
+            .map(|mb| mb.to_string())
+            .unwrap_or_default();
+        editor.app_ephemeral_profile = launcher.browser.ephemeral_profile.unwrap_or(false);
+        editor.app_web_features = launcher.browser.web_features.clone().unwrap_or_default();
+        editor.app_geolocation_accuracy =
+            geolocation_accuracy_index(launcher.browser.geolocation_accuracy.unwrap_or_default());
+        editor.app_password_autofill = launcher.browser.password_autofill.unwrap_or(false);
+        editor.app_ca_certificate = launcher.browser.ca_certificate.clone().unwrap_or_default();
+        editor.app_do_not_track = launcher.browser.do_not_track.unwrap_or(false);
+        editor.app_referrer_policy =
+            referrer_policy_index(launcher.browser.referrer_policy.unwrap_or_default());
+        editor.app_fingerprint_resistance =
+            launcher.browser.fingerprint_resistance.unwrap_or(false);
+        editor.app_muted = launcher.browser.muted.unwrap_or(false);
+        editor.app_data_saver = launcher.browser.data_saver.unwrap_or(false);
+        editor.app_global_shortcut = launcher.browser.global_shortcut.clone().unwrap_or_default();
+        editor.app_start_hidden = launcher.browser.start_hidden.unwrap_or(false);
+        editor.app_autostart = launcher.browser.autostart.unwrap_or(false);
+        editor.app_autostart_hidden = launcher.browser.autostart_hidden.unwrap_or(false);
+        if let Some(quiet) = &launcher.browser.quiet_hours {
+            editor.app_quiet_hours = quiet
+                .window
+                .map(webapps::browser::QuietHours::format_window)
+                .unwrap_or_default();
+            editor.app_quiet_while_locked = quiet.while_locked;
+            editor.app_quiet_action = quiet_hours_action_index(quiet.action);
+        }
+        editor.app_spellcheck = launcher.browser.spellcheck.unwrap_or(false);
+        editor.app_spellcheck_languages = launcher
+            .browser
+            .spellcheck_languages
+            .clone()
+            .unwrap_or_default()
+            .join(", ");
+        editor.app_upload_directory = launcher.browser.upload_directory.clone().unwrap_or_default();
+        editor.app_window_mode =
+            window_mode_index(launcher.browser.window_mode.unwrap_or_default());
+        if let Some((x, y)) = launcher.browser.window_position {
+            editor.app_window_x = x.to_string();
+            editor.app_window_y = y.to_string();
+        }
+        editor.app_monitor = launcher.browser.monitor.clone().unwrap_or_default();
+        editor.app_sticky = launcher.browser.sticky.unwrap_or(false);
+        editor.app_skip_taskbar = launcher.browser.skip_taskbar.unwrap_or(false);
+        editor.app_disable_hardware_acceleration = launcher.browser.disable_hardware_acceleration.unwrap_or(false);
+        if let Some(device) = &launcher.browser.device_emulation {
+            editor.app_device_preset = device_preset_index(device.preset);
+            editor.app_device_pixel_ratio = device.device_pixel_ratio.to_string();
+        }
+        editor.app_swipe_navigation = launcher.browser.swipe_navigation.unwrap_or(true);
+        editor.app_context_menu =
+            context_menu_index(launcher.browser.context_menu.unwrap_or_default());
+        editor.app_navigation_log = launcher.browser.navigation_log.unwrap_or(false);
+        editor.navigation_log =
+            webapps::audit::recent(launcher.browser.app_id.as_ref(), NAVIGATION_LOG_LIMIT);
+        if let Some(lock) = &launcher.browser.app_lock {
+            editor.app_lock_enabled = true;
+            editor.app_lock_method = lock_method_index(lock.method);
+            editor.app_lock_pin_hash = lock.pin_hash.clone();
+            editor.app_lock_idle = lock.idle_minutes.to_string();
+        }
+        editor.app_sandbox = launcher.browser.sandbox.unwrap_or(false);
+        editor.app_network_isolation =
+            network_isolation_index(launcher.browser.network_isolation.unwrap_or_default());
+        editor.app_doh_url = launcher.browser.doh_url.clone().unwrap_or_default();
+        editor.app_host_overrides = webapps::browser::HostOverride::format_list(
+            launcher.browser.host_overrides.as_deref().unwrap_or_default(),
+        );
+        editor.saved_state = editor.state();
 
         editor
     }
 
+    /// Handle `message`, recording the change it makes to the app for undo.
     pub fn update(&mut self, message: Message) -> Task<Action<crate::pages::Message>> {
+        match message {
+            Message::Undo => {
+                let current = self.snapshot();
+                if let Some(previous) = self.edits.undo(current) {
+                    self.restore(previous);
+                }
+                return Task::none();
+            }
+            Message::Redo => {
+                let current = self.snapshot();
+                if let Some(next) = self.edits.redo(current) {
+                    self.restore(next);
+                }
+                return Task::none();
+            }
+            Message::StartWizard => {
+                // The presets are where the wizard starts from rather than changes to
+                // undo, and leave a new app unmodified
+                let unmodified = !self.is_modified();
+                let wizard = Wizard::default();
+                wizard.window.apply(self);
+                wizard.privacy.apply(self);
+                self.wizard = Some(wizard);
+                if unmodified {
+                    self.saved_state = self.state();
+                }
+                return Task::none();
+            }
+            _ => {}
+        }
+
+        let kind = std::mem::discriminant(&message);
+        let state = self.state();
+        let before = self.snapshot();
+        let task = self.apply(message);
+        if self.state() != state {
+            self.edits.record(before, kind);
+        }
+        task
+    }
+
+    fn apply(&mut self, message: Message) -> Task<Action<crate::pages::Message>> {
         match message {
             Message::AppIncognito(flag) => {
                 self.app_private_mode = flag;
@@ -237,22 +1832,132 @@ impl AppEditor {
             Message::AppSimulateMobile(flag) => {
                 self.app_simulate_mobile = flag;
             }
-            Message::CustomCss(css) => {
-                self.app_custom_css = css;
+            Message::CustomCss(action) => {
+                self.app_custom_css.perform(action);
             }
-            Message::CustomJs(js) => {
-                self.app_custom_js = js;
+            Message::CustomJs(action) => {
+                self.app_custom_js.perform(action);
             }
-            Message::Category(idx) => {
-                self.app_category = webapps::Category::from_index(idx as u8);
-                self.category_idx = Some(idx);
+            Message::PickCodeFile(language, use_as) => {
+                return Task::perform(pick_code_file(language), move |picked| match picked {
+                    Some((path, code)) => cosmic::Action::App(crate::pages::Message::Editor(
+                        Message::CodeFilePicked(language, use_as, path, code),
+                    )),
+                    None => cosmic::Action::None,
+                });
+            }
+            Message::CodeFilePicked(language, use_as, path, code) => {
+                // A linked file's contents are kept too, used if the file goes missing
+                let (editor, file) = self.code_mut(language);
+                *editor = CodeEditor::new(language, &code);
+                *file = (use_as == CodeFile::Link).then_some(path);
+            }
+            Message::UnlinkCodeFile(language) => {
+                *self.code_mut(language).1 = None;
+            }
+            Message::CodeScope(language, scope) => match language {
+                Language::Css => self.app_custom_css_scope = scope,
+                Language::JavaScript => self.app_custom_js_scope = scope,
+            },
+            Message::ToggleStyles => {
+                self.show_styles = !self.show_styles;
+                if self.show_styles && self.site_styles.is_empty() {
+                    self.site_styles = webapps::site_styles::load();
+                }
+            }
+            Message::FetchStyles => {
+                self.styles_fetching = true;
+                self.styles_fetch_failed = false;
+                return Task::perform(
+                    async {
+                        webapps::site_styles::fetch(&webapps::http::Cancel::default())
+                            .await
+                            .map_err(|e| e.to_string())
+                    },
+                    |result| {
+                        cosmic::Action::App(crate::pages::Message::Editor(
+                            Message::StylesFetched(result),
+                        ))
+                    },
+                );
+            }
+            Message::StylesFetched(result) => {
+                self.styles_fetching = false;
+                match result {
+                    Ok(styles) => self.site_styles = styles,
+                    Err(e) => {
+                        tracing::warn!("Failed to fetch the style gallery: {e}");
+                        self.styles_fetch_failed = true;
+                    }
+                }
+            }
+            Message::Preview => {
+                self.previewing = true;
+                let Some(path) = self.update_preview() else {
+                    return Task::none();
+                };
+                return Task::perform(
+                    async move {
+                        if webapps::process::preview_running() {
+                            return;
+                        }
+                        if let Err(e) = tokio::process::Command::new("dev.heppen.webapps.webview")
+                            .arg("--preview")
+                            .arg(path)
+                            .spawn()
+                        {
+                            tracing::error!("Failed to spawn the preview: {e}");
+                        }
+                    },
+                    |_| cosmic::Action::None,
+                );
+            }
+            Message::ApplyStyle(idx) => {
+                if let Some(style) = self.site_styles.get(idx) {
+                    let mut css = self.app_custom_css.text();
+                    // Applying a style twice would only repeat it
+                    if !css.contains(style.css.trim()) {
+                        if !css.trim().is_empty() {
+                            css = format!("{}\n\n", css.trim_end());
+                        }
+                        css.push_str(&style.snippet());
+                        self.app_custom_css = CodeEditor::new(Language::Css, &css);
+                    }
+                }
+            }
+            Message::Category(choice) => {
+                self.app_category = choice.category;
+                self.app_custom_category = choice.custom;
+            }
+            Message::TagInput(text) => {
+                // Everything up to the last comma becomes tags
+                match text.rsplit_once(',') {
+                    Some((tags, rest)) => {
+                        let tags = webapps::launcher::parse_tags(tags, &self.app_tags);
+                        self.app_tags.extend(tags);
+                        self.tag_input = rest.trim_start().to_string();
+                    }
+                    None => self.tag_input = text,
+                }
+            }
+            Message::AddTag => {
+                let tags = webapps::launcher::parse_tags(&self.tag_input, &self.app_tags);
+                self.app_tags.extend(tags);
+                self.tag_input.clear();
+            }
+            Message::RemoveTag(idx) => {
+                if idx < self.app_tags.len() {
+                    self.app_tags.remove(idx);
+                }
             }
             Message::DownloadFavicon => {
                 let url = self.app_url.clone();
                 if webapps::url_valid(&url) {
                     let url2 = url.clone();
+                    let cancel = self.fetch_cancel.clone();
+                    let cancel2 = cancel.clone();
                     let favicon_task = Task::perform(
-                        async move { webapps::download_favicon(&url).await },
+                        async move { webapps::download_favicon(&url, &cancel).await },
                         |result| {
                             cosmic::Action::App(crate::pages::Message::Editor(
                                 Message::FaviconResult(result),
@@ -262,7 +1967,7 @@ impl AppEditor {
                     // Also fetch site title if title field is empty
                     if self.app_title.is_empty() {
                         let title_task = Task::perform(
-                            async move { webapps::fetch_site_title(&url2).await },
+                            async move { webapps::fetch_site_title(&url2, &cancel2).await },
                             |result| {
                                 cosmic::Action::App(crate::pages::Message::Editor(
                                     Message::SiteTitleResult(result),
@@ -274,6 +1979,10 @@ impl AppEditor {
                     return favicon_task;
                 }
             }
+            Message::ChooseSiteIcon => {
+                let url = self.app_url.clone();
+                return task::future(async { pages::Message::OpenSiteIcons(url) });
+            }
             Message::FaviconResult(result) => {
                 if let Some(path) = result {
                     return Task::perform(
@@ -293,8 +2002,24 @@ impl AppEditor {
                     duplicate.app_window_decorations = browser.window_decorations.unwrap_or(true);
                     duplicate.app_private_mode = browser.private_mode.unwrap_or(false);
                     duplicate.app_simulate_mobile = browser.try_simulate_mobile.unwrap_or(false);
-                    duplicate.app_custom_css = browser.custom_css.clone().unwrap_or_default();
-                    duplicate.app_custom_js = browser.custom_js.clone().unwrap_or_default();
+                    duplicate.app_custom_css = CodeEditor::new(
+                        Language::Css,
+                        browser.custom_css.as_deref().unwrap_or_default(),
+                    );
+                    duplicate.app_custom_js = CodeEditor::new(
+                        Language::JavaScript,
+                        browser.custom_js.as_deref().unwrap_or_default(),
+                    );
+                    duplicate.app_custom_css_file = browser.custom_css_file.clone();
+                    duplicate.app_custom_js_file = browser.custom_js_file.clone();
+                    duplicate.app_custom_css_scope = browser.custom_css_scope
+                        .as_ref()
+                        .map(|scope| scope.join(", "))
+                        .unwrap_or_default();
+                    duplicate.app_custom_js_scope = browser.custom_js_scope
+                        .as_ref()
+                        .map(|scope| scope.join(", "))
+                        .unwrap_or_default();
                     if let Some(ref size) = browser.window_size {
                         duplicate.app_window_width = size.0.to_string();
                         duplicate.app_window_height = size.1.to_string();
@@ -313,12 +2038,16 @@ impl AppEditor {
                     let perms = browser.permissions.clone().unwrap_or_default();
                     duplicate.app_allow_camera = perms.allow_camera;
                     duplicate.app_allow_microphone = perms.allow_microphone;
+                    duplicate.app_allow_screen_sharing = perms.allow_screen_sharing;
                     duplicate.app_allow_geolocation = perms.allow_geolocation;
                     duplicate.app_allow_notifications = perms.allow_notifications;
                     duplicate.app_url_schemes = browser.url_schemes
                         .as_ref()
                         .map(|schemes| schemes.join(", "))
                         .unwrap_or_default();
+                    let rules = browser.navigation_rules.clone().unwrap_or_default();
+                    duplicate.app_allowed_hosts = rules.allow.join(", ");
+                    duplicate.app_blocked_hosts = rules.deny.join(", ");
                     duplicate.app_content_blocking = browser.content_blocking.unwrap_or(false);
                     duplicate.app_block_cookies = browser.block_third_party_cookies.unwrap_or(false);
                     duplicate.app_block_webrtc = browser.block_webrtc.unwrap_or(false);
@@ -327,6 +2056,92 @@ impl AppEditor {
                     duplicate.app_restore_session = browser.restore_session.unwrap_or(false);
                     duplicate.app_minimize_to_background = browser.minimize_to_background.unwrap_or(false);
                     duplicate.app_auto_dark_mode = browser.auto_dark_mode.unwrap_or(false);
+                    duplicate.app_auto_reload = browser
+                        .auto_reload_minutes
+                        .filter(|m| *m > 0)
+                        .map(|m| m.to_string())
+                        .unwrap_or_default();
+                    duplicate.app_daily_limit = browser
+                        .daily_limit_minutes
+                        .filter(|m| *m > 0)
+                        .map(|m| m.to_string())
+                        .unwrap_or_default();
+                    duplicate.app_suspend_after = browser
+                        .suspend_after_minutes
+                        .filter(|m| *m > 0)
+                        .map(|m| m.to_string())
+                        .unwrap_or_default();
+                    duplicate.app_splash_screen = browser.splash_screen.unwrap_or(true);
+                    duplicate.app_theme_color = browser.theme_color.clone().unwrap_or_default();
+                    duplicate.app_navigation_toolbar = browser.navigation_toolbar.unwrap_or(false);
+                    duplicate.app_cache_limit = browser
+                        .cache_limit_mb
+                        .map(|mb| mb.to_string())
+                        .unwrap_or_default();
+                    duplicate.app_ephemeral_profile = browser.ephemeral_profile.unwrap_or(false);
+                    duplicate.app_web_features = browser.web_features.clone().unwrap_or_default();
+                    duplicate.app_geolocation_accuracy = geolocation_accuracy_index(
+                        browser.geolocation_accuracy.unwrap_or_default(),
+                    );
+                    duplicate.app_password_autofill = browser.password_autofill.unwrap_or(false);
+                    duplicate.app_ca_certificate =
+                        browser.ca_certificate.clone().unwrap_or_default();
+                    duplicate.app_do_not_track = browser.do_not_track.unwrap_or(false);
+                    duplicate.app_referrer_policy =
+                        referrer_policy_index(browser.referrer_policy.unwrap_or_default());
+                    duplicate.app_fingerprint_resistance =
+                        browser.fingerprint_resistance.unwrap_or(false);
+                    duplicate.app_muted = browser.muted.unwrap_or(false);
+                    duplicate.app_data_saver = browser.data_saver.unwrap_or(false);
+                    duplicate.app_global_shortcut =
+                        browser.global_shortcut.clone().unwrap_or_default();
+                    duplicate.app_start_hidden = browser.start_hidden.unwrap_or(false);
+                    duplicate.app_autostart = browser.autostart.unwrap_or(false);
+                    duplicate.app_autostart_hidden = browser.autostart_hidden.unwrap_or(false);
+                    if let Some(quiet) = &browser.quiet_hours {
+                        duplicate.app_quiet_hours = quiet
+                            .window
+                            .map(webapps::browser::QuietHours::format_window)
+                            .unwrap_or_default();
+                        duplicate.app_quiet_while_locked = quiet.while_locked;
+                        duplicate.app_quiet_action = quiet_hours_action_index(quiet.action);
+                    }
+                    duplicate.app_spellcheck = browser.spellcheck.unwrap_or(false);
+                    duplicate.app_spellcheck_languages =
+                        browser.spellcheck_languages.clone().unwrap_or_default().join(", ");
+                    duplicate.app_upload_directory =
+                        browser.upload_directory.clone().unwrap_or_default();
+                    duplicate.app_window_mode =
+                        window_mode_index(browser.window_mode.unwrap_or_default());
+                    if let Some((x, y)) = browser.window_position {
+                        duplicate.app_window_x = x.to_string();
+                        duplicate.app_window_y = y.to_string();
+                    }
+                    duplicate.app_monitor = browser.monitor.clone().unwrap_or_default();
+                    duplicate.app_sticky = browser.sticky.unwrap_or(false);
+                    duplicate.app_skip_taskbar = browser.skip_taskbar.unwrap_or(false);
+                    duplicate.app_disable_hardware_acceleration = browser.disable_hardware_acceleration.unwrap_or(false);
+                    if let Some(device) = &browser.device_emulation {
+                        duplicate.app_device_preset = device_preset_index(device.preset);
+                        duplicate.app_device_pixel_ratio = device.device_pixel_ratio.to_string();
+                    }
+                    duplicate.app_swipe_navigation = browser.swipe_navigation.unwrap_or(true);
+                    duplicate.app_context_menu =
+                        context_menu_index(browser.context_menu.unwrap_or_default());
+                    duplicate.app_navigation_log = browser.navigation_log.unwrap_or(false);
+                    if let Some(lock) = &browser.app_lock {
+                        duplicate.app_lock_enabled = true;
+                        duplicate.app_lock_method = lock_method_index(lock.method);
+                        duplicate.app_lock_pin_hash = lock.pin_hash.clone();
+                        duplicate.app_lock_idle = lock.idle_minutes.to_string();
+                    }
+                    duplicate.app_sandbox = browser.sandbox.unwrap_or(false);
+                    duplicate.app_network_isolation =
+                        network_isolation_index(browser.network_isolation.unwrap_or_default());
+                    duplicate.app_doh_url = browser.doh_url.clone().unwrap_or_default();
+                    duplicate.app_host_overrides = webapps::browser::HostOverride::format_list(
+                        browser.host_overrides.as_deref().unwrap_or_default(),
+                    );
                 }
                 return task::future(async move {
                     crate::pages::Message::DuplicateApp(Box::new(duplicate))
@@ -334,7 +2149,7 @@ impl AppEditor {
             }
             Message::Done => {
                 let browser = if let Some(browser) = &self.app_browser {
-                    browser.clone()
+                    self.apply_settings(browser.clone())
                 } else {
                     let app_id = format!(
                         "{}{}",
@@ -342,52 +2157,9 @@ impl AppEditor {
                         rng().random_range(1000..10000)
                     );
 
-                    let mut browser = webapps::browser::Browser::new(&app_id, self.app_persistent);
-                    browser.window_title = Some(self.app_title.clone());
-                    browser.url = Some(self.app_url.clone());
-                    browser.window_size = Some(self.app_window_size.clone());
-                    browser.window_decorations = Some(self.app_window_decorations);
-                    browser.private_mode = Some(self.app_private_mode);
-                    browser.try_simulate_mobile = Some(self.app_simulate_mobile);
-                    if !self.app_custom_css.is_empty() {
-                        browser.custom_css = Some(self.app_custom_css.clone());
-                    }
-                    if !self.app_custom_js.is_empty() {
-                        browser.custom_js = Some(self.app_custom_js.clone());
-                    }
-                    browser.user_agent = Some(match self.app_user_agent {
-                        1 => webapps::browser::UserAgent::Mobile,
-                        2 => webapps::browser::UserAgent::Custom(self.app_custom_ua.clone()),
-                        _ => webapps::browser::UserAgent::Default,
-                    });
-                    browser.permissions = Some(webapps::browser::PermissionPolicy {
-                        allow_camera: self.app_allow_camera,
-                        allow_microphone: self.app_allow_microphone,
-                        allow_geolocation: self.app_allow_geolocation,
-                        allow_notifications: self.app_allow_notifications,
-                    });
-                    // Parse URL schemes
-                    let schemes: Vec<String> = self.app_url_schemes
-                        .split(',')
-                        .map(|s| s.trim().to_lowercase())
-                        .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '+' || c == '.'))
-                        .collect();
-                    if !schemes.is_empty() {
-                        browser.url_schemes = Some(schemes);
-                    }
-                    browser.content_blocking = Some(self.app_content_blocking);
-                    browser.block_third_party_cookies = Some(self.app_block_cookies);
-                    browser.block_webrtc = Some(self.app_block_webrtc);
-                    if !self.app_proxy_url.is_empty() {
-                        browser.proxy_url = Some(self.app_proxy_url.clone());
-                    }
-                    let zoom: f64 = self.app_zoom_level.parse().unwrap_or(1.0);
-                    browser.zoom_level = Some(zoom.clamp(0.25, 5.0));
-                    browser.restore_session = Some(self.app_restore_session);
-                    browser.minimize_to_background = Some(self.app_minimize_to_background);
-                    browser.auto_dark_mode = Some(self.app_auto_dark_mode);
-                    browser
+                    self.build_browser(&app_id)
                 };
+                let pin = self.app_lock_pin.trim().to_string();
 
                 if webapps::launcher::webapplauncher_is_valid(
                     &self.app_icon,
@@ -400,9 +2172,13 @@ impl AppEditor {
                         name: self.app_title.clone(),
                         icon: self.app_icon.clone(),
                         category: self.app_category.clone(),
+                        custom_category: self.app_custom_category.clone(),
+                        tags: self.app_tags.clone(),
                     };
 
                     return task::future(async move {
+                        let mut launcher = launcher;
+                        hash_entered_pin(&mut launcher.browser, pin).await;
                         if launcher.create().await.is_ok() {
                             crate::pages::Message::SaveLauncher(launcher)
                         } else {
@@ -413,6 +2189,18 @@ impl AppEditor {
                     return Task::none();
                 }
             }
+            Message::AppIdInput(id) => {
+                self.app_id_input = id;
+            }
+            Message::RenameAppId => {
+                if let Some(browser) = &self.app_browser {
+                    let old_id = browser.app_id.id.clone();
+                    let new_id = webapps::browser::sanitize_app_id(&self.app_id_input);
+                    return task::future(async move {
+                        crate::pages::Message::RenameApp(old_id, new_id)
+                    });
+                }
+            }
             Message::PersistentProfile(flag) => {
                 self.app_persistent = flag;
             }
@@ -431,6 +2219,20 @@ impl AppEditor {
             }
             Message::Url(url) => {
                 self.app_url = url;
+                self.fetch_cancel.cancel();
+                self.fetch_cancel = webapps::http::Cancel::default();
+                return self.check_url();
+            }
+            Message::UrlChecked(url, result) => {
+                if url == webapps::normalize_app_url(&self.app_url) {
+                    self.url_checking = false;
+                    self.url_check = Some(result);
+                }
+            }
+            Message::UseRedirectTarget => {
+                if let Some(Ok(head)) = self.url_check.clone() {
+                    return self.apply(Message::Url(head.url));
+                }
             }
             Message::WindowDecorations(decorations) => {
                 self.app_window_decorations = decorations;
@@ -463,6 +2265,9 @@ impl AppEditor {
             Message::AllowMicrophone(v) => {
                 self.app_allow_microphone = v;
             }
+            Message::AllowScreenSharing(v) => {
+                self.app_allow_screen_sharing = v;
+            }
             Message::AllowGeolocation(v) => {
                 self.app_allow_geolocation = v;
             }
@@ -473,13 +2278,290 @@ impl AppEditor {
                 if let Some(browser) = &self.app_browser {
                     let app_id = browser.app_id.as_ref().to_string();
                     return task::future(
-                        async move { crate::pages::Message::ClearAppData(app_id) },
+                        async move { crate::pages::Message::ClearAppData(app_id, None) },
+                    );
+                }
+            }
+            Message::ClearAppDataCategory(category) => {
+                if let Some(browser) = &self.app_browser {
+                    let app_id = browser.app_id.as_ref().to_string();
+                    return task::future(async move {
+                        crate::pages::Message::ClearAppData(app_id, Some(category))
+                    });
+                }
+            }
+            Message::LoadStorageUsage => {
+                if let Some(browser) = &self.app_browser {
+                    let app_id = browser.app_id.as_ref().to_string();
+                    let profile = browser.profile.clone();
+                    let cookie_profile = profile.clone();
+                    let usage = Task::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || webapps::profile_usage(&app_id))
+                                .await
+                                .unwrap_or_default()
+                        },
+                        |usage| {
+                            cosmic::Action::App(crate::pages::Message::Editor(
+                                Message::StorageUsage(usage),
+                            ))
+                        },
                     );
+                    let history = Task::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || {
+                                profile
+                                    .map(|profile| webapps::history::load(&profile))
+                                    .unwrap_or_default()
+                            })
+                            .await
+                            .unwrap_or_default()
+                        },
+                        |history| {
+                            cosmic::Action::App(crate::pages::Message::Editor(Message::History(
+                                history,
+                            )))
+                        },
+                    );
+                    let cookies = Task::perform(
+                        async move {
+                            tokio::task::spawn_blocking(move || {
+                                cookie_profile
+                                    .map(|profile| webapps::cookies::load(&profile))
+                                    .unwrap_or_default()
+                            })
+                            .await
+                            .unwrap_or_default()
+                        },
+                        |cookies| {
+                            cosmic::Action::App(crate::pages::Message::Editor(Message::Cookies(
+                                cookies,
+                            )))
+                        },
+                    );
+                    return Task::batch([usage, history, cookies]);
+                }
+            }
+            Message::StorageUsage(usage) => {
+                self.storage_usage = usage;
+            }
+            Message::History(history) => {
+                self.history = history.into_iter().rev().take(HISTORY_LIMIT).collect();
+            }
+            Message::Cookies(cookies) => {
+                self.cookies = cookies;
+            }
+            Message::DeleteCookie(idx) => {
+                let profile = self.app_browser.as_ref().and_then(|b| b.profile.clone());
+                if let (Some(profile), Some(cookie)) = (profile, self.cookies.get(idx)) {
+                    match webapps::cookies::delete(&profile, cookie) {
+                        Ok(()) => {
+                            self.cookies.remove(idx);
+                        }
+                        Err(e) => tracing::error!("Failed to delete cookie: {e}"),
+                    }
+                }
+            }
+            Message::ExportCookies => {
+                if let Some(profile) = self.app_browser.as_ref().and_then(|b| b.profile.clone()) {
+                    let app_name = self.app_title.clone();
+                    return task::future(async move {
+                        crate::pages::Message::ExportCookies(profile, app_name)
+                    });
+                }
+            }
+            Message::CacheLimit(limit) => {
+                self.app_cache_limit = filter_digits(limit);
+            }
+            Message::EphemeralProfile(flag) => {
+                self.app_ephemeral_profile = flag;
+            }
+            Message::EnableJavaScript(flag) => {
+                self.app_web_features.javascript = flag;
+            }
+            Message::EnableWebGl(flag) => {
+                self.app_web_features.webgl = flag;
+            }
+            Message::AutoplayPolicy(idx) => {
+                if let Some(policy) = webapps::browser::AutoplayPolicy::iter().nth(idx) {
+                    self.app_web_features.autoplay = policy;
+                }
+            }
+            Message::SmoothScrolling(flag) => {
+                self.app_web_features.smooth_scrolling = flag;
+            }
+            Message::PageCache(flag) => {
+                self.app_web_features.page_cache = flag;
+            }
+            Message::PasswordAutofill(flag) => {
+                self.app_password_autofill = flag;
+            }
+            Message::CaCertificate(path) => {
+                self.app_ca_certificate = path;
+            }
+            Message::ReferrerPolicy(idx) => {
+                self.app_referrer_policy = idx;
+            }
+            Message::RemoveTlsException(idx) => {
+                if let (Some(browser), true) = (
+                    &self.app_browser,
+                    idx < self.app_state.tls_exceptions.len(),
+                ) {
+                    let host = self.app_state.tls_exceptions.remove(idx).host;
+                    let saved = webapps::app_state::update(browser.app_id.as_ref(), |state| {
+                        state.tls_exceptions.retain(|e| e.host != host);
+                    });
+                    if let Err(e) = saved {
+                        tracing::error!("Failed to remove the certificate exception: {e}");
+                    }
+                }
+            }
+            Message::RemoveBookmark(idx) => {
+                if let (Some(browser), true) =
+                    (&self.app_browser, idx < self.app_state.bookmarks.len())
+                {
+                    let url = self.app_state.bookmarks.remove(idx).url;
+                    let saved = webapps::app_state::update(browser.app_id.as_ref(), |state| {
+                        state.bookmarks.retain(|b| b.url != url);
+                    });
+                    if let Err(e) = saved {
+                        tracing::error!("Failed to remove the bookmark: {e}");
+                    }
+                }
+            }
+            Message::DoNotTrack(flag) => {
+                self.app_do_not_track = flag;
+            }
+            Message::FingerprintResistance(flag) => {
+                self.app_fingerprint_resistance = flag;
+            }
+            Message::Muted(flag) => {
+                self.app_muted = flag;
+            }
+            Message::DataSaver(flag) => {
+                self.app_data_saver = flag;
+            }
+            Message::GlobalShortcut(value) => {
+                self.app_global_shortcut = value;
+            }
+            Message::StartHidden(flag) => {
+                self.app_start_hidden = flag;
+            }
+            Message::Autostart(flag) => {
+                self.app_autostart = flag;
+            }
+            Message::AutostartHidden(flag) => {
+                self.app_autostart_hidden = flag;
+            }
+            Message::QuietHours(value) => {
+                self.app_quiet_hours = value;
+            }
+            Message::QuietWhileLocked(flag) => {
+                self.app_quiet_while_locked = flag;
+            }
+            Message::QuietAction(idx) => {
+                self.app_quiet_action = idx;
+            }
+            Message::Spellcheck(flag) => {
+                self.app_spellcheck = flag;
+            }
+            Message::SpellcheckLanguages(languages) => {
+                self.app_spellcheck_languages = languages;
+            }
+            Message::UploadDirectory(value) => {
+                self.app_upload_directory = value;
+            }
+            Message::WindowMode(idx) => {
+                self.app_window_mode = idx;
+            }
+            Message::WindowX(x) => {
+                self.app_window_x = filter_digits(x);
+            }
+            Message::WindowY(y) => {
+                self.app_window_y = filter_digits(y);
+            }
+            Message::Monitor(monitor) => {
+                self.app_monitor = monitor;
+            }
+            Message::Sticky(flag) => {
+                self.app_sticky = flag;
+            }
+            Message::SkipTaskbar(flag) => {
+                self.app_skip_taskbar = flag;
+            }
+            Message::DisableHardwareAcceleration(flag) => {
+                self.app_disable_hardware_acceleration = flag;
+            }
+            Message::DevicePreset(idx) => {
+                self.app_device_preset = idx;
+                let profile = webapps::browser::DevicePreset::iter()
+                    .nth(idx)
+                    .and_then(webapps::browser::DevicePreset::profile);
+                if let Some(profile) = profile {
+                    self.app_window_size = webapps::WindowSize(profile.width, profile.height);
+                    self.app_window_width = profile.width.to_string();
+                    self.app_window_height = profile.height.to_string();
+                    self.app_user_agent = 2;
+                    self.app_custom_ua = profile.user_agent.to_string();
+                    self.app_device_pixel_ratio = profile.device_pixel_ratio.to_string();
                 }
             }
+            Message::DevicePixelRatio(ratio) => {
+                self.app_device_pixel_ratio = filter_numeric(ratio);
+            }
+            Message::SwipeNavigation(flag) => {
+                self.app_swipe_navigation = flag;
+            }
+            Message::ContextMenu(idx) => {
+                self.app_context_menu = idx;
+            }
+            Message::NavigationLog(flag) => {
+                self.app_navigation_log = flag;
+            }
+            Message::ClearNavigationLog => {
+                if let Some(browser) = &self.app_browser {
+                    match webapps::audit::clear(browser.app_id.as_ref()) {
+                        Ok(()) => self.navigation_log.clear(),
+                        Err(e) => tracing::error!("Failed to clear navigation log: {e}"),
+                    }
+                }
+            }
+            Message::AppLock(flag) => {
+                self.app_lock_enabled = flag;
+            }
+            Message::AppLockMethod(idx) => {
+                self.app_lock_method = idx;
+            }
+            Message::AppLockPin(pin) => {
+                self.app_lock_pin = pin;
+            }
+            Message::AppLockIdle(minutes) => {
+                self.app_lock_idle = filter_digits(minutes);
+            }
+            Message::Sandbox(flag) => {
+                self.app_sandbox = flag;
+            }
+            Message::NetworkIsolation(idx) => {
+                self.app_network_isolation = idx;
+            }
+            Message::DohUrl(value) => {
+                self.app_doh_url = value;
+            }
+            Message::HostOverrides(value) => {
+                self.app_host_overrides = value;
+            }
+            Message::GeolocationAccuracy(idx) => {
+                self.app_geolocation_accuracy = idx;
+            }
             Message::UrlSchemes(schemes) => {
                 self.app_url_schemes = schemes;
             }
+            Message::AllowedHosts(hosts) => {
+                self.app_allowed_hosts = hosts;
+            }
+            Message::BlockedHosts(hosts) => {
+                self.app_blocked_hosts = hosts;
+            }
             Message::ContentBlocking(flag) => {
                 self.app_content_blocking = flag;
             }
@@ -504,15 +2586,85 @@ impl AppEditor {
             Message::AutoDarkMode(flag) => {
                 self.app_auto_dark_mode = flag;
             }
+            Message::AutoReload(minutes) => {
+                self.app_auto_reload = filter_digits(minutes);
+            }
+            Message::DailyLimit(minutes) => {
+                self.app_daily_limit = filter_digits(minutes);
+            }
+            Message::SuspendAfter(minutes) => {
+                self.app_suspend_after = filter_digits(minutes);
+            }
+            Message::SplashScreen(flag) => {
+                self.app_splash_screen = flag;
+            }
+            Message::ThemeColor(color) => {
+                self.app_theme_color = color;
+            }
+            Message::NavigationToolbar(flag) => {
+                self.app_navigation_toolbar = flag;
+            }
             Message::ToggleAdvanced(flag) => {
                 self.show_advanced = flag;
             }
+            Message::ToggleIconEdit => {
+                self.show_icon_edit = !self.show_icon_edit;
+            }
+            Message::IconEditFit(idx) => {
+                self.icon_edit_fit = idx;
+            }
+            Message::IconEditPadding(padding) => {
+                self.icon_edit_padding = filter_digits(padding);
+            }
+            Message::IconEditRadius(radius) => {
+                self.icon_edit_radius = filter_digits(radius);
+            }
+            Message::IconEditBackground(color) => {
+                self.icon_edit_background = color;
+            }
+            Message::ApplyIconEdit => {
+                let source = self
+                    .icon_edit_source
+                    .get_or_insert_with(|| self.app_icon.clone())
+                    .clone();
+                if !std::path::Path::new(&source).is_file() {
+                    return Task::none();
+                }
+                let edit = webapps::icon_edit::IconEdit {
+                    fit: if self.icon_edit_fit == 1 {
+                        webapps::icon_edit::Fit::Crop
+                    } else {
+                        webapps::icon_edit::Fit::Pad
+                    },
+                    padding: self.icon_edit_padding.parse().unwrap_or(0),
+                    background: webapps::parse_hex_color(&self.icon_edit_background),
+                    corner_radius: self.icon_edit_radius.parse().unwrap_or(0),
+                };
+                return Task::perform(
+                    async move {
+                        let path = webapps::icon_edit::save(source, edit).await?;
+                        webapps::image_handle(path).await
+                    },
+                    |icon| {
+                        cosmic::Action::App(crate::pages::Message::Editor(Message::IconEdited(
+                            icon,
+                        )))
+                    },
+                );
+            }
+            Message::IconEdited(icon) => {
+                if let Some(icon) = icon {
+                    self.app_icon = icon.path.clone();
+                    self.selected_icon = Some(icon);
+                }
+            }
             Message::FetchThumbnail => {
                 if !self.thumbnail_loading && webapps::url_valid(&self.app_url) {
                     self.thumbnail_loading = true;
                     let url = self.app_url.clone();
+                    let cancel = self.fetch_cancel.clone();
                     return Task::perform(
-                        async move { webapps::download_thumbnail(&url).await },
+                        async move { webapps::download_thumbnail(&url, &cancel).await },
                         |result| {
                             cosmic::Action::App(crate::pages::Message::Editor(
                                 Message::ThumbnailResult(result),
@@ -552,12 +2704,43 @@ impl AppEditor {
                     }
                 }
             }
+            Message::WizardStep(step) => {
+                let Some(wizard) = &mut self.wizard else {
+                    return Task::none();
+                };
+                wizard.step = step;
+                // The site's name and icon are fetched once its URL is entered
+                if step == Step::Details && wizard.fetched_url != self.app_url {
+                    wizard.fetched_url = self.app_url.clone();
+                    return self.apply(Message::DownloadFavicon);
+                }
+            }
+            Message::WindowPreset(preset) => {
+                if let Some(wizard) = &mut self.wizard {
+                    wizard.window = preset;
+                }
+                preset.apply(self);
+            }
+            Message::PrivacyPreset(preset) => {
+                if let Some(wizard) = &mut self.wizard {
+                    wizard.privacy = preset;
+                }
+                preset.apply(self);
+            }
+            Message::CloseWizard => {
+                self.wizard = None;
+            }
+            // Handled by `update`, as they must not be recorded themselves
+            Message::Undo | Message::Redo | Message::StartWizard => {}
         }
         Task::none()
     }
 
     pub fn update_icon(&mut self, icon: Option<webapps::Icon>) {
         if let Some(icon) = icon {
+            let before = self.snapshot();
+            self.edits.record(before, std::mem::discriminant(&Message::OpenIconPicker));
+            self.icon_edit_source = None;
             self.app_icon = icon.path.clone();
             self.selected_icon = Some(icon);
         }
@@ -594,10 +2777,36 @@ impl AppEditor {
         .into()
     }
 
-    pub fn view(&self) -> Element<'_, Message> {
+    /// Show the editor, checking the app's name against the `installed` apps.
+    pub fn view<'a>(
+        &'a self,
+        installed: &[webapps::launcher::WebAppLauncher],
+        categories: &'a CategoryChoices,
+    ) -> Element<'a, Message> {
+        if let Some(wizard) = &self.wizard {
+            return self.wizard(wizard, categories);
+        }
+        let mut problems = self.problems(installed);
+        let blocked = problems.iter().any(Problem::blocks_saving);
+        // Missing fields aren't shown before anything was entered, so a new app doesn't
+        // start out full of errors
+        if !self.is_modified() {
+            problems.retain(|problem| !problem.is_missing());
+        }
+
         widget::container(
             widget::column()
                 .spacing(24)
+                .push_maybe(self.app_browser.is_none().then(|| {
+                    widget::row()
+                        .spacing(8)
+                        .align_y(Vertical::Center)
+                        .push(widget::text::body(fl!("wizard-offer")).width(Length::Fill))
+                        .push(
+                            widget::button::standard(fl!("wizard-start"))
+                                .on_press(Message::StartWizard),
+                        )
+                }))
                 .push(
                     widget::container(
                         widget::row()
@@ -624,8 +2833,15 @@ impl AppEditor {
                                         .push(widget::text::title4(format!(
                                             "{}: {}",
                                             fl!("category"),
-                                            self.app_category.name()
-                                        ))),
+                                            self.category_name()
+                                        )))
+                                        .push(
+                                            widget::button::standard(fl!("edit-icon"))
+                                                .on_press_maybe(
+                                                    (!self.app_icon.is_empty())
+                                                        .then_some(Message::ToggleIconEdit),
+                                                ),
+                                        ),
                                 )
                                 .height(Length::Fixed(96.))
                                 .align_y(Vertical::Center),
@@ -635,6 +2851,46 @@ impl AppEditor {
                     .width(Length::Fill)
                     .class(style::Container::Card),
                 )
+                .push_maybe(field_problems(&problems, Field::Icon))
+                .push_maybe(self.show_icon_edit.then(|| {
+                    widget::settings::section()
+                        .title(fl!("icon-editing"))
+                        .add(widget::settings::item(
+                            fl!("icon-fit"),
+                            widget::dropdown(
+                                &self.icon_fit_options,
+                                Some(self.icon_edit_fit),
+                                Message::IconEditFit,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("icon-padding"),
+                            widget::text_input("0", &self.icon_edit_padding)
+                                .on_input(Message::IconEditPadding)
+                                .width(Length::Fixed(80.0)),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("icon-corner-radius"),
+                            widget::text_input("0", &self.icon_edit_radius)
+                                .on_input(Message::IconEditRadius)
+                                .width(Length::Fixed(80.0)),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("icon-background"),
+                            widget::text_input(
+                                fl!("icon-background-placeholder"),
+                                &self.icon_edit_background,
+                            )
+                            .on_input(Message::IconEditBackground)
+                            .width(Length::Fixed(160.0)),
+                        ))
+                        .add(widget::settings::item_row(vec![
+                            widget::horizontal_space().into(),
+                            widget::button::suggested(fl!("apply-icon-edit"))
+                                .on_press(Message::ApplyIconEdit)
+                                .into(),
+                        ]))
+                }))
                 // Thumbnail preview
                 .push_maybe(if let Some(handle) = &self.thumbnail_handle {
                     Some(
@@ -668,47 +2924,54 @@ impl AppEditor {
                     None
                 })
                 .push(widget::text_input(fl!("title"), &self.app_title).on_input(Message::Title))
-                .push_maybe(if !self.app_title.is_empty() && self.app_title.len() < 3 {
-                    Some(widget::text::caption(fl!("warning-app-name")).class(style::Text::Accent))
-                } else {
-                    None
-                })
+                .push_maybe(field_problems(&problems, Field::Title))
                 .push(
                     widget::row()
                         .spacing(8)
                         .push(widget::text_input(fl!("url"), &self.app_url).on_input(Message::Url))
                         .push(
                             widget::button::standard(fl!("download-favicon")).on_press_maybe(
-                                if webapps::url_valid(&self.app_url) {
+                                if webapps::url_valid(&self.app_url)
+                                    && !webapps::is_local_url(&self.app_url)
+                                {
                                     Some(Message::DownloadFavicon)
                                 } else {
                                     None
                                 },
                             ),
-                        ),
-                )
-                .push_maybe(
-                    if !self.app_url.is_empty() && !webapps::url_valid(&self.app_url) {
-                        Some(
-                            widget::text::caption(fl!("warning-app-url"))
-                                .class(style::Text::Accent),
                         )
-                    } else {
-                        None
-                    },
+                        .push(
+                            widget::button::standard(fl!("choose-site-icon")).on_press_maybe(
+                                if webapps::url_valid(&self.app_url)
+                                    && !webapps::is_local_url(&self.app_url)
+                                {
+                                    Some(Message::ChooseSiteIcon)
+                                } else {
+                                    None
+                                },
+                            ),
+                        ),
                 )
+                .push_maybe(self.url_status())
+                .push_maybe(field_problems(&problems, Field::Url))
                 // Basic settings section
                 .push(
                     widget::settings::section()
                         .title(fl!("basic-settings"))
                         .add(widget::settings::item(
                             fl!("select-category"),
-                            widget::dropdown(
-                                &self.categories,
-                                self.category_idx,
-                                Message::Category,
-                            ),
+                            widget::column()
+                                .spacing(4)
+                                .align_x(Horizontal::Right)
+                                .push(
+                                    categories.dropdown(
+                                        Some(&self.category_choice()),
+                                        Message::Category,
+                                    ),
+                                )
+                                .push_maybe(field_problems(&problems, Field::Category)),
                         ))
+                        .add(widget::settings::item(fl!("tags"), self.tags_field()))
                         .add(widget::settings::item(
                             fl!("persistent-profile"),
                             widget::toggler(self.app_persistent)
@@ -733,10 +2996,81 @@ impl AppEditor {
                                     .on_input(Message::WindowHeight),
                                 ),
                         ))
+                        .add(widget::settings::item(
+                            fl!("device-preset"),
+                            widget::row()
+                                .spacing(8)
+                                .push(widget::dropdown(
+                                    &self.device_preset_options,
+                                    Some(self.app_device_preset),
+                                    Message::DevicePreset,
+                                ))
+                                .push_maybe((self.app_device_preset != 0).then(|| {
+                                    widget::text_input(
+                                        fl!("device-pixel-ratio"),
+                                        &self.app_device_pixel_ratio,
+                                    )
+                                    .on_input(Message::DevicePixelRatio)
+                                })),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("window-mode"),
+                            widget::dropdown(
+                                &self.window_mode_options,
+                                Some(self.app_window_mode),
+                                Message::WindowMode,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("window-position"),
+                            widget::row()
+                                .spacing(8)
+                                .push(
+                                    widget::text_input(fl!("window-position-x"), &self.app_window_x)
+                                        .on_input(Message::WindowX),
+                                )
+                                .push(
+                                    widget::text_input(fl!("window-position-y"), &self.app_window_y)
+                                        .on_input(Message::WindowY),
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("monitor"),
+                            widget::text_input(fl!("monitor-placeholder"), &self.app_monitor)
+                                .on_input(Message::Monitor),
+                        ))
                         .add(widget::settings::item(
                             fl!("decorations"),
                             widget::toggler(self.app_window_decorations)
                                 .on_toggle(Message::WindowDecorations),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("sticky"),
+                            widget::toggler(self.app_sticky)
+                                .on_toggle(Message::Sticky),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("skip-taskbar"),
+                            widget::toggler(self.app_skip_taskbar)
+                                .on_toggle(Message::SkipTaskbar),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("navigation-toolbar"),
+                            widget::toggler(self.app_navigation_toolbar)
+                                .on_toggle(Message::NavigationToolbar),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("context-menu"),
+                            widget::dropdown(
+                                &self.context_menu_options,
+                                Some(self.app_context_menu),
+                                Message::ContextMenu,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("swipe-navigation"),
+                            widget::toggler(self.app_swipe_navigation)
+                                .on_toggle(Message::SwipeNavigation),
                         )),
                 )
                 // Advanced settings toggle
@@ -755,6 +3089,21 @@ impl AppEditor {
                             fl!("private-mode"),
                             widget::toggler(self.app_private_mode).on_toggle(Message::AppIncognito),
                         ))
+                        .add(widget::settings::item(
+                            fl!("ephemeral-profile"),
+                            widget::toggler(self.app_ephemeral_profile)
+                                .on_toggle(Message::EphemeralProfile),
+                        ));
+
+                    // Flatpak already confines the apps and can't nest bubblewrap
+                    if !webapps::in_flatpak() {
+                        advanced = advanced.add(widget::settings::item(
+                            fl!("sandbox"),
+                            widget::toggler(self.app_sandbox).on_toggle(Message::Sandbox),
+                        ));
+                    }
+
+                    advanced = advanced
                         .add(widget::settings::item(
                             fl!("simulate-mobile"),
                             widget::toggler(self.app_simulate_mobile)
@@ -790,37 +3139,90 @@ impl AppEditor {
                             widget::toggler(self.app_allow_microphone)
                                 .on_toggle(Message::AllowMicrophone),
                         ))
+                        .add(widget::settings::item(
+                            fl!("permission-screen-sharing"),
+                            widget::toggler(self.app_allow_screen_sharing)
+                                .on_toggle(Message::AllowScreenSharing),
+                        ))
                         .add(widget::settings::item(
                             fl!("permission-geolocation"),
                             widget::toggler(self.app_allow_geolocation)
                                 .on_toggle(Message::AllowGeolocation),
-                        ))
+                        ));
+
+                    if self.app_allow_geolocation {
+                        advanced = advanced.add(widget::settings::item(
+                            fl!("geolocation-accuracy"),
+                            widget::dropdown(
+                                &self.geolocation_accuracy_options,
+                                Some(self.app_geolocation_accuracy),
+                                Message::GeolocationAccuracy,
+                            ),
+                        ));
+                    }
+
+                    advanced = advanced
                         .add(widget::settings::item(
                             fl!("permission-notifications"),
                             widget::toggler(self.app_allow_notifications)
                                 .on_toggle(Message::AllowNotifications),
                         ))
                         .add(widget::settings::item(
-                            fl!("custom-css"),
-                            widget::text_input(fl!("custom-css-placeholder"), &self.app_custom_css)
-                                .on_input(Message::CustomCss),
-                        ))
-                        .add(widget::settings::item(
-                            fl!("custom-js"),
+                            fl!("quiet-hours"),
                             widget::column()
                                 .spacing(4)
                                 .push(
                                     widget::text_input(
-                                        fl!("custom-js-placeholder"),
-                                        &self.app_custom_js,
+                                        fl!("quiet-hours-placeholder"),
+                                        &self.app_quiet_hours,
                                     )
-                                    .on_input(Message::CustomJs),
+                                    .on_input(Message::QuietHours),
                                 )
+                                .push_maybe(
+                                    (!self.app_quiet_hours.trim().is_empty()
+                                        && webapps::browser::QuietHours::parse_window(
+                                            &self.app_quiet_hours,
+                                        )
+                                        .is_none())
+                                    .then(|| {
+                                        widget::text::caption(fl!("quiet-hours-invalid"))
+                                            .class(style::Text::Accent)
+                                    }),
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("quiet-while-locked"),
+                            widget::toggler(self.app_quiet_while_locked)
+                                .on_toggle(Message::QuietWhileLocked),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("quiet-hours-action"),
+                            widget::dropdown(
+                                &self.quiet_action_options,
+                                Some(self.app_quiet_action),
+                                Message::QuietAction,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("muted"),
+                            widget::toggler(self.app_muted)
+                                .on_toggle(Message::Muted),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("password-autofill"),
+                            widget::toggler(self.app_password_autofill)
+                                .on_toggle(Message::PasswordAutofill),
+                        ))
+                        .add(self.code_field(Language::Css))
+                        .add(
+                            widget::column()
+                                .spacing(4)
+                                .push(self.code_field(Language::JavaScript))
                                 .push(
                                     widget::text::caption(fl!("custom-js-warning"))
                                         .class(style::Text::Accent),
                                 ),
-                        ))
+                        )
                         .add(widget::settings::item(
                             fl!("url-schemes"),
                             widget::text_input(
@@ -829,11 +3231,106 @@ impl AppEditor {
                             )
                             .on_input(Message::UrlSchemes),
                         ))
+                        .add(widget::settings::item(
+                            fl!("allowed-hosts"),
+                            widget::text_input(
+                                fl!("allowed-hosts-placeholder"),
+                                &self.app_allowed_hosts,
+                            )
+                            .on_input(Message::AllowedHosts),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("blocked-hosts"),
+                            widget::text_input(
+                                fl!("blocked-hosts-placeholder"),
+                                &self.app_blocked_hosts,
+                            )
+                            .on_input(Message::BlockedHosts),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("navigation-log"),
+                            widget::toggler(self.app_navigation_log)
+                                .on_toggle(Message::NavigationLog),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("app-lock"),
+                            widget::toggler(self.app_lock_enabled)
+                                .on_toggle(Message::AppLock),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("app-lock-method"),
+                            widget::dropdown(
+                                &self.lock_method_options,
+                                Some(self.app_lock_method),
+                                Message::AppLockMethod,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("app-lock-pin"),
+                            widget::column()
+                                .spacing(4)
+                                .push(
+                                    widget::text_input(
+                                        fl!("app-lock-pin-placeholder"),
+                                        &self.app_lock_pin,
+                                    )
+                                    .password()
+                                    .on_input(Message::AppLockPin),
+                                )
+                                .push_maybe(
+                                    (self.app_lock_enabled
+                                        && self.app_lock_method == 0
+                                        && self.app_lock_pin.trim().is_empty())
+                                    .then(|| {
+                                        widget::text::caption(fl!("app-lock-pin-missing"))
+                                            .class(style::Text::Accent)
+                                    }),
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("app-lock-idle"),
+                            widget::text_input(
+                                fl!("app-lock-idle-placeholder"),
+                                &self.app_lock_idle,
+                            )
+                            .on_input(Message::AppLockIdle),
+                        ))
                         .add(widget::settings::item(
                             fl!("content-blocking"),
                             widget::toggler(self.app_content_blocking)
                                 .on_toggle(Message::ContentBlocking),
                         ))
+                        .add(widget::settings::item(
+                            fl!("data-saver"),
+                            widget::toggler(self.app_data_saver)
+                                .on_toggle(Message::DataSaver),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("disable-hardware-acceleration"),
+                            widget::toggler(self.app_disable_hardware_acceleration)
+                                .on_toggle(Message::DisableHardwareAcceleration),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("spellcheck"),
+                            widget::toggler(self.app_spellcheck)
+                                .on_toggle(Message::Spellcheck),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("spellcheck-languages"),
+                            widget::text_input(
+                                fl!("spellcheck-languages-placeholder"),
+                                &self.app_spellcheck_languages,
+                            )
+                            .on_input(Message::SpellcheckLanguages),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("upload-directory"),
+                            widget::text_input(
+                                fl!("upload-directory-placeholder"),
+                                &self.app_upload_directory,
+                            )
+                            .on_input(Message::UploadDirectory),
+                        ))
                         .add(widget::settings::item(
                             fl!("block-third-party-cookies"),
                             widget::toggler(self.app_block_cookies)
@@ -844,6 +3341,24 @@ impl AppEditor {
                             widget::toggler(self.app_block_webrtc)
                                 .on_toggle(Message::BlockWebRTC),
                         ))
+                        .add(widget::settings::item(
+                            fl!("do-not-track"),
+                            widget::toggler(self.app_do_not_track)
+                                .on_toggle(Message::DoNotTrack),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("referrer-policy"),
+                            widget::dropdown(
+                                &self.referrer_policy_options,
+                                Some(self.app_referrer_policy),
+                                Message::ReferrerPolicy,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("fingerprint-resistance"),
+                            widget::toggler(self.app_fingerprint_resistance)
+                                .on_toggle(Message::FingerprintResistance),
+                        ))
                         .add(widget::settings::item(
                             fl!("proxy-url"),
                             widget::text_input(
@@ -852,6 +3367,56 @@ impl AppEditor {
                             )
                             .on_input(Message::ProxyUrl),
                         ))
+                        .add(widget::settings::item(
+                            fl!("network-isolation"),
+                            widget::dropdown(
+                                &self.network_isolation_options,
+                                Some(self.app_network_isolation),
+                                Message::NetworkIsolation,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("doh-url"),
+                            widget::column()
+                                .spacing(4)
+                                .push(
+                                    widget::text_input(
+                                        fl!("doh-url-placeholder"),
+                                        &self.app_doh_url,
+                                    )
+                                    .on_input(Message::DohUrl),
+                                )
+                                .push_maybe(
+                                    (!self.app_doh_url.trim().is_empty()
+                                        && !self.app_doh_url.trim().starts_with("https://"))
+                                    .then(|| {
+                                        widget::text::caption(fl!("doh-url-invalid"))
+                                            .class(style::Text::Accent)
+                                    }),
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("host-overrides"),
+                            widget::column()
+                                .spacing(4)
+                                .push(
+                                    widget::text_input(
+                                        fl!("host-overrides-placeholder"),
+                                        &self.app_host_overrides,
+                                    )
+                                    .on_input(Message::HostOverrides),
+                                )
+                                .push_maybe(
+                                    webapps::browser::HostOverride::parse_list(
+                                        &self.app_host_overrides,
+                                    )
+                                    .is_none()
+                                    .then(|| {
+                                        widget::text::caption(fl!("host-overrides-invalid"))
+                                            .class(style::Text::Accent)
+                                    }),
+                                ),
+                        ))
                         .add(widget::settings::item(
                             fl!("zoom-level"),
                             widget::text_input(
@@ -870,12 +3435,210 @@ impl AppEditor {
                             widget::toggler(self.app_minimize_to_background)
                                 .on_toggle(Message::MinimizeToBackground),
                         ))
+                        .add(widget::settings::item(
+                            fl!("start-hidden"),
+                            widget::toggler(self.app_start_hidden)
+                                .on_toggle(Message::StartHidden),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("autostart"),
+                            widget::row()
+                                .spacing(12)
+                                .align_y(Vertical::Center)
+                                .push_maybe(self.app_autostart.then(|| {
+                                    widget::checkbox(
+                                        fl!("autostart-hidden"),
+                                        self.app_autostart_hidden,
+                                    )
+                                    .on_toggle(Message::AutostartHidden)
+                                }))
+                                .push(
+                                    widget::toggler(self.app_autostart)
+                                        .on_toggle(Message::Autostart),
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("global-shortcut"),
+                            widget::text_input(
+                                fl!("global-shortcut-placeholder"),
+                                &self.app_global_shortcut,
+                            )
+                            .on_input(Message::GlobalShortcut),
+                        ))
                         .add(widget::settings::item(
                             fl!("auto-dark-mode"),
                             widget::toggler(self.app_auto_dark_mode)
                                 .on_toggle(Message::AutoDarkMode),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("auto-reload"),
+                            widget::text_input(
+                                fl!("auto-reload-placeholder"),
+                                &self.app_auto_reload,
+                            )
+                            .on_input(Message::AutoReload),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("daily-limit"),
+                            widget::text_input(
+                                fl!("daily-limit-placeholder"),
+                                &self.app_daily_limit,
+                            )
+                            .on_input(Message::DailyLimit),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("suspend-after"),
+                            widget::text_input(
+                                fl!("suspend-after-placeholder"),
+                                &self.app_suspend_after,
+                            )
+                            .on_input(Message::SuspendAfter),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("splash-screen"),
+                            widget::toggler(self.app_splash_screen)
+                                .on_toggle(Message::SplashScreen),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("theme-color"),
+                            widget::column()
+                                .spacing(4)
+                                .push(
+                                    widget::text_input(
+                                        fl!("theme-color-placeholder"),
+                                        &self.app_theme_color,
+                                    )
+                                    .on_input(Message::ThemeColor),
+                                )
+                                .push_maybe(
+                                    if !self.app_theme_color.is_empty()
+                                        && webapps::parse_hex_color(&self.app_theme_color)
+                                            .is_none()
+                                    {
+                                        Some(
+                                            widget::text::caption(fl!("warning-theme-color"))
+                                                .class(style::Text::Accent),
+                                        )
+                                    } else {
+                                        None
+                                    },
+                                ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("cache-limit"),
+                            widget::text_input(
+                                fl!("cache-limit-placeholder"),
+                                &self.app_cache_limit,
+                            )
+                            .on_input(Message::CacheLimit),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("enable-javascript"),
+                            widget::toggler(self.app_web_features.javascript)
+                                .on_toggle(Message::EnableJavaScript),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("enable-webgl"),
+                            widget::toggler(self.app_web_features.webgl)
+                                .on_toggle(Message::EnableWebGl),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("autoplay-policy"),
+                            widget::dropdown(
+                                &self.autoplay_options,
+                                webapps::browser::AutoplayPolicy::iter()
+                                    .position(|p| p == self.app_web_features.autoplay),
+                                Message::AutoplayPolicy,
+                            ),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("smooth-scrolling"),
+                            widget::toggler(self.app_web_features.smooth_scrolling)
+                                .on_toggle(Message::SmoothScrolling),
+                        ))
+                        .add(widget::settings::item(
+                            fl!("page-cache"),
+                            widget::toggler(self.app_web_features.page_cache)
+                                .on_toggle(Message::PageCache),
                         ));
 
+                    advanced = advanced.add(widget::settings::item(
+                        fl!("ca-certificate"),
+                        widget::column()
+                            .spacing(4)
+                            .push(
+                                widget::text_input(
+                                    fl!("ca-certificate-placeholder"),
+                                    &self.app_ca_certificate,
+                                )
+                                .on_input(Message::CaCertificate),
+                            )
+                            .push_maybe(
+                                if !self.app_ca_certificate.trim().is_empty()
+                                    && !std::path::Path::new(self.app_ca_certificate.trim())
+                                        .is_file()
+                                {
+                                    Some(
+                                        widget::text::caption(fl!("warning-ca-certificate"))
+                                            .class(style::Text::Accent),
+                                    )
+                                } else {
+                                    None
+                                },
+                            ),
+                    ));
+
+                    // Certificates accepted from the TLS error page
+                    let exceptions = &self.app_state.tls_exceptions;
+                    if !exceptions.is_empty() {
+                        let mut hosts = widget::column().spacing(4);
+                        for (idx, exception) in exceptions.iter().enumerate() {
+                            hosts = hosts.push(
+                                widget::row()
+                                    .spacing(12)
+                                    .align_y(Vertical::Center)
+                                    .push(
+                                        widget::text::body(exception.host.as_str())
+                                            .width(Length::Fill),
+                                    )
+                                    .push(
+                                        widget::button::standard(fl!("remove"))
+                                            .on_press(Message::RemoveTlsException(idx)),
+                                    ),
+                            );
+                        }
+                        advanced =
+                            advanced.add(widget::settings::item(fl!("tls-exceptions"), hosts));
+                    }
+
+                    // Pages bookmarked from inside the app
+                    let bookmarks = &self.app_state.bookmarks;
+                    if !bookmarks.is_empty() {
+                        let mut pages = widget::column().spacing(4);
+                        for (idx, bookmark) in bookmarks.iter().enumerate() {
+                            let title = if bookmark.title.is_empty() {
+                                bookmark.url.as_str()
+                            } else {
+                                bookmark.title.as_str()
+                            };
+                            pages = pages.push(
+                                widget::row()
+                                    .spacing(12)
+                                    .align_y(Vertical::Center)
+                                    .push(widget::text::body(title).width(Length::Fill))
+                                    .push(
+                                        widget::button::standard(fl!("remove"))
+                                            .on_press(Message::RemoveBookmark(idx)),
+                                    ),
+                            );
+                        }
+                        advanced = advanced.add(widget::settings::item(fl!("bookmarks"), pages));
+                    }
+
+                    if self.is_installed {
+                        advanced = advanced.add(self.app_id_field(installed));
+                    }
+
                     // Show usage stats for installed apps (read-only)
                     if self.is_installed {
                         advanced = advanced
@@ -890,13 +3653,149 @@ impl AppEditor {
                                         .map(webapps::format_timestamp)
                                         .unwrap_or_else(|| fl!("never-launched")),
                                 ),
+                            ))
+                            .add(widget::settings::item(
+                                fl!("usage-sessions"),
+                                widget::text::body(self.app_stats.sessions.to_string()),
+                            ))
+                            .add(widget::settings::item(
+                                fl!("usage-total-time"),
+                                widget::text::body(fl!(
+                                    "usage-total-time-value",
+                                    open = webapps::format_duration(self.app_stats.open_secs),
+                                    focused = webapps::format_duration(self.app_stats.focus_secs)
+                                )),
+                            ))
+                            .add(widget::settings::item(
+                                fl!("usage-last-session"),
+                                widget::text::body(webapps::format_duration(
+                                    self.app_stats.last_session_secs,
+                                )),
                             ));
+                        if let Some(day) = &self.app_state.usage_day {
+                            let seconds = self.app_state.usage_seconds;
+                            advanced = advanced.add(widget::settings::item(
+                                fl!("usage-time"),
+                                widget::text::body(fl!(
+                                    "usage-time-value",
+                                    minutes = seconds / 60,
+                                    day = day.as_str()
+                                )),
+                            ));
+                        }
                     }
 
                     Some(advanced)
                 } else {
                     None
                 })
+                // Storage usage per data category (installed apps with a persistent profile)
+                .push_maybe(if self.is_installed && self.app_persistent {
+                    let total: u64 = self.storage_usage.iter().map(|(_, size)| size).sum();
+                    let mut storage = widget::settings::section().title(fl!("profile-data-size"));
+                    for (category, size) in &self.storage_usage {
+                        storage = storage.add(widget::settings::item(
+                            profile_data_name(*category),
+                            widget::row()
+                                .spacing(12)
+                                .align_y(Vertical::Center)
+                                .push(widget::text::body(webapps::format_bytes(*size)))
+                                .push(
+                                    widget::button::standard(fl!("clear")).on_press_maybe(
+                                        (*size > 0)
+                                            .then_some(Message::ClearAppDataCategory(*category)),
+                                    ),
+                                ),
+                        ));
+                    }
+                    storage = storage.add(widget::settings::item(
+                        fl!("data-total"),
+                        widget::text::body(webapps::format_bytes(total)),
+                    ));
+                    Some(storage)
+                } else {
+                    None
+                })
+                // Recently visited pages, cleared with the history data category
+                .push_maybe(if self.is_installed && !self.history.is_empty() {
+                    let mut history = widget::settings::section().title(fl!("history"));
+                    for entry in &self.history {
+                        let title = if entry.title.is_empty() {
+                            entry.url.clone()
+                        } else {
+                            entry.title.clone()
+                        };
+                        history = history.add(widget::settings::item_row(vec![
+                            widget::column()
+                                .push(widget::text::body(title))
+                                .push(widget::text::caption(entry.url.clone()))
+                                .width(Length::Fill)
+                                .into(),
+                            widget::text::caption(webapps::format_timestamp(entry.visited))
+                                .into(),
+                        ]));
+                    }
+                    history = history.add(widget::settings::item(
+                        fl!("clear-history"),
+                        widget::button::standard(fl!("clear"))
+                            .on_press(Message::ClearAppDataCategory(webapps::ProfileData::History)),
+                    ));
+                    Some(history)
+                } else {
+                    None
+                })
+                // Cookies in the profile, deletable one by one and exportable as cookies.txt
+                .push_maybe(if self.is_installed && !self.cookies.is_empty() {
+                    let mut cookies = widget::settings::section().title(fl!("cookies"));
+                    for (idx, cookie) in self.cookies.iter().enumerate() {
+                        cookies = cookies.add(widget::settings::item_row(vec![
+                            widget::column()
+                                .push(widget::text::body(cookie.name.clone()))
+                                .push(widget::text::caption(cookie.domain.clone()))
+                                .width(Length::Fill)
+                                .into(),
+                            widget::text::caption(cookie_expiry(cookie.expires)).into(),
+                            widget::button::standard(fl!("remove"))
+                                .on_press(Message::DeleteCookie(idx))
+                                .into(),
+                        ]));
+                    }
+                    cookies = cookies.add(widget::settings::item(
+                        fl!("export-cookies"),
+                        widget::button::standard(fl!("export")).on_press(Message::ExportCookies),
+                    ));
+                    Some(cookies)
+                } else {
+                    None
+                })
+                // Navigation audit log, newest first
+                .push_maybe(if self.is_installed && !self.navigation_log.is_empty() {
+                    let mut log = widget::settings::section().title(fl!("navigation-log"));
+                    for entry in &self.navigation_log {
+                        let verdict = if entry.allowed {
+                            widget::text::caption(fl!("navigation-allowed"))
+                        } else {
+                            widget::text::caption(fl!("navigation-blocked"))
+                                .class(style::Text::Accent)
+                        };
+                        log = log.add(widget::settings::item_row(vec![
+                            widget::text::body(entry.url.clone())
+                                .width(Length::Fill)
+                                .into(),
+                            verdict.into(),
+                            widget::text::caption(webapps::format_timestamp(entry.timestamp))
+                                .into(),
+                        ]));
+                    }
+                    log = log.add(widget::settings::item(
+                        fl!("clear-navigation-log"),
+                        widget::button::standard(fl!("clear"))
+                            .on_press(Message::ClearNavigationLog),
+                    ));
+                    Some(log)
+                } else {
+                    None
+                })
                 .push(
                     widget::row()
                         .spacing(8)
@@ -917,6 +3816,11 @@ impl AppEditor {
                                     .on_press(Message::Duplicate),
                             )
                         })
+                        .push(
+                            widget::button::standard(fl!("preview")).on_press_maybe(
+                                webapps::url_valid(&self.app_url).then_some(Message::Preview),
+                            ),
+                        )
                         .push_maybe(if !self.is_installed {
                             None
                         } else {
@@ -925,17 +3829,11 @@ impl AppEditor {
                                     .on_press(Message::LaunchApp),
                             )
                         })
+                        .push_maybe(blocked.then(|| {
+                            widget::text::caption(fl!("validation-summary"))
+                        }))
                         .push(widget::button::suggested(fl!("create")).on_press_maybe(
-                            if webapps::launcher::webapplauncher_is_valid(
-                                &self.app_icon,
-                                &self.app_title,
-                                &Some(self.app_url.clone()),
-                                &self.app_category,
-                            ) {
-                                Some(Message::Done)
-                            } else {
-                                None
-                            },
+                            (!blocked).then_some(Message::Done),
                         )),
                 ),
         )
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
 use cosmic::{
     Element, Task,
@@ -6,7 +8,7 @@ use cosmic::{
     task, theme,
     widget::{self},
 };
-use webapps::fl;
+use webapps::{fl, icon_index::IconIndex};
 
 use crate::pages;
 
@@ -24,16 +26,59 @@ pub struct IconPicker {
     pub icon_searching: String,
     pub icons: Vec<webapps::Icon>,
     pub has_searched: bool,
+    index: Arc<IconIndex>,
 }
 
+/// Shortest search text matched while typing.
+const MIN_QUERY_LEN: usize = 2;
+
 impl IconPicker {
+    pub fn new(index: Arc<IconIndex>) -> Self {
+        Self {
+            index,
+            ..Default::default()
+        }
+    }
+
     pub fn push_icon(&mut self, icon: webapps::Icon) {
         self.icons.push(icon);
     }
 
+    /// Use a refreshed index, updating the shown matches.
+    pub fn set_index(&mut self, index: Arc<IconIndex>) {
+        self.index = index;
+        if self.has_searched {
+            self.show_matches();
+        }
+    }
+
+    /// Show the indexed icons matching the search text. Sizes are already known,
+    /// so the files don't have to be read first.
+    fn show_matches(&mut self) {
+        self.has_searched = true;
+        self.icons = self
+            .index
+            .search(&self.icon_searching)
+            .into_iter()
+            .map(|path| {
+                let icon = if webapps::is_svg(&path) {
+                    webapps::IconType::Svg(widget::svg::Handle::from_path(&path))
+                } else {
+                    webapps::IconType::Raster(widget::image::Handle::from_path(&path))
+                };
+                webapps::Icon::new(icon, path, false)
+            })
+            .collect();
+    }
+
     pub fn update(&mut self, message: Message) -> Task<Action<pages::Message>> {
         match message {
-            Message::CustomIconsSearch(input) => self.icon_searching = input,
+            Message::CustomIconsSearch(input) => {
+                self.icon_searching = input;
+                if !self.index.is_empty() && self.icon_searching.trim().len() >= MIN_QUERY_LEN {
+                    self.show_matches();
+                }
+            }
             Message::DownloadIconsPack => return task::message(pages::Message::DownloaderStarted),
             Message::OpenIconPickerDialog => {
                 return task::future(async move {
@@ -71,6 +116,7 @@ impl IconPicker {
                     }
                 });
             }
+            Message::IconSearch if !self.index.is_empty() => self.show_matches(),
             Message::IconSearch => {
                 self.icons.clear();
                 self.has_searched = true;
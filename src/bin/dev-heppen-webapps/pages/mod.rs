@@ -1,7 +1,20 @@
+mod app_order;
+mod code_editor;
+mod edit_history;
 pub mod editor;
 mod iconpicker;
-
-use crate::{config::AppConfig, pages::iconpicker::IconPicker, themes::Theme};
+mod statistics;
+mod thumbnails;
+mod wizard;
+
+use crate::{
+    config::{AppConfig, AppGroup, GridDensity, SortMode},
+    pages::{
+        app_order::DraggedApp, iconpicker::IconPicker, statistics::Statistics,
+        thumbnails::Thumbnails,
+    },
+    themes::Theme,
+};
 use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
 use cosmic::{
     Application, Element,
@@ -9,9 +22,11 @@ use cosmic::{
     command::set_theme,
     cosmic_theme,
     iced::{
-        Alignment, Length, Subscription,
+        Alignment, Event, Length, Subscription,
         alignment::Horizontal,
+        event,
         futures::{SinkExt as _, future},
+        keyboard::{self, Key, Modifiers},
     },
     surface, task, theme,
     widget::{
@@ -20,7 +35,7 @@ use cosmic::{
         nav_bar, responsive_menu_bar,
     },
 };
-use editor::AppEditor;
+use editor::{AppEditor, CategoryChoice, CategoryChoices};
 use ron::ser::to_string_pretty;
 use std::{
     collections::HashMap,
@@ -31,6 +46,7 @@ use std::{
     sync::{Arc, LazyLock},
     time::Duration,
 };
+use strum::IntoEnumIterator as _;
 use tokio::{
     io::{AsyncBufReadExt, BufReader},
     process::Command,
@@ -50,7 +66,9 @@ pub enum Message {
     CloseDialog,
     CloseToast(widget::toaster::ToastId),
     Editor(editor::Message),
-    Delete(widget::segmented_button::Entity),
+    Delete,
+    PurgeProfile(bool),
+    PurgeIcons(bool),
     DeletionDone(widget::segmented_button::Entity),
     DuplicateApp(Box<editor::AppEditor>),
     DownloaderDone,
@@ -58,21 +76,59 @@ pub enum Message {
     DownloaderStream(String),
     DownloaderStreamFinished,
     IconPicker(iconpicker::Message),
+    IconIndex(std::sync::Arc<webapps::icon_index::IconIndex>),
     IconsResult(Vec<String>),
+    GridScrolled(cosmic::iced::widget::scrollable::Viewport),
     ImportThemeFilePicker,
+    InstalledApps(Vec<webapps::launcher::WebAppLauncher>),
     Launch(webapps::WebviewArgs),
     LaunchUrl(String),
     LoadThemes,
     OpenFileResult(Vec<String>),
     OpenIconPicker,
     OpenRepositoryUrl,
+    OpenSiteIcons(String),
     OpenThemeResult(String),
     ConfirmDeletion(widget::segmented_button::Entity),
     PushIcon(webapps::Icon),
     ReloadNavbarItems,
     ResetSettings,
     SaveLauncher(webapps::launcher::WebAppLauncher),
+    /// Give the app with the first id the second one
+    RenameApp(String, String),
+    AppRenamed(Result<(String, String), String>),
+    SelectApps(Vec<String>),
+    StorageSizes(HashMap<String, u64>),
+    AppActivity(HashMap<String, Activity>),
+    SortApps(usize),
+    GridDensity(usize),
+    /// Drop the dragged app before the other one
+    MoveApp(String, String),
+    DropOnNavItem(nav_bar::Id, String),
+    NewCategoryName(String),
+    NewCategoryXdg(usize),
+    AddCategory,
+    CategoryXdg(usize, usize),
+    RemoveCategory(usize),
+    MoveAppsFrom(CategoryChoice),
+    MoveAppsTo(CategoryChoice),
+    MoveApps,
+    AppsMoved(usize),
+    NewGroupName(String),
+    AddGroup,
+    RemoveGroup(usize),
+    /// Fold or unfold a group in the list
+    ToggleGroup(usize),
+    /// Show the apps of a group in the grid, or the top level
+    OpenGroup(Option<usize>),
+    AssignGroup(String, Option<usize>),
+    /// Put the selected apps into the group at this index of the options, or none
+    BulkAssignGroup(usize),
+    TogglePin(String),
     SetIcon(Option<webapps::Icon>),
+    SiteIconChosen(webapps::IconCandidate),
+    SiteIcons(Vec<webapps::IconCandidate>),
+    ThumbnailLoaded(String, Option<webapps::IconType>),
     Surface(surface::Action),
     DownloaderStop,
     ExportApps,
@@ -80,24 +136,52 @@ pub enum Message {
     ImportApps,
     ImportAppsFilePicked(Vec<String>),
     SearchApps(String),
+    FilterApps(usize),
+    Statistics(statistics::Message),
+    ShowStatistics,
     ToggleContextPage(ContextPage),
+    ToggleWarmPool(bool),
     UpdateConfig(AppConfig),
     UpdateTheme(Box<Theme>),
-    ClearAppData(String),
+    ClearAppData(String, Option<webapps::ProfileData>),
     ClearAppDataDone(Result<(), String>),
+    ExportCookies(std::path::PathBuf, String),
+    ExportCookiesResult(Result<usize, String>),
+    FaviconRefresh(usize),
+    FaviconsRefreshed(Vec<webapps::favicon_refresh::FaviconChange>),
+    RefreshFavicons,
     FocusSearch,
     LaunchCurrentApp,
+    ControlApp(String, webapps::control::Command),
+    ControlCurrentApp(webapps::control::Command),
     DuplicateCurrentApp,
     OpenCurrentAppUrl,
     ToggleViewMode,
     SelectAppFromGrid(String),
+    UpdateProcesses(Vec<webapps::process::WebviewProcess>),
     UpdateRunningApps(std::collections::HashSet<String>),
+    KillApp(u32),
     ToggleBulkMode,
     ToggleBulkSelect(String),
     BulkDelete,
     BulkDeleteDone(usize),
     BulkExport,
     BulkExportResult(Result<(), String>),
+    BulkRefetchIcons,
+    BulkCategory(CategoryChoice),
+    BulkPersistent(bool),
+    BulkSaved(usize),
+    BulkLaunch,
+    RefetchAllIcons,
+    IconRefetched(String, webapps::favicon_refresh::Refetch),
+    Key(Modifiers, Key),
+    NewApp,
+    OpenPage(nav_bar::Id),
+    /// Go on with the message though the editor has unsaved changes
+    DiscardChanges(Box<Message>),
+    /// Create the app in the editor though it looks like an installed one
+    SaveAnyway,
+    OpenDuplicate(String),
     // empty message
     None,
 }
@@ -110,8 +194,151 @@ pub enum Page {
 #[derive(Debug, Clone)]
 pub enum Dialogs {
     IconPicker(IconPicker),
-    Confirmation((widget::segmented_button::Entity, String)),
+    /// Icons offered by the app's site, `None` while they download
+    SiteIcons(Option<Vec<webapps::IconCandidate>>),
+    Deletion(Deletion),
     IconsDownloader,
+    RefetchIcons(RefetchProgress),
+    /// Leaving the editor with unsaved changes, which the message would do
+    UnsavedChanges(Box<Message>),
+    /// Installed apps like the one about to be created
+    Duplicates(Vec<webapps::launcher::WebAppLauncher>),
+}
+
+/// Apps about to be deleted, and what is removed along with them
+#[derive(Debug, Clone)]
+pub struct Deletion {
+    /// Nav entry of the open app, when it is deleted on its own
+    entity: Option<widget::segmented_button::Entity>,
+    apps: Vec<webapps::launcher::WebAppLauncher>,
+    purge: webapps::launcher::Purge,
+}
+
+/// Apps whose icons are downloaded again, one after another
+#[derive(Debug, Clone, Default)]
+pub struct RefetchProgress {
+    /// The app being downloaded is already taken out
+    pending: Vec<webapps::launcher::WebAppLauncher>,
+    /// App names and outcomes, in the order they finished
+    results: Vec<(String, webapps::favicon_refresh::Refetch)>,
+    total: usize,
+}
+
+impl RefetchProgress {
+    fn is_finished(&self) -> bool {
+        self.results.len() == self.total
+    }
+}
+
+/// A group's header in the nav bar, by the group's index in the config
+#[derive(Debug, Clone, Copy)]
+struct NavGroup(usize);
+
+/// Options to move apps to a group, with no group first.
+fn group_options(groups: &[AppGroup]) -> Vec<String> {
+    std::iter::once(fl!("no-group"))
+        .chain(groups.iter().map(|group| group.name.clone()))
+        .collect()
+}
+
+/// The XDG categories the user's own categories can be shown in.
+fn xdg_categories() -> impl Iterator<Item = webapps::Category> {
+    webapps::Category::iter().filter(|category| *category != webapps::Category::None)
+}
+
+/// Put `apps` into the category `to`, and install them again if menus show them
+/// elsewhere now.
+fn move_apps(apps: Vec<webapps::launcher::WebAppLauncher>, to: CategoryChoice) -> Task<Message> {
+    if apps.is_empty() {
+        return Task::none();
+    }
+    task::future(async move {
+        let mut moved = 0;
+        for mut app in apps {
+            let reinstall = app.category != to.category;
+            app.category = to.category.clone();
+            app.custom_category = to.custom.clone();
+            if let Err(e) = app.save() {
+                tracing::error!("Failed to move web app {}: {e}", app.name);
+                continue;
+            }
+            if reinstall {
+                if let Err(e) = app.create().await {
+                    tracing::error!("Failed to reinstall web app {}: {e}", app.name);
+                }
+            }
+            moved += 1;
+        }
+        cosmic::action::app(Message::AppsMoved(moved))
+    })
+}
+
+/// What the app list can be narrowed down to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppFilter {
+    /// By name, as the user's own categories share XDG ones
+    Category(String),
+    Tag(String),
+}
+
+impl AppFilter {
+    /// The categories of `apps`, then their tags by name.
+    fn for_apps(apps: &[webapps::launcher::WebAppLauncher]) -> Vec<Self> {
+        let mut filters = Vec::new();
+        let mut tags: Vec<&String> = Vec::new();
+        for app in apps {
+            let category = AppFilter::Category(app.category_name());
+            if !filters.contains(&category) {
+                filters.push(category);
+            }
+            for tag in &app.tags {
+                if !tags.iter().any(|other| other.eq_ignore_ascii_case(tag)) {
+                    tags.push(tag);
+                }
+            }
+        }
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        filters.extend(tags.into_iter().map(|tag| AppFilter::Tag(tag.clone())));
+        filters
+    }
+
+    fn name(&self) -> String {
+        match self {
+            AppFilter::Category(name) => name.clone(),
+            AppFilter::Tag(tag) => fl!("tag-filter", tag = tag.as_str()),
+        }
+    }
+
+    fn matches(&self, app: &webapps::launcher::WebAppLauncher) -> bool {
+        match self {
+            AppFilter::Category(name) => app.category_name() == *name,
+            AppFilter::Tag(tag) => app.tags.iter().any(|other| other.eq_ignore_ascii_case(tag)),
+        }
+    }
+}
+
+/// How an app was used, to sort by
+#[derive(Debug, Clone, Default)]
+pub struct Activity {
+    launches: u64,
+    /// Unix timestamp of the last launch
+    last_used: Option<u64>,
+    /// When the app was created, or else last saved
+    added: Option<std::time::SystemTime>,
+}
+
+impl Activity {
+    fn load(app_id: &str) -> Self {
+        let stats = webapps::stats::load(app_id);
+        let added = webapps::database_path(&format!("{app_id}.ron"))
+            .and_then(|path| std::fs::metadata(path).ok())
+            .and_then(|metadata| metadata.created().or_else(|_| metadata.modified()).ok());
+        Self {
+            launches: stats.sessions,
+            last_used: stats.last_used,
+            added,
+        }
+    }
 }
 
 pub struct QuickWebApps {
@@ -126,13 +353,54 @@ pub struct QuickWebApps {
     downloader_id: usize,
     downloader_output: String,
     search_query: String,
+    // Categories and tags of the installed apps to filter by, and the dropdown's options
+    // with "all apps" first
+    filters: Vec<AppFilter>,
+    // Categories offered for apps, and the form to add the user's own
+    category_choices: CategoryChoices,
+    xdg_category_options: Vec<String>,
+    new_category_name: String,
+    new_category_xdg: usize,
+    move_apps_from: CategoryChoice,
+    move_apps_to: CategoryChoice,
+    new_group_name: String,
+    // "No group" and the groups' names, to move the selected apps to
+    group_options: Vec<String>,
+    /// Group whose folder is open in the grid
+    open_group: Option<usize>,
+    filter_options: Vec<String>,
+    app_filter: Option<AppFilter>,
     cached_apps: Vec<webapps::launcher::WebAppLauncher>,
     themes_list: Vec<Theme>,
     theme_idx: Option<usize>,
     toasts: widget::toaster::Toasts<Message>,
     running_app_ids: std::collections::HashSet<String>,
+    processes: Vec<webapps::process::WebviewProcess>,
+    /// CPU usage in percent of one core per webview pid, over the last poll interval
+    cpu_usage: HashMap<u32, f32>,
+    processes_sampled: Option<std::time::Instant>,
     bulk_mode: bool,
     selected_app_ids: std::collections::HashSet<String>,
+    statistics: Statistics,
+    /// Profile disk usage per app id, filled in the background after each reload
+    storage_sizes: HashMap<String, u64>,
+    /// Usage and install time per app id, for sorting
+    activity: HashMap<String, Activity>,
+    sort_options: Vec<String>,
+    density_options: Vec<String>,
+    warm_pool: bool,
+    icon_index: std::sync::Arc<webapps::icon_index::IconIndex>,
+    icon_index_refreshed: bool,
+    thumbnails: Thumbnails,
+    grid_viewport: Option<cosmic::iced::widget::scrollable::Viewport>,
+    /// Stops the site icon downloads when their dialog closes
+    site_icons_cancel: webapps::http::Cancel,
+    /// Stops re-downloading app icons when their dialog closes
+    refetch_cancel: webapps::http::Cancel,
+    favicon_refresh_options: Vec<String>,
+    /// Apps whose favicon changed in a refresh, newest first
+    favicon_changes: Vec<webapps::favicon_refresh::FaviconChange>,
+    favicons_refreshing: bool,
 }
 
 impl Application for QuickWebApps {
@@ -152,6 +420,8 @@ impl Application for QuickWebApps {
 
     fn init(core: Core, _flags: Self::Flags) -> (Self, Task<Self::Message>) {
         let config = AppConfig::config();
+        let category_choices = CategoryChoices::new(&config.custom_categories);
+        let group_options = group_options(&config.app_groups);
         let add_page = Page::Editor(AppEditor::default());
         let nav = nav_bar::Model::default();
 
@@ -207,8 +477,25 @@ impl Application for QuickWebApps {
             },
             MenuAction::DuplicateApp,
         );
+        key_binds.insert(
+            menu::KeyBind {
+                modifiers: vec![menu::key_bind::Modifier::Ctrl],
+                key: cosmic::iced_core::keyboard::Key::Character("z".into()),
+            },
+            MenuAction::Undo,
+        );
+        key_binds.insert(
+            menu::KeyBind {
+                modifiers: vec![
+                    menu::key_bind::Modifier::Ctrl,
+                    menu::key_bind::Modifier::Shift,
+                ],
+                key: cosmic::iced_core::keyboard::Key::Character("Z".into()),
+            },
+            MenuAction::Redo,
+        );
 
-        let windows = QuickWebApps {
+        let mut windows = QuickWebApps {
             core,
             context_page: ContextPage::About,
             nav,
@@ -220,20 +507,74 @@ impl Application for QuickWebApps {
             downloader_id: 1,
             downloader_output: String::new(),
             search_query: String::new(),
+            filters: Vec::new(),
+            category_choices,
+            xdg_category_options: xdg_categories().map(|category| category.name()).collect(),
+            new_category_name: String::new(),
+            new_category_xdg: 0,
+            move_apps_from: CategoryChoice::default(),
+            move_apps_to: CategoryChoice::default(),
+            new_group_name: String::new(),
+            group_options,
+            open_group: None,
+            filter_options: vec![fl!("all-apps")],
+            app_filter: None,
             cached_apps: Vec::new(),
             themes_list,
             theme_idx: Some(0),
             toasts: widget::toaster::Toasts::new(Message::CloseToast),
             running_app_ids: std::collections::HashSet::new(),
+            processes: Vec::new(),
+            cpu_usage: HashMap::new(),
+            processes_sampled: None,
             bulk_mode: false,
             selected_app_ids: std::collections::HashSet::new(),
+            statistics: Statistics::default(),
+            storage_sizes: HashMap::new(),
+            activity: HashMap::new(),
+            sort_options: SortMode::ALL
+                .iter()
+                .map(|mode| match mode {
+                    SortMode::Category => fl!("sort-category"),
+                    SortMode::Name => fl!("sort-name"),
+                    SortMode::RecentlyLaunched => fl!("sort-recently-launched"),
+                    SortMode::MostLaunched => fl!("sort-most-launched"),
+                    SortMode::RecentlyAdded => fl!("sort-recently-added"),
+                    SortMode::Manual => fl!("sort-manual"),
+                })
+                .collect(),
+            density_options: vec![
+                fl!("grid-density-compact"),
+                fl!("grid-density-comfortable"),
+                fl!("grid-density-large"),
+            ],
+            warm_pool: webapps::warm::is_enabled(),
+            icon_index: Default::default(),
+            icon_index_refreshed: false,
+            thumbnails: Thumbnails::default(),
+            grid_viewport: None,
+            site_icons_cancel: Default::default(),
+            refetch_cancel: Default::default(),
+            favicon_refresh_options: vec![
+                fl!("favicon-refresh-off"),
+                fl!("favicon-refresh-launch"),
+                fl!("favicon-refresh-weekly"),
+            ],
+            favicon_changes: Vec::new(),
+            favicons_refreshing: false,
         };
 
-        let tasks = vec![
+        let refresh_log = webapps::favicon_refresh::RefreshLog::load();
+        let mut tasks = vec![
             task::message(Message::ReloadNavbarItems),
             task::message(Message::LoadThemes),
+            Self::refresh_icon_index(None),
             task::message(Message::UpdateTheme(Box::new(Theme::Light))),
         ];
+        if windows.config.favicon_refresh.is_due(&refresh_log) {
+            tasks.push(task::message(Message::RefreshFavicons));
+        }
+        windows.favicon_changes = refresh_log.changes;
 
         (windows, Task::batch(tasks))
     }
@@ -247,15 +588,26 @@ impl Application for QuickWebApps {
                 .map(|update| Message::UpdateConfig(update.config)),
         );
 
+        // Shortcuts also apply while typing in a field, which takes the key presses
+        subscriptions.push(event::listen_with(|event, _status, _window| match event {
+            Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. })
+                if modifiers.control() =>
+            {
+                Some(Message::Key(modifiers, key))
+            }
+            _ => None,
+        }));
+
         // Poll for running webview processes every 5 seconds
         subscriptions.push(Subscription::run_with_id(
             "running-apps-poll",
             cosmic::iced::stream::channel(1, |mut channel| async move {
                 loop {
-                    let ids = tokio::task::spawn_blocking(webapps::running_webview_app_ids)
-                        .await
-                        .unwrap_or_default();
-                    let _ = channel.send(Message::UpdateRunningApps(ids)).await;
+                    let processes =
+                        tokio::task::spawn_blocking(webapps::process::running_webviews)
+                            .await
+                            .unwrap_or_default();
+                    let _ = channel.send(Message::UpdateProcesses(processes)).await;
                     tokio::time::sleep(Duration::from_secs(5)).await;
                 }
             }),
@@ -335,7 +687,11 @@ impl Application for QuickWebApps {
                     ))));
                 }
             }
-            Message::CloseDialog => self.dialogs = None,
+            Message::CloseDialog => {
+                self.site_icons_cancel.cancel();
+                self.refetch_cancel.cancel();
+                self.dialogs = None;
+            }
             Message::CloseToast(id) => {
                 self.toasts.remove(id);
             }
@@ -344,36 +700,87 @@ impl Application for QuickWebApps {
 
                 if let Some(page) = data {
                     let Page::Editor(app_editor) = page;
-                    self.dialogs = Some(Dialogs::Confirmation((id, app_editor.app_title.clone())))
-                };
-            }
-            Message::Editor(msg) => match &mut self.page {
-                Page::Editor(app_editor) => tasks.push(app_editor.update(msg)),
-            },
-            Message::Delete(id) => {
-                let data = self.nav.data::<Page>(id);
-
-                if let Some(page) = data {
-                    let Page::Editor(app_editor) = page;
-
                     if let Some(browser) = &app_editor.app_browser {
                         let launcher = webapps::launcher::WebAppLauncher {
                             browser: browser.clone(),
                             name: app_editor.app_title.clone(),
                             icon: app_editor.app_icon.clone(),
                             category: app_editor.app_category.clone(),
+                            custom_category: app_editor.app_custom_category.clone(),
+                            tags: app_editor.app_tags.clone(),
                         };
-
-                        self.dialogs = None;
-                        return task::future(async move {
-                            if let Err(e) = launcher.delete().await {
+                        self.dialogs = Some(Dialogs::Deletion(Deletion {
+                            entity: Some(id),
+                            apps: vec![launcher],
+                            purge: webapps::launcher::Purge::default(),
+                        }));
+                    }
+                };
+            }
+            Message::Editor(editor::Message::Done) => {
+                let Page::Editor(app_editor) = &self.page;
+                let duplicates = app_editor.duplicates(&self.cached_apps);
+                if duplicates.is_empty() {
+                    return self.update(Message::SaveAnyway);
+                }
+                self.dialogs = Some(Dialogs::Duplicates(duplicates));
+            }
+            Message::SaveAnyway => {
+                self.dialogs = None;
+                let Page::Editor(app_editor) = &mut self.page;
+                tasks.push(app_editor.update(editor::Message::Done));
+            }
+            Message::OpenDuplicate(app_id) => {
+                self.dialogs = None;
+                return self.update(Message::SelectAppFromGrid(app_id));
+            }
+            Message::Editor(msg) => match &mut self.page {
+                Page::Editor(app_editor) => {
+                    tasks.push(app_editor.update(msg));
+                    app_editor.update_preview();
+                }
+            },
+            Message::PurgeProfile(purge) => {
+                if let Some(Dialogs::Deletion(deletion)) = &mut self.dialogs {
+                    deletion.purge.profile = purge;
+                }
+            }
+            Message::PurgeIcons(purge) => {
+                if let Some(Dialogs::Deletion(deletion)) = &mut self.dialogs {
+                    deletion.purge.icons = purge;
+                }
+            }
+            Message::Delete => {
+                let Some(Dialogs::Deletion(deletion)) = self.dialogs.take() else {
+                    return Task::none();
+                };
+                let Deletion {
+                    entity,
+                    apps,
+                    purge,
+                } = deletion;
+
+                if let Some(id) = entity {
+                    return task::future(async move {
+                        for launcher in apps {
+                            if let Err(e) = launcher.delete(purge).await {
                                 tracing::error!("Failed to delete web app: {e}");
                                 return cosmic::action::app(Message::CloseDialog);
                             }
-                            cosmic::action::app(Message::DeletionDone(id))
-                        });
-                    }
+                        }
+                        cosmic::action::app(Message::DeletionDone(id))
+                    });
                 }
+
+                let count = apps.len();
+                return task::future(async move {
+                    for launcher in apps {
+                        if let Err(e) = launcher.delete(purge).await {
+                            tracing::error!("Failed to delete web app {}: {e}", launcher.name);
+                        }
+                    }
+                    cosmic::action::app(Message::BulkDeleteDone(count))
+                });
             }
             Message::DeletionDone(id) => {
                 self.nav.remove(id);
@@ -385,6 +792,26 @@ impl Application for QuickWebApps {
                         .map(cosmic::Action::App),
                 );
             }
+            Message::Key(modifiers, key) => {
+                let action = self
+                    .key_binds
+                    .iter()
+                    .find(|(key_bind, _)| key_bind.matches(modifiers, &key))
+                    .map(|(_, action)| *action);
+                if let Some(action) = action {
+                    return self.update(menu::action::MenuAction::message(&action));
+                }
+            }
+            Message::NewApp => {
+                return self.leave_editor(Message::ReloadNavbarItems);
+            }
+            Message::OpenPage(id) => {
+                return self.open_page(id);
+            }
+            Message::DiscardChanges(next) => {
+                self.dialogs = None;
+                return self.update(*next);
+            }
             Message::DuplicateApp(editor) => {
                 self.page = Page::Editor(*editor);
                 // Select the "Create new" entry so the user can save the duplicate
@@ -395,7 +822,10 @@ impl Application for QuickWebApps {
             }
             Message::DownloaderDone => {
                 self.downloader_started = false;
-                return task::message(cosmic::action::app(Message::CloseDialog));
+                return Task::batch([
+                    task::message(cosmic::action::app(Message::CloseDialog)),
+                    Self::refresh_icon_index(Some(self.icon_index.clone())),
+                ]);
             }
             Message::DownloaderStarted => {
                 self.dialogs = None;
@@ -416,6 +846,22 @@ impl Application for QuickWebApps {
             Message::SearchApps(query) => {
                 self.search_query = query;
                 self.rebuild_nav_from_cache(None);
+                return self.request_visible_thumbnails();
+            }
+            Message::FilterApps(idx) => {
+                // The first option shows all apps
+                self.app_filter = idx
+                    .checked_sub(1)
+                    .and_then(|idx| self.filters.get(idx).cloned());
+                self.rebuild_nav_from_cache(None);
+                return self.request_visible_thumbnails();
+            }
+            Message::GridScrolled(viewport) => {
+                self.grid_viewport = Some(viewport);
+                return self.request_visible_thumbnails();
+            }
+            Message::ThumbnailLoaded(path, icon) => {
+                self.thumbnails.insert(path, icon);
             }
             Message::ExportApps => {
                 return task::future(async {
@@ -474,6 +920,52 @@ impl Application for QuickWebApps {
                     );
                 }
             },
+            Message::ExportCookies(profile, app_name) => {
+                return task::future(async move {
+                    let title = fl!("file-dialog-export-cookies-title");
+                    let label = fl!("file-dialog-save");
+                    let response = match SelectedFiles::save_file()
+                        .title(title.as_str())
+                        .accept_label(label.as_str())
+                        .modal(true)
+                        .current_name(format!("{app_name} cookies.txt").as_str())
+                        .send()
+                        .await
+                    {
+                        Ok(r) => r.response(),
+                        Err(e) => {
+                            tracing::error!("Failed to open save dialog: {e}");
+                            return cosmic::action::app(Message::ExportCookiesResult(Err(fl!(
+                                "toast-export-cookies-error"
+                            ))));
+                        }
+                    };
+
+                    if let Some(uri) = response.ok().and_then(|r| r.uris().first().cloned()) {
+                        let path = std::path::PathBuf::from(uri.path());
+                        let result = match webapps::cookies::export(&profile, &path) {
+                            Ok(count) => Ok(count),
+                            Err(e) => {
+                                tracing::error!("Cookie export failed: {e}");
+                                Err(fl!("toast-export-cookies-error"))
+                            }
+                        };
+                        return cosmic::action::app(Message::ExportCookiesResult(result));
+                    }
+                    cosmic::action::none()
+                });
+            }
+            Message::ExportCookiesResult(result) => {
+                let message = match result {
+                    Ok(count) => fl!("toast-export-cookies-success", count = count),
+                    Err(msg) => msg,
+                };
+                tasks.push(
+                    self.toasts
+                        .push(widget::toaster::Toast::new(message))
+                        .map(cosmic::Action::App),
+                );
+            }
             Message::ImportApps => {
                 return task::future(async {
                     let title = fl!("file-dialog-import-title");
@@ -573,6 +1065,17 @@ impl Application for QuickWebApps {
                     tasks.push(icon_picker.update(msg));
                 };
             }
+            Message::IconIndex(index) => {
+                self.icon_index = index;
+                if let Some(Dialogs::IconPicker(icon_picker)) = &mut self.dialogs {
+                    icon_picker.set_index(self.icon_index.clone());
+                }
+                // The saved index arrives first, then it is brought up to date once
+                if !self.icon_index_refreshed {
+                    self.icon_index_refreshed = true;
+                    return Self::refresh_icon_index(Some(self.icon_index.clone()));
+                }
+            }
             Message::IconsResult(result) => {
                 if let Some(Dialogs::IconPicker(_icon_picker)) = &mut self.dialogs {
                     for path in result {
@@ -739,11 +1242,21 @@ impl Application for QuickWebApps {
                 return task::message(cosmic::action::app(Message::IconsResult(moved)));
             }
             Message::OpenIconPicker => {
-                self.dialogs = Some(Dialogs::IconPicker(IconPicker::default()));
+                self.dialogs = Some(Dialogs::IconPicker(IconPicker::new(self.icon_index.clone())));
             }
             Message::OpenRepositoryUrl => {
                 _ = open::that_detached(REPOSITORY);
             }
+            Message::OpenSiteIcons(url) => {
+                self.site_icons_cancel.cancel();
+                self.site_icons_cancel = webapps::http::Cancel::default();
+                self.dialogs = Some(Dialogs::SiteIcons(None));
+                let cancel = self.site_icons_cancel.clone();
+                return task::future(async move {
+                    let candidates = webapps::icon_candidates(&url, &cancel).await;
+                    cosmic::action::app(Message::SiteIcons(candidates))
+                });
+            }
             Message::OpenThemeResult(theme) => {
                 if !theme.is_empty() {
                     let from_path = Path::new(&theme);
@@ -766,13 +1279,249 @@ impl Application for QuickWebApps {
                 }
             }
             Message::ReloadNavbarItems => {
-                // Read from disk and cache
-                self.cached_apps = webapps::launcher::installed_webapps();
-                self.cached_apps.sort_by(|a, b| {
-                    let cat_cmp = a.category.name().cmp(&b.category.name());
-                    cat_cmp.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+                // Read from disk off the UI thread; unchanged files come from the cache
+                return task::future(async {
+                    let apps = webapps::launcher::installed_webapps_async().await;
+                    cosmic::action::app(Message::InstalledApps(apps))
                 });
+            }
+            Message::InstalledApps(apps) => {
+                self.cached_apps = apps;
+                self.sort_apps();
+                self.filters = AppFilter::for_apps(&self.cached_apps);
+                self.filter_options = std::iter::once(fl!("all-apps"))
+                    .chain(self.filters.iter().map(AppFilter::name))
+                    .collect();
+                if self
+                    .app_filter
+                    .as_ref()
+                    .is_some_and(|filter| !self.filters.contains(filter))
+                {
+                    self.app_filter = None;
+                }
                 self.rebuild_nav_from_cache(None);
+                // First-time users are guided through creating their first app
+                if self.cached_apps.is_empty() {
+                    tasks.push(task::message(cosmic::action::app(Message::Editor(
+                        editor::Message::StartWizard,
+                    ))));
+                }
+                tasks.push(self.load_storage_sizes());
+                tasks.push(self.load_activity());
+                tasks.push(self.request_visible_thumbnails());
+            }
+            Message::StorageSizes(sizes) => {
+                self.storage_sizes = sizes;
+                let active_app_id = match &self.page {
+                    Page::Editor(editor) => editor
+                        .app_browser
+                        .as_ref()
+                        .map(|b| b.app_id.as_ref().to_string()),
+                };
+                self.rebuild_nav_from_cache(active_app_id.as_deref());
+            }
+            Message::AppActivity(activity) => {
+                self.activity = activity;
+                if self.config.sort_mode.uses_activity() {
+                    return self.resort_apps();
+                }
+            }
+            Message::SortApps(idx) => {
+                self.config.sort_mode = SortMode::ALL.get(idx).copied().unwrap_or_default();
+                if let Some(handler) = AppConfig::config_handler() {
+                    let _ = self.config.set_sort_mode(&handler, self.config.sort_mode);
+                }
+                return self.resort_apps();
+            }
+            Message::MoveApp(app_id, target) => {
+                // Start from the order shown, so the other apps stay where they are
+                let mut order: Vec<String> = self
+                    .cached_apps
+                    .iter()
+                    .map(|app| app.browser.app_id.as_ref().to_string())
+                    .collect();
+                app_order::move_before(&mut order, &app_id, &target);
+                self.config.sort_mode = SortMode::Manual;
+                self.config.app_order = order;
+                if let Some(handler) = AppConfig::config_handler() {
+                    let _ = self.config.set_sort_mode(&handler, SortMode::Manual);
+                    let _ = self.config.set_app_order(&handler, self.config.app_order.clone());
+                }
+                return self.resort_apps();
+            }
+            Message::DropOnNavItem(id, app_id) => {
+                // Apps dropped on a group's header join the group
+                if let Some(NavGroup(idx)) = self.nav.data::<NavGroup>(id) {
+                    return self.update(Message::AssignGroup(app_id, Some(*idx)));
+                }
+                if let Some(Page::Editor(editor)) = self.nav.data::<Page>(id) {
+                    if let Some(browser) = &editor.app_browser {
+                        let target = browser.app_id.as_ref().to_string();
+                        return self.update(Message::MoveApp(app_id, target));
+                    }
+                }
+            }
+            Message::NewCategoryName(name) => {
+                self.new_category_name = name;
+            }
+            Message::NewCategoryXdg(idx) => {
+                self.new_category_xdg = idx;
+            }
+            Message::AddCategory => {
+                let name = self.new_category_name.trim().to_string();
+                let taken = self
+                    .category_choices
+                    .names()
+                    .iter()
+                    .any(|other| other.eq_ignore_ascii_case(&name));
+                if name.is_empty() || taken {
+                    return Task::none();
+                }
+                let xdg = xdg_categories().nth(self.new_category_xdg).unwrap_or_default();
+                self.config.custom_categories.push(webapps::CustomCategory { name, xdg });
+                self.new_category_name.clear();
+                self.save_custom_categories();
+            }
+            Message::CategoryXdg(idx, xdg_idx) => {
+                let Some(custom) = self.config.custom_categories.get_mut(idx) else {
+                    return Task::none();
+                };
+                let from = CategoryChoice {
+                    category: custom.xdg.clone(),
+                    custom: Some(custom.name.clone()),
+                };
+                custom.xdg = xdg_categories().nth(xdg_idx).unwrap_or_default();
+                let to = CategoryChoice {
+                    category: custom.xdg.clone(),
+                    custom: Some(custom.name.clone()),
+                };
+                self.save_custom_categories();
+                return self.move_apps(&from, to);
+            }
+            Message::RemoveCategory(idx) => {
+                if idx >= self.config.custom_categories.len() {
+                    return Task::none();
+                }
+                // Its apps stay in the XDG category it was shown in
+                let custom = self.config.custom_categories.remove(idx);
+                self.save_custom_categories();
+                let to = CategoryChoice {
+                    category: custom.xdg.clone(),
+                    custom: None,
+                };
+                let from = CategoryChoice {
+                    category: custom.xdg,
+                    custom: Some(custom.name),
+                };
+                return self.move_apps(&from, to);
+            }
+            Message::MoveAppsFrom(choice) => {
+                self.move_apps_from = choice;
+            }
+            Message::MoveAppsTo(choice) => {
+                self.move_apps_to = choice;
+            }
+            Message::MoveApps => {
+                let from = &self.move_apps_from;
+                if *from == self.move_apps_to {
+                    return Task::none();
+                }
+                let apps = self
+                    .cached_apps
+                    .iter()
+                    .filter(|app| {
+                        app.category == from.category && app.custom_category == from.custom
+                    })
+                    .cloned()
+                    .collect();
+                return move_apps(apps, self.move_apps_to.clone());
+            }
+            Message::AppsMoved(count) => {
+                tasks.push(
+                    self.toasts
+                        .push(widget::toaster::Toast::new(fl!("toast-apps-moved", count = count)))
+                        .map(cosmic::Action::App),
+                );
+                tasks.push(task::message(cosmic::action::app(Message::ReloadNavbarItems)));
+            }
+            Message::NewGroupName(name) => {
+                self.new_group_name = name;
+            }
+            Message::AddGroup => {
+                let name = self.new_group_name.trim().to_string();
+                let taken = self
+                    .config
+                    .app_groups
+                    .iter()
+                    .any(|group| group.name.eq_ignore_ascii_case(&name));
+                if name.is_empty() || taken {
+                    return Task::none();
+                }
+                self.config.app_groups.push(AppGroup {
+                    name,
+                    ..AppGroup::default()
+                });
+                self.new_group_name.clear();
+                return self.save_groups();
+            }
+            Message::RemoveGroup(idx) => {
+                if idx < self.config.app_groups.len() {
+                    self.config.app_groups.remove(idx);
+                    self.open_group = None;
+                    return self.save_groups();
+                }
+            }
+            Message::ToggleGroup(idx) => {
+                if let Some(group) = self.config.app_groups.get_mut(idx) {
+                    group.collapsed = !group.collapsed;
+                    return self.save_groups();
+                }
+            }
+            Message::OpenGroup(idx) => {
+                self.open_group = idx;
+                self.grid_viewport = None;
+                return self.request_visible_thumbnails();
+            }
+            Message::AssignGroup(app_id, idx) => {
+                for group in &mut self.config.app_groups {
+                    group.app_ids.retain(|id| *id != app_id);
+                }
+                if let Some(group) = idx.and_then(|idx| self.config.app_groups.get_mut(idx)) {
+                    group.app_ids.push(app_id);
+                }
+                return self.save_groups();
+            }
+            Message::BulkAssignGroup(option) => {
+                // The first option takes the apps out of their groups
+                let idx = option.checked_sub(1);
+                for app_id in &self.selected_app_ids {
+                    for group in &mut self.config.app_groups {
+                        group.app_ids.retain(|id| id != app_id);
+                    }
+                    if let Some(group) = idx.and_then(|idx| self.config.app_groups.get_mut(idx)) {
+                        group.app_ids.push(app_id.clone());
+                    }
+                }
+                return self.save_groups();
+            }
+            Message::TogglePin(app_id) => {
+                if self.config.pinned_apps.contains(&app_id) {
+                    self.config.pinned_apps.retain(|id| *id != app_id);
+                } else {
+                    self.config.pinned_apps.push(app_id);
+                }
+                if let Some(handler) = AppConfig::config_handler() {
+                    let _ = self
+                        .config
+                        .set_pinned_apps(&handler, self.config.pinned_apps.clone());
+                }
+            }
+            Message::GridDensity(idx) => {
+                self.config.grid_density = GridDensity::ALL.get(idx).copied().unwrap_or_default();
+                if let Some(handler) = AppConfig::config_handler() {
+                    let _ = self.config.set_grid_density(&handler, self.config.grid_density);
+                }
+                return self.request_visible_thumbnails();
             }
             Message::ResetSettings => {
                 if let Some(handler) = AppConfig::config_handler() {
@@ -814,23 +1563,70 @@ impl Application for QuickWebApps {
 
                     // Reload and re-select saved app
                     self.cached_apps = webapps::launcher::installed_webapps();
-                    self.cached_apps.sort_by(|a, b| {
-                        let cat_cmp = a.category.name().cmp(&b.category.name());
-                        cat_cmp.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
-                    });
+                    self.sort_apps();
                     let app_id = launcher.browser.app_id.as_ref().to_string();
                     self.rebuild_nav_from_cache(Some(&app_id));
                     return Task::batch(tasks);
                 }
             }
+            Message::RenameApp(old_id, new_id) => {
+                if self.running_app_ids.contains(&old_id) {
+                    return self
+                        .toasts
+                        .push(widget::toaster::Toast::new(fl!("toast-rename-running")))
+                        .map(cosmic::Action::App);
+                }
+                let Some(launcher) = webapps::launcher::WebAppLauncher::from_appid(&old_id) else {
+                    return Task::none();
+                };
+                return task::future(async move {
+                    let result = launcher
+                        .rename(&new_id)
+                        .await
+                        .map(|renamed| (old_id, renamed.browser.app_id.id))
+                        .map_err(|e| e.to_string());
+                    cosmic::action::app(Message::AppRenamed(result))
+                });
+            }
+            Message::AppRenamed(Ok((old_id, new_id))) => {
+                self.rename_references(&old_id, &new_id);
+                tasks.push(
+                    self.toasts
+                        .push(widget::toaster::Toast::new(fl!("toast-app-renamed")))
+                        .map(cosmic::Action::App),
+                );
+                self.cached_apps = webapps::launcher::installed_webapps();
+                self.sort_apps();
+                self.rebuild_nav_from_cache(Some(&new_id));
+                return Task::batch(tasks);
+            }
+            Message::AppRenamed(Err(e)) => {
+                tracing::error!("Failed to rename web app: {e}");
+                tasks.push(
+                    self.toasts
+                        .push(widget::toaster::Toast::new(fl!("toast-rename-error")))
+                        .map(cosmic::Action::App),
+                );
+            }
             Message::SetIcon(icon) => {
                 let Page::Editor(app_editor) = &mut self.page;
                 app_editor.update_icon(icon);
                 self.dialogs = None;
             }
-            Message::Surface(a) => {
-                return cosmic::task::message(cosmic::Action::Cosmic(
-                    cosmic::app::Action::Surface(a),
+            Message::SiteIconChosen(candidate) => {
+                return task::future(async move {
+                    let icon = webapps::save_icon_candidate(candidate).await;
+                    cosmic::action::app(Message::SetIcon(icon))
+                });
+            }
+            Message::SiteIcons(candidates) => {
+                if let Some(Dialogs::SiteIcons(loading @ None)) = &mut self.dialogs {
+                    *loading = Some(candidates);
+                }
+            }
+            Message::Surface(a) => {
+                return cosmic::task::message(cosmic::Action::Cosmic(
+                    cosmic::app::Action::Surface(a),
                 ));
             }
             Message::ToggleContextPage(context_page) => {
@@ -842,8 +1638,65 @@ impl Application for QuickWebApps {
                 }
             }
 
+            Message::FaviconRefresh(idx) => {
+                use webapps::favicon_refresh::Schedule;
+                self.config.favicon_refresh = match idx {
+                    1 => Schedule::OnLaunch,
+                    2 => Schedule::Weekly,
+                    _ => Schedule::Off,
+                };
+                if let Some(handler) = AppConfig::config_handler() {
+                    let _ = self
+                        .config
+                        .set_favicon_refresh(&handler, self.config.favicon_refresh);
+                }
+            }
+            Message::RefreshFavicons => {
+                if !self.favicons_refreshing {
+                    self.favicons_refreshing = true;
+                    return task::future(async {
+                        let changes = webapps::favicon_refresh::run().await;
+                        cosmic::action::app(Message::FaviconsRefreshed(changes))
+                    });
+                }
+            }
+            Message::FaviconsRefreshed(changes) => {
+                self.favicons_refreshing = false;
+                self.favicon_changes = webapps::favicon_refresh::RefreshLog::load().changes;
+                if !changes.is_empty() {
+                    tasks.push(
+                        self.toasts
+                            .push(widget::toaster::Toast::new(fl!(
+                                "toast-favicons-refreshed",
+                                count = changes.len()
+                            )))
+                            .map(cosmic::Action::App),
+                    );
+                    tasks.push(task::message(cosmic::action::app(Message::ReloadNavbarItems)));
+                }
+            }
+            Message::ToggleWarmPool(enabled) => {
+                let result = if enabled {
+                    webapps::warm::enable()
+                } else {
+                    webapps::warm::disable()
+                };
+                match result {
+                    Ok(()) => self.warm_pool = enabled,
+                    Err(e) => {
+                        tracing::error!("Failed to change the renderer pool: {e}");
+                        tasks.push(
+                            self.toasts
+                                .push(widget::toaster::Toast::new(fl!("toast-warm-pool-error")))
+                                .map(cosmic::Action::App),
+                        );
+                    }
+                }
+            }
             Message::UpdateConfig(config) => {
                 self.config = config;
+                self.category_choices = CategoryChoices::new(&self.config.custom_categories);
+                self.group_options = group_options(&self.config.app_groups);
             }
             Message::UpdateTheme(theme) => {
                 if let Theme::Custom(theme) = *theme {
@@ -873,10 +1726,24 @@ impl Application for QuickWebApps {
                     tasks.push(theme_selector);
                 }
             }
-            Message::ClearAppData(app_id) => {
+            Message::ClearAppData(app_id, category) => {
+                // Removing files under a running WebKit leaves it with stale state, so a
+                // running app clears its own data
+                if self.running_app_ids.contains(&app_id) {
+                    return task::future(async move {
+                        let command = webapps::control::Command::ClearData(category);
+                        let result = webapps::control::send(&app_id, &command)
+                            .await
+                            .map_err(|e| e.to_string());
+                        cosmic::action::app(Message::ClearAppDataDone(result))
+                    });
+                }
                 return task::future(async move {
-                    match tokio::task::spawn_blocking(move || webapps::clear_profile_data(&app_id))
-                        .await
+                    match tokio::task::spawn_blocking(move || match category {
+                        Some(category) => webapps::clear_profile_category(&app_id, category),
+                        None => webapps::clear_profile_data(&app_id),
+                    })
+                    .await
                     {
                         Ok(Ok(())) => cosmic::action::app(Message::ClearAppDataDone(Ok(()))),
                         Ok(Err(e)) => {
@@ -895,6 +1762,10 @@ impl Application for QuickWebApps {
                             .push(widget::toaster::Toast::new(fl!("toast-data-cleared")))
                             .map(cosmic::Action::App),
                     );
+                    tasks.push(task::message(cosmic::action::app(Message::Editor(
+                        editor::Message::LoadStorageUsage,
+                    ))));
+                    tasks.push(self.load_storage_sizes());
                 }
                 Err(msg) => {
                     tracing::error!("Failed to clear app data: {msg}");
@@ -915,6 +1786,20 @@ impl Application for QuickWebApps {
                     return task::future(async { crate::pages::Message::Launch(arg_id) });
                 }
             }
+            Message::ControlCurrentApp(command) => {
+                let Page::Editor(editor) = &self.page;
+                if let Some(browser) = &editor.app_browser {
+                    let app_id = browser.app_id.as_ref().to_string();
+                    return Task::perform(
+                        async move {
+                            if let Err(e) = webapps::control::send(&app_id, &command).await {
+                                tracing::error!("Failed to control {app_id}: {e}");
+                            }
+                        },
+                        |_| cosmic::Action::App(Message::None),
+                    );
+                }
+            }
             Message::DuplicateCurrentApp => {
                 return task::message(cosmic::action::app(Message::Editor(
                     editor::Message::Duplicate,
@@ -929,6 +1814,8 @@ impl Application for QuickWebApps {
                 if let Some(handler) = AppConfig::config_handler() {
                     let _ = self.config.set_view_mode(&handler, self.config.view_mode);
                 }
+                self.grid_viewport = None;
+                return self.request_visible_thumbnails();
             }
             Message::SelectAppFromGrid(app_id) => {
                 // Find the target entity first (immutable borrow)
@@ -955,6 +1842,45 @@ impl Application for QuickWebApps {
                     }
                 }
             }
+            Message::UpdateProcesses(processes) => {
+                let now = std::time::Instant::now();
+                let elapsed = self
+                    .processes_sampled
+                    .map(|sampled| now.duration_since(sampled).as_secs_f32());
+                self.cpu_usage = processes
+                    .iter()
+                    .filter_map(|process| {
+                        let previous = self.processes.iter().find(|p| p.pid == process.pid)?;
+                        let ticks = process.cpu_ticks.saturating_sub(previous.cpu_ticks);
+                        let secs = ticks as f32 / webapps::process::TICKS_PER_SEC as f32;
+                        Some((process.pid, secs / elapsed? * 100.0))
+                    })
+                    .collect();
+                self.processes_sampled = Some(now);
+                let ids = processes.iter().map(|p| p.app_id.clone()).collect();
+                self.processes = processes;
+                return self.update(Message::UpdateRunningApps(ids));
+            }
+            Message::ControlApp(app_id, command) => {
+                return Task::perform(
+                    async move {
+                        if let Err(e) = webapps::control::send(&app_id, &command).await {
+                            tracing::error!("Failed to control {app_id}: {e}");
+                        }
+                    },
+                    |_| cosmic::Action::App(Message::None),
+                );
+            }
+            Message::KillApp(pid) => {
+                return Task::perform(
+                    async move {
+                        if let Err(e) = webapps::process::kill(pid).await {
+                            tracing::error!("Failed to kill webview {pid}: {e}");
+                        }
+                    },
+                    |_| cosmic::Action::App(Message::None),
+                );
+            }
             Message::UpdateRunningApps(ids) => {
                 if ids != self.running_app_ids {
                     self.running_app_ids = ids;
@@ -985,6 +1911,23 @@ impl Application for QuickWebApps {
                     self.selected_app_ids.clear();
                 }
             }
+            Message::SelectApps(app_ids) => {
+                self.bulk_mode = true;
+                self.selected_app_ids = app_ids.into_iter().collect();
+                self.core.window.show_context = false;
+            }
+            Message::ShowStatistics => {
+                if !(self.core.window.show_context && self.context_page == ContextPage::Statistics)
+                {
+                    tasks.push(self.statistics.update(statistics::Message::Load));
+                }
+                tasks.push(task::message(cosmic::action::app(Message::ToggleContextPage(
+                    ContextPage::Statistics,
+                ))));
+            }
+            Message::Statistics(msg) => {
+                tasks.push(self.statistics.update(msg));
+            }
             Message::ToggleBulkSelect(app_id) => {
                 if self.selected_app_ids.contains(&app_id) {
                     self.selected_app_ids.remove(&app_id);
@@ -993,23 +1936,11 @@ impl Application for QuickWebApps {
                 }
             }
             Message::BulkDelete => {
-                let ids_to_delete: Vec<String> = self.selected_app_ids.iter().cloned().collect();
-                let apps_to_delete: Vec<webapps::launcher::WebAppLauncher> = self
-                    .cached_apps
-                    .iter()
-                    .filter(|app| ids_to_delete.contains(&app.browser.app_id.as_ref().to_string()))
-                    .cloned()
-                    .collect();
-
-                let count = apps_to_delete.len();
-                return task::future(async move {
-                    for launcher in apps_to_delete {
-                        if let Err(e) = launcher.delete().await {
-                            tracing::error!("Failed to delete web app {}: {e}", launcher.name);
-                        }
-                    }
-                    cosmic::action::app(Message::BulkDeleteDone(count))
-                });
+                self.dialogs = Some(Dialogs::Deletion(Deletion {
+                    entity: None,
+                    apps: self.selected_apps(),
+                    purge: webapps::launcher::Purge::default(),
+                }));
             }
             Message::BulkDeleteDone(count) => {
                 self.bulk_mode = false;
@@ -1022,6 +1953,63 @@ impl Application for QuickWebApps {
                 tasks.push(task::message(cosmic::action::app(Message::ReloadNavbarItems)));
                 tracing::info!("Bulk deleted {count} apps");
             }
+            Message::BulkRefetchIcons => {
+                return self.refetch_icons(self.selected_apps());
+            }
+            Message::BulkCategory(choice) => {
+                return move_apps(self.selected_apps(), choice);
+            }
+            Message::BulkPersistent(persistent) => {
+                let apps = self.selected_apps();
+                return task::future(async move {
+                    let mut saved = 0;
+                    for mut app in apps {
+                        app.browser.set_persistent(persistent);
+                        if let Err(e) = app.save() {
+                            tracing::error!("Failed to save web app {}: {e}", app.name);
+                            continue;
+                        }
+                        saved += 1;
+                    }
+                    cosmic::action::app(Message::BulkSaved(saved))
+                });
+            }
+            Message::BulkSaved(count) => {
+                tasks.push(
+                    self.toasts
+                        .push(widget::toaster::Toast::new(fl!("toast-bulk-saved", count = count)))
+                        .map(cosmic::Action::App),
+                );
+                tasks.push(task::message(cosmic::action::app(Message::ReloadNavbarItems)));
+            }
+            Message::BulkLaunch => {
+                return Task::batch(self.selected_apps().into_iter().map(|app| {
+                    task::message(cosmic::action::app(Message::Launch(app.browser.app_id)))
+                }));
+            }
+            Message::RefetchAllIcons => {
+                return self.refetch_icons(self.cached_apps.clone());
+            }
+            Message::IconRefetched(name, result) => {
+                let Some(Dialogs::RefetchIcons(progress)) = &mut self.dialogs else {
+                    return Task::none();
+                };
+                progress.results.push((name, result));
+                if let Some(app) = progress.pending.pop() {
+                    return self.refetch_next(app);
+                }
+
+                let updated = progress
+                    .results
+                    .iter()
+                    .any(|(_, result)| *result == webapps::favicon_refresh::Refetch::Updated);
+                if updated {
+                    // Updated icons may be saved over the files the grid decoded
+                    self.thumbnails = Thumbnails::default();
+                    self.favicon_changes = webapps::favicon_refresh::RefreshLog::load().changes;
+                    tasks.push(task::message(cosmic::action::app(Message::ReloadNavbarItems)));
+                }
+            }
             Message::BulkExport => {
                 let ids_to_export: Vec<String> = self.selected_app_ids.iter().cloned().collect();
                 let apps_to_export: Vec<webapps::launcher::WebAppLauncher> = self
@@ -1123,20 +2111,41 @@ impl Application for QuickWebApps {
                         fl!("app"),
                         vec![
                             menu::Item::Button(fl!("new-app"), None, MenuAction::NewApp),
+                            menu::Item::Button(fl!("undo"), None, MenuAction::Undo),
+                            menu::Item::Button(fl!("redo"), None, MenuAction::Redo),
                             menu::Item::Divider,
                             menu::Item::Button(fl!("export-apps"), None, MenuAction::ExportApps),
                             menu::Item::Button(fl!("import-apps"), None, MenuAction::ImportApps),
                             menu::Item::Divider,
+                            menu::Item::Button(fl!("running-apps"), None, MenuAction::Running),
+                            menu::Item::Button(fl!("statistics"), None, MenuAction::Statistics),
                             menu::Item::Button(fl!("settings"), None, MenuAction::Settings),
                             menu::Item::Button(fl!("about"), None, MenuAction::About),
                         ],
                     )],
                 ),
-            widget::text_input(fl!("search"), &self.search_query)
+            widget::text_input(fl!("search-apps"), &self.search_query)
                 .on_input(Message::SearchApps)
                 .width(Length::Fixed(200.0))
                 .id(SEARCH_ID.clone())
                 .into(),
+            widget::dropdown(
+                &self.filter_options,
+                Some(self.app_filter.as_ref().map_or(0, |filter| {
+                    self.filters
+                        .iter()
+                        .position(|f| f == filter)
+                        .map_or(0, |idx| idx + 1)
+                })),
+                Message::FilterApps,
+            )
+            .into(),
+            widget::dropdown(
+                &self.sort_options,
+                SortMode::ALL.iter().position(|mode| *mode == self.config.sort_mode),
+                Message::SortApps,
+            )
+            .into(),
             widget::tooltip(
                 widget::button::icon(match self.config.view_mode {
                     crate::config::ViewMode::List => widget::icon::from_name("view-grid-symbolic"),
@@ -1168,6 +2177,12 @@ impl Application for QuickWebApps {
             cosmic::Action::Cosmic(cosmic::app::Action::NavBar(id))
         })
         .on_close(|id| cosmic::action::app(Message::ConfirmDeletion(id)))
+        .on_dnd_drop::<DraggedApp>(|id, dragged, _| {
+            cosmic::action::app(match dragged {
+                Some(DraggedApp(app_id)) => Message::DropOnNavItem(id, app_id),
+                None => Message::None,
+            })
+        })
         .into_container()
         .width(Length::Shrink)
         .height(Length::Shrink);
@@ -1186,21 +2201,10 @@ impl Application for QuickWebApps {
     }
 
     fn on_nav_select(&mut self, id: nav_bar::Id) -> Task<Message> {
-        self.nav.activate(id);
-        if let Some(page) = self.nav.data::<Page>(id) {
-            self.page = page.clone();
-
-            // Auto-trigger thumbnail fetch for installed apps with URLs
-            let Page::Editor(editor) = &self.page;
-            if editor.is_installed
-                && editor.thumbnail_handle.is_none()
-                && !editor.thumbnail_loading
-                && webapps::url_valid(&editor.app_url)
-            {
-                return task::message(Message::Editor(editor::Message::FetchThumbnail));
-            }
+        if let Some(NavGroup(idx)) = self.nav.data::<NavGroup>(id) {
+            return self.update(Message::ToggleGroup(*idx));
         }
-        Task::none()
+        self.leave_editor(Message::OpenPage(id))
     }
 
     fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Message>> {
@@ -1219,6 +2223,16 @@ impl Application for QuickWebApps {
                 Message::ToggleContextPage(ContextPage::Settings),
             )
             .title(fl!("settings")),
+            ContextPage::Running => context_drawer::context_drawer(
+                self.running(),
+                Message::ToggleContextPage(ContextPage::Running),
+            )
+            .title(fl!("running-apps")),
+            ContextPage::Statistics => context_drawer::context_drawer(
+                self.statistics.view(),
+                Message::ToggleContextPage(ContextPage::Statistics),
+            )
+            .title(fl!("statistics")),
         })
     }
 
@@ -1238,57 +2252,77 @@ impl Application for QuickWebApps {
         let main_content = match self.config.view_mode {
             crate::config::ViewMode::Grid if has_installed_apps => {
                 // Grid/card view of all apps
-                let query = self.search_query.to_lowercase();
-                let card_elements: Vec<Element<'_, Message>> = self
-                    .cached_apps
-                    .iter()
-                    .filter(|app| {
-                        query.is_empty() || app.name.to_lowercase().contains(&query)
-                    })
-                    .map(|app| {
-                        let is_running = self
-                            .running_app_ids
-                            .contains(app.browser.app_id.as_ref());
-
-                        let display_name = if is_running {
-                            format!("{} {}", fl!("running-indicator"), app.name)
-                        } else {
-                            app.name.clone()
-                        };
+                let density = self.config.grid_density;
+                let card_size = density.card_size();
+                let folders = self
+                    .grid_folders()
+                    .into_iter()
+                    .map(|(idx, count)| self.grid_folder(idx, count));
+                let app_cards = self.grid_apps().into_iter().map(|app| -> Element<'_, Message> {
+                    let is_running = self
+                        .running_app_ids
+                        .contains(app.browser.app_id.as_ref());
+
+                    let display_name = if is_running {
+                        format!("{} {}", fl!("running-indicator"), app.name)
+                    } else {
+                        app.name.clone()
+                    };
 
-                        let app_id = app.browser.app_id.as_ref().to_string();
-                        let is_selected = self.selected_app_ids.contains(&app_id);
-                        let btn_class = if self.bulk_mode && is_selected {
-                            cosmic::style::Button::Suggested
-                        } else {
-                            cosmic::style::Button::Image
-                        };
-                        let press_msg = if self.bulk_mode {
-                            Message::ToggleBulkSelect(app_id)
-                        } else {
-                            Message::SelectAppFromGrid(app_id)
-                        };
-                        widget::button::custom(
-                            widget::column()
-                                .spacing(8)
-                                .push(
-                                    widget::icon::from_name(app.icon.clone())
-                                        .size(64),
-                                )
-                                .push(
-                                    widget::text::body(display_name)
-                                        .width(Length::Fixed(100.0))
-                                        .align_x(Horizontal::Center),
-                                )
-                                .align_x(Alignment::Center),
-                        )
-                        .width(Length::Fixed(120.0))
-                        .height(Length::Fixed(120.0))
-                        .on_press(press_msg)
-                        .class(btn_class)
-                        .into()
-                    })
-                    .collect();
+                    let app_id = app.browser.app_id.as_ref().to_string();
+                    let is_selected = self.selected_app_ids.contains(&app_id);
+                    let btn_class = if self.bulk_mode && is_selected {
+                        cosmic::style::Button::Suggested
+                    } else {
+                        cosmic::style::Button::Image
+                    };
+                    let press_msg = if self.bulk_mode {
+                        Message::ToggleBulkSelect(app_id.clone())
+                    } else {
+                        Message::SelectAppFromGrid(app_id.clone())
+                    };
+                    let card = widget::button::custom(
+                        widget::column()
+                            .spacing(8)
+                            .push(self.grid_icon(&app.icon, density.icon_size()))
+                            .push(
+                                widget::text::body(display_name)
+                                    .width(Length::Fixed(card_size - 20.0))
+                                    .align_x(Horizontal::Center),
+                            )
+                            .push_maybe(
+                                self.storage_sizes
+                                    .get(app.browser.app_id.as_ref())
+                                    .filter(|size| {
+                                        **size > 0 && density != GridDensity::Compact
+                                    })
+                                    .map(|size| {
+                                        widget::text::caption(webapps::format_bytes(*size))
+                                    }),
+                            )
+                            .align_x(Alignment::Center),
+                    )
+                    .width(Length::Fixed(card_size))
+                    .height(Length::Fixed(card_size))
+                    .on_press(press_msg)
+                    .class(btn_class);
+
+                    // Cards are dragged onto each other to reorder them
+                    let dragged = app_id.clone();
+                    let source = widget::dnd_source::<Message, DraggedApp>(card)
+                        .drag_content(move || DraggedApp(dragged.clone()));
+                    widget::dnd_destination::DndDestination::for_data(
+                        source,
+                        move |dropped: Option<DraggedApp>, _| match dropped {
+                            Some(DraggedApp(dropped)) => {
+                                Message::MoveApp(dropped, app_id.clone())
+                            }
+                            None => Message::None,
+                        },
+                    )
+                    .into()
+                });
+                let card_elements: Vec<Element<'_, Message>> = folders.chain(app_cards).collect();
 
                 let cards = widget::row::with_children(card_elements)
                     .spacing(12)
@@ -1296,29 +2330,41 @@ impl Application for QuickWebApps {
 
                 let mut grid_col = widget::column().spacing(12);
 
-                // Bulk action toolbar
-                if self.bulk_mode && !self.selected_app_ids.is_empty() {
+                grid_col = grid_col.push_maybe(self.bulk_toolbar());
+
+                // The open folder's name, with a way back that apps can be dropped on to
+                // take them out of the group
+                let open_group = self
+                    .open_group
+                    .and_then(|idx| self.config.app_groups.get(idx));
+                if let Some(group) = open_group {
+                    let back =
+                        widget::button::icon(widget::icon::from_name("go-previous-symbolic"))
+                            .on_press(Message::OpenGroup(None));
                     grid_col = grid_col.push(
                         widget::container(
                             widget::row()
                                 .spacing(8)
-                                .push(
-                                    widget::button::destructive(fl!("bulk-delete"))
-                                        .on_press(Message::BulkDelete),
-                                )
-                                .push(
-                                    widget::button::standard(fl!("bulk-export"))
-                                        .on_press(Message::BulkExport),
-                                ),
+                                .align_y(Alignment::Center)
+                                .push(widget::dnd_destination::DndDestination::for_data(
+                                    back,
+                                    |dropped: Option<DraggedApp>, _| match dropped {
+                                        Some(DraggedApp(app_id)) => {
+                                            Message::AssignGroup(app_id, None)
+                                        }
+                                        None => Message::None,
+                                    },
+                                ))
+                                .push(widget::text::title4(group.name.as_str())),
                         )
                         .padding([0, 24]),
                     );
                 }
 
                 grid_col = grid_col.push(
-                    widget::container(cards)
-                        .padding(24)
-                        .width(Length::Fill),
+                    widget::scrollable(widget::container(cards).padding(24).width(Length::Fill))
+                        .height(Length::Fill)
+                        .on_scroll(Message::GridScrolled),
                 );
 
                 widget::container(grid_col)
@@ -1346,8 +2392,19 @@ impl Application for QuickWebApps {
                     );
                 }
 
-                // Quick-actions toolbar for installed apps
-                if content.is_installed {
+                // In bulk mode, apps are picked from a checklist in place of the editor.
+                // Otherwise a quick-actions toolbar for installed apps.
+                if self.bulk_mode {
+                    col = col
+                        .push_maybe(self.bulk_toolbar())
+                        .push(self.bulk_checklist());
+                } else if content.is_installed {
+                    use webapps::control::Command;
+                    let is_running = content
+                        .app_browser
+                        .as_ref()
+                        .is_some_and(|b| self.running_app_ids.contains(b.app_id.as_ref()));
+
                     col = col.push(
                         widget::container(
                             widget::row()
@@ -1356,6 +2413,14 @@ impl Application for QuickWebApps {
                                     widget::button::standard(fl!("run-app"))
                                         .on_press(Message::LaunchCurrentApp),
                                 )
+                                .push_maybe(is_running.then(|| {
+                                    widget::button::standard(fl!("reload-app"))
+                                        .on_press(Message::ControlCurrentApp(Command::Reload))
+                                }))
+                                .push_maybe(is_running.then(|| {
+                                    widget::button::standard(fl!("quit-app"))
+                                        .on_press(Message::ControlCurrentApp(Command::Quit))
+                                }))
                                 .push(
                                     widget::button::standard(fl!("duplicate"))
                                         .on_press(Message::DuplicateCurrentApp),
@@ -1364,6 +2429,16 @@ impl Application for QuickWebApps {
                                     widget::button::standard(fl!("open-in-browser"))
                                         .on_press(Message::OpenCurrentAppUrl),
                                 )
+                                .push_maybe(content.app_browser.as_ref().map(|browser| {
+                                    let app_id = browser.app_id.as_ref().to_string();
+                                    let label = if self.config.pinned_apps.contains(&app_id) {
+                                        fl!("unpin-app")
+                                    } else {
+                                        fl!("pin-app")
+                                    };
+                                    widget::button::standard(label)
+                                        .on_press(Message::TogglePin(app_id))
+                                }))
                                 .push(widget::horizontal_space())
                                 .push(
                                     widget::button::destructive(fl!("delete"))
@@ -1378,7 +2453,13 @@ impl Application for QuickWebApps {
                     );
                 }
 
-                col = col.push(content.view().map(Message::Editor));
+                if !self.bulk_mode {
+                    col = col.push(
+                        content
+                            .view(&self.cached_apps, &self.category_choices)
+                            .map(Message::Editor),
+                    );
+                }
 
                 widget::container(col)
                     .width(Length::Fill)
@@ -1388,6 +2469,11 @@ impl Application for QuickWebApps {
             }
         };
 
+        let main_content = widget::column()
+            .push_maybe(self.pinned_apps())
+            .push_maybe(self.recent_apps())
+            .push(main_content);
+
         widget::toaster::toaster(&self.toasts, main_content).into()
     }
 
@@ -1399,19 +2485,81 @@ impl Application for QuickWebApps {
                         widget::button::standard(fl!("close")).on_press(Message::CloseDialog),
                     )
                     .control(icon_picker.view().map(Message::IconPicker)),
-                Dialogs::Confirmation((entity, title)) => widget::dialog()
+                Dialogs::SiteIcons(candidates) => widget::dialog()
+                    .title(fl!("site-icons"))
+                    .primary_action(
+                        widget::button::standard(fl!("close")).on_press(Message::CloseDialog),
+                    )
+                    .control(self.site_icons(candidates.as_deref())),
+                Dialogs::Deletion(deletion) => widget::dialog()
                     .title(fl!("delete"))
                     .primary_action(
-                        widget::button::destructive(fl!("yes"))
-                            .on_press(Message::Delete(entity.to_owned())),
+                        widget::button::destructive(fl!("yes")).on_press(Message::Delete),
                     )
                     .secondary_action(
                         widget::button::suggested(fl!("no")).on_press(Message::CloseDialog),
                     )
-                    .body(fl!(
-                        "confirm-delete",
-                        HashMap::from([("app", title.as_str())])
-                    )),
+                    .body(match deletion.apps.as_slice() {
+                        [app] => {
+                            fl!("confirm-delete", HashMap::from([("app", app.name.as_str())]))
+                        }
+                        apps => fl!("confirm-delete-apps", count = apps.len()),
+                    })
+                    .control(self.deletion_contents(deletion)),
+                Dialogs::Duplicates(apps) => {
+                    let mut list = widget::column().spacing(8);
+                    for app in apps {
+                        list = list.push(
+                            widget::row()
+                                .spacing(8)
+                                .align_y(Alignment::Center)
+                                .push(
+                                    widget::column()
+                                        .width(Length::Fill)
+                                        .push(widget::text::body(app.name.clone()))
+                                        .push(widget::text::caption(
+                                            app.browser.url.clone().unwrap_or_default(),
+                                        )),
+                                )
+                                .push(widget::button::standard(fl!("open")).on_press(
+                                    Message::OpenDuplicate(app.browser.app_id.as_ref().to_string()),
+                                )),
+                        );
+                    }
+                    widget::dialog()
+                        .title(fl!("duplicate-apps"))
+                        .body(fl!("duplicate-apps-body"))
+                        .control(list)
+                        .primary_action(
+                            widget::button::suggested(fl!("create-anyway"))
+                                .on_press(Message::SaveAnyway),
+                        )
+                        .secondary_action(
+                            widget::button::standard(fl!("cancel")).on_press(Message::CloseDialog),
+                        )
+                }
+                Dialogs::UnsavedChanges(next) => widget::dialog()
+                    .title(fl!("unsaved-changes"))
+                    .body(fl!("unsaved-changes-body"))
+                    .primary_action(
+                        widget::button::destructive(fl!("discard-changes"))
+                            .on_press(Message::DiscardChanges(next.clone())),
+                    )
+                    .secondary_action(
+                        widget::button::suggested(fl!("keep-editing"))
+                            .on_press(Message::CloseDialog),
+                    ),
+                Dialogs::RefetchIcons(progress) => widget::dialog()
+                    .title(fl!("refetch-icons"))
+                    .primary_action(
+                        widget::button::standard(if progress.is_finished() {
+                            fl!("close")
+                        } else {
+                            fl!("cancel")
+                        })
+                        .on_press(Message::CloseDialog),
+                    )
+                    .control(self.refetch_progress(progress)),
                 Dialogs::IconsDownloader => widget::dialog()
                     .title(fl!("icons-installer-header"))
                     .body(self.downloader_output.clone())
@@ -1432,6 +2580,669 @@ impl Application for QuickWebApps {
 }
 
 impl QuickWebApps {
+    /// Decode the icons of the grid cards in view, and a row beyond.
+    fn request_visible_thumbnails(&mut self) -> Task<Message> {
+        // The grid's padding, and the spacing between cards
+        const PADDING: f32 = 24.0;
+        const SPACING: f32 = 12.0;
+
+        if self.config.view_mode != crate::config::ViewMode::Grid {
+            return Task::none();
+        }
+        // Until the grid reports its viewport, assume a large window
+        let (offset, width, height) = self
+            .grid_viewport
+            .map(|v| (v.absolute_offset().y, v.bounds().width, v.bounds().height))
+            .unwrap_or((0.0, 1920.0, 1080.0));
+        let card = self.config.grid_density.card_size() + SPACING;
+        let columns = ((width - 2.0 * PADDING + SPACING) / card).floor().max(1.0) as usize;
+        let first_row = ((offset - PADDING).max(0.0) / card).floor() as usize;
+        let rows = (height / card).ceil() as usize + 1;
+
+        // Folders come before the apps
+        let folders = self.grid_folders().len();
+        let paths: Vec<&str> = self
+            .grid_apps()
+            .into_iter()
+            .skip((first_row * columns).saturating_sub(folders))
+            .take(rows * columns)
+            .map(|app| app.icon.as_str())
+            .collect();
+        self.thumbnails.request(paths)
+    }
+
+    /// A grid card's icon: decoded once in view, a placeholder until then.
+    fn grid_icon(&self, icon: &str, size: u16) -> Element<'_, Message> {
+        if !thumbnails::is_file(icon) {
+            return widget::icon::from_name(icon).size(size).into();
+        }
+        let length = Length::Fixed(f32::from(size));
+        match self.thumbnails.get(icon) {
+            Some(webapps::IconType::Raster(handle)) => widget::image(handle.clone())
+                .width(length)
+                .height(length)
+                .into(),
+            Some(webapps::IconType::Svg(handle)) => widget::svg(handle.clone())
+                .width(length)
+                .height(length)
+                .into(),
+            None => widget::icon::from_name("image-x-generic-symbolic")
+                .size(size)
+                .into(),
+        }
+    }
+
+    /// Load the saved icon index, or bring `index` up to date with the icon directory.
+    fn refresh_icon_index(
+        index: Option<std::sync::Arc<webapps::icon_index::IconIndex>>,
+    ) -> Task<Message> {
+        task::future(async move {
+            let index = tokio::task::spawn_blocking(move || match index {
+                None => webapps::icon_index::IconIndex::load(),
+                Some(index) => {
+                    let refreshed = index.refresh();
+                    if let Err(e) = refreshed.save() {
+                        tracing::warn!("Failed to save the icon index: {e}");
+                    }
+                    refreshed
+                }
+            })
+            .await
+            .unwrap_or_default();
+            cosmic::action::app(Message::IconIndex(std::sync::Arc::new(index)))
+        })
+    }
+
+    /// Measure every installed app's profile off the UI thread.
+    fn load_storage_sizes(&self) -> Task<Message> {
+        let app_ids: Vec<String> = self
+            .cached_apps
+            .iter()
+            .map(|app| app.browser.app_id.as_ref().to_string())
+            .collect();
+        task::future(async move {
+            let sizes = tokio::task::spawn_blocking(move || {
+                app_ids
+                    .into_iter()
+                    .map(|app_id| {
+                        let size = webapps::profile_usage(&app_id)
+                            .iter()
+                            .map(|(_, size)| size)
+                            .sum();
+                        (app_id, size)
+                    })
+                    .collect()
+            })
+            .await
+            .unwrap_or_default();
+            cosmic::action::app(Message::StorageSizes(sizes))
+        })
+    }
+
+    /// Read the usage statistics and install times of the apps in the background.
+    fn load_activity(&self) -> Task<Message> {
+        let app_ids: Vec<String> = self
+            .cached_apps
+            .iter()
+            .map(|app| app.browser.app_id.as_ref().to_string())
+            .collect();
+        task::future(async move {
+            let activity = tokio::task::spawn_blocking(move || {
+                app_ids
+                    .into_iter()
+                    .map(|app_id| {
+                        let activity = Activity::load(&app_id);
+                        (app_id, activity)
+                    })
+                    .collect()
+            })
+            .await
+            .unwrap_or_default();
+            cosmic::action::app(Message::AppActivity(activity))
+        })
+    }
+
+    /// Order the cached apps by the configured sort mode, then by name.
+    fn sort_apps(&mut self) {
+        let mode = self.config.sort_mode;
+        let activity = &self.activity;
+        let order = &self.config.app_order;
+        let default = Activity::default();
+        self.cached_apps.sort_by(|a, b| {
+            let a_activity = activity.get(a.browser.app_id.as_ref()).unwrap_or(&default);
+            let b_activity = activity.get(b.browser.app_id.as_ref()).unwrap_or(&default);
+            let order = match mode {
+                SortMode::Category => a.category_name().cmp(&b.category_name()),
+                SortMode::Name => std::cmp::Ordering::Equal,
+                SortMode::RecentlyLaunched => b_activity.last_used.cmp(&a_activity.last_used),
+                SortMode::MostLaunched => b_activity.launches.cmp(&a_activity.launches),
+                SortMode::RecentlyAdded => b_activity.added.cmp(&a_activity.added),
+                SortMode::Manual => app_order::position(order, a.browser.app_id.as_ref())
+                    .cmp(&app_order::position(order, b.browser.app_id.as_ref())),
+            };
+            order.then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        });
+    }
+
+    /// Actions on the apps selected in bulk mode.
+    fn bulk_toolbar(&self) -> Option<Element<'_, Message>> {
+        if !self.bulk_mode || self.selected_app_ids.is_empty() {
+            return None;
+        }
+        let toolbar = widget::row()
+            .spacing(8)
+            .align_y(Alignment::Center)
+            .push(widget::button::suggested(fl!("bulk-launch")).on_press(Message::BulkLaunch))
+            .push(widget::button::destructive(fl!("bulk-delete")).on_press(Message::BulkDelete))
+            .push(widget::button::standard(fl!("bulk-export")).on_press(Message::BulkExport))
+            .push(
+                widget::button::standard(fl!("bulk-refetch-icons"))
+                    .on_press(Message::BulkRefetchIcons),
+            )
+            .push(
+                widget::row()
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(widget::text::body(fl!("bulk-category")))
+                    .push(self.category_choices.dropdown(None, Message::BulkCategory)),
+            )
+            .push(
+                widget::button::standard(fl!("bulk-persistent-on"))
+                    .on_press(Message::BulkPersistent(true)),
+            )
+            .push(
+                widget::button::standard(fl!("bulk-persistent-off"))
+                    .on_press(Message::BulkPersistent(false)),
+            )
+            .push_maybe((!self.config.app_groups.is_empty()).then(|| {
+                widget::row()
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(widget::text::body(fl!("bulk-move-to-group")))
+                    .push(widget::dropdown(
+                        &self.group_options,
+                        None,
+                        Message::BulkAssignGroup,
+                    ))
+            }))
+            .wrap();
+        Some(widget::container(toolbar).padding([0, 24]).into())
+    }
+
+    /// The shown apps with checkboxes to select them in bulk mode.
+    fn bulk_checklist(&self) -> Element<'_, Message> {
+        let shown: Vec<&webapps::launcher::WebAppLauncher> = self
+            .cached_apps
+            .iter()
+            .filter(|app| self.is_shown(app))
+            .collect();
+        let all_selected = !shown.is_empty()
+            && shown
+                .iter()
+                .all(|app| self.selected_app_ids.contains(app.browser.app_id.as_ref()));
+        let all_ids: Vec<String> = shown
+            .iter()
+            .map(|app| app.browser.app_id.as_ref().to_string())
+            .collect();
+        let select_all = widget::checkbox(fl!("bulk-select-all"), all_selected)
+            .on_toggle(move |select| {
+                Message::SelectApps(if select { all_ids.clone() } else { Vec::new() })
+            });
+        let list = shown.into_iter().fold(
+            widget::settings::section().add(select_all),
+            |section, app| {
+                let app_id = app.browser.app_id.as_ref().to_string();
+                let checked = self.selected_app_ids.contains(&app_id);
+                section.add(
+                    widget::row()
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .push(
+                            widget::checkbox("", checked)
+                                .on_toggle(move |_| Message::ToggleBulkSelect(app_id.clone())),
+                        )
+                        .push(widget::icon::from_name(app.icon.clone()).size(24))
+                        .push(widget::text::body(app.name.as_str()).width(Length::Fill))
+                        .push(widget::text::caption(app.category_name())),
+                )
+            },
+        );
+        widget::scrollable(widget::container(list).padding([0, 24]))
+            .height(Length::Fill)
+            .into()
+    }
+
+    /// Buttons launching the pinned apps, above the list and the grid.
+    fn pinned_apps(&self) -> Option<Element<'_, Message>> {
+        let apps = self.config.pinned_apps.iter().filter_map(|app_id| {
+            self.cached_apps
+                .iter()
+                .find(|app| app.browser.app_id.as_ref() == app_id)
+        });
+        self.launch_strip(fl!("pinned-apps"), apps)
+    }
+
+    /// Buttons relaunching the apps launched last, newest first.
+    fn recent_apps(&self) -> Option<Element<'_, Message>> {
+        const COUNT: usize = 5;
+
+        let mut apps: Vec<(u64, &webapps::launcher::WebAppLauncher)> = self
+            .cached_apps
+            .iter()
+            .filter_map(|app| {
+                let last_used = self.activity.get(app.browser.app_id.as_ref())?.last_used?;
+                Some((last_used, app))
+            })
+            .collect();
+        apps.sort_by(|a, b| b.0.cmp(&a.0));
+        self.launch_strip(
+            fl!("recent-apps"),
+            apps.into_iter().take(COUNT).map(|(_, app)| app),
+        )
+    }
+
+    /// A titled row of buttons launching `apps`, or nothing without apps.
+    fn launch_strip<'a>(
+        &self,
+        title: String,
+        apps: impl Iterator<Item = &'a webapps::launcher::WebAppLauncher>,
+    ) -> Option<Element<'a, Message>> {
+        let buttons: Vec<Element<'a, Message>> = apps
+            .map(|app| {
+                let button = widget::button::custom(
+                    widget::row()
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .push(widget::icon::from_name(app.icon.clone()).size(24))
+                        .push(widget::text::body(app.name.as_str())),
+                )
+                .padding([4, 12])
+                .class(cosmic::style::Button::Standard)
+                .on_press(Message::Launch(app.browser.app_id.clone()));
+                widget::tooltip(
+                    button,
+                    widget::text(fl!("launch-pinned", name = app.name.as_str())),
+                    widget::tooltip::Position::Bottom,
+                )
+                .into()
+            })
+            .collect();
+        if buttons.is_empty() {
+            return None;
+        }
+        Some(
+            widget::container(
+                widget::row()
+                    .spacing(12)
+                    .align_y(Alignment::Center)
+                    .push(widget::text::heading(title))
+                    .push(widget::row::with_children(buttons).spacing(8).wrap()),
+            )
+            .padding([8, 24])
+            .width(Length::Fill)
+            .into(),
+        )
+    }
+
+    /// Store the groups and show the apps in them, keeping the editor as it is.
+    /// Follow an app to its new id in the order, pins and groups.
+    fn rename_references(&mut self, old_id: &str, new_id: &str) {
+        let rename = |ids: &mut Vec<String>| {
+            for id in ids.iter_mut().filter(|id| *id == old_id) {
+                *id = new_id.to_string();
+            }
+        };
+        rename(&mut self.config.app_order);
+        rename(&mut self.config.pinned_apps);
+        for group in &mut self.config.app_groups {
+            rename(&mut group.app_ids);
+        }
+        if let Some(handler) = AppConfig::config_handler() {
+            let _ = self
+                .config
+                .set_app_order(&handler, self.config.app_order.clone());
+            let _ = self
+                .config
+                .set_pinned_apps(&handler, self.config.pinned_apps.clone());
+            let _ = self
+                .config
+                .set_app_groups(&handler, self.config.app_groups.clone());
+        }
+        if let Some(activity) = self.activity.remove(old_id) {
+            self.activity.insert(new_id.to_string(), activity);
+        }
+        if let Some(size) = self.storage_sizes.remove(old_id) {
+            self.storage_sizes.insert(new_id.to_string(), size);
+        }
+        if self.selected_app_ids.remove(old_id) {
+            self.selected_app_ids.insert(new_id.to_string());
+        }
+    }
+
+    fn save_groups(&mut self) -> Task<Message> {
+        if let Some(handler) = AppConfig::config_handler() {
+            let _ = self
+                .config
+                .set_app_groups(&handler, self.config.app_groups.clone());
+        }
+        self.group_options = group_options(&self.config.app_groups);
+        let page = self.page.clone();
+        let Page::Editor(editor) = &page;
+        let active_app_id = editor
+            .app_browser
+            .as_ref()
+            .map(|b| b.app_id.as_ref().to_string());
+        self.rebuild_nav_from_cache(active_app_id.as_deref());
+        self.page = page;
+        self.request_visible_thumbnails()
+    }
+
+    /// Index of the group `app_id` is in.
+    fn group_of(&self, app_id: &str) -> Option<usize> {
+        self.config
+            .app_groups
+            .iter()
+            .position(|group| group.app_ids.iter().any(|id| id == app_id))
+    }
+
+    /// The apps shown as cards in the grid: those in the open group, or else those in
+    /// no group.
+    fn grid_apps(&self) -> Vec<&webapps::launcher::WebAppLauncher> {
+        self.cached_apps
+            .iter()
+            .filter(|app| self.is_shown(app))
+            .filter(|app| self.group_of(app.browser.app_id.as_ref()) == self.open_group)
+            .collect()
+    }
+
+    /// Groups shown as folders before the apps in the grid, with how many of their apps
+    /// are shown.
+    fn grid_folders(&self) -> Vec<(usize, usize)> {
+        if self.open_group.is_some() {
+            return Vec::new();
+        }
+        self.config
+            .app_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, group)| {
+                let count = self
+                    .cached_apps
+                    .iter()
+                    .filter(|app| group.app_ids.iter().any(|id| id == app.browser.app_id.as_ref()))
+                    .filter(|app| self.is_shown(app))
+                    .count();
+                (count > 0).then_some((idx, count))
+            })
+            .collect()
+    }
+
+    /// A folder card opening group `idx`, which apps dragged onto it join.
+    fn grid_folder(&self, idx: usize, count: usize) -> Element<'_, Message> {
+        let density = self.config.grid_density;
+        let card_size = density.card_size();
+        let name = self
+            .config
+            .app_groups
+            .get(idx)
+            .map(|group| group.name.clone())
+            .unwrap_or_default();
+        let card = widget::button::custom(
+            widget::column()
+                .spacing(8)
+                .push(widget::icon::from_name("folder").size(density.icon_size()))
+                .push(
+                    widget::text::body(name)
+                        .width(Length::Fixed(card_size - 20.0))
+                        .align_x(Horizontal::Center),
+                )
+                .push_maybe(
+                    (density != GridDensity::Compact)
+                        .then(|| widget::text::caption(fl!("group-apps", count = count))),
+                )
+                .align_x(Alignment::Center),
+        )
+        .width(Length::Fixed(card_size))
+        .height(Length::Fixed(card_size))
+        .on_press(Message::OpenGroup(Some(idx)))
+        .class(cosmic::style::Button::Image);
+        widget::dnd_destination::DndDestination::for_data(
+            card,
+            move |dropped: Option<DraggedApp>, _| match dropped {
+                Some(DraggedApp(app_id)) => Message::AssignGroup(app_id, Some(idx)),
+                None => Message::None,
+            },
+        )
+        .into()
+    }
+
+    /// The user's groups, to add and remove.
+    fn app_groups(&self) -> Element<'_, Message> {
+        let section = self.config.app_groups.iter().enumerate().fold(
+            widget::settings::section().title(fl!("app-groups")),
+            |section, (idx, group)| {
+                section.add(widget::settings::item(
+                    group.name.clone(),
+                    widget::row()
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .push(widget::text::caption(fl!(
+                            "group-apps",
+                            count = group.app_ids.len()
+                        )))
+                        .push(
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::RemoveGroup(idx)),
+                        ),
+                ))
+            },
+        );
+        section
+            .add(widget::settings::item_row(vec![
+                widget::text_input(fl!("new-group-placeholder"), &self.new_group_name)
+                    .on_input(Message::NewGroupName)
+                    .on_submit(|_| Message::AddGroup)
+                    .width(Length::Fill)
+                    .into(),
+                widget::button::standard(fl!("add-group"))
+                    .on_press_maybe(
+                        (!self.new_group_name.trim().is_empty()).then_some(Message::AddGroup),
+                    )
+                    .into(),
+            ]))
+            .into()
+    }
+
+    /// Store the user's own categories and offer them in the editor.
+    fn save_custom_categories(&mut self) {
+        if let Some(handler) = AppConfig::config_handler() {
+            let _ = self
+                .config
+                .set_custom_categories(&handler, self.config.custom_categories.clone());
+        }
+        self.category_choices = CategoryChoices::new(&self.config.custom_categories);
+    }
+
+    /// What deleting the apps removes, with the data that may be kept.
+    fn deletion_contents(&self, deletion: &Deletion) -> Element<'_, Message> {
+        let mut col = widget::column().spacing(8);
+        if deletion.apps.len() > 1 {
+            col = deletion.apps.iter().fold(col, |col, app| {
+                col.push(widget::text::body(app.name.clone()))
+            });
+        }
+        col = col.push(widget::text::caption(fl!("delete-removes")));
+
+        let profiles: Vec<&str> = deletion
+            .apps
+            .iter()
+            .filter(|app| app.browser.profile.is_some())
+            .map(|app| app.browser.app_id.as_ref())
+            .collect();
+        if !profiles.is_empty() {
+            let size: u64 = profiles
+                .iter()
+                .filter_map(|app_id| self.storage_sizes.get(*app_id))
+                .sum();
+            col = col.push(
+                widget::checkbox(
+                    fl!("delete-profile", size = webapps::format_bytes(size)),
+                    deletion.purge.profile,
+                )
+                .on_toggle(Message::PurgeProfile),
+            );
+        }
+        col.push(
+            widget::checkbox(fl!("delete-icons"), deletion.purge.icons)
+                .on_toggle(Message::PurgeIcons),
+        )
+        .into()
+    }
+
+    /// The apps selected in bulk mode.
+    fn selected_apps(&self) -> Vec<webapps::launcher::WebAppLauncher> {
+        self.cached_apps
+            .iter()
+            .filter(|app| self.selected_app_ids.contains(app.browser.app_id.as_ref()))
+            .cloned()
+            .collect()
+    }
+
+    /// The user's own categories with the XDG category menus show each in, and moving
+    /// apps between categories.
+    fn custom_categories(&self) -> Element<'_, Message> {
+        let section = self.config.custom_categories.iter().enumerate().fold(
+            widget::settings::section().title(fl!("custom-categories")),
+            |section, (idx, custom)| {
+                let xdg_idx = xdg_categories().position(|category| category == custom.xdg);
+                section.add(widget::settings::item(
+                    custom.name.clone(),
+                    widget::row()
+                        .spacing(8)
+                        .align_y(Alignment::Center)
+                        .push(widget::dropdown(
+                            &self.xdg_category_options,
+                            xdg_idx,
+                            move |xdg_idx| Message::CategoryXdg(idx, xdg_idx),
+                        ))
+                        .push(
+                            widget::button::icon(widget::icon::from_name("edit-delete-symbolic"))
+                                .on_press(Message::RemoveCategory(idx)),
+                        ),
+                ))
+            },
+        );
+        section
+            .add(widget::settings::item_row(vec![
+                widget::text_input(fl!("new-category-placeholder"), &self.new_category_name)
+                    .on_input(Message::NewCategoryName)
+                    .on_submit(|_| Message::AddCategory)
+                    .width(Length::Fill)
+                    .into(),
+                widget::dropdown(
+                    &self.xdg_category_options,
+                    Some(self.new_category_xdg),
+                    Message::NewCategoryXdg,
+                )
+                .into(),
+                widget::button::standard(fl!("add-category"))
+                    .on_press_maybe(
+                        (!self.new_category_name.trim().is_empty()).then_some(Message::AddCategory),
+                    )
+                    .into(),
+            ]))
+            .add(widget::settings::item(
+                fl!("move-apps"),
+                widget::row()
+                    .spacing(8)
+                    .align_y(Alignment::Center)
+                    .push(
+                        self.category_choices
+                            .dropdown(Some(&self.move_apps_from), Message::MoveAppsFrom),
+                    )
+                    .push(widget::text::body(fl!("move-apps-to")))
+                    .push(
+                        self.category_choices
+                            .dropdown(Some(&self.move_apps_to), Message::MoveAppsTo),
+                    )
+                    .push(
+                        widget::button::standard(fl!("move")).on_press_maybe(
+                            (self.move_apps_from != self.move_apps_to)
+                                .then_some(Message::MoveApps),
+                        ),
+                    ),
+            ))
+            .into()
+    }
+
+    /// Sort the apps again and show them in the new order, keeping the open app.
+    fn resort_apps(&mut self) -> Task<Message> {
+        self.sort_apps();
+        let active_app_id = match &self.page {
+            Page::Editor(editor) => editor
+                .app_browser
+                .as_ref()
+                .map(|b| b.app_id.as_ref().to_string()),
+        };
+        self.rebuild_nav_from_cache(active_app_id.as_deref());
+        self.request_visible_thumbnails()
+    }
+
+    /// Go on with `next`, which replaces the editor, once unsaved changes in it are
+    /// confirmed to be dropped.
+    fn leave_editor(&mut self, next: Message) -> Task<Message> {
+        let Page::Editor(editor) = &self.page;
+        if editor.is_modified() {
+            self.dialogs = Some(Dialogs::UnsavedChanges(Box::new(next)));
+            return Task::none();
+        }
+        self.update(next)
+    }
+
+    fn open_page(&mut self, id: nav_bar::Id) -> Task<Message> {
+        self.nav.activate(id);
+        if let Some(page) = self.nav.data::<Page>(id) {
+            self.page = page.clone();
+
+            let mut tasks = Vec::new();
+
+            // Auto-trigger thumbnail fetch for installed apps with URLs
+            let Page::Editor(editor) = &self.page;
+            if editor.is_installed
+                && editor.thumbnail_handle.is_none()
+                && !editor.thumbnail_loading
+                && webapps::url_valid(&editor.app_url)
+            {
+                tasks.push(task::message(Message::Editor(
+                    editor::Message::FetchThumbnail,
+                )));
+            }
+
+            // Load profile storage usage for the data section
+            if editor.is_installed && editor.app_persistent {
+                tasks.push(task::message(Message::Editor(
+                    editor::Message::LoadStorageUsage,
+                )));
+            }
+
+            return Task::batch(tasks);
+        }
+        Task::none()
+    }
+
+    /// Whether `app` matches the search, by name, URL, category or tag, and the filter.
+    fn is_shown(&self, app: &webapps::launcher::WebAppLauncher) -> bool {
+        if self.app_filter.as_ref().is_some_and(|filter| !filter.matches(app)) {
+            return false;
+        }
+        let query = self.search_query.trim().to_lowercase();
+        query.is_empty()
+            || app.name.to_lowercase().contains(&query)
+            || app.browser.url.as_deref().is_some_and(|url| url.to_lowercase().contains(&query))
+            || app.category_name().to_lowercase().contains(&query)
+            || app.tags.iter().any(|tag| tag.to_lowercase().contains(&query))
+    }
+
     /// Rebuild the nav bar from the in-memory app cache, applying the current search filter.
     /// If select_app_id is provided, attempts to re-select that app after rebuilding.
     fn rebuild_nav_from_cache(&mut self, select_app_id: Option<&str>) {
@@ -1444,52 +3255,298 @@ impl QuickWebApps {
             .data::<Page>(Page::Editor(AppEditor::default()))
             .activate();
 
-        let query = self.search_query.to_lowercase();
+        let shown: Vec<webapps::launcher::WebAppLauncher> = self
+            .cached_apps
+            .iter()
+            .filter(|app| self.is_shown(app))
+            .cloned()
+            .collect();
         let mut selected_entity = None;
-
-        for app in &self.cached_apps {
-            if !query.is_empty() && !app.name.to_lowercase().contains(&query) {
+        let mut grouped = std::collections::HashSet::new();
+
+        // Apps in groups come first, under a header that folds the group
+        for (idx, group) in self.config.app_groups.clone().into_iter().enumerate() {
+            let apps: Vec<&webapps::launcher::WebAppLauncher> = shown
+                .iter()
+                .filter(|app| group.app_ids.iter().any(|id| id == app.browser.app_id.as_ref()))
+                .collect();
+            grouped.extend(apps.iter().map(|app| app.browser.app_id.as_ref().to_string()));
+            if apps.is_empty() {
                 continue;
             }
-            let is_running = self
-                .running_app_ids
-                .contains(app.browser.app_id.as_ref());
-            let display_name = if is_running {
-                format!("{} {}", fl!("running-indicator"), app.name)
-            } else {
-                app.name.clone()
-            };
             self.nav
                 .insert()
-                .icon(widget::icon::from_name(app.icon.clone()))
-                .text(display_name)
-                .data::<Page>(Page::Editor(editor::AppEditor::from(app.clone())))
-                .closable();
-
-            // Check if this is the app we want to re-select
-            if let Some(target_id) = select_app_id {
-                if app.browser.app_id.as_ref() == target_id {
-                    // Find the entity we just inserted by iterating and comparing data
-                    for entity in self.nav.iter() {
-                        if let Some(Page::Editor(editor)) = self.nav.data::<Page>(entity) {
-                            if let Some(browser) = &editor.app_browser {
-                                if browser.app_id.as_ref() == target_id {
-                                    selected_entity = Some((entity, app.clone()));
-                                    break;
-                                }
-                            }
-                        }
-                    }
+                .icon(widget::icon::from_name(if group.collapsed {
+                    "folder-symbolic"
+                } else {
+                    "folder-open-symbolic"
+                }))
+                .text(fl!("group-header", name = group.name.as_str(), count = apps.len()))
+                .data::<NavGroup>(NavGroup(idx))
+                .divider_above(true);
+            if group.collapsed {
+                continue;
+            }
+            for app in apps {
+                if let Some(entity) = self.insert_nav_app(app, select_app_id, false) {
+                    selected_entity = Some(entity);
                 }
             }
         }
 
-        if let Some((entity, app)) = selected_entity {
+        // Apps without a group follow, set apart from the groups
+        let mut divider = !grouped.is_empty();
+        for app in &shown {
+            if grouped.contains(app.browser.app_id.as_ref()) {
+                continue;
+            }
+            if let Some(entity) = self.insert_nav_app(app, select_app_id, divider) {
+                selected_entity = Some(entity);
+            }
+            divider = false;
+        }
+
+        let selected_app = select_app_id
+            .and_then(|id| shown.iter().find(|app| app.browser.app_id.as_ref() == id));
+        if let Some(entity) = selected_entity {
             self.nav.activate(entity);
-            self.page = Page::Editor(editor::AppEditor::from(app));
+        }
+        // Apps in a folded group stay open
+        self.page = match selected_app {
+            Some(app) => Page::Editor(editor::AppEditor::from(app.clone())),
+            None => Page::Editor(AppEditor::default()),
+        };
+    }
+
+    /// Add `app` to the nav bar, returning its entity if it is the one to select.
+    fn insert_nav_app(
+        &mut self,
+        app: &webapps::launcher::WebAppLauncher,
+        select_app_id: Option<&str>,
+        divider_above: bool,
+    ) -> Option<nav_bar::Id> {
+        let is_running = self
+            .running_app_ids
+            .contains(app.browser.app_id.as_ref());
+        let mut display_name = if is_running {
+            format!("{} {}", fl!("running-indicator"), app.name)
         } else {
-            self.page = Page::Editor(AppEditor::default());
+            app.name.clone()
+        };
+        if let Some(size) = self
+            .storage_sizes
+            .get(app.browser.app_id.as_ref())
+            .filter(|size| **size > 0)
+        {
+            display_name = fl!(
+                "app-with-storage",
+                name = display_name,
+                size = webapps::format_bytes(*size)
+            );
         }
+        self.nav
+            .insert()
+            .icon(widget::icon::from_name(app.icon.clone()))
+            .text(display_name)
+            .data::<Page>(Page::Editor(editor::AppEditor::from(app.clone())))
+            .divider_above(divider_above)
+            .closable();
+
+        // Check if this is the app we want to re-select
+        let target_id = select_app_id.filter(|id| app.browser.app_id.as_ref() == *id)?;
+        // Find the entity we just inserted by iterating and comparing data
+        self.nav.iter().find(|entity| {
+            self.nav
+                .data::<Page>(*entity)
+                .and_then(|Page::Editor(editor)| editor.app_browser.as_ref())
+                .is_some_and(|browser| browser.app_id.as_ref() == target_id)
+        })
+    }
+
+    fn running(&self) -> Element<'_, Message> {
+        use webapps::control::Command;
+
+        if self.processes.is_empty() {
+            return widget::text::body(fl!("running-apps-empty")).into();
+        }
+
+        let mut section = widget::settings::section();
+        for process in &self.processes {
+            let app = self
+                .cached_apps
+                .iter()
+                .find(|app| app.browser.app_id.as_ref() == process.app_id);
+            let cpu = self
+                .cpu_usage
+                .get(&process.pid)
+                .map(|cpu| format!("{cpu:.1}%"))
+                .unwrap_or_else(|| "…".to_string());
+            let app_id = process.app_id.clone();
+            section = section.add(
+                widget::column()
+                    .spacing(8)
+                    .push(widget::settings::item_row(vec![
+                        widget::icon::from_name(
+                            app.map(|app| app.icon.clone()).unwrap_or_default(),
+                        )
+                        .size(32)
+                        .into(),
+                        widget::column()
+                            .push(widget::text::body(
+                                app.map(|app| app.name.clone())
+                                    .unwrap_or_else(|| process.app_id.clone()),
+                            ))
+                            .push(widget::text::caption(fl!(
+                                "running-app-usage",
+                                memory = webapps::format_bytes(process.memory),
+                                cpu = cpu,
+                                pid = process.pid
+                            )))
+                            .width(Length::Fill)
+                            .into(),
+                    ]))
+                    .push(
+                        widget::row()
+                            .spacing(8)
+                            .push(
+                                widget::button::standard(fl!("focus-app"))
+                                    .on_press(Message::ControlApp(app_id.clone(), Command::Show)),
+                            )
+                            .push(
+                                widget::button::standard(fl!("reload-app"))
+                                    .on_press(Message::ControlApp(app_id, Command::Reload)),
+                            )
+                            .push(
+                                widget::button::destructive(fl!("kill-app"))
+                                    .on_press(Message::KillApp(process.pid)),
+                            ),
+                    ),
+            );
+        }
+        section.into()
+    }
+
+    /// The icons a site offers, best first, each with its size and where it came from.
+    fn site_icons<'a>(
+        &self,
+        candidates: Option<&'a [webapps::IconCandidate]>,
+    ) -> Element<'a, Message> {
+        let Some(candidates) = candidates else {
+            return widget::text::body(fl!("site-icons-loading")).into();
+        };
+        if candidates.is_empty() {
+            return widget::text::body(fl!("site-icons-empty")).into();
+        }
+
+        let cards = candidates
+            .iter()
+            .map(|candidate| {
+                let icon: Element<Message> = match &candidate.icon.icon {
+                    webapps::IconType::Raster(handle) => widget::image(handle.clone())
+                        .width(Length::Fixed(64.))
+                        .height(Length::Fixed(64.))
+                        .into(),
+                    webapps::IconType::Svg(handle) => widget::svg(handle.clone())
+                        .width(Length::Fixed(64.))
+                        .height(Length::Fixed(64.))
+                        .into(),
+                };
+                let size = match candidate.size {
+                    Some((width, height)) => fl!("site-icon-size", width = width, height = height),
+                    None => fl!("site-icon-scalable"),
+                };
+                let source = url::Url::parse(&candidate.source)
+                    .ok()
+                    .and_then(|url| url.host_str().map(str::to_string))
+                    .unwrap_or_default();
+                widget::button::custom(
+                    widget::column()
+                        .spacing(4)
+                        .align_x(Alignment::Center)
+                        .push(icon)
+                        .push(widget::text::caption(size))
+                        .push(widget::text::caption(source)),
+                )
+                .width(Length::Fixed(132.))
+                .on_press(Message::SiteIconChosen(candidate.clone()))
+                .class(theme::Button::Icon)
+                .into()
+            })
+            .collect::<Vec<Element<Message>>>();
+
+        widget::container(widget::scrollable(widget::flex_row(cards)))
+            .height(Length::Fixed(320.))
+            .into()
+    }
+
+    /// Open the progress dialog and download the icons of `apps` again.
+    fn refetch_icons(
+        &mut self,
+        mut apps: Vec<webapps::launcher::WebAppLauncher>,
+    ) -> cosmic::Task<cosmic::Action<Message>> {
+        // Popped from the back, so reverse to go in order
+        apps.reverse();
+        let Some(first) = apps.pop() else {
+            return Task::none();
+        };
+        self.refetch_cancel.cancel();
+        self.refetch_cancel = webapps::http::Cancel::default();
+        self.bulk_mode = false;
+        self.selected_app_ids.clear();
+        self.dialogs = Some(Dialogs::RefetchIcons(RefetchProgress {
+            total: apps.len() + 1,
+            pending: apps,
+            results: Vec::new(),
+        }));
+        self.refetch_next(first)
+    }
+
+    fn refetch_next(
+        &self,
+        app: webapps::launcher::WebAppLauncher,
+    ) -> cosmic::Task<cosmic::Action<Message>> {
+        let cancel = self.refetch_cancel.clone();
+        task::future(async move {
+            let name = app.name.clone();
+            let result = webapps::favicon_refresh::refetch(app, &cancel).await;
+            cosmic::action::app(Message::IconRefetched(name, result))
+        })
+    }
+
+    fn refetch_progress<'a>(&self, progress: &'a RefetchProgress) -> Element<'a, Message> {
+        use webapps::favicon_refresh::Refetch;
+
+        let (done, total) = (progress.results.len(), progress.total);
+        let status = if progress.is_finished() {
+            fl!("refetch-finished", total = total)
+        } else {
+            fl!("refetch-progress", done = done, total = total)
+        };
+
+        let results = progress.results.iter().fold(
+            widget::column().spacing(4),
+            |column, (name, result)| {
+                let outcome = match result {
+                    Refetch::Updated => fl!("refetch-updated"),
+                    Refetch::Unchanged => fl!("refetch-unchanged"),
+                    Refetch::NoIcon => fl!("refetch-no-icon"),
+                    Refetch::Failed(error) => fl!("refetch-failed", error = error.as_str()),
+                };
+                column.push(
+                    widget::row()
+                        .spacing(8)
+                        .push(widget::text::body(name.as_str()).width(Length::Fill))
+                        .push(widget::text::caption(outcome)),
+                )
+            },
+        );
+
+        widget::column()
+            .spacing(12)
+            .push(widget::text::body(status))
+            .push(widget::progress_bar(0.0..=total as f32, done as f32))
+            .push(widget::container(widget::scrollable(results)).max_height(320.))
+            .into()
     }
 
     fn about(&self) -> Element<'_, Message> {
@@ -1543,11 +3600,64 @@ impl QuickWebApps {
                             Message::ChangeUserTheme,
                         ),
                     ))
+                    .add(widget::settings::item(
+                        fl!("grid-density"),
+                        widget::dropdown(
+                            &self.density_options,
+                            GridDensity::ALL
+                                .iter()
+                                .position(|density| *density == self.config.grid_density),
+                            Message::GridDensity,
+                        ),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("warm-pool"),
+                        widget::toggler(self.warm_pool).on_toggle(Message::ToggleWarmPool),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("favicon-refresh"),
+                        widget::row()
+                            .spacing(8)
+                            .push(widget::dropdown(
+                                &self.favicon_refresh_options,
+                                Some(match self.config.favicon_refresh {
+                                    webapps::favicon_refresh::Schedule::Off => 0,
+                                    webapps::favicon_refresh::Schedule::OnLaunch => 1,
+                                    webapps::favicon_refresh::Schedule::Weekly => 2,
+                                }),
+                                Message::FaviconRefresh,
+                            ))
+                            .push(
+                                widget::button::standard(fl!("refresh-now")).on_press_maybe(
+                                    (!self.favicons_refreshing)
+                                        .then_some(Message::RefreshFavicons),
+                                ),
+                            ),
+                    ))
+                    .add(widget::settings::item(
+                        fl!("refetch-all-icons"),
+                        widget::button::standard(fl!("refetch")).on_press_maybe(
+                            (!self.cached_apps.is_empty()).then_some(Message::RefetchAllIcons),
+                        ),
+                    ))
                     .add(widget::settings::item(
                         fl!("reset-settings"),
                         widget::button::standard(fl!("reset")).on_press(Message::ResetSettings),
                     )),
             )
+            .push(self.app_groups())
+            .push(self.custom_categories())
+            .push_maybe((!self.favicon_changes.is_empty()).then(|| {
+                self.favicon_changes.iter().fold(
+                    widget::settings::section().title(fl!("favicon-changes")),
+                    |section, change| {
+                        section.add(widget::settings::item(
+                            change.name.clone(),
+                            widget::text::caption(webapps::format_timestamp(change.time)),
+                        ))
+                    },
+                )
+            }))
             .align_x(Alignment::Center)
             .spacing(space_xxs)
             .into()
@@ -1558,7 +3668,9 @@ impl QuickWebApps {
 pub enum ContextPage {
     #[default]
     About,
+    Running,
     Settings,
+    Statistics,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -1570,8 +3682,12 @@ pub enum MenuAction {
     ImportApps,
     LaunchApp,
     NewApp,
+    Redo,
+    Running,
     Save,
     Settings,
+    Statistics,
+    Undo,
 }
 
 impl menu::action::MenuAction for MenuAction {
@@ -1585,9 +3701,13 @@ impl menu::action::MenuAction for MenuAction {
             MenuAction::FocusSearch => Message::FocusSearch,
             MenuAction::ImportApps => Message::ImportApps,
             MenuAction::LaunchApp => Message::Editor(editor::Message::LaunchApp),
-            MenuAction::NewApp => Message::ReloadNavbarItems,
+            MenuAction::NewApp => Message::NewApp,
+            MenuAction::Redo => Message::Editor(editor::Message::Redo),
+            MenuAction::Running => Message::ToggleContextPage(ContextPage::Running),
             MenuAction::Save => Message::Editor(editor::Message::Done),
             MenuAction::Settings => Message::ToggleContextPage(ContextPage::Settings),
+            MenuAction::Statistics => Message::ShowStatistics,
+            MenuAction::Undo => Message::Editor(editor::Message::Undo),
         }
     }
 }
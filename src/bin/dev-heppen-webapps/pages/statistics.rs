@@ -0,0 +1,231 @@
+use cosmic::{
+    Element, Task,
+    action::Action,
+    iced::{Alignment, Length},
+    style, task,
+    widget::{self},
+};
+use strum::IntoEnumIterator as _;
+use strum_macros::EnumIter;
+use webapps::fl;
+
+use crate::pages;
+
+/// Apps not used for this many days are suggested for removal.
+const UNUSED_DAYS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, EnumIter)]
+pub enum SortBy {
+    #[default]
+    Usage,
+    Launches,
+    LastUsed,
+    Storage,
+    Name,
+}
+
+/// One app's row on the dashboard.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub app_id: String,
+    pub name: String,
+    pub icon: String,
+    pub launches: u64,
+    pub focus_secs: u64,
+    pub last_used: Option<u64>,
+    pub storage: u64,
+}
+
+impl Entry {
+    fn from_app(app: &webapps::launcher::WebAppLauncher) -> Self {
+        let app_id = app.browser.app_id.as_ref().to_string();
+        let stats = webapps::stats::load(&app_id);
+        Self {
+            // Launches from the manager predate the stats file; count whichever is higher
+            launches: stats.sessions.max(app.browser.launch_count.unwrap_or(0)),
+            focus_secs: stats.focus_secs,
+            last_used: stats.last_used.max(app.browser.last_launched),
+            storage: webapps::profile_usage(&app_id)
+                .iter()
+                .map(|(_, size)| size)
+                .sum(),
+            name: app.name.clone(),
+            icon: app.icon.clone(),
+            app_id,
+        }
+    }
+
+    fn is_unused(&self, now: u64) -> bool {
+        self.last_used
+            .is_none_or(|last| now.saturating_sub(last) > UNUSED_DAYS * 86400)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    Load,
+    Loaded(Vec<Entry>),
+    Sort(usize),
+}
+
+#[derive(Debug, Clone)]
+pub struct Statistics {
+    entries: Vec<Entry>,
+    sort: SortBy,
+    sort_options: Vec<String>,
+    loading: bool,
+}
+
+impl Default for Statistics {
+    fn default() -> Self {
+        Self {
+            entries: Vec::new(),
+            sort: SortBy::default(),
+            sort_options: vec![
+                fl!("statistics-sort-usage"),
+                fl!("statistics-sort-launches"),
+                fl!("statistics-sort-last-used"),
+                fl!("statistics-sort-storage"),
+                fl!("statistics-sort-name"),
+            ],
+            loading: false,
+        }
+    }
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl Statistics {
+    pub fn update(&mut self, message: Message) -> Task<Action<pages::Message>> {
+        match message {
+            Message::Load => {
+                self.loading = true;
+                return Task::perform(
+                    async {
+                        tokio::task::spawn_blocking(|| {
+                            webapps::launcher::installed_webapps()
+                                .iter()
+                                .map(Entry::from_app)
+                                .collect()
+                        })
+                        .await
+                        .unwrap_or_default()
+                    },
+                    |entries| {
+                        cosmic::Action::App(pages::Message::Statistics(Message::Loaded(entries)))
+                    },
+                );
+            }
+            Message::Loaded(entries) => {
+                self.entries = entries;
+                self.loading = false;
+                self.sort_entries();
+            }
+            Message::Sort(idx) => {
+                self.sort = SortBy::iter().nth(idx).unwrap_or_default();
+                self.sort_entries();
+            }
+        }
+        task::none()
+    }
+
+    fn sort_entries(&mut self) {
+        match self.sort {
+            SortBy::Usage => self.entries.sort_by(|a, b| b.focus_secs.cmp(&a.focus_secs)),
+            SortBy::Launches => self.entries.sort_by(|a, b| b.launches.cmp(&a.launches)),
+            SortBy::LastUsed => self.entries.sort_by(|a, b| b.last_used.cmp(&a.last_used)),
+            SortBy::Storage => self.entries.sort_by(|a, b| b.storage.cmp(&a.storage)),
+            SortBy::Name => self
+                .entries
+                .sort_by_key(|entry| entry.name.to_lowercase()),
+        }
+    }
+
+    pub fn view(&self) -> Element<'_, pages::Message> {
+        let now = now();
+        let total_focus: u64 = self.entries.iter().map(|e| e.focus_secs).sum();
+        let total_storage: u64 = self.entries.iter().map(|e| e.storage).sum();
+
+        let summary = widget::settings::section()
+            .add(widget::settings::item(
+                fl!("statistics-apps"),
+                widget::text::body(self.entries.len().to_string()),
+            ))
+            .add(widget::settings::item(
+                fl!("usage-total-time"),
+                widget::text::body(webapps::format_duration(total_focus)),
+            ))
+            .add(widget::settings::item(
+                fl!("data-total"),
+                widget::text::body(webapps::format_bytes(total_storage)),
+            ))
+            .add(widget::settings::item(
+                fl!("statistics-sort"),
+                widget::dropdown(
+                    &self.sort_options,
+                    SortBy::iter().position(|s| s == self.sort),
+                    |idx| pages::Message::Statistics(Message::Sort(idx)),
+                ),
+            ));
+
+        let mut apps = widget::settings::section().title(fl!("statistics-per-app"));
+        for entry in &self.entries {
+            let last_used = entry
+                .last_used
+                .map(webapps::format_timestamp)
+                .unwrap_or_else(|| fl!("never-launched"));
+            apps = apps.add(widget::settings::item_row(vec![
+                widget::icon::from_name(entry.icon.clone()).size(32).into(),
+                widget::column()
+                    .push(widget::text::body(entry.name.clone()))
+                    .push(widget::text::caption(fl!(
+                        "statistics-entry",
+                        launches = entry.launches,
+                        time = webapps::format_duration(entry.focus_secs),
+                        last = last_used,
+                        storage = webapps::format_bytes(entry.storage)
+                    )))
+                    .width(Length::Fill)
+                    .into(),
+            ]));
+        }
+
+        let unused: Vec<&Entry> = self.entries.iter().filter(|e| e.is_unused(now)).collect();
+        let suggestions = (!unused.is_empty()).then(|| {
+            let mut section = widget::settings::section().title(fl!("statistics-unused"));
+            for entry in &unused {
+                section = section.add(widget::settings::item(
+                    entry.name.clone(),
+                    widget::text::caption(
+                        entry
+                            .last_used
+                            .map(webapps::format_timestamp)
+                            .unwrap_or_else(|| fl!("never-launched")),
+                    ),
+                ));
+            }
+            let ids: Vec<String> = unused.iter().map(|e| e.app_id.clone()).collect();
+            section.add(widget::settings::item(
+                fl!("statistics-unused-hint", days = UNUSED_DAYS),
+                widget::button::destructive(fl!("statistics-select-unused"))
+                    .on_press(pages::Message::SelectApps(ids)),
+            ))
+        });
+
+        widget::column()
+            .spacing(12)
+            .push_maybe(self.loading.then(|| {
+                widget::text::caption(fl!("statistics-loading")).class(style::Text::Accent)
+            }))
+            .push(summary)
+            .push_maybe(suggestions)
+            .push(apps)
+            .align_x(Alignment::Center)
+            .into()
+    }
+}
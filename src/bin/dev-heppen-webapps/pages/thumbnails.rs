@@ -0,0 +1,78 @@
+//! Decoded app icons for the grid view, loaded as cards scroll into view and kept in a
+//! small least-recently-used cache.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use cosmic::{Task, action::Action, task};
+
+use crate::pages;
+
+/// Decoded icons kept in memory.
+const CAPACITY: usize = 128;
+
+#[derive(Debug, Default)]
+pub struct Thumbnails {
+    icons: HashMap<String, webapps::IconType>,
+    /// Least recently used first
+    order: VecDeque<String>,
+    pending: HashSet<String>,
+    /// Files that couldn't be decoded, not tried again
+    failed: HashSet<String>,
+}
+
+/// Whether `icon` is a file rather than a themed icon name.
+pub fn is_file(icon: &str) -> bool {
+    icon.starts_with('/')
+}
+
+impl Thumbnails {
+    pub fn get(&self, path: &str) -> Option<&webapps::IconType> {
+        self.icons.get(path)
+    }
+
+    fn touch(&mut self, path: &str) {
+        if let Some(pos) = self.order.iter().position(|p| p == path) {
+            if let Some(path) = self.order.remove(pos) {
+                self.order.push_back(path);
+            }
+        }
+    }
+
+    /// Mark `paths` as in view: cached icons become most recently used, missing ones are
+    /// decoded in the background.
+    pub fn request<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a str>,
+    ) -> Task<Action<pages::Message>> {
+        let mut tasks = Vec::new();
+        for path in paths.into_iter().filter(|path| is_file(path)) {
+            if self.icons.contains_key(path) {
+                self.touch(path);
+            } else if !self.pending.contains(path) && !self.failed.contains(path) {
+                self.pending.insert(path.to_string());
+                let path = path.to_string();
+                tasks.push(task::future(async move {
+                    let icon = webapps::image_handle(path.clone()).await;
+                    pages::Message::ThumbnailLoaded(path, icon.map(|icon| icon.icon))
+                }));
+            }
+        }
+        Task::batch(tasks)
+    }
+
+    pub fn insert(&mut self, path: String, icon: Option<webapps::IconType>) {
+        self.pending.remove(&path);
+        let Some(icon) = icon else {
+            self.failed.insert(path);
+            return;
+        };
+        if self.icons.insert(path.clone(), icon).is_none() {
+            self.order.push_back(path);
+        }
+        while self.order.len() > CAPACITY {
+            if let Some(oldest) = self.order.pop_front() {
+                self.icons.remove(&oldest);
+            }
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Step-by-step creation of an app, as an alternative to the full editor form for
+//! first-time users. The steps fill in the same fields as the form, which can be
+//! switched to at any point.
+
+use webapps::{WindowSize, browser::WindowMode, fl};
+
+use crate::pages::editor::{self, AppEditor};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Step {
+    #[default]
+    Url,
+    /// Name, icon and category, filled in from the site
+    Details,
+    Presets,
+    Confirm,
+}
+
+impl Step {
+    pub const COUNT: usize = 4;
+
+    pub fn number(self) -> usize {
+        match self {
+            Step::Url => 1,
+            Step::Details => 2,
+            Step::Presets => 3,
+            Step::Confirm => 4,
+        }
+    }
+
+    pub fn next(self) -> Option<Step> {
+        match self {
+            Step::Url => Some(Step::Details),
+            Step::Details => Some(Step::Presets),
+            Step::Presets => Some(Step::Confirm),
+            Step::Confirm => None,
+        }
+    }
+
+    pub fn previous(self) -> Option<Step> {
+        match self {
+            Step::Url => None,
+            Step::Details => Some(Step::Url),
+            Step::Presets => Some(Step::Details),
+            Step::Confirm => Some(Step::Presets),
+        }
+    }
+
+    pub fn title(self) -> String {
+        match self {
+            Step::Url => fl!("wizard-url"),
+            Step::Details => fl!("wizard-details"),
+            Step::Presets => fl!("wizard-presets"),
+            Step::Confirm => fl!("wizard-confirm"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WindowPreset {
+    #[default]
+    Standard,
+    /// Narrow, to keep beside other windows
+    Compact,
+    Maximized,
+}
+
+impl WindowPreset {
+    pub const ALL: [WindowPreset; 3] = [
+        WindowPreset::Standard,
+        WindowPreset::Compact,
+        WindowPreset::Maximized,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            WindowPreset::Standard => fl!("window-preset-standard"),
+            WindowPreset::Compact => fl!("window-preset-compact"),
+            WindowPreset::Maximized => fl!("window-preset-maximized"),
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            WindowPreset::Standard => fl!("window-preset-standard-description"),
+            WindowPreset::Compact => fl!("window-preset-compact-description"),
+            WindowPreset::Maximized => fl!("window-preset-maximized-description"),
+        }
+    }
+
+    pub fn apply(self, editor: &mut AppEditor) {
+        let (size, mode) = match self {
+            WindowPreset::Standard => (WindowSize::default(), WindowMode::Normal),
+            WindowPreset::Compact => (WindowSize(480.0, 800.0), WindowMode::Normal),
+            WindowPreset::Maximized => (WindowSize::default(), WindowMode::Maximized),
+        };
+        editor.app_window_width = size.0.to_string();
+        editor.app_window_height = size.1.to_string();
+        editor.app_window_size = size;
+        editor.app_window_mode = editor::window_mode_index(mode);
+        editor.app_window_decorations = true;
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PrivacyPreset {
+    /// Stays signed in, like a browser tab
+    #[default]
+    Standard,
+    /// Forgets everything when closed
+    Private,
+    /// Stays signed in, with trackers and fingerprinting blocked
+    Strict,
+}
+
+impl PrivacyPreset {
+    pub const ALL: [PrivacyPreset; 3] = [
+        PrivacyPreset::Standard,
+        PrivacyPreset::Private,
+        PrivacyPreset::Strict,
+    ];
+
+    pub fn name(self) -> String {
+        match self {
+            PrivacyPreset::Standard => fl!("privacy-preset-standard"),
+            PrivacyPreset::Private => fl!("privacy-preset-private"),
+            PrivacyPreset::Strict => fl!("privacy-preset-strict"),
+        }
+    }
+
+    pub fn description(self) -> String {
+        match self {
+            PrivacyPreset::Standard => fl!("privacy-preset-standard-description"),
+            PrivacyPreset::Private => fl!("privacy-preset-private-description"),
+            PrivacyPreset::Strict => fl!("privacy-preset-strict-description"),
+        }
+    }
+
+    pub fn apply(self, editor: &mut AppEditor) {
+        let strict = self == PrivacyPreset::Strict;
+        editor.app_persistent = self != PrivacyPreset::Private;
+        editor.app_private_mode = self == PrivacyPreset::Private;
+        editor.app_content_blocking = strict;
+        editor.app_block_cookies = strict;
+        editor.app_block_webrtc = strict;
+        editor.app_do_not_track = strict;
+        editor.app_fingerprint_resistance = strict;
+    }
+}
+
+/// Where the wizard is, and the presets chosen so far.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Wizard {
+    pub step: Step,
+    pub window: WindowPreset,
+    pub privacy: PrivacyPreset,
+    /// The URL the site's name and icon were last fetched for
+    pub fetched_url: String,
+}
@@ -1,73 +1,287 @@
+mod bookmarks;
+mod chrome;
+mod context_menu;
+mod cookies;
+mod custom_code;
+mod data_saver;
+mod dbus_service;
+mod error_page;
+mod fingerprint;
+mod geolocation;
+mod global_shortcut;
+mod history;
+mod launcher_entry;
+mod list_dialog;
+mod lock;
+mod media;
+mod mobile;
+mod navigation;
+mod network;
+mod notifications;
+mod passwords;
+mod preview;
+mod privacy;
+mod resolver;
+mod sandbox;
+mod scaling;
+mod screenshot;
+mod session;
+mod settings;
+mod shortcuts;
+mod splash;
+mod suspend;
+mod theme;
+mod time_limit;
+mod tls;
+mod toast;
+mod toolbar;
+mod tray;
+mod uploads;
+mod warm;
+mod website_data;
+mod windows;
+
 use clap::Parser;
 use tao::{
-    event::{Event, WindowEvent},
+    event::{ElementState, Event, MouseButton, WindowEvent},
     event_loop::{ControlFlow, EventLoopBuilder},
-    platform::unix::EventLoopBuilderExtUnix,
-    window::{WindowAttributes, WindowBuilder},
+    platform::unix::{EventLoopBuilderExtUnix, WindowBuilderExtUnix},
+    window::{Fullscreen, WindowAttributes, WindowBuilder},
 };
 use url::Url;
+use webapps::browser::{NetworkIsolation, WindowMode};
 use wry::{
     WebContext, WebViewBuilder,
-    dpi::{LogicalSize, Size},
+    dpi::{LogicalPosition, LogicalSize, Position, Size},
 };
 
-fn is_url_safe(url_str: &str) -> bool {
+/// Events delivered to the tao event loop from helper threads.
+#[derive(Debug, Clone)]
+enum UserEvent {
+    /// Reload the current page (auto-reload timer).
+    Reload,
+    /// The page started or stopped watching its location.
+    GeolocationStart,
+    GeolocationStop,
+    /// Position from the location portal, as a JSON object.
+    GeolocationUpdate(String),
+    /// The location portal failed or the request was denied.
+    GeolocationError(String),
+    /// The page has a login form for this origin.
+    CredentialsQuery(String),
+    /// Stored logins for an origin, ready to fill in.
+    CredentialsFound(String, Vec<passwords::Credential>),
+    /// A login form was submitted.
+    CredentialsSubmitted(passwords::Credential),
+    /// Submitted login not yet in the keyring; ask whether to save it.
+    CredentialsOffer(passwords::Credential),
+    /// Proceed past the TLS error page shown for a host.
+    TlsProceed(String),
+    /// A window-level keyboard shortcut was pressed.
+    Shortcut(shortcuts::Shortcut),
+    /// Bring the window to the front (D-Bus activation), with the launcher's activation
+    /// token when it passed one.
+    Activate(Option<String>),
+    /// Open a URI in the running window.
+    OpenUri(String),
+    /// Run one of the desktop entry actions, with the launcher's activation token.
+    ActivateAction(String, Option<String>),
+    /// Theme color declared by the site (meta tag or manifest).
+    ThemeColor(String),
+    /// Latest URL and scroll state reported by the page, saved on exit.
+    SessionState(webapps::session::Session),
+    /// Command received over the control channel.
+    Control(webapps::control::Command),
+    /// The global show/hide shortcut fired.
+    ToggleVisibility,
+    /// A forwarded notification, or one of its action buttons, was clicked.
+    NotificationClicked {
+        id: u64,
+        action: Option<String>,
+        url: Option<String>,
+    },
+    /// The editor changed the app shown in the preview.
+    PreviewChanged,
+}
+
+/// http(s) URLs are always allowed; `file://` only for apps that wrap local pages.
+fn is_url_safe(url_str: &str, allow_files: bool) -> bool {
     match Url::parse(url_str) {
-        Ok(url) => matches!(url.scheme(), "http" | "https"),
+        Ok(url) => {
+            matches!(url.scheme(), "http" | "https") || (allow_files && url.scheme() == "file")
+        }
         Err(_) => false,
     }
 }
 
+/// Send a remote-control command to the running instance of `app_id`.
+fn remote_control(app_id: &str, command: &webapps::control::Command) -> i32 {
+    let runtime = match tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            eprintln!("Failed to start runtime: {e}");
+            return 1;
+        }
+    };
+
+    match runtime.block_on(webapps::control::send(app_id, command)) {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("No running instance of '{app_id}' accepted the command: {e}");
+            1
+        }
+    }
+}
+
 fn main() -> wry::Result<()> {
-    let args = webapps::WebviewArgs::parse();
+    let mut args = webapps::WebviewArgs::parse();
 
-    if let Err(e) = gtk::init() {
-        eprintln!("Failed to initialize GTK: {e}");
-        std::process::exit(1);
+    if let Some(command) = args.remote_command() {
+        std::process::exit(remote_control(&args.id, &command));
     }
 
-    gtk::glib::set_program_name(args.id.clone().into());
-    gtk::glib::set_application_name(&args.id);
+    // A warm renderer takes the launch over if one is waiting. Private, hidden and preview
+    // launches never run warm, so they don't wait on it either.
+    let mut startup_id = None;
+    if args.warm {
+        (args, startup_id) = warm::serve();
+    } else if !args.private
+        && !args.hidden
+        && args.preview.is_none()
+        && webapps::warm::hand_off(&std::env::args().skip(1).collect::<Vec<_>>())
+    {
+        std::process::exit(0);
+    }
 
-    let mut browser = match webapps::browser::Browser::from_appid(&args.id) {
-        Some(b) => b,
+    let launcher = match &args.preview {
+        Some(path) => webapps::preview::read(path),
+        None => webapps::launcher::WebAppLauncher::from_appid(&args.id),
+    };
+    let launcher = match launcher {
+        Some(l) => l,
         None => {
             eprintln!("Failed to load web app configuration for '{}'", args.id);
             std::process::exit(1);
         }
     };
+    let app_icon = launcher.icon;
+    let mut browser = launcher.browser;
+    if args.preview.is_some() {
+        webapps::preview::prepare(&mut browser);
+        args.private = true;
+    }
+
+    // Before anything else runs, so the whole process lives inside the sandbox
+    sandbox::enter(&browser, &app_icon);
+
+    // Environment changes have to happen before GTK spawns threads
+    let fingerprint_resistance = browser.fingerprint_resistance.unwrap_or(false);
+    if fingerprint_resistance {
+        fingerprint::freeze_timezone();
+    }
+    let software_rendering = browser.disable_hardware_acceleration.unwrap_or(false);
+    if software_rendering {
+        settings::disable_hardware_acceleration();
+    }
+
+    if let Err(e) = gtk::init() {
+        eprintln!("Failed to initialize GTK: {e}");
+        std::process::exit(1);
+    }
+
+    gtk::glib::set_program_name(args.id.clone().into());
+    gtk::glib::set_application_name(&args.id);
 
     // Override private mode if --private CLI flag was passed
     if args.private {
         browser.private_mode = Some(true);
     }
 
+    let url_schemes = browser.valid_url_schemes();
+
     // Validate URL scheme before loading
     let url = browser.url.unwrap_or_default();
-    if !url.is_empty() && !is_url_safe(&url) {
+    let local_files = url.starts_with("file://");
+    if !url.is_empty() && !is_url_safe(&url, local_files) {
         eprintln!("Refusing to load unsafe URL scheme: {url}");
         std::process::exit(1);
     }
 
-    let event_loop = EventLoopBuilder::new().with_any_thread(true).build();
+    let event_loop = EventLoopBuilder::<UserEvent>::with_user_event()
+        .with_any_thread(true)
+        .build();
 
-    // Clone title before window builder consumes it (needed for notification forwarding)
+    // One instance per app: if it's already running, activate that window instead.
+    // Private windows are independent and never claim the app's bus name.
+    if !args.private {
+        if let dbus_service::Claim::Forwarded = dbus_service::claim(
+            browser.app_id.as_ref(),
+            args.uri.clone(),
+            startup_id.clone(),
+            event_loop.create_proxy(),
+        ) {
+            std::process::exit(0);
+        }
+    }
+
+    // A URI we were launched with is opened once the window is up
+    if let Some(uri) = args.uri.clone() {
+        let _ = event_loop.create_proxy().send_event(UserEvent::OpenUri(uri));
+    }
+
+    // Clone title before window builder consumes it (needed for notifications, splash and error page)
     let app_title_for_notifications = browser
         .window_title
         .clone()
         .unwrap_or_else(|| "Web App".to_string());
 
+    let start_hidden = args.hidden || browser.start_hidden.unwrap_or(false);
+    let window_mode = browser.window_mode.unwrap_or_default();
+
     let mut attrs = WindowAttributes::default();
-    if let Some(size) = browser.window_size {
+    if let Some(size) = &browser.window_size {
         attrs.inner_size = Some(Size::new(LogicalSize::new(size.0, size.1)));
     }
 
+    // Positions are relative to the chosen monitor. Wayland compositors decide
+    // placement themselves, so there only fullscreen honours the monitor.
+    let monitor = browser.monitor.as_deref().and_then(|name| {
+        let found = event_loop
+            .available_monitors()
+            .find(|m| m.name().as_deref() == Some(name));
+        if found.is_none() {
+            eprintln!("Monitor '{name}' not found, using the default");
+        }
+        found
+    });
+    let origin = monitor
+        .as_ref()
+        .map(|m| m.position().to_logical::<i32>(m.scale_factor()))
+        .unwrap_or_default();
+    if let Some((x, y)) = browser.window_position {
+        attrs.position = Some(Position::new(LogicalPosition::new(origin.x + x, origin.y + y)));
+    } else if monitor.is_some() {
+        attrs.position = Some(Position::new(origin));
+    }
+
+    let fullscreen =
+        (window_mode == WindowMode::Fullscreen).then(|| Fullscreen::Borderless(monitor.clone()));
+
+    let decorations = browser.window_decorations.unwrap_or(true);
     let mut window_builder = WindowBuilder::new();
     window_builder.window = attrs;
 
     let window = match window_builder
         .with_title(browser.window_title.unwrap_or(webapps::fl!("app")))
-        .with_decorations(browser.window_decorations.unwrap_or(true))
+        .with_decorations(decorations)
+        .with_visible(!start_hidden)
+        .with_maximized(window_mode == WindowMode::Maximized)
+        .with_visible_on_all_workspaces(browser.sticky.unwrap_or(false))
+        .with_skip_taskbar(browser.skip_taskbar.unwrap_or(false))
+        .with_fullscreen(fullscreen)
         .build(&event_loop)
     {
         Ok(w) => w,
@@ -77,12 +291,21 @@ fn main() -> wry::Result<()> {
         }
     };
 
+    // A warm renderer opened its display before this launch, so GTK never saw its token
+    if let Some(id) = &startup_id {
+        use gtk::prelude::GtkWindowExt;
+        use tao::platform::unix::WindowExtUnix;
+        window.gtk_window().set_startup_id(id);
+    }
+
     // Issue #46: WM_CLASS is set via gtk::glib::set_program_name() above (line 29),
     // which GTK uses as the WM_CLASS res_name on X11. This matches StartupWMClass
     // in the generated .desktop entry.
 
     // #54: Set proxy environment variables if configured
-    if let Some(ref proxy) = browser.proxy_url {
+    // An isolated app never talks to a configured proxy either
+    let network_isolation = browser.network_isolation.unwrap_or_default();
+    if let (Some(proxy), NetworkIsolation::Off) = (&browser.proxy_url, network_isolation) {
         if !proxy.trim().is_empty() {
             // SAFETY: Called before any threads are spawned
             unsafe {
@@ -92,30 +315,70 @@ fn main() -> wry::Result<()> {
         }
     }
 
-    let mut context = WebContext::new(browser.profile);
+    // Ephemeral mode: a brand-new profile directory per launch, removed on exit
+    let ephemeral = if browser.ephemeral_profile.unwrap_or(false) {
+        webapps::ephemeral_profile_path(browser.app_id.as_ref())
+    } else {
+        None
+    };
+    let ephemeral_dir = ephemeral.as_ref().map(|profile| profile.path.clone());
+    // Browsing history is kept only in the app's persistent profile
+    let history_profile = browser
+        .profile
+        .clone()
+        .filter(|_| ephemeral_dir.is_none() && !browser.private_mode.unwrap_or(false));
+    let profile = ephemeral_dir.clone().or(browser.profile);
+
+    let mut context = WebContext::new(profile);
+
+    let navigation_rules = browser.navigation_rules.clone().unwrap_or_default();
+    let window_rules = navigation_rules.clone();
+    let audit_app_id = browser
+        .navigation_log
+        .unwrap_or(false)
+        .then(|| browser.app_id.as_ref().to_string());
+    let window_audit_app_id = audit_app_id.clone();
 
+    // Loaded only once the webview is set up: the web process extension has to be in
+    // place before WebKit starts its first web process
+    let mut start_url = url.clone();
+    // Windows the app's pages open, reopened with the session
+    let windows = windows::Windows::new(&app_icon);
+    let opened_windows = windows.clone();
+    let mut restored_windows = Vec::new();
     let mut builder = WebViewBuilder::new_with_web_context(&mut context)
-        .with_url(&url)
         .with_incognito(browser.private_mode.unwrap_or(false))
         .with_devtools(false)
-        .with_navigation_handler(|nav_url| {
-            if is_url_safe(&nav_url) {
-                true
-            } else {
-                eprintln!("Blocked navigation to unsafe URL: {nav_url}");
+        // Touchpad swipes and touchscreen edge swipes
+        .with_back_forward_navigation_gestures(browser.swipe_navigation.unwrap_or(true))
+        .with_new_window_req_handler(move |new_url, features| {
+            let allowed = if !is_url_safe(&new_url, local_files) {
+                eprintln!("Blocked new window with unsafe URL: {new_url}");
                 false
+            } else if !window_rules.allows(&new_url) {
+                eprintln!("New window for {new_url} blocked by the app's rules");
+                false
+            } else {
+                true
+            };
+            if let Some(app_id) = &window_audit_app_id {
+                if let Err(e) = webapps::audit::append(app_id, &new_url, allowed) {
+                    eprintln!("Failed to write navigation log: {e}");
+                }
             }
-        })
-        .with_new_window_req_handler(|new_url, _features| {
-            if is_url_safe(&new_url) {
-                wry::NewWindowResponse::Allow
+            if allowed {
+                let size = features
+                    .size
+                    .map(|size| (size.width.round() as i32, size.height.round() as i32));
+                wry::NewWindowResponse::Create {
+                    webview: opened_windows.open(&features.opener.webview, size),
+                }
             } else {
-                eprintln!("Blocked new window with unsafe URL: {new_url}");
                 wry::NewWindowResponse::Deny
             }
         })
-        .with_download_started_handler(|url, dest_path| {
-            if !is_url_safe(&url) {
+        .with_download_started_handler(move |url, dest_path| {
+            if !is_url_safe(&url, local_files) {
                 eprintln!("Blocked download from unsafe URL: {url}");
                 return false;
             }
@@ -132,6 +395,29 @@ fn main() -> wry::Result<()> {
             true
         });
 
+    // Mobile mode and device presets also emulate touch input and a mobile viewport
+    let emulated_device = browser
+        .device_emulation
+        .as_ref()
+        .filter(|d| d.preset != webapps::browser::DevicePreset::Off);
+    let touch_device = emulated_device.is_some_and(|d| {
+        matches!(
+            d.preset,
+            webapps::browser::DevicePreset::Phone | webapps::browser::DevicePreset::Tablet
+        )
+    });
+    if browser.try_simulate_mobile == Some(true) || touch_device {
+        builder = builder.with_initialization_script(mobile::TOUCH_SCRIPT);
+    }
+    if let Some(device) = emulated_device {
+        let size = browser.window_size.clone().unwrap_or_default();
+        builder = builder.with_initialization_script(&mobile::viewport_script(
+            size.0,
+            size.1,
+            device.device_pixel_ratio,
+        ));
+    }
+
     // Issue #38: Apply user agent (try_simulate_mobile takes precedence for backwards compat)
     if let Some(true) = browser.try_simulate_mobile {
         builder = builder.with_user_agent(webapps::MOBILE_UA);
@@ -205,10 +491,34 @@ fn main() -> wry::Result<()> {
         );
     }
 
+    if perms.allow_geolocation {
+        permission_overrides.push(geolocation::SHIM.to_string());
+    }
+
+    if !perms.allow_screen_sharing {
+        permission_overrides.push(media::BLOCK_DISPLAY_MEDIA.to_string());
+    }
+
     for script in &permission_overrides {
         builder = builder.with_initialization_script(script);
     }
 
+    // Privacy signals for scripts; the headers and referrer policy are set per request
+    if browser.do_not_track.unwrap_or(false) {
+        builder = builder.with_initialization_script(privacy::DNT_SCRIPT);
+    }
+
+    if fingerprint_resistance {
+        for script in fingerprint::scripts() {
+            builder = builder.with_initialization_script(&script);
+        }
+    }
+
+    let password_autofill = browser.password_autofill.unwrap_or(false);
+    if password_autofill {
+        builder = builder.with_initialization_script(passwords::SCRIPT);
+    }
+
     // #53: Content blocking (ads/trackers)
     if let Some(true) = browser.content_blocking {
         builder = builder.with_initialization_script(
@@ -233,6 +543,28 @@ fn main() -> wry::Result<()> {
         );
     }
 
+    if let Some(proxy) = network::proxy_config(network_isolation) {
+        builder = builder.with_proxy_config(proxy);
+    } else {
+        // A configured proxy resolves names itself, so overrides and DoH apply without one
+        let proxied = browser.proxy_url.as_deref().is_some_and(|p| !p.trim().is_empty());
+        let overrides = browser.host_overrides.as_deref().unwrap_or_default();
+        let doh_url = browser
+            .doh_url
+            .as_deref()
+            .filter(|endpoint| endpoint.starts_with("https://"));
+        if !proxied && (!overrides.is_empty() || doh_url.is_some()) {
+            if let Some(proxy) = resolver::start(overrides, doh_url) {
+                builder = builder.with_proxy_config(proxy);
+            }
+        }
+    }
+
+    let data_saver = browser.data_saver.unwrap_or(false);
+    if data_saver {
+        builder = builder.with_initialization_script(data_saver::SCRIPT);
+    }
+
     // #60: Block third-party cookies
     if let Some(true) = browser.block_third_party_cookies {
         builder = builder.with_initialization_script(
@@ -272,22 +604,7 @@ fn main() -> wry::Result<()> {
 
     // Issue #39: Forward web notifications to COSMIC desktop notifications
     if perms.allow_notifications {
-        builder = builder.with_initialization_script(
-            r#"(function(){
-                window.Notification = class extends EventTarget {
-                    constructor(title, options) {
-                        super();
-                        window.ipc.postMessage(JSON.stringify({
-                            type: 'notification',
-                            title: title || '',
-                            body: (options && options.body) || ''
-                        }));
-                    }
-                    static get permission() { return 'granted'; }
-                    static requestPermission() { return Promise.resolve('granted'); }
-                };
-            })()"#,
-        );
+        builder = builder.with_initialization_script(notifications::SCRIPT);
     }
 
     // Issue #43: Media session integration (always inject)
@@ -362,51 +679,120 @@ fn main() -> wry::Result<()> {
 
     // Always set up IPC handler for media controls, badges, session URL, and optionally notifications
     let forward_notifications = perms.allow_notifications;
-    let app_title = app_title_for_notifications.clone();
+    // Set by the lock and limit screens once they are built
+    let covered = lock::Covered::default();
+    let notifier = notifications::Notifier::new(
+        app_title_for_notifications.clone(),
+        event_loop.create_proxy(),
+        browser.quiet_hours.clone(),
+        covered.clone(),
+    );
     let restore_session_enabled = browser.restore_session.unwrap_or(false);
-    let ipc_app_id = browser.app_id.as_ref().to_string();
+    let session_dir = history_profile
+        .clone()
+        .or_else(|| webapps::profiles_path(browser.app_id.as_ref()))
+        .filter(|_| {
+            restore_session_enabled
+                && ephemeral_dir.is_none()
+                && !browser.private_mode.unwrap_or(false)
+        });
+    let forward_geolocation = perms.allow_geolocation;
+    let ipc_proxy = event_loop.create_proxy();
+    let launcher_entry = launcher_entry::LauncherEntry::spawn(browser.app_id.as_ref());
+    let uploads = uploads::Uploads::new(browser.upload_directory.as_deref());
+    let ipc_uploads = uploads.clone();
+    builder = builder.with_initialization_script(uploads::SCRIPT);
+    let ipc_launcher_entry = launcher_entry.clone();
     builder = builder.with_ipc_handler(move |req| {
+        // Trust the frame's URL for the origin rather than anything the page reports
+        let page_url = Url::parse(&req.uri().to_string()).ok();
+        let origin = page_url
+            .as_ref()
+            .map(|u| u.origin())
+            .filter(|o| o.is_tuple())
+            .map(|o| o.ascii_serialization());
         let msg = req.body();
         if let Ok(parsed) = serde_json::from_str::<serde_json::Value>(msg) {
             match parsed.get("type").and_then(|t| t.as_str()) {
                 Some("notification") if forward_notifications => {
-                    let title = parsed
-                        .get("title")
-                        .and_then(|t| t.as_str())
-                        .unwrap_or("Notification");
-                    let body = parsed.get("body").and_then(|b| b.as_str()).unwrap_or("");
-                    let _ = notify_rust::Notification::new()
-                        .summary(&format!("{} — {}", app_title, title))
-                        .body(body)
-                        .appname("dev.heppen.webapps")
-                        .show();
+                    match serde_json::from_value(parsed.clone()) {
+                        Ok(notification) => notifier.show(notification),
+                        Err(e) => eprintln!("Ignoring malformed notification: {e}"),
+                    }
                 }
                 Some("media") => {
                     if let Some(state) = parsed.get("state").and_then(|s| s.as_str()) {
                         tracing::debug!("Media state: {state}");
                     }
                 }
+                Some("geolocation") if forward_geolocation => {
+                    let event = match parsed.get("action").and_then(|a| a.as_str()) {
+                        Some("start") => Some(UserEvent::GeolocationStart),
+                        Some("stop") => Some(UserEvent::GeolocationStop),
+                        _ => None,
+                    };
+                    if let Some(event) = event {
+                        let _ = ipc_proxy.send_event(event);
+                    }
+                }
+                Some("credentials") if password_autofill => {
+                    let Some(origin) = origin else { return };
+                    match parsed.get("action").and_then(|a| a.as_str()) {
+                        Some("query") => {
+                            let _ = ipc_proxy.send_event(UserEvent::CredentialsQuery(origin));
+                        }
+                        Some("save") => {
+                            let field = |name: &str| {
+                                parsed
+                                    .get(name)
+                                    .and_then(|v| v.as_str())
+                                    .unwrap_or_default()
+                                    .to_string()
+                            };
+                            let credential = passwords::Credential {
+                                origin,
+                                username: field("username"),
+                                password: field("password"),
+                            };
+                            if !credential.password.is_empty() {
+                                let _ = ipc_proxy
+                                    .send_event(UserEvent::CredentialsSubmitted(credential));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                Some("tls") => {
+                    let host = page_url.as_ref().and_then(|u| u.host_str());
+                    if let (Some("proceed"), Some(host)) =
+                        (parsed.get("action").and_then(|a| a.as_str()), host)
+                    {
+                        let _ = ipc_proxy.send_event(UserEvent::TlsProceed(host.to_string()));
+                    }
+                }
+                Some("file-chooser") => {
+                    let directory = parsed.get("directory").and_then(|d| d.as_bool());
+                    ipc_uploads.set_directory(directory.unwrap_or(false));
+                }
                 Some("badge") => {
                     if let Some(count) = parsed.get("count").and_then(|c| c.as_u64()) {
                         tracing::debug!("Badge count: {count}");
+                        ipc_launcher_entry.set_count(count);
                     }
                 }
-                Some("save_url") if restore_session_enabled => {
-                    if let Some(new_url) = parsed.get("url").and_then(|u| u.as_str()) {
-                        if !new_url.is_empty() {
-                            // Update last_url in the RON database
-                            let safe_id = webapps::browser::sanitize_app_id(&ipc_app_id);
-                            if let Some(db_path) = webapps::database_path(&format!("{safe_id}.ron")) {
-                                if let Ok(content) = std::fs::read_to_string(&db_path) {
-                                    if let Ok(mut launcher) = ron::from_str::<webapps::launcher::WebAppLauncher>(&content) {
-                                        launcher.browser.last_url = Some(new_url.to_string());
-                                        if let Ok(serialized) = ron::ser::to_string_pretty(&launcher, ron::ser::PrettyConfig::default()) {
-                                            let _ = std::fs::write(&db_path, serialized);
-                                        }
-                                    }
-                                }
-                            }
+                Some("theme-color") => {
+                    if let Some(color) = parsed.get("color").and_then(|c| c.as_str()) {
+                        let _ = ipc_proxy.send_event(UserEvent::ThemeColor(color.to_string()));
+                    }
+                }
+                Some("session") if restore_session_enabled => {
+                    match serde_json::from_value::<webapps::session::Session>(parsed.clone()) {
+                        // The other windows report here too, but are read back natively
+                        Ok(state) if Url::parse(&state.url).ok() == page_url => {
+                            let _ = ipc_proxy.send_event(UserEvent::SessionState(state));
                         }
+                        Ok(_) => {}
+                        Err(e) => eprintln!("Invalid session state: {e}"),
                     }
                 }
                 _ => {}
@@ -415,19 +801,18 @@ fn main() -> wry::Result<()> {
     });
 
     // Inject custom CSS if configured
-    if let Some(ref css) = browser.custom_css {
+    if let Some(ref css) = browser.load_custom_css() {
         if !css.trim().is_empty() {
-            let css_escaped = css.replace('\\', "\\\\").replace('`', "\\`");
-            builder = builder.with_initialization_script(&format!(
-                "(function(){{var s=document.createElement('style');s.textContent=`{css_escaped}`;document.head.appendChild(s)}})()"
-            ));
+            let scope = browser.custom_css_scope.as_deref().unwrap_or_default();
+            builder = builder.with_initialization_script(&custom_code::css_script(css, scope));
         }
     }
 
     // Inject custom JavaScript if configured
-    if let Some(ref js) = browser.custom_js {
+    if let Some(ref js) = browser.load_custom_js() {
         if !js.trim().is_empty() {
-            builder = builder.with_initialization_script(js);
+            let scope = browser.custom_js_scope.as_deref().unwrap_or_default();
+            builder = builder.with_initialization_script(&custom_code::js_script(js, scope));
         }
     }
 
@@ -442,35 +827,37 @@ fn main() -> wry::Result<()> {
         }
     }
 
-    // #56: Session restore — navigate to last URL if enabled
-    if let Some(true) = browser.restore_session {
-        if let Some(ref last) = browser.last_url {
-            if !last.is_empty() && is_url_safe(last) && last != &url {
-                builder = builder.with_url(last);
-            }
+    // #56: Session restore — reopen the last page at its scroll position. The page
+    // reports its state as it changes; it is written once, on exit.
+    if let Some(ref dir) = session_dir {
+        // Sessions saved before scroll restore only have the URL
+        let saved = webapps::session::load(dir).or_else(|| {
+            browser.last_url.clone().map(|url| webapps::session::Session {
+                url,
+                scroll_x: 0.0,
+                scroll_y: 0.0,
+                elements: Vec::new(),
+                windows: Vec::new(),
+            })
+        });
+        if let Some(mut saved) =
+            saved.filter(|s| !s.url.is_empty() && is_url_safe(&s.url, local_files))
+        {
+            // Held to the same rules as when the pages first opened them
+            saved.windows.retain(|window| {
+                is_url_safe(&window.page.url, local_files)
+                    && navigation_rules.allows(&window.page.url)
+            });
+            start_url.clone_from(&saved.url);
+            builder = builder.with_initialization_script(&session::restore_script(&saved));
+            restored_windows = saved.windows;
         }
+        builder = builder.with_initialization_script(session::CAPTURE);
+        windows.remember();
     }
 
-    // #56: Session URL saving — periodically report current URL via IPC
-    if let Some(true) = browser.restore_session {
-        builder = builder.with_initialization_script(
-            r#"(function(){
-                setInterval(function() {
-                    window.ipc.postMessage(JSON.stringify({
-                        type: 'save_url',
-                        url: window.location.href
-                    }));
-                }, 30000);
-                // Also save on page unload
-                window.addEventListener('beforeunload', function() {
-                    window.ipc.postMessage(JSON.stringify({
-                        type: 'save_url',
-                        url: window.location.href
-                    }));
-                });
-            })()"#,
-        );
-    }
+    // Desktop accent color, color scheme and interface font as CSS variables
+    builder = builder.with_initialization_script(&theme::script());
 
     // #62: Auto dark mode CSS injection based on system preference
     if let Some(true) = browser.auto_dark_mode {
@@ -491,7 +878,31 @@ fn main() -> wry::Result<()> {
         );
     }
 
-    let _webview = {
+    // Bookmarks, usage and accepted certificates recorded on earlier runs
+    let app_state = webapps::app_state::load(browser.app_id.as_ref());
+
+    // Paint the theme color behind the page to avoid a white flash while loading
+    let theme_color = browser
+        .theme_color
+        .as_deref()
+        .or(app_state.theme_color.as_deref())
+        .and_then(webapps::parse_hex_color);
+    if let Some((r, g, b)) = theme_color {
+        builder = builder.with_background_color((r, g, b, 255));
+    }
+
+    // Title bar in the theme color; without one configured, take it from the site
+    if let (true, Some(color)) = (decorations, theme_color) {
+        use tao::platform::unix::WindowExtUnix;
+        chrome::apply(window.gtk_window(), color);
+    }
+    let mut detect_theme_color = theme_color.is_none();
+    if detect_theme_color {
+        builder = builder.with_initialization_script(chrome::DETECT_SCRIPT);
+    }
+
+    let daily_limit = browser.daily_limit_minutes.filter(|minutes| *minutes > 0);
+    let (webview, splash, toolbar, lock, limit_screen) = {
         use tao::platform::unix::WindowExtUnix;
         use wry::WebViewBuilderExtUnix;
         let vbox = match window.default_vbox() {
@@ -501,25 +912,579 @@ fn main() -> wry::Result<()> {
                 std::process::exit(1);
             }
         };
-        builder.build_gtk(vbox)?
+        // The lock screen covers everything, toolbar included, until unlocked
+        let lock = browser
+            .app_lock
+            .clone()
+            .map(|config| {
+                lock::Lock::new(vbox, &app_title_for_notifications, &app_icon, config, &covered)
+            });
+        let vbox = lock.as_ref().map_or(vbox, |lock| &lock.content);
+        // Inside the lock, so unlocking shows the limit screen once the time is used up
+        let limit_screen = daily_limit.map(|minutes| {
+            time_limit::LimitScreen::new(
+                vbox,
+                &app_title_for_notifications,
+                &app_icon,
+                minutes,
+                &covered,
+            )
+        });
+        let vbox = limit_screen.as_ref().map_or(vbox, |screen| &screen.content);
+        let toolbar = browser
+            .navigation_toolbar
+            .unwrap_or(false)
+            .then(|| toolbar::Toolbar::new(vbox));
+        if browser.splash_screen.unwrap_or(true) {
+            let splash =
+                splash::Splash::new(vbox, &app_title_for_notifications, &app_icon, theme_color);
+            let webview = builder.build_gtk(&splash.content)?;
+            (webview, Some(splash), toolbar, lock, limit_screen)
+        } else {
+            (builder.build_gtk(vbox)?, None, toolbar, lock, limit_screen)
+        }
+    };
+
+    // Navigation policy, WebKit feature toggles, branded error page with retry, TLS
+    // error interstitial, splash screen until the first load finishes, and the optional
+    // navigation toolbar
+    let tls_interstitial = {
+        use wry::WebViewExtUnix;
+        let wk_webview = webview.webview();
+        privacy::install(
+            &wk_webview,
+            browser.do_not_track.unwrap_or(false),
+            browser.referrer_policy.unwrap_or_default(),
+        );
+        let web_features = browser.web_features.clone().unwrap_or_default();
+        navigation::install(
+            &wk_webview,
+            web_features.autoplay,
+            local_files,
+            navigation_rules.clone(),
+            audit_app_id.clone(),
+        );
+        settings::apply_features(&wk_webview, &web_features);
+        network::install(&wk_webview, network_isolation);
+        media::install(&wk_webview, &perms);
+        if software_rendering {
+            settings::software_rendering(&wk_webview);
+        }
+        if local_files {
+            settings::allow_local_files(&wk_webview);
+        }
+        if data_saver {
+            data_saver::install(&wk_webview);
+        }
+        if browser.spellcheck.unwrap_or(false) {
+            settings::enable_spellcheck(
+                &wk_webview,
+                browser.spellcheck_languages.as_deref().unwrap_or_default(),
+            );
+        }
+        if browser.muted.unwrap_or(false) {
+            use webkit2gtk::WebViewExt;
+            wk_webview.set_is_muted(true);
+        }
+        context_menu::install(&wk_webview, browser.context_menu.unwrap_or_default());
+        error_page::install(&wk_webview, &app_title_for_notifications, &app_icon);
+        launcher_entry.track_downloads(&wk_webview);
+        if let Some(profile) = history_profile.clone() {
+            history::install(&wk_webview, profile);
+        }
+        uploads.install(&wk_webview);
+        let tls_interstitial = tls::TlsInterstitial::install(
+            &wk_webview,
+            browser.app_id.as_ref(),
+            &app_title_for_notifications,
+            &app_icon,
+            &app_state.tls_exceptions,
+            browser
+                .ca_certificate
+                .as_deref()
+                .map(tls::load_ca_bundle)
+                .unwrap_or_default(),
+        );
+        if let Some(splash) = splash {
+            splash.hide_on_first_load(&wk_webview);
+        }
+        if let Some(toolbar) = toolbar {
+            toolbar.connect(&wk_webview, url.clone());
+        }
+        if let Some(lock) = &lock {
+            lock.lock_when_idle(&wk_webview);
+        }
+        {
+            use tao::platform::unix::WindowExtUnix;
+            time_limit::track(
+                window.gtk_window(),
+                &wk_webview,
+                browser.app_id.as_ref().to_string(),
+                app_state.usage_on(&time_limit::today()),
+                daily_limit.zip(limit_screen),
+            );
+            if let Some(minutes) = browser.suspend_after_minutes.filter(|m| *m > 0) {
+                suspend::install(window.gtk_window(), &wk_webview, minutes);
+            }
+        }
+        tls_interstitial
     };
 
+    // Disk cache limit: 0 disables caching, otherwise the cache is trimmed as it grows
+    if let Some(limit_mb) = browser.cache_limit_mb {
+        use wry::WebViewExtUnix;
+        website_data::limit_cache(&webview.webview(), limit_mb);
+    }
+
+    // Real positions from the location portal, with an indicator while in use
+    let mut geolocation = None;
+    if perms.allow_geolocation {
+        use tao::platform::unix::WindowExtUnix;
+        use webkit2gtk::{LoadEvent, WebViewExt};
+        use wry::WebViewExtUnix;
+        if let Some(vbox) = window.default_vbox() {
+            geolocation = Some(geolocation::Geolocation::new(
+                vbox,
+                browser.geolocation_accuracy.unwrap_or_default(),
+                event_loop.create_proxy(),
+            ));
+        }
+        // A new document has no watchers; don't keep the session alive for it
+        let proxy = event_loop.create_proxy();
+        webview.webview().connect_load_changed(move |_, event| {
+            if event == LoadEvent::Started {
+                let _ = proxy.send_event(UserEvent::GeolocationStop);
+            }
+        });
+    }
+
+    // Saved logins from the Secret Service
+    let passwords = if password_autofill {
+        use tao::platform::unix::WindowExtUnix;
+        window.default_vbox().map(|vbox| {
+            passwords::Passwords::new(
+                vbox,
+                browser.app_id.as_ref(),
+                &app_title_for_notifications,
+                event_loop.create_proxy(),
+            )
+        })
+    } else {
+        None
+    };
+
+    {
+        use tao::platform::unix::WindowExtUnix;
+        shortcuts::install(window.gtk_window(), event_loop.create_proxy(), covered.clone());
+    }
+
     // #59: Minimize to background on close
     let minimize_on_close = browser.minimize_to_background.unwrap_or(false);
+    let app_id = browser.app_id.as_ref().to_string();
 
+    // Auto-reload timer for dashboard-style apps
+    if let Some(minutes) = browser.auto_reload_minutes.filter(|m| *m > 0) {
+        let proxy = event_loop.create_proxy();
+        let interval = std::time::Duration::from_secs(u64::from(minutes) * 60);
+        std::thread::spawn(move || {
+            loop {
+                std::thread::sleep(interval);
+                if proxy.send_event(UserEvent::Reload).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+
+    if let Some(path) = args.preview.clone() {
+        preview::watch(path, event_loop.create_proxy());
+    }
+
+    // A hidden window needs a way back
+    if minimize_on_close && !args.private {
+        tray::spawn(
+            app_id.clone(),
+            app_title_for_notifications.clone(),
+            app_icon.clone(),
+            event_loop.create_proxy(),
+        );
+    }
+
+    // Private windows leave the shortcut to the app's main window
+    if let Some(trigger) = browser.global_shortcut.clone().filter(|_| !args.private) {
+        global_shortcut::register(
+            trigger,
+            app_title_for_notifications.clone(),
+            event_loop.create_proxy(),
+        );
+    }
+
+    // Without a configured zoom, start from one that suits the display and follow
+    // the window across monitors
+    let auto_zoom = browser
+        .zoom_level
+        .is_none_or(|zoom| (zoom - 1.0).abs() <= f64::EPSILON);
+    let mut zoom_monitor = None;
+    if auto_zoom {
+        use tao::platform::unix::WindowExtUnix;
+        zoom_monitor = scaling::current_monitor(window.gtk_window());
+        let zoom = scaling::default_zoom(window.gtk_window());
+        if (zoom - 1.0).abs() > f64::EPSILON {
+            if let Err(e) = webview.zoom(zoom) {
+                eprintln!("Failed to set zoom: {e}");
+            }
+        }
+    }
+
+    if let Err(e) = webview.load_url(&start_url) {
+        eprintln!("Failed to open {start_url}: {e}");
+    }
+    {
+        use wry::WebViewExtUnix;
+        let wk_webview = webview.webview();
+        for saved in &restored_windows {
+            windows.restore(&wk_webview, saved);
+        }
+    }
+
+    let mut session_state: Option<webapps::session::Session> = None;
+    // Usage statistics, recorded once on exit
+    let launched = std::time::Instant::now();
+    let launched_at = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+    let mut focused_since = None;
+    let mut focus_time = std::time::Duration::ZERO;
     event_loop.run(move |event, _, control_flow| {
         *control_flow = ControlFlow::Wait;
 
-        if let Event::WindowEvent {
-            event: WindowEvent::CloseRequested,
-            ..
-        } = event
-        {
-            if minimize_on_close {
-                window.set_visible(false);
-            } else {
+        match event {
+            Event::WindowEvent {
+                event: WindowEvent::ScaleFactorChanged { .. } | WindowEvent::Moved(_),
+                ..
+            } if auto_zoom => {
+                use tao::platform::unix::WindowExtUnix;
+                let monitor = scaling::current_monitor(window.gtk_window());
+                if monitor != zoom_monitor {
+                    zoom_monitor = monitor;
+                    if let Err(e) = webview.zoom(scaling::default_zoom(window.gtk_window())) {
+                        eprintln!("Failed to set zoom: {e}");
+                    }
+                }
+            }
+            // Extra mouse buttons (8 and 9) navigate history like in a browser
+            Event::WindowEvent {
+                event:
+                    WindowEvent::MouseInput {
+                        state: ElementState::Pressed,
+                        button: MouseButton::Other(button @ (8 | 9)),
+                        ..
+                    },
+                ..
+            } => {
+                use webkit2gtk::WebViewExt;
+                use wry::WebViewExtUnix;
+                let wk_webview = webview.webview();
+                if button == 8 {
+                    wk_webview.go_back();
+                } else {
+                    wk_webview.go_forward();
+                }
+            }
+            Event::WindowEvent {
+                event: WindowEvent::Focused(focused),
+                ..
+            } => {
+                if focused {
+                    focused_since.get_or_insert_with(std::time::Instant::now);
+                } else if let Some(since) = focused_since.take() {
+                    focus_time += since.elapsed();
+                }
+            }
+            Event::WindowEvent {
+                event: WindowEvent::CloseRequested,
+                ..
+            } => {
+                if minimize_on_close {
+                    window.set_visible(false);
+                } else {
+                    *control_flow = ControlFlow::Exit;
+                }
+            }
+            Event::LoopDestroyed => {
+                let focus_time =
+                    focus_time + focused_since.map(|since| since.elapsed()).unwrap_or_default();
+                let session = webapps::stats::Session {
+                    started: launched_at,
+                    open_secs: launched.elapsed().as_secs(),
+                    focus_secs: focus_time.as_secs(),
+                };
+                // A preview isn't a use of the app
+                if args.preview.is_none() {
+                    if let Err(e) =
+                        webapps::stats::record(&app_id, &time_limit::today(), session)
+                    {
+                        eprintln!("Failed to save usage statistics: {e}");
+                    }
+                }
+                if let (Some(dir), Some(state)) = (&session_dir, &mut session_state) {
+                    state.windows = windows.sessions();
+                    if let Err(e) = webapps::session::save(dir, state) {
+                        eprintln!("Failed to save session: {e}");
+                    }
+                }
+                if let Some(ref profile) = ephemeral {
+                    if let Err(e) = std::fs::remove_dir_all(&profile.path) {
+                        eprintln!("Failed to remove ephemeral profile: {e}");
+                    }
+                }
+            }
+            Event::UserEvent(UserEvent::ThemeColor(color)) if detect_theme_color => {
+                detect_theme_color = false;
+                if let Some(rgb) = webapps::parse_hex_color(&color) {
+                    if decorations {
+                        use tao::platform::unix::WindowExtUnix;
+                        chrome::apply(window.gtk_window(), rgb);
+                    }
+                    // Keep it for the splash screen and title bar on the next launch
+                    if args.preview.is_none() {
+                        let saved = webapps::app_state::update(&app_id, |state| {
+                            state.theme_color = Some(color);
+                        });
+                        if let Err(e) = saved {
+                            eprintln!("Failed to save theme color: {e}");
+                        }
+                    }
+                }
+            }
+            Event::UserEvent(UserEvent::SessionState(state)) => {
+                session_state = Some(state);
+            }
+            Event::UserEvent(UserEvent::PreviewChanged) => {
+                if let Some(path) = &args.preview {
+                    preview::restart(path);
+                }
                 *control_flow = ControlFlow::Exit;
             }
+            Event::UserEvent(UserEvent::Reload) => {
+                if let Err(e) = webview.reload() {
+                    eprintln!("Failed to reload page: {e}");
+                }
+            }
+            Event::UserEvent(UserEvent::GeolocationStart) => {
+                if let Some(ref mut geolocation) = geolocation {
+                    geolocation.start();
+                }
+            }
+            Event::UserEvent(UserEvent::GeolocationStop) => {
+                if let Some(ref mut geolocation) = geolocation {
+                    geolocation.stop();
+                }
+            }
+            Event::UserEvent(UserEvent::GeolocationUpdate(position)) => {
+                let _ = webview.evaluate_script(&format!(
+                    "window.__webappsGeolocation && window.__webappsGeolocation.update({position})"
+                ));
+            }
+            Event::UserEvent(UserEvent::CredentialsQuery(origin)) => {
+                if let Some(ref passwords) = passwords {
+                    passwords.query(origin);
+                }
+            }
+            Event::UserEvent(UserEvent::CredentialsFound(origin, credentials)) => {
+                let origin = serde_json::to_string(&origin).unwrap_or_default();
+                let credentials = serde_json::to_string(&credentials).unwrap_or_default();
+                let _ = webview.evaluate_script(&format!(
+                    "window.__webappsCredentials && window.__webappsCredentials.fill({origin}, {credentials})"
+                ));
+            }
+            Event::UserEvent(UserEvent::CredentialsSubmitted(credential)) => {
+                if let Some(ref passwords) = passwords {
+                    passwords.submitted(credential);
+                }
+            }
+            Event::UserEvent(UserEvent::CredentialsOffer(credential)) => {
+                if let Some(ref passwords) = passwords {
+                    passwords.offer(credential);
+                }
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::ToggleMute)) => {
+                use webkit2gtk::WebViewExt;
+                use wry::WebViewExtUnix;
+                let wk_webview = webview.webview();
+                wk_webview.set_is_muted(!wk_webview.is_muted());
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::History)) => {
+                use tao::platform::unix::WindowExtUnix;
+                use wry::WebViewExtUnix;
+                if let Some(profile) = &history_profile {
+                    history::show(window.gtk_window(), &webview.webview(), profile);
+                }
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::Bookmark)) => {
+                use webkit2gtk::WebViewExt;
+                use wry::WebViewExtUnix;
+                let wk_webview = webview.webview();
+                if let Some(uri) = wk_webview.uri() {
+                    let title = wk_webview.title().unwrap_or_default();
+                    match bookmarks::add(&app_id, &uri, &title) {
+                        Ok(true) => toast::show(&wk_webview, &webapps::fl!("bookmark-added")),
+                        Ok(false) => toast::show(&wk_webview, &webapps::fl!("bookmark-exists")),
+                        Err(e) => eprintln!("Failed to save bookmark: {e}"),
+                    }
+                }
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::Bookmarks)) => {
+                use tao::platform::unix::WindowExtUnix;
+                use wry::WebViewExtUnix;
+                bookmarks::show(window.gtk_window(), &webview.webview(), &app_id);
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::Cookies)) => {
+                use tao::platform::unix::WindowExtUnix;
+                use wry::WebViewExtUnix;
+                cookies::show(window.gtk_window(), &webview.webview());
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::Screenshot)) => {
+                use wry::WebViewExtUnix;
+                screenshot::capture(&webview.webview(), &app_title_for_notifications, &app_icon);
+            }
+            Event::UserEvent(UserEvent::Shortcut(shortcuts::Shortcut::CopyUrl)) => {
+                use wry::WebViewExtUnix;
+                context_menu::copy_page_url(&webview.webview());
+            }
+            Event::UserEvent(UserEvent::Activate(token)) => {
+                if let Some(token) = token {
+                    use gtk::prelude::GtkWindowExt;
+                    use tao::platform::unix::WindowExtUnix;
+                    window.gtk_window().set_startup_id(&token);
+                }
+                window.set_visible(true);
+                window.set_minimized(false);
+                window.set_focus();
+            }
+            Event::UserEvent(UserEvent::OpenUri(uri)) => {
+                match webapps::scheme_uri_to_url(&uri, &url_schemes, &url) {
+                    Some(target) if is_url_safe(&target, local_files) => {
+                        if let Err(e) = webview.load_url(&target) {
+                            eprintln!("Failed to open {target}: {e}");
+                        }
+                    }
+                    _ => eprintln!("Refusing to open unsafe URL: {uri}"),
+                }
+            }
+            Event::UserEvent(UserEvent::ActivateAction(action, token)) => match action.as_str() {
+                "new-window" => {
+                    if let Some(token) = token {
+                        use gtk::prelude::GtkWindowExt;
+                        use tao::platform::unix::WindowExtUnix;
+                        window.gtk_window().set_startup_id(&token);
+                    }
+                    window.set_visible(true);
+                    window.set_minimized(false);
+                    window.set_focus();
+                }
+                "new-private-window" => {
+                    let spawned = std::env::current_exe().and_then(|exe| {
+                        std::process::Command::new(exe)
+                            .arg(&app_id)
+                            .arg("--private")
+                            .spawn()
+                    });
+                    if let Err(e) = spawned {
+                        eprintln!("Failed to open private window: {e}");
+                    }
+                }
+                "open-in-browser" => {
+                    if let Ok(current) = webview.url() {
+                        if let Err(e) = open::that_detached(&current) {
+                            eprintln!("Failed to open {current} in browser: {e}");
+                        }
+                    }
+                }
+                _ => eprintln!("Unknown action: {action}"),
+            },
+            Event::UserEvent(UserEvent::Control(command)) => {
+                use webapps::control::Command;
+                match command {
+                    Command::Reload => {
+                        if let Err(e) = webview.reload() {
+                            eprintln!("Failed to reload page: {e}");
+                        }
+                    }
+                    Command::Navigate(url) => {
+                        if is_url_safe(&url, local_files) {
+                            if let Err(e) = webview.load_url(&url) {
+                                eprintln!("Failed to open {url}: {e}");
+                            }
+                        } else {
+                            eprintln!("Refusing to open unsafe URL: {url}");
+                        }
+                    }
+                    Command::Show => {
+                        window.set_visible(true);
+                        window.set_minimized(false);
+                        window.set_focus();
+                    }
+                    Command::Hide => window.set_visible(false),
+                    Command::SetZoom(level) => {
+                        if let Err(e) = webview.zoom(level) {
+                            eprintln!("Failed to set zoom: {e}");
+                        }
+                    }
+                    Command::ClearData(category) => {
+                        use wry::WebViewExtUnix;
+                        website_data::clear(
+                            &webview.webview(),
+                            category,
+                            history_profile.as_deref(),
+                        );
+                    }
+                    Command::Quit => *control_flow = ControlFlow::Exit,
+                }
+            }
+            Event::UserEvent(UserEvent::NotificationClicked { id, action, url }) => {
+                window.set_visible(true);
+                window.set_minimized(false);
+                window.set_focus();
+                match url {
+                    Some(url) if is_url_safe(&url, local_files) => {
+                        if let Err(e) = webview.load_url(&url) {
+                            eprintln!("Failed to open {url}: {e}");
+                        }
+                    }
+                    _ => {
+                        let action = serde_json::to_string(&action).unwrap_or_default();
+                        let script =
+                            format!("window.__webappsNotificationClicked({id}, {action})");
+                        if let Err(e) = webview.evaluate_script(&script) {
+                            eprintln!("Failed to deliver notification click: {e}");
+                        }
+                    }
+                }
+            }
+            Event::UserEvent(UserEvent::ToggleVisibility) => {
+                if window.is_visible() && !window.is_minimized() {
+                    window.set_visible(false);
+                } else {
+                    window.set_visible(true);
+                    window.set_minimized(false);
+                    window.set_focus();
+                }
+            }
+            Event::UserEvent(UserEvent::TlsProceed(host)) => {
+                tls_interstitial.proceed(&host);
+            }
+            Event::UserEvent(UserEvent::GeolocationError(message)) => {
+                if let Some(ref mut geolocation) = geolocation {
+                    geolocation.stop();
+                }
+                let message = serde_json::to_string(&message).unwrap_or_default();
+                let _ = webview.evaluate_script(&format!(
+                    "window.__webappsGeolocation && window.__webappsGeolocation.error({message})"
+                ));
+            }
+            _ => {}
         }
     });
 }
@@ -0,0 +1,60 @@
+//! Bookmarks saved from inside the web app (Ctrl+D) and a native dialog to open them
+//! (Ctrl+Shift+O).
+//!
+//! They live in the app's state (see [`webapps::app_state`]), so the manager can list and
+//! remove them too.
+
+use webapps::{app_state, browser::Bookmark, fl};
+use webkit2gtk::WebViewExt;
+
+use crate::list_dialog::ListDialog;
+
+/// Keeps the app's state file a manageable size.
+const MAX_BOOKMARKS: usize = 200;
+
+/// Bookmark `url` for `app_id`; returns false when it was already bookmarked.
+pub fn add(app_id: &str, url: &str, title: &str) -> std::io::Result<bool> {
+    let mut added = false;
+    app_state::update(app_id, |state| {
+        let bookmarks = &mut state.bookmarks;
+        if bookmarks.iter().all(|b| b.url != url) && bookmarks.len() < MAX_BOOKMARKS {
+            bookmarks.push(Bookmark {
+                title: title.to_string(),
+                url: url.to_string(),
+            });
+            added = true;
+        }
+    })?;
+    Ok(added)
+}
+
+pub fn remove(app_id: &str, url: &str) -> std::io::Result<()> {
+    app_state::update(app_id, |state| state.bookmarks.retain(|b| b.url != url))
+}
+
+/// Show the bookmarks saved for `app_id` over `parent`; opening one loads it in `webview`.
+pub fn show(parent: &gtk::ApplicationWindow, webview: &webkit2gtk::WebView, app_id: &str) {
+    let dialog = ListDialog::new(parent, &fl!("bookmarks"), &fl!("bookmarks-empty"));
+    for bookmark in app_state::load(app_id).bookmarks {
+        let title = if bookmark.title.is_empty() {
+            &bookmark.url
+        } else {
+            &bookmark.title
+        };
+        let (webview, url) = (webview.clone(), bookmark.url.clone());
+        let on_open = move || webview.load_uri(&url);
+        let (app_id, url) = (app_id.to_string(), bookmark.url.clone());
+        let on_remove = move || {
+            if let Err(e) = remove(&app_id, &url) {
+                eprintln!("Failed to remove bookmark: {e}");
+            }
+        };
+        dialog.add(
+            title,
+            &bookmark.url,
+            Some(Box::new(on_open)),
+            Some(Box::new(on_remove)),
+        );
+    }
+    dialog.show();
+}
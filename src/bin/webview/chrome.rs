@@ -0,0 +1,58 @@
+//! Colors the window's title bar with the site's theme color.
+
+use gtk::prelude::*;
+
+/// Reports the page's theme color, from `<meta name="theme-color">` or else the web app
+/// manifest, normalized to `#rrggbb`.
+pub const DETECT_SCRIPT: &str = r#"(function() {
+    if (window.top !== window) return;
+    function normalize(color) {
+        const ctx = document.createElement('canvas').getContext('2d');
+        ctx.fillStyle = '#000001';
+        ctx.fillStyle = color;
+        return ctx.fillStyle === '#000001' ? null : ctx.fillStyle;
+    }
+    function report(color) {
+        const hex = color && normalize(color.trim());
+        if (hex && hex.startsWith('#')) {
+            window.ipc.postMessage(JSON.stringify({ type: 'theme-color', color: hex }));
+        }
+    }
+    window.addEventListener('load', function() {
+        const metas = Array.from(document.querySelectorAll('meta[name="theme-color"]'));
+        const meta = metas.find(m => !m.media || window.matchMedia(m.media).matches);
+        if (meta) {
+            report(meta.content);
+            return;
+        }
+        const manifest = document.querySelector('link[rel="manifest"]');
+        if (manifest && manifest.href) {
+            fetch(manifest.href, { credentials: 'same-origin' })
+                .then(r => r.json())
+                .then(m => report(m.theme_color))
+                .catch(() => {});
+        }
+    });
+})()"#;
+
+/// Paint the title bar `(r, g, b)` with a readable text color.
+pub fn apply(window: &gtk::ApplicationWindow, (r, g, b): (u8, u8, u8)) {
+    // Relative luminance decides between dark and light title text
+    let luminance = 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+    let text = if luminance > 140.0 { "#000000" } else { "#ffffff" };
+    let css = format!(
+        "headerbar, .titlebar, headerbar:backdrop, .titlebar:backdrop {{ \
+         background: rgb({r}, {g}, {b}); color: {text}; }} \
+         headerbar button, .titlebar button {{ color: {text}; }}"
+    );
+
+    let Some(screen) = WidgetExt::screen(window).or_else(gtk::gdk::Screen::default) else {
+        return;
+    };
+    let provider = gtk::CssProvider::new();
+    if let Err(e) = provider.load_from_data(css.as_bytes()) {
+        eprintln!("Failed to load title bar style: {e}");
+        return;
+    }
+    gtk::StyleContext::add_provider_for_screen(&screen, &provider, gtk::STYLE_PROVIDER_PRIORITY_APPLICATION);
+}
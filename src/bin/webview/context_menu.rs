@@ -0,0 +1,92 @@
+//! Per-app right-click menu: WebKit's default, extended with app actions, or disabled.
+
+use gtk::{gio, gio::prelude::*, prelude::*};
+use webapps::{browser::ContextMenu, fl};
+use webkit2gtk::{ContextMenuExt, ContextMenuItem, HitTestResultExt, WebViewExt};
+
+/// Toggles a minimal reader view: page chrome hidden, text in a narrow centred column.
+const READER_TOGGLE: &str = r#"(function() {
+    const existing = document.getElementById('__webapps_reader');
+    if (existing) {
+        existing.remove();
+        return;
+    }
+    const style = document.createElement('style');
+    style.id = '__webapps_reader';
+    style.textContent = `
+        header, nav, aside, footer, iframe,
+        [role="banner"], [role="navigation"], [role="complementary"], [role="contentinfo"] {
+            display: none !important;
+        }
+        body {
+            max-width: 70ch !important;
+            margin: 0 auto !important;
+            padding: 2em 1em !important;
+            font-size: 1.15em !important;
+            line-height: 1.6 !important;
+        }
+        img, video { max-width: 100% !important; height: auto !important; }
+    `;
+    document.head.appendChild(style);
+})();"#;
+
+fn run_script(webview: &webkit2gtk::WebView, script: &str) {
+    webview.evaluate_javascript(script, None, None, None::<&gio::Cancellable>, |_| {});
+}
+
+/// Copy the current page URL to the clipboard and confirm it with a toast.
+pub fn copy_page_url(webview: &webkit2gtk::WebView) {
+    let Some(uri) = webview.uri() else {
+        return;
+    };
+    gtk::Clipboard::get(&gtk::gdk::SELECTION_CLIPBOARD).set_text(&uri);
+    crate::toast::show(webview, &fl!("page-url-copied"));
+}
+
+fn item(label: &str, activate: impl Fn() + 'static) -> ContextMenuItem {
+    let action = gio::SimpleAction::new("webapps-context-action", None);
+    action.connect_activate(move |_, _| activate());
+    ContextMenuItem::from_gaction(&action, label, None)
+}
+
+/// Apply `mode` to the page's context menu.
+pub fn install(webview: &webkit2gtk::WebView, mode: ContextMenu) {
+    match mode {
+        ContextMenu::Default => {}
+        ContextMenu::Disabled => {
+            webview.connect_context_menu(|_, _, _, _| true);
+        }
+        ContextMenu::Extended => {
+            webview.connect_context_menu(|view, menu, _, hit_test| {
+                menu.append(&ContextMenuItem::new_separator());
+
+                if let Some(link) = hit_test
+                    .context_is_link()
+                    .then(|| hit_test.link_uri())
+                    .flatten()
+                {
+                    menu.append(&item(&fl!("context-menu-open-in-browser"), move || {
+                        if let Err(e) = open::that_detached(link.as_str()) {
+                            eprintln!("Failed to open {link} in browser: {e}");
+                        }
+                    }));
+                }
+
+                let page = view.clone();
+                menu.append(&item(&fl!("context-menu-copy-url"), move || {
+                    copy_page_url(&page)
+                }));
+
+                let page = view.clone();
+                menu.append(&item(&fl!("context-menu-reload"), move || page.reload()));
+
+                let page = view.clone();
+                menu.append(&item(&fl!("context-menu-reader-mode"), move || {
+                    run_script(&page, READER_TOGGLE)
+                }));
+
+                false
+            });
+        }
+    }
+}
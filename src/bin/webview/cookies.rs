@@ -0,0 +1,72 @@
+//! Lists the current site's cookies in a native dialog (Ctrl+Shift+K), where they can be
+//! deleted one by one. The page never sees the list, HttpOnly cookies included.
+
+use gtk::gio;
+use webapps::fl;
+use webkit2gtk::{CookieManagerExt, WebViewExt, WebsiteDataManagerExt};
+
+use crate::list_dialog::ListDialog;
+
+/// Domain, path, flags and expiry of `cookie`.
+fn details(cookie: &mut soup::Cookie) -> String {
+    let domain = cookie.domain().map(|s| s.to_string()).unwrap_or_default();
+    let path = cookie.path().map(|s| s.to_string()).unwrap_or_default();
+    let mut details = vec![format!("{domain}{path}")];
+    if cookie.is_secure() {
+        details.push("Secure".to_string());
+    }
+    if cookie.is_http_only() {
+        details.push("HttpOnly".to_string());
+    }
+    details.push(
+        cookie
+            .expires()
+            .and_then(|date| date.to_local().ok())
+            .and_then(|date| date.format("%c").ok())
+            .map_or_else(|| fl!("cookie-session"), |date| date.to_string()),
+    );
+    details.join(" · ")
+}
+
+/// Show the cookies sent to the current page over `parent`.
+pub fn show(parent: &gtk::ApplicationWindow, webview: &webkit2gtk::WebView) {
+    let Some(uri) = webview.uri().filter(|u| u.starts_with("http")) else {
+        return;
+    };
+    let Some(manager) = webview
+        .website_data_manager()
+        .and_then(|manager| manager.cookie_manager())
+    else {
+        return;
+    };
+    let parent = parent.clone();
+    let cookie_manager = manager.clone();
+    manager.cookies(&uri, None::<&gio::Cancellable>, move |result| {
+        let cookies = match result {
+            Ok(cookies) => cookies,
+            Err(e) => {
+                eprintln!("Failed to read cookies: {e}");
+                return;
+            }
+        };
+        let dialog = ListDialog::new(&parent, &fl!("cookies"), &fl!("cookies-empty"));
+        for mut cookie in cookies {
+            let name = cookie.name().map(|s| s.to_string()).unwrap_or_default();
+            let row = dialog.add(&name, &details(&mut cookie), None, {
+                let (manager, cookie) = (cookie_manager.clone(), cookie.clone());
+                Some(Box::new(move || {
+                    let mut cookie = cookie.clone();
+                    manager.delete_cookie(&mut cookie, None::<&gio::Cancellable>, |result| {
+                        if let Err(e) = result {
+                            eprintln!("Failed to delete cookie: {e}");
+                        }
+                    });
+                }))
+            });
+            if let Some(value) = cookie.value() {
+                row.set_tooltip_text(Some(&value));
+            }
+        }
+        dialog.show();
+    });
+}
@@ -0,0 +1,71 @@
+//! The app's custom CSS and JavaScript, limited to the pages matching their scope.
+//!
+//! Scopes are checked in the page by a small matcher, as the scripts are injected at
+//! document start for every page. Patterns follow the navigation rules, plus paths like
+//! `/mail/*` that match any host of the app.
+
+/// Returns a function telling whether the current page matches any of the given patterns.
+const MATCHER: &str = r#"(function(patterns) {
+    function glob(pattern, text) {
+        const parts = pattern.split('*').map(function(part) {
+            return part.replace(/[.+?^${}()|[\]\\]/g, '\\$&');
+        });
+        return new RegExp('^' + parts.join('.*') + '$').test(text);
+    }
+    return function() {
+        const host = location.hostname.toLowerCase();
+        const path = location.pathname.toLowerCase();
+        return patterns.some(function(pattern) {
+            if (pattern.startsWith('/')) return glob(pattern, path);
+            if (pattern.includes('/')) return glob(pattern, host + path);
+            return glob(pattern, host) || (pattern.startsWith('*.') && pattern.slice(2) === host);
+        });
+    };
+})"#;
+
+fn matcher(scope: &[String]) -> String {
+    let patterns = serde_json::to_string(scope).unwrap_or_else(|_| "[]".to_string());
+    format!("{MATCHER}({patterns})")
+}
+
+/// Add `css` to the page. With a scope, the style is switched on and off as single-page
+/// apps move between pages without loading them.
+pub fn css_script(css: &str, scope: &[String]) -> String {
+    let css = serde_json::to_string(css).unwrap_or_default();
+    if scope.is_empty() {
+        return format!(
+            "(function(){{var s=document.createElement('style');s.textContent={css};\
+             (document.head||document.documentElement).appendChild(s)}})()"
+        );
+    }
+    format!(
+        r#"(function() {{
+    const matches = {matcher};
+    const style = document.createElement('style');
+    style.textContent = {css};
+    function update() {{ style.disabled = !matches(); }}
+    (document.head || document.documentElement).appendChild(style);
+    update();
+    ['pushState', 'replaceState'].forEach(function(name) {{
+        const original = history[name];
+        history[name] = function() {{
+            const result = original.apply(this, arguments);
+            update();
+            return result;
+        }};
+    }});
+    addEventListener('popstate', update);
+    addEventListener('hashchange', update);
+}})()"#,
+        matcher = matcher(scope),
+    )
+}
+
+/// Run `js` on pages matching the scope when they load. The code stays at the top level
+/// so its `var` and function declarations are still globals.
+pub fn js_script(js: &str, scope: &[String]) -> String {
+    if scope.is_empty() {
+        return js.to_string();
+    }
+    format!("if ({}()) {{\n{js}\n}}", matcher(scope))
+}
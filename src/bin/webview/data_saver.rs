@@ -0,0 +1,57 @@
+//! Data-saver mode: block images and web fonts and turn off prefetching.
+
+use gtk::{gio, glib};
+use webkit2gtk::{
+    SettingsExt, UserContentFilterStore, UserContentFilterStoreExt, UserContentManagerExt,
+    WebViewExt,
+};
+
+const FILTER_ID: &str = "data-saver";
+
+/// WebKit content blocker rules (Safari content blocker format).
+const RULES: &str = r#"[
+    {
+        "trigger": { "url-filter": ".*", "resource-type": ["image", "font"] },
+        "action": { "type": "block" }
+    }
+]"#;
+
+/// Drop resource hints so nothing is fetched ahead of use.
+pub const SCRIPT: &str = r#"(function(){
+    const hints = 'link[rel~="prefetch"], link[rel~="prerender"], link[rel~="preconnect"], link[rel~="dns-prefetch"]';
+    function strip() {
+        document.querySelectorAll(hints).forEach(function(el) { el.remove(); });
+    }
+    new MutationObserver(strip).observe(document, { childList: true, subtree: true });
+})()"#;
+
+/// Compile and attach the blocking rules and disable DNS prefetching.
+pub fn install(webview: &webkit2gtk::WebView) {
+    if let Some(settings) = WebViewExt::settings(webview) {
+        #[allow(deprecated)]
+        settings.set_enable_dns_prefetching(false);
+    }
+
+    let Some(manager) = webview.user_content_manager() else {
+        eprintln!("Failed to access user content manager");
+        return;
+    };
+    let Some(store_path) = dirs::cache_dir()
+        .map(|dir| dir.join(webapps::APP_ID).join("content-filters"))
+    else {
+        return;
+    };
+
+    // Compiled rules are cached on disk; compiling is asynchronous, so the very
+    // first load may still fetch a few images.
+    let store = UserContentFilterStore::new(&store_path.to_string_lossy());
+    store.save(
+        FILTER_ID,
+        &glib::Bytes::from_static(RULES.as_bytes()),
+        None::<&gio::Cancellable>,
+        move |result| match result {
+            Ok(filter) => manager.add_filter(&filter),
+            Err(e) => eprintln!("Failed to compile data-saver filter: {e}"),
+        },
+    );
+}
@@ -0,0 +1,240 @@
+//! Per-app D-Bus service implementing `org.freedesktop.Application`, so the desktop
+//! can activate a running web app and open URIs in it, plus the control interface
+//! from [`webapps::control`].
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, mpsc},
+};
+use strum::IntoEnumIterator as _;
+use tao::event_loop::EventLoopProxy;
+use webapps::control::Command;
+use zbus::{
+    fdo,
+    zvariant::{OwnedValue, Value},
+};
+
+use crate::UserEvent;
+
+const INTERFACE: &str = "org.freedesktop.Application";
+
+/// Result of trying to become the app's single running instance.
+pub enum Claim {
+    /// This process owns the bus name and serves the interface.
+    Owned,
+    /// Another instance owns the name and has been activated instead.
+    Forwarded,
+    /// No session bus or invalid name; run standalone.
+    Unavailable,
+}
+
+struct Application {
+    proxy: Mutex<EventLoopProxy<UserEvent>>,
+}
+
+impl Application {
+    fn send(&self, event: UserEvent) {
+        if let Ok(proxy) = self.proxy.lock() {
+            let _ = proxy.send_event(event);
+        }
+    }
+}
+
+/// Token the launcher passed along for focusing the window, if any.
+fn activation_token(platform_data: &HashMap<String, OwnedValue>) -> Option<String> {
+    ["activation-token", "desktop-startup-id"]
+        .into_iter()
+        .filter_map(|key| platform_data.get(key))
+        .find_map(|value| <&str>::try_from(value).ok())
+        .map(str::to_string)
+}
+
+#[zbus::interface(name = "org.freedesktop.Application")]
+impl Application {
+    fn activate(&self, platform_data: HashMap<String, OwnedValue>) {
+        self.send(UserEvent::Activate(activation_token(&platform_data)));
+    }
+
+    fn open(&self, uris: Vec<String>, platform_data: HashMap<String, OwnedValue>) {
+        self.send(UserEvent::Activate(activation_token(&platform_data)));
+        if let Some(uri) = uris.into_iter().next() {
+            self.send(UserEvent::OpenUri(uri));
+        }
+    }
+
+    fn activate_action(
+        &self,
+        action_name: String,
+        _parameter: Vec<OwnedValue>,
+        platform_data: HashMap<String, OwnedValue>,
+    ) {
+        self.send(UserEvent::ActivateAction(
+            action_name,
+            activation_token(&platform_data),
+        ));
+    }
+}
+
+struct Control {
+    proxy: Mutex<EventLoopProxy<UserEvent>>,
+}
+
+impl Control {
+    fn send(&self, command: Command) {
+        if let Ok(proxy) = self.proxy.lock() {
+            let _ = proxy.send_event(UserEvent::Control(command));
+        }
+    }
+}
+
+#[zbus::interface(name = "dev.heppen.webapps.Control")]
+impl Control {
+    fn reload(&self) {
+        self.send(Command::Reload);
+    }
+
+    fn navigate(&self, url: String) -> fdo::Result<()> {
+        if url::Url::parse(&url).is_err() {
+            return Err(fdo::Error::InvalidArgs(format!("invalid URL: {url}")));
+        }
+        self.send(Command::Navigate(url));
+        Ok(())
+    }
+
+    fn show(&self) {
+        self.send(Command::Show);
+    }
+
+    fn hide(&self) {
+        self.send(Command::Hide);
+    }
+
+    fn set_zoom(&self, level: f64) -> fdo::Result<()> {
+        if !(0.25..=5.0).contains(&level) {
+            return Err(fdo::Error::InvalidArgs(
+                "zoom level must be between 0.25 and 5.0".to_string(),
+            ));
+        }
+        self.send(Command::SetZoom(level));
+        Ok(())
+    }
+
+    /// `category` is a [`webapps::ProfileData`] name, or empty to clear everything.
+    fn clear_data(&self, category: String) -> fdo::Result<()> {
+        let category = if category.is_empty() {
+            None
+        } else {
+            let found = webapps::ProfileData::iter().find(|c| format!("{c:?}") == category);
+            Some(found.ok_or_else(|| {
+                fdo::Error::InvalidArgs(format!("unknown data category: {category}"))
+            })?)
+        };
+        self.send(Command::ClearData(category));
+        Ok(())
+    }
+
+    fn quit(&self) {
+        self.send(Command::Quit);
+    }
+}
+
+/// Ask the running instance to come to the front, handing it `uri` if there is one.
+async fn activate_existing(
+    name: &str,
+    path: &str,
+    uri: Option<String>,
+    token: Option<String>,
+) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+    let mut platform_data: HashMap<&str, Value> = HashMap::new();
+    if let Some(token) = token.or_else(|| std::env::var("XDG_ACTIVATION_TOKEN").ok()) {
+        platform_data.insert("activation-token", Value::from(token));
+    }
+    let (name, interface) = (Some(name), Some(INTERFACE));
+    match uri {
+        Some(uri) => {
+            connection
+                .call_method(name, path, interface, "Open", &(vec![uri], platform_data))
+                .await?
+        }
+        None => {
+            connection
+                .call_method(name, path, interface, "Activate", &(platform_data,))
+                .await?
+        }
+    };
+    Ok(())
+}
+
+/// Claim `dev.heppen.webapps.<app_id>` and serve the application interface on a
+/// background thread for the rest of the process lifetime. If another instance
+/// already runs, it is activated and given `uri` instead, along with the launch's
+/// activation `token` when a warm renderer took it over.
+pub fn claim(
+    app_id: &str,
+    uri: Option<String>,
+    token: Option<String>,
+    proxy: EventLoopProxy<UserEvent>,
+) -> Claim {
+    let Some(name) = webapps::dbus_name(app_id) else {
+        return Claim::Unavailable;
+    };
+    let path = webapps::dbus_object_path(&name);
+
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start D-Bus runtime: {e}");
+                let _ = tx.send(Claim::Unavailable);
+                return;
+            }
+        };
+
+        runtime.block_on(async move {
+            let application = Application {
+                proxy: Mutex::new(proxy.clone()),
+            };
+            let control = Control {
+                proxy: Mutex::new(proxy),
+            };
+            let connection = async {
+                zbus::connection::Builder::session()?
+                    .name(name.as_str())?
+                    .serve_at(path.as_str(), application)?
+                    .serve_at(path.as_str(), control)?
+                    .build()
+                    .await
+            }
+            .await;
+
+            match connection {
+                Ok(_connection) => {
+                    let _ = tx.send(Claim::Owned);
+                    // Keep serving until the process exits
+                    std::future::pending::<()>().await;
+                }
+                Err(zbus::Error::NameTaken) => {
+                    let claim = match activate_existing(&name, &path, uri, token).await {
+                        Ok(()) => Claim::Forwarded,
+                        Err(e) => {
+                            eprintln!("Failed to activate running instance: {e}");
+                            Claim::Unavailable
+                        }
+                    };
+                    let _ = tx.send(claim);
+                }
+                Err(e) => {
+                    eprintln!("Failed to register D-Bus service {name}: {e}");
+                    let _ = tx.send(Claim::Unavailable);
+                }
+            }
+        });
+    });
+
+    rx.recv().unwrap_or(Claim::Unavailable)
+}
@@ -0,0 +1,107 @@
+//! Branded offline/load-error page with automatic retry when connectivity returns.
+
+use base64::Engine as _;
+use gtk::{gio, gio::prelude::*};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use webkit2gtk::{LoadEvent, NetworkError, PolicyError, WebViewExt};
+
+const TEMPLATE: &str = include_str!("../../../resources/pages/load-error.html");
+
+/// Escape text for safe inclusion in HTML.
+pub fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Read an icon file and encode it as a `data:` URI so the error page can show it
+/// without network or filesystem access.
+pub fn icon_data_uri(icon_path: &str) -> String {
+    let Ok(bytes) = std::fs::read(icon_path) else {
+        return String::new();
+    };
+    let mime = if webapps::is_svg(icon_path) {
+        "image/svg+xml"
+    } else {
+        "image/png"
+    };
+    format!(
+        "data:{mime};base64,{}",
+        base64::engine::general_purpose::STANDARD.encode(bytes)
+    )
+}
+
+/// Render the error page for a failed load of `failing_uri`.
+fn render(app_title: &str, icon_uri: &str, failing_uri: &str, reason: &str) -> String {
+    TEMPLATE
+        .replace("{{title}}", &escape_html(app_title))
+        .replace("{{icon}}", icon_uri)
+        .replace("{{heading}}", &escape_html(&webapps::fl!("error-page-heading")))
+        .replace("{{message}}", &escape_html(&webapps::fl!("error-page-message")))
+        .replace(
+            "{{details}}",
+            &escape_html(&format!("{failing_uri} — {reason}")),
+        )
+        .replace("{{retry}}", &escape_html(&webapps::fl!("error-page-retry")))
+}
+
+/// Show the error page whenever a main-frame load fails, and reload the failed URL
+/// automatically once the network monitor reports connectivity again.
+pub fn install(webview: &webkit2gtk::WebView, app_title: &str, icon_path: &str) {
+    let failed_uri: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+    let error_page_pending = Rc::new(Cell::new(false));
+    let app_title = app_title.to_string();
+    let icon_uri = icon_data_uri(icon_path);
+
+    {
+        let failed_uri = failed_uri.clone();
+        let error_page_pending = error_page_pending.clone();
+        webview.connect_load_failed(move |view, _event, uri, error| {
+            // Navigations replaced by another navigation or turned into downloads
+            // are reported as failures too; those aren't errors for the user.
+            if error.matches(NetworkError::Cancelled)
+                || error.matches(PolicyError::FrameLoadInterruptedByPolicyChange)
+            {
+                return false;
+            }
+
+            eprintln!("Failed to load {uri}: {error}");
+            failed_uri.replace(Some(uri.to_string()));
+            error_page_pending.set(true);
+            view.load_alternate_html(
+                &render(&app_title, &icon_uri, uri, error.message()),
+                uri,
+                None,
+            );
+            true
+        });
+    }
+
+    {
+        let failed_uri = failed_uri.clone();
+        webview.connect_load_changed(move |_, event| {
+            // The error page itself starts a load; any other load means the user
+            // (or a retry) moved on, so forget the failure.
+            if event == LoadEvent::Started && !error_page_pending.replace(false) {
+                failed_uri.replace(None);
+            }
+        });
+    }
+
+    let view = webview.clone();
+    gio::NetworkMonitor::default().connect_network_changed(move |_, available| {
+        if !available {
+            return;
+        }
+        // Release the borrow before loading; load_uri emits load-changed synchronously.
+        let uri = failed_uri.borrow_mut().take();
+        if let Some(uri) = uri {
+            view.load_uri(&uri);
+        }
+    });
+}
@@ -0,0 +1,108 @@
+//! Fingerprinting resistance: coordinated init scripts that blur the values
+//! trackers combine into a device fingerprint, plus a frozen timezone.
+
+use rand::Rng;
+
+/// Shared noise helper; flips the low bit of a few pixels, seeded once per launch
+/// so repeated readouts within a session stay consistent.
+const NOISE: &str = r#"function __webappsNoise(data) {
+        let s = __webappsSeed;
+        for (let i = 0; i < data.length; i += 4) {
+            s = (s * 1103515245 + 12345) & 0x7fffffff;
+            if ((s & 0xff) < 8) data[i] ^= 1;
+        }
+    }"#;
+
+/// 2D canvas readouts go through a noised copy of the canvas.
+const CANVAS: &str = r#"
+    const getImageData = CanvasRenderingContext2D.prototype.getImageData;
+    CanvasRenderingContext2D.prototype.getImageData = function(...args) {
+        const image = getImageData.apply(this, args);
+        __webappsNoise(image.data);
+        return image;
+    };
+    const toDataURL = HTMLCanvasElement.prototype.toDataURL;
+    const toBlob = HTMLCanvasElement.prototype.toBlob;
+    function noised(canvas) {
+        const copy = document.createElement('canvas');
+        copy.width = canvas.width;
+        copy.height = canvas.height;
+        if (!copy.width || !copy.height) return copy;
+        const ctx = copy.getContext('2d');
+        ctx.drawImage(canvas, 0, 0);
+        const image = getImageData.call(ctx, 0, 0, copy.width, copy.height);
+        __webappsNoise(image.data);
+        ctx.putImageData(image, 0, 0);
+        return copy;
+    }
+    HTMLCanvasElement.prototype.toDataURL = function(...args) {
+        return toDataURL.apply(noised(this), args);
+    };
+    HTMLCanvasElement.prototype.toBlob = function(...args) {
+        return toBlob.apply(noised(this), args);
+    };"#;
+
+/// Generic GPU vendor/renderer strings and noised pixel readback.
+const WEBGL: &str = r#"
+    const UNMASKED_VENDOR = 0x9245;
+    const UNMASKED_RENDERER = 0x9246;
+    for (const ctx of [window.WebGLRenderingContext, window.WebGL2RenderingContext]) {
+        if (!ctx) continue;
+        const proto = ctx.prototype;
+        const getParameter = proto.getParameter;
+        proto.getParameter = function(name) {
+            if (name === UNMASKED_VENDOR || name === this.VENDOR) return 'WebKit';
+            if (name === UNMASKED_RENDERER || name === this.RENDERER) return 'WebKit WebGL';
+            return getParameter.call(this, name);
+        };
+        const readPixels = proto.readPixels;
+        proto.readPixels = function(...args) {
+            readPixels.apply(this, args);
+            const pixels = args[6];
+            if (pixels && pixels.length) __webappsNoise(pixels);
+        };
+    }"#;
+
+/// Screen dimensions rounded to coarse buckets, with a common color depth.
+const SCREEN: &str = r#"(function(){
+    const bucket = (value, step) => Math.max(step, Math.floor(value / step) * step);
+    const width = bucket(screen.width, 200);
+    const height = bucket(screen.height, 100);
+    const values = {
+        width: width, height: height, availWidth: width, availHeight: height,
+        colorDepth: 24, pixelDepth: 24
+    };
+    for (const [name, value] of Object.entries(values)) {
+        Object.defineProperty(Screen.prototype, name, { get: () => value, configurable: true });
+    }
+})()"#;
+
+/// The most common hardware profile.
+const HARDWARE: &str = r#"(function(){
+    Object.defineProperty(Navigator.prototype, 'hardwareConcurrency', { get: () => 4, configurable: true });
+    Object.defineProperty(Navigator.prototype, 'deviceMemory', { get: () => 8, configurable: true });
+})()"#;
+
+/// Init scripts for the hardening mode, sharing one per-launch noise seed.
+pub fn scripts() -> Vec<String> {
+    let seed: u32 = rand::rng().random_range(1..0x7fff_ffff);
+    let with_noise = |body: &str| {
+        format!("(function(){{\n    const __webappsSeed = {seed};\n    {NOISE}\n{body}\n}})()")
+    };
+
+    vec![
+        with_noise(CANVAS),
+        with_noise(WEBGL),
+        SCREEN.to_string(),
+        HARDWARE.to_string(),
+    ]
+}
+
+/// Report UTC to pages. The web process inherits our environment, so `Date` and
+/// `Intl` agree with each other.
+///
+/// Must be called before GTK starts any threads.
+pub fn freeze_timezone() {
+    // SAFETY: called at the top of main while the process is still single-threaded.
+    unsafe { std::env::set_var("TZ", "UTC") };
+}
@@ -0,0 +1,213 @@
+//! Geolocation API backed by the XDG location portal (GeoClue behind it).
+
+use ashpd::desktop::location::{Accuracy, LocationProxy};
+use futures_util::StreamExt;
+use gtk::prelude::*;
+use tao::event_loop::EventLoopProxy;
+use tokio::sync::oneshot;
+use webapps::browser::GeolocationAccuracy;
+
+use crate::UserEvent;
+
+/// Page-side replacement for `navigator.geolocation`. Requests are forwarded over IPC
+/// and answered through `window.__webappsGeolocation` from the event loop.
+pub const SHIM: &str = r#"(function(){
+    const pending = [];
+    const watchers = new Map();
+    let nextId = 1;
+    let last = null;
+    let active = false;
+    function post(action) {
+        window.ipc.postMessage(JSON.stringify({type:'geolocation', action: action}));
+    }
+    function sync() {
+        const wanted = pending.length > 0 || watchers.size > 0;
+        if (wanted !== active) {
+            active = wanted;
+            post(wanted ? 'start' : 'stop');
+        }
+    }
+    function toPosition(p) {
+        return {
+            coords: {
+                latitude: p.latitude, longitude: p.longitude, accuracy: p.accuracy,
+                altitude: p.altitude, altitudeAccuracy: null, heading: p.heading, speed: p.speed
+            },
+            timestamp: p.timestamp
+        };
+    }
+    function fail(cb, code, message) {
+        if (cb) cb({ code: code, message: message, PERMISSION_DENIED: 1, POSITION_UNAVAILABLE: 2, TIMEOUT: 3 });
+    }
+    window.__webappsGeolocation = {
+        update(p) {
+            last = p;
+            const pos = toPosition(p);
+            pending.splice(0).forEach(r => { clearTimeout(r.timer); r.success(pos); });
+            watchers.forEach(w => w.success(pos));
+            sync();
+        },
+        error(message) {
+            // The native side has already stopped; a later request starts it again
+            active = false;
+            pending.splice(0).forEach(r => { clearTimeout(r.timer); fail(r.error, 2, message); });
+            watchers.forEach(w => fail(w.error, 2, message));
+        }
+    };
+    navigator.geolocation.getCurrentPosition = function(success, error, options) {
+        const maxAge = (options && options.maximumAge) || 0;
+        if (last && Date.now() - last.timestamp <= maxAge) {
+            setTimeout(() => success(toPosition(last)), 0);
+            return;
+        }
+        const request = { success: success, error: error, timer: null };
+        if (options && options.timeout !== undefined && options.timeout !== Infinity) {
+            request.timer = setTimeout(() => {
+                const i = pending.indexOf(request);
+                if (i >= 0) {
+                    pending.splice(i, 1);
+                    fail(error, 3, 'Timeout expired');
+                    sync();
+                }
+            }, options.timeout);
+        }
+        pending.push(request);
+        sync();
+    };
+    navigator.geolocation.watchPosition = function(success, error) {
+        const id = nextId++;
+        watchers.set(id, { success: success, error: error });
+        if (last) {
+            setTimeout(() => {
+                const w = watchers.get(id);
+                if (w) w.success(toPosition(last));
+            }, 0);
+        }
+        sync();
+        return id;
+    };
+    navigator.geolocation.clearWatch = function(id) {
+        watchers.delete(id);
+        sync();
+    };
+})()"#;
+
+fn portal_accuracy(accuracy: GeolocationAccuracy) -> Accuracy {
+    match accuracy {
+        GeolocationAccuracy::Country => Accuracy::Country,
+        GeolocationAccuracy::City => Accuracy::City,
+        GeolocationAccuracy::Neighborhood => Accuracy::Neighborhood,
+        GeolocationAccuracy::Street => Accuracy::Street,
+        GeolocationAccuracy::Exact => Accuracy::Exact,
+    }
+}
+
+/// Owns the portal session while the page is watching the location and shows an
+/// indicator bar for as long as it runs.
+pub struct Geolocation {
+    accuracy: Accuracy,
+    proxy: EventLoopProxy<UserEvent>,
+    stop: Option<oneshot::Sender<()>>,
+    indicator: gtk::InfoBar,
+}
+
+impl Geolocation {
+    /// Create the (hidden) in-use indicator at the bottom of `vbox`.
+    pub fn new(
+        vbox: &gtk::Box,
+        accuracy: GeolocationAccuracy,
+        proxy: EventLoopProxy<UserEvent>,
+    ) -> Self {
+        let indicator = gtk::InfoBar::new();
+        indicator.set_message_type(gtk::MessageType::Info);
+        indicator.set_no_show_all(true);
+        let label = gtk::Label::new(Some(&webapps::fl!("location-in-use")));
+        label.show();
+        indicator.content_area().add(&label);
+        vbox.pack_end(&indicator, false, false, 0);
+
+        Self {
+            accuracy: portal_accuracy(accuracy),
+            proxy,
+            stop: None,
+            indicator,
+        }
+    }
+
+    /// Start a portal session unless one is already running.
+    pub fn start(&mut self) {
+        if self.stop.is_some() {
+            return;
+        }
+
+        let (stop_tx, stop_rx) = oneshot::channel();
+        self.stop = Some(stop_tx);
+        self.indicator.set_visible(true);
+
+        let accuracy = self.accuracy;
+        let proxy = self.proxy.clone();
+        std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    let _ = proxy.send_event(UserEvent::GeolocationError(e.to_string()));
+                    return;
+                }
+            };
+            if let Err(e) = runtime.block_on(watch(accuracy, proxy.clone(), stop_rx)) {
+                eprintln!("Location portal failed: {e}");
+                let _ = proxy.send_event(UserEvent::GeolocationError(e.to_string()));
+            }
+        });
+    }
+
+    /// Stop the running session, if any, and hide the indicator.
+    pub fn stop(&mut self) {
+        if let Some(stop) = self.stop.take() {
+            let _ = stop.send(());
+        }
+        self.indicator.set_visible(false);
+    }
+}
+
+/// Forward location updates to the event loop until `stop` fires.
+async fn watch(
+    accuracy: Accuracy,
+    proxy: EventLoopProxy<UserEvent>,
+    mut stop: oneshot::Receiver<()>,
+) -> ashpd::Result<()> {
+    let portal = LocationProxy::new().await?;
+    let session = portal.create_session(None, None, Some(accuracy)).await?;
+    let updates = portal.receive_location_updated().await?;
+    let mut updates = std::pin::pin!(updates);
+    portal.start(&session, None).await?.response()?;
+
+    loop {
+        tokio::select! {
+            _ = &mut stop => break,
+            location = updates.next() => {
+                let Some(location) = location else { break };
+                let position = serde_json::json!({
+                    "latitude": location.latitude(),
+                    "longitude": location.longitude(),
+                    "accuracy": location.accuracy(),
+                    "altitude": location.altitude(),
+                    "heading": location.heading(),
+                    "speed": location.speed(),
+                    "timestamp": location.timestamp().as_millis() as u64,
+                });
+                if proxy
+                    .send_event(UserEvent::GeolocationUpdate(position.to_string()))
+                    .is_err()
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    session.close().await
+}
@@ -0,0 +1,53 @@
+//! Global show/hide shortcut registered through the GlobalShortcuts portal.
+
+use ashpd::desktop::global_shortcuts::{GlobalShortcuts, NewShortcut};
+use futures_util::StreamExt;
+use tao::event_loop::EventLoopProxy;
+
+use crate::UserEvent;
+
+const SHORTCUT_ID: &str = "toggle-visibility";
+
+/// Bind `trigger` (a preferred trigger in the portal's shortcut format) and send
+/// [`UserEvent::ToggleVisibility`] whenever it fires. The compositor may let the user
+/// pick a different trigger; it stays bound for the lifetime of the process.
+pub fn register(trigger: String, title: String, proxy: EventLoopProxy<UserEvent>) {
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start global shortcut runtime: {e}");
+                return;
+            }
+        };
+        if let Err(e) = runtime.block_on(listen(&trigger, &title, proxy)) {
+            eprintln!("Global shortcuts portal failed: {e}");
+        }
+    });
+}
+
+async fn listen(
+    trigger: &str,
+    title: &str,
+    proxy: EventLoopProxy<UserEvent>,
+) -> ashpd::Result<()> {
+    let portal = GlobalShortcuts::new().await?;
+    let session = portal.create_session().await?;
+    let shortcut = NewShortcut::new(SHORTCUT_ID, format!("Show or hide {title}"))
+        .preferred_trigger(trigger);
+    portal.bind_shortcuts(&session, &[shortcut], None).await?.response()?;
+
+    let mut activated = std::pin::pin!(portal.receive_activated().await?);
+    while let Some(event) = activated.next().await {
+        if event.shortcut_id() == SHORTCUT_ID
+            && proxy.send_event(UserEvent::ToggleVisibility).is_err()
+        {
+            break;
+        }
+    }
+
+    session.close().await
+}
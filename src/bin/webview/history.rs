@@ -0,0 +1,86 @@
+//! Records visited pages into the app's history and shows them in a native dialog (Ctrl+H).
+
+use std::{
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use gtk::glib;
+use webapps::{fl, history};
+use webkit2gtk::{LoadEvent, WebViewExt};
+
+use crate::list_dialog::ListDialog;
+
+/// Entries shown in the dialog, newest first.
+const DIALOG_LIMIT: usize = 200;
+
+fn visit(webview: &webkit2gtk::WebView) -> Option<(String, String)> {
+    let url = webview.uri().filter(|u| u.starts_with("http"))?;
+    Some((url.to_string(), webview.title().unwrap_or_default().to_string()))
+}
+
+/// Record every finished page load, and late title changes, into `profile`'s history.
+pub fn install(webview: &webkit2gtk::WebView, profile: PathBuf) {
+    // One writer thread, so visits never race each other on the history file
+    let (sender, visits) = mpsc::channel::<(String, String)>();
+    std::thread::spawn(move || {
+        for (url, title) in visits {
+            if let Err(e) = history::record(&profile, &url, &title) {
+                tracing::warn!("Failed to record history: {e}");
+            }
+        }
+    });
+
+    let load_sender = sender.clone();
+    webview.connect_load_changed(move |view, event| {
+        if event == LoadEvent::Finished {
+            if let Some(visit) = visit(view) {
+                let _ = load_sender.send(visit);
+            }
+        }
+    });
+    webview.connect_title_notify(move |view| {
+        if let Some(visit) = visit(view).filter(|_| !view.is_loading()) {
+            let _ = sender.send(visit);
+        }
+    });
+}
+
+/// Local date and time of a visit.
+fn visited_at(visited: u64) -> String {
+    i64::try_from(visited)
+        .ok()
+        .and_then(|visited| glib::DateTime::from_unix_local(visited).ok())
+        .and_then(|date| date.format("%c").ok())
+        .map(|date| date.to_string())
+        .unwrap_or_default()
+}
+
+/// Show the history of `profile` over `parent`; opening an entry loads it in `webview`.
+pub fn show(parent: &gtk::ApplicationWindow, webview: &webkit2gtk::WebView, profile: &Path) {
+    let dialog = ListDialog::new(parent, &fl!("history"), &fl!("history-empty"))
+        .with_search(&fl!("history-search"));
+    let clear_profile = profile.to_path_buf();
+    dialog.add_action(&fl!("clear-history"), move |dialog| {
+        match history::clear(&clear_profile) {
+            Ok(()) => dialog.clear(),
+            Err(e) => eprintln!("Failed to clear history: {e}"),
+        }
+    });
+    for entry in history::load(profile).into_iter().rev().take(DIALOG_LIMIT) {
+        let title = if entry.title.is_empty() {
+            &entry.url
+        } else {
+            &entry.title
+        };
+        let subtitle = format!("{} · {}", visited_at(entry.visited), entry.url);
+        let (webview, url) = (webview.clone(), entry.url.clone());
+        dialog.add(
+            title,
+            &subtitle,
+            Some(Box::new(move || webview.load_uri(&url))),
+            None,
+        );
+    }
+    dialog.show();
+}
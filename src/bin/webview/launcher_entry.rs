@@ -0,0 +1,116 @@
+//! Dock badge and progress through `com.canonical.Unity.LauncherEntry`, keyed to
+//! the app's desktop entry.
+
+use std::{cell::Cell, collections::HashMap, rc::Rc};
+use tokio::sync::mpsc;
+use webkit2gtk::{DownloadExt, WebContextExt, WebViewExt};
+use zbus::{names::BusName, zvariant::Value};
+
+const INTERFACE: &str = "com.canonical.Unity.LauncherEntry";
+const PATH: &str = "/dev/heppen/webapps/LauncherEntry";
+
+enum Update {
+    Count(u64),
+    Progress(Option<f64>),
+}
+
+/// Handle for updating the dock entry; updates are emitted from a background thread.
+#[derive(Clone)]
+pub struct LauncherEntry {
+    tx: mpsc::UnboundedSender<Update>,
+}
+
+impl LauncherEntry {
+    pub fn spawn(app_id: &str) -> Self {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app_uri = format!("application://{}.{app_id}.desktop", webapps::APP_ID);
+
+        std::thread::spawn(move || {
+            let runtime = match tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtime,
+                Err(e) => {
+                    eprintln!("Failed to start launcher entry runtime: {e}");
+                    return;
+                }
+            };
+            if let Err(e) = runtime.block_on(emit_updates(app_uri, rx)) {
+                eprintln!("Failed to update dock entry: {e}");
+            }
+        });
+
+        Self { tx }
+    }
+
+    /// Show `count` on the dock icon; zero hides the badge.
+    pub fn set_count(&self, count: u64) {
+        let _ = self.tx.send(Update::Count(count));
+    }
+
+    /// Show a progress bar on the dock icon (0.0–1.0); `None` hides it.
+    pub fn set_progress(&self, progress: Option<f64>) {
+        let _ = self.tx.send(Update::Progress(progress));
+    }
+
+    /// Mirror download progress on the dock icon while downloads are running.
+    pub fn track_downloads(&self, webview: &webkit2gtk::WebView) {
+        let Some(context) = webview.context() else {
+            return;
+        };
+        let active = Rc::new(Cell::new(0u32));
+        let entry = self.clone();
+
+        context.connect_download_started(move |_, download| {
+            active.set(active.get() + 1);
+            entry.set_progress(Some(0.0));
+
+            let progress_entry = entry.clone();
+            download.connect_estimated_progress_notify(move |download| {
+                progress_entry.set_progress(Some(download.estimated_progress()));
+            });
+
+            // `finished` also fires after `failed`
+            let (entry, active) = (entry.clone(), active.clone());
+            download.connect_finished(move |_| {
+                active.set(active.get().saturating_sub(1));
+                if active.get() == 0 {
+                    entry.set_progress(None);
+                }
+            });
+        });
+    }
+}
+
+async fn emit_updates(
+    app_uri: String,
+    mut rx: mpsc::UnboundedReceiver<Update>,
+) -> zbus::Result<()> {
+    let connection = zbus::Connection::session().await?;
+
+    while let Some(update) = rx.recv().await {
+        let mut properties: HashMap<&str, Value> = HashMap::new();
+        match update {
+            Update::Count(count) => {
+                properties.insert("count", Value::from(count as i64));
+                properties.insert("count-visible", Value::from(count > 0));
+            }
+            Update::Progress(progress) => {
+                properties.insert("progress", Value::from(progress.unwrap_or(0.0)));
+                properties.insert("progress-visible", Value::from(progress.is_some()));
+            }
+        }
+        connection
+            .emit_signal(
+                None::<BusName<'_>>,
+                PATH,
+                INTERFACE,
+                "Update",
+                &(app_uri.as_str(), properties),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
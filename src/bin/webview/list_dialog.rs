@@ -0,0 +1,170 @@
+//! Native dialogs listing the app's own data, such as its cookies. They are GTK windows on
+//! top of the app rather than overlays in the page, so a site can neither read what they
+//! show nor act on it.
+
+use std::{cell::RefCell, rc::Rc};
+
+use gtk::{gdk, glib, pango, prelude::*};
+use webapps::fl;
+
+#[derive(Clone)]
+pub struct ListDialog {
+    window: gtk::Window,
+    header: gtk::HeaderBar,
+    content: gtk::Box,
+    list: gtk::ListBox,
+    /// Each row with the lowercase text the search matches against
+    rows: Rc<RefCell<Vec<(gtk::ListBoxRow, String)>>>,
+}
+
+fn label(text: &str, ellipsize: pango::EllipsizeMode) -> gtk::Label {
+    let label = gtk::Label::new(Some(text));
+    label.set_xalign(0.0);
+    label.set_ellipsize(ellipsize);
+    label
+}
+
+impl ListDialog {
+    /// An empty dialog over `parent`; `empty` is shown while it has no rows.
+    pub fn new(parent: &gtk::ApplicationWindow, title: &str, empty: &str) -> Self {
+        let window = gtk::Window::new(gtk::WindowType::Toplevel);
+        window.set_transient_for(Some(parent));
+        window.set_modal(true);
+        window.set_destroy_with_parent(true);
+        window.set_type_hint(gdk::WindowTypeHint::Dialog);
+        window.set_default_size(560, 480);
+        window.connect_key_press_event(|window, event| {
+            if event.keyval() == gdk::keys::constants::Escape {
+                window.close();
+                return glib::Propagation::Stop;
+            }
+            glib::Propagation::Proceed
+        });
+
+        let header = gtk::HeaderBar::new();
+        header.set_title(Some(title));
+        header.set_show_close_button(true);
+        window.set_titlebar(Some(&header));
+
+        let list = gtk::ListBox::new();
+        list.set_selection_mode(gtk::SelectionMode::None);
+        let placeholder = label(empty, pango::EllipsizeMode::None);
+        placeholder.set_xalign(0.5);
+        placeholder.set_margin_top(24);
+        placeholder.style_context().add_class("dim-label");
+        placeholder.show();
+        list.set_placeholder(Some(&placeholder));
+
+        let scrolled = gtk::ScrolledWindow::new(None::<&gtk::Adjustment>, None::<&gtk::Adjustment>);
+        scrolled.add(&list);
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        content.pack_end(&scrolled, true, true, 0);
+        window.add(&content);
+
+        Self {
+            window,
+            header,
+            content,
+            list,
+            rows: Rc::default(),
+        }
+    }
+
+    /// Filter the rows by what is typed into a search entry above them.
+    pub fn with_search(self, placeholder: &str) -> Self {
+        let entry = gtk::SearchEntry::new();
+        entry.set_placeholder_text(Some(placeholder));
+        entry.set_margin_top(6);
+        entry.set_margin_bottom(6);
+        entry.set_margin_start(6);
+        entry.set_margin_end(6);
+        let rows = self.rows.clone();
+        entry.connect_search_changed(move |entry| {
+            let query = entry.text().to_lowercase();
+            for (row, text) in rows.borrow().iter() {
+                row.set_visible(text.contains(&query));
+            }
+        });
+        self.content.pack_start(&entry, false, false, 0);
+        self
+    }
+
+    /// A button in the header bar acting on the whole list.
+    pub fn add_action(&self, label: &str, on_click: impl Fn(&Self) + 'static) {
+        let button = gtk::Button::with_label(label);
+        let dialog = self.clone();
+        button.connect_clicked(move |_| on_click(&dialog));
+        self.header.pack_end(&button);
+    }
+
+    /// Add a row showing `title` over `subtitle`. Clicking it runs `on_open` and closes the
+    /// dialog, and with `on_remove` it gets a button that runs it and takes the row away.
+    pub fn add(
+        &self,
+        title: &str,
+        subtitle: &str,
+        on_open: Option<Box<dyn Fn()>>,
+        on_remove: Option<Box<dyn Fn()>>,
+    ) -> gtk::ListBoxRow {
+        let labels = gtk::Box::new(gtk::Orientation::Vertical, 2);
+        labels.pack_start(&label(title, pango::EllipsizeMode::End), false, false, 0);
+        let subtitle_label = label(subtitle, pango::EllipsizeMode::Middle);
+        subtitle_label.style_context().add_class("dim-label");
+        labels.pack_start(&subtitle_label, false, false, 0);
+
+        let content = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+        content.set_margin_start(6);
+        content.set_margin_end(6);
+        match on_open {
+            Some(on_open) => {
+                let button = gtk::Button::new();
+                button.set_relief(gtk::ReliefStyle::None);
+                button.add(&labels);
+                let window = self.window.clone();
+                button.connect_clicked(move |_| {
+                    on_open();
+                    window.close();
+                });
+                content.pack_start(&button, true, true, 0);
+            }
+            None => {
+                labels.set_margin_top(6);
+                labels.set_margin_bottom(6);
+                content.pack_start(&labels, true, true, 0);
+            }
+        }
+
+        let row = gtk::ListBoxRow::new();
+        if let Some(on_remove) = on_remove {
+            let button =
+                gtk::Button::from_icon_name(Some("edit-delete-symbolic"), gtk::IconSize::Button);
+            button.set_relief(gtk::ReliefStyle::None);
+            button.set_valign(gtk::Align::Center);
+            button.set_tooltip_text(Some(&fl!("remove")));
+            let (list, rows, removed) = (self.list.clone(), self.rows.clone(), row.clone());
+            button.connect_clicked(move |_| {
+                on_remove();
+                rows.borrow_mut().retain(|(row, _)| row != &removed);
+                list.remove(&removed);
+            });
+            content.pack_end(&button, false, false, 0);
+        }
+        row.add(&content);
+        self.list.add(&row);
+        self.rows
+            .borrow_mut()
+            .push((row.clone(), format!("{title}\n{subtitle}").to_lowercase()));
+        row
+    }
+
+    /// Remove every row.
+    pub fn clear(&self) {
+        for (row, _) in self.rows.borrow_mut().drain(..) {
+            self.list.remove(&row);
+        }
+    }
+
+    pub fn show(&self) {
+        self.window.show_all();
+    }
+}
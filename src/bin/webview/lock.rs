@@ -0,0 +1,253 @@
+//! Lock screen covering the app until it is unlocked with a PIN or the system password,
+//! and again after a configurable idle time.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        Arc,
+        atomic::{AtomicU8, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use gtk::{gio, glib, prelude::*};
+use tokio::sync::oneshot;
+use webapps::{
+    browser::{AppLock, LockMethod},
+    fl,
+};
+use zbus::zvariant::Value;
+
+/// polkit action checked for system password unlocks, asking for the user's own password.
+/// Installed from `resources/dev.heppen.webapps.policy`.
+const POLKIT_ACTION: &str = "dev.heppen.webapps.unlock";
+
+/// How often the idle timer checks for inactivity.
+const IDLE_CHECK_SECS: u32 = 15;
+
+/// Ask polkit to authenticate the user, showing the desktop's password prompt.
+async fn authorize() -> zbus::Result<bool> {
+    let connection = zbus::Connection::system().await?;
+    let name = connection
+        .unique_name()
+        .ok_or_else(|| zbus::Error::Failure("no unique bus name".to_string()))?
+        .to_string();
+    // Identified by bus name, which also works from inside a PID namespace
+    let subject = ("system-bus-name", HashMap::from([("name", Value::from(name))]));
+    let details: HashMap<&str, &str> = HashMap::new();
+    // 1 = AllowUserInteraction
+    let reply = connection
+        .call_method(
+            Some("org.freedesktop.PolicyKit1"),
+            "/org/freedesktop/PolicyKit1/Authority",
+            Some("org.freedesktop.PolicyKit1.Authority"),
+            "CheckAuthorization",
+            &(subject, POLKIT_ACTION, details, 1u32, ""),
+        )
+        .await?;
+    let (authorized, _challenge, _details): (bool, bool, HashMap<String, String>) =
+        reply.body().deserialize()?;
+    Ok(authorized)
+}
+
+/// Bit of [`Covered`] set while the lock screen is up.
+const LOCK_SCREEN: u8 = 1;
+
+/// Bit of [`Covered`] set while the time limit screen is up.
+pub const LIMIT_SCREEN: u8 = 2;
+
+/// Whether the app is hidden behind the lock or the time limit screen. Shortcuts and
+/// forwarded notifications do nothing meanwhile, as they would act on or reveal the app.
+#[derive(Clone, Default)]
+pub struct Covered(Arc<AtomicU8>);
+
+impl Covered {
+    pub fn get(&self) -> bool {
+        self.0.load(Ordering::Relaxed) != 0
+    }
+
+    /// Count the app as covered by `screen` while `stack` shows `page`.
+    pub fn follow(&self, stack: &gtk::Stack, page: &'static str, screen: u8) {
+        let covered = self.0.clone();
+        let update = move |stack: &gtk::Stack| {
+            if stack.visible_child_name().as_deref() == Some(page) {
+                covered.fetch_or(screen, Ordering::Relaxed);
+            } else {
+                covered.fetch_and(!screen, Ordering::Relaxed);
+            }
+        };
+        update(stack);
+        stack.connect_visible_child_name_notify(update);
+    }
+}
+
+/// Lock screen wrapper around the app's content.
+pub struct Lock {
+    stack: gtk::Stack,
+    config: AppLock,
+    /// Container the toolbar, splash screen and webview go into.
+    pub content: gtk::Box,
+}
+
+impl Lock {
+    /// Insert a stack into `vbox` holding the lock page and the content box, starting locked.
+    pub fn new(
+        vbox: &gtk::Box,
+        title: &str,
+        icon_path: &str,
+        config: AppLock,
+        covered: &Covered,
+    ) -> Self {
+        let page = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        page.set_valign(gtk::Align::Center);
+        page.set_halign(gtk::Align::Center);
+        page.set_vexpand(true);
+
+        if let Ok(pixbuf) = gtk::gdk_pixbuf::Pixbuf::from_file_at_scale(icon_path, 96, 96, true) {
+            page.pack_start(&gtk::Image::from_pixbuf(Some(&pixbuf)), false, false, 0);
+        }
+        page.pack_start(&gtk::Label::new(Some(title)), false, false, 0);
+
+        let error = gtk::Label::new(None);
+        let unlock = gtk::Button::with_label(&fl!("app-lock-unlock"));
+        unlock.style_context().add_class("suggested-action");
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let stack = gtk::Stack::new();
+        stack.set_transition_type(gtk::StackTransitionType::Crossfade);
+        stack.set_transition_duration(200);
+
+        match config.method {
+            LockMethod::Pin => {
+                let entry = gtk::Entry::new();
+                entry.set_visibility(false);
+                entry.set_input_purpose(gtk::InputPurpose::Pin);
+                entry.set_placeholder_text(Some(&fl!("app-lock-pin")));
+                page.pack_start(&entry, false, false, 0);
+
+                let check = {
+                    let (stack, config, error, entry, unlock) = (
+                        stack.clone(),
+                        config.clone(),
+                        error.clone(),
+                        entry.clone(),
+                        unlock.clone(),
+                    );
+                    move || {
+                        let pin = entry.text().to_string();
+                        entry.set_text("");
+                        entry.set_sensitive(false);
+                        unlock.set_sensitive(false);
+                        // Hashing takes a moment, the answer comes back to the main loop
+                        let config = config.clone();
+                        let (stack, error, entry, unlock) =
+                            (stack.clone(), error.clone(), entry.clone(), unlock.clone());
+                        glib::MainContext::default().spawn_local(async move {
+                            let verified = gio::spawn_blocking(move || config.verify_pin(&pin))
+                                .await
+                                .unwrap_or(false);
+                            if verified {
+                                error.set_text("");
+                                stack.set_visible_child_name("content");
+                            } else {
+                                error.set_text(&fl!("app-lock-wrong-pin"));
+                            }
+                            entry.set_sensitive(true);
+                            unlock.set_sensitive(true);
+                            entry.grab_focus();
+                        });
+                    }
+                };
+                let on_activate = check.clone();
+                entry.connect_activate(move |_| on_activate());
+                unlock.connect_clicked(move |_| check());
+            }
+            LockMethod::System => {
+                let (stack, error) = (stack.clone(), error.clone());
+                unlock.connect_clicked(move |button| {
+                    button.set_sensitive(false);
+                    // polkit runs on its own runtime; the answer comes back to the main loop
+                    let (tx, rx) = oneshot::channel();
+                    std::thread::spawn(move || {
+                        let authorized = match tokio::runtime::Builder::new_current_thread()
+                            .enable_all()
+                            .build()
+                        {
+                            Ok(runtime) => runtime.block_on(authorize()),
+                            Err(e) => Err(zbus::Error::Failure(e.to_string())),
+                        };
+                        let _ = tx.send(authorized);
+                    });
+
+                    let (stack, error, button) = (stack.clone(), error.clone(), button.clone());
+                    glib::MainContext::default().spawn_local(async move {
+                        match rx.await {
+                            Ok(Ok(true)) => {
+                                error.set_text("");
+                                stack.set_visible_child_name("content");
+                            }
+                            Ok(Ok(false)) => error.set_text(&fl!("app-lock-denied")),
+                            Ok(Err(e)) => {
+                                eprintln!("polkit authentication failed: {e}");
+                                error.set_text(&fl!("app-lock-unavailable"));
+                            }
+                            Err(_) => {}
+                        }
+                        button.set_sensitive(true);
+                    });
+                });
+            }
+        }
+        page.pack_start(&unlock, false, false, 0);
+        page.pack_start(&error, false, false, 0);
+
+        stack.add_named(&page, "lock");
+        stack.add_named(&content, "content");
+        vbox.pack_start(&stack, true, true, 0);
+        stack.show_all();
+        stack.set_visible_child_name("lock");
+        covered.follow(&stack, "lock", LOCK_SCREEN);
+
+        Self {
+            stack,
+            config,
+            content,
+        }
+    }
+
+    /// Lock again once `widget` (the webview) has seen no input for the idle time.
+    pub fn lock_when_idle(&self, widget: &impl IsA<gtk::Widget>) {
+        if self.config.idle_minutes == 0 {
+            return;
+        }
+        let idle = Duration::from_secs(u64::from(self.config.idle_minutes) * 60);
+        let last_activity = Rc::new(Cell::new(Instant::now()));
+
+        let activity = last_activity.clone();
+        widget.connect_event(move |_, event| {
+            use gtk::gdk::EventType;
+            if matches!(
+                event.event_type(),
+                EventType::KeyPress
+                    | EventType::ButtonPress
+                    | EventType::MotionNotify
+                    | EventType::Scroll
+                    | EventType::TouchBegin
+            ) {
+                activity.set(Instant::now());
+            }
+            glib::Propagation::Proceed
+        });
+
+        let stack = self.stack.clone();
+        glib::timeout_add_seconds_local(IDLE_CHECK_SECS, move || {
+            if last_activity.get().elapsed() >= idle {
+                stack.set_visible_child_name("lock");
+                last_activity.set(Instant::now());
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+}
@@ -0,0 +1,47 @@
+//! Camera, microphone and screen capture. WebKit captures screens through the
+//! ScreenCast portal once a display capture request is allowed here.
+
+use gtk::glib::prelude::*;
+use webkit2gtk::{
+    PermissionRequestExt, SettingsExt, UserMediaPermissionRequest, WebViewExt,
+    user_media_permission_is_for_audio_device, user_media_permission_is_for_display_device,
+    user_media_permission_is_for_video_device,
+};
+
+/// Rejects `getDisplayMedia` up front when screen sharing isn't allowed, instead of
+/// leaving the call to fail silently.
+pub const BLOCK_DISPLAY_MEDIA: &str = r#"(function(){
+    if (navigator.mediaDevices) {
+        navigator.mediaDevices.getDisplayMedia = function() {
+            return Promise.reject(new DOMException('Screen sharing denied by app settings', 'NotAllowedError'));
+        };
+    }
+})()"#;
+
+/// Enable media streams and answer capture requests from the app's permissions.
+pub fn install(webview: &webkit2gtk::WebView, perms: &webapps::browser::PermissionPolicy) {
+    if let Some(settings) = WebViewExt::settings(webview) {
+        settings.set_enable_media_stream(true);
+    }
+
+    let perms = perms.clone();
+    webview.connect_permission_request(move |_, request| {
+        let Some(media) = request.downcast_ref::<UserMediaPermissionRequest>() else {
+            return false;
+        };
+
+        let allowed = if user_media_permission_is_for_display_device(media) {
+            perms.allow_screen_sharing
+        } else {
+            let audio = user_media_permission_is_for_audio_device(media);
+            let video = user_media_permission_is_for_video_device(media);
+            (!audio || perms.allow_microphone) && (!video || perms.allow_camera)
+        };
+        if allowed {
+            request.allow();
+        } else {
+            request.deny();
+        }
+        true
+    });
+}
@@ -0,0 +1,87 @@
+//! Mobile emulation beyond the user agent: touch support and a device-width viewport,
+//! so sites that feature-detect touch serve their mobile UI.
+
+/// Reports touch support, turns mouse input into single-finger touch events (where
+/// WebKit was built with touch events) and adds a mobile viewport when the page has none.
+pub const TOUCH_SCRIPT: &str = r#"(function(){
+    try {
+        Object.defineProperty(Navigator.prototype, 'maxTouchPoints', { get: function() { return 5; } });
+    } catch (e) {}
+    if (!('ontouchstart' in window)) window.ontouchstart = null;
+    if (!('ontouchstart' in document.documentElement)) document.documentElement.ontouchstart = null;
+
+    const coarse = ['(pointer: coarse)', '(any-pointer: coarse)', '(hover: none)', '(any-hover: none)'];
+    const matchMedia = window.matchMedia.bind(window);
+    window.matchMedia = function(query) {
+        const q = String(query).replace(/\s+/g, ' ').trim();
+        if (coarse.indexOf(q) !== -1) {
+            const result = matchMedia('all');
+            Object.defineProperty(result, 'media', { value: q });
+            return result;
+        }
+        return matchMedia(query);
+    };
+
+    let active = null;
+    function dispatch(type, e) {
+        if (typeof Touch === 'undefined' || typeof TouchEvent === 'undefined') return;
+        const touch = new Touch({
+            identifier: 1,
+            target: active,
+            clientX: e.clientX, clientY: e.clientY,
+            screenX: e.screenX, screenY: e.screenY,
+            pageX: e.pageX, pageY: e.pageY,
+            radiusX: 1, radiusY: 1, force: 1
+        });
+        const ending = type === 'touchend';
+        return active.dispatchEvent(new TouchEvent(type, {
+            bubbles: true, cancelable: true, composed: true,
+            touches: ending ? [] : [touch],
+            targetTouches: ending ? [] : [touch],
+            changedTouches: [touch]
+        }));
+    }
+    window.addEventListener('mousedown', function(e) {
+        if (e.button !== 0) return;
+        active = e.target;
+        dispatch('touchstart', e);
+    }, true);
+    window.addEventListener('mousemove', function(e) {
+        if (active) dispatch('touchmove', e);
+    }, true);
+    window.addEventListener('mouseup', function(e) {
+        if (!active) return;
+        dispatch('touchend', e);
+        active = null;
+    }, true);
+
+    function ensureViewport() {
+        if (!document.head || document.querySelector('meta[name="viewport"]')) return;
+        const meta = document.createElement('meta');
+        meta.name = 'viewport';
+        meta.content = 'width=device-width, initial-scale=1';
+        document.head.appendChild(meta);
+    }
+    if (document.readyState === 'loading') {
+        document.addEventListener('DOMContentLoaded', ensureViewport);
+    } else {
+        ensureViewport();
+    }
+})()"#;
+
+/// Screen metrics of an emulated device: `devicePixelRatio` (for `srcset` and canvas
+/// sizing) and a screen matching the device size.
+pub fn viewport_script(width: f64, height: f64, device_pixel_ratio: f64) -> String {
+    format!(
+        r#"(function(){{
+    const define = function(target, name, value) {{
+        try {{ Object.defineProperty(target, name, {{ get: function() {{ return value; }} }}); }} catch (e) {{}}
+    }};
+    define(window, 'devicePixelRatio', {device_pixel_ratio});
+    define(Screen.prototype, 'width', {width});
+    define(Screen.prototype, 'height', {height});
+    define(Screen.prototype, 'availWidth', {width});
+    define(Screen.prototype, 'availHeight', {height});
+}})()"#
+    )
+}
@@ -0,0 +1,67 @@
+//! Navigation policy: URL safety checks and the website policies applied to each load.
+
+use gtk::glib::prelude::*;
+use webapps::browser::{AutoplayPolicy, NavigationRules};
+use webkit2gtk::{
+    NavigationPolicyDecision, PolicyDecisionExt, PolicyDecisionType, WebViewExt, WebsitePolicies,
+};
+
+fn website_policies(autoplay: AutoplayPolicy) -> WebsitePolicies {
+    let autoplay = match autoplay {
+        AutoplayPolicy::Allow => webkit2gtk::AutoplayPolicy::Allow,
+        AutoplayPolicy::AllowMuted => webkit2gtk::AutoplayPolicy::AllowWithoutSound,
+        AutoplayPolicy::Block => webkit2gtk::AutoplayPolicy::Deny,
+    };
+    WebsitePolicies::builder().autoplay(autoplay).build()
+}
+
+/// Decide every navigation: refuse unsafe URLs and load the rest with the app's
+/// website policies (which WebKit only accepts per navigation). `allow_files` permits
+/// `file://` navigations for apps wrapping local pages; `rules` are the app's own
+/// allow/deny host patterns. With `audit_app_id`, every decision goes to that app's
+/// navigation log.
+pub fn install(
+    webview: &webkit2gtk::WebView,
+    autoplay: AutoplayPolicy,
+    allow_files: bool,
+    rules: NavigationRules,
+    audit_app_id: Option<String>,
+) {
+    let policies = website_policies(autoplay);
+
+    webview.connect_decide_policy(move |_, decision, decision_type| {
+        if decision_type != PolicyDecisionType::NavigationAction {
+            return false;
+        }
+        let Some(navigation) = decision.downcast_ref::<NavigationPolicyDecision>() else {
+            return false;
+        };
+
+        let uri = navigation
+            .navigation_action()
+            .and_then(|mut action| action.request())
+            .and_then(|request| request.uri());
+        let allowed = match &uri {
+            Some(uri) if !crate::is_url_safe(uri, allow_files) => {
+                eprintln!("Blocked navigation to unsafe URL: {uri}");
+                decision.ignore();
+                false
+            }
+            Some(uri) if !rules.allows(uri) => {
+                eprintln!("Navigation to {uri} blocked by the app's rules");
+                decision.ignore();
+                false
+            }
+            _ => {
+                decision.use_with_policies(&policies);
+                true
+            }
+        };
+        if let (Some(app_id), Some(uri)) = (&audit_app_id, &uri) {
+            if let Err(e) = webapps::audit::append(app_id, uri, allowed) {
+                eprintln!("Failed to write navigation log: {e}");
+            }
+        }
+        true
+    });
+}
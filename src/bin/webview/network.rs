@@ -0,0 +1,47 @@
+//! Network isolation: every request is sent to a proxy that doesn't exist, so nothing can
+//! leave the machine, with loopback addresses exempt in localhost-only mode.
+
+use webapps::browser::NetworkIsolation;
+use webkit2gtk::{
+    NetworkProxyMode, NetworkProxySettings, SettingsExt, WebViewExt, WebsiteDataManagerExt,
+};
+use wry::{ProxyConfig, ProxyEndpoint};
+
+/// Discard port on loopback: connections fail instead of reaching anything.
+const NOWHERE_HOST: &str = "127.0.0.1";
+const NOWHERE_PORT: &str = "9";
+
+/// Hosts reached directly in localhost-only mode.
+const LOOPBACK: &[&str] = &["localhost", "*.localhost", "127.0.0.0/8", "::1"];
+
+/// Proxy for the web context, set before the first page starts loading.
+pub fn proxy_config(isolation: NetworkIsolation) -> Option<ProxyConfig> {
+    (isolation != NetworkIsolation::Off).then(|| {
+        ProxyConfig::Http(ProxyEndpoint {
+            host: NOWHERE_HOST.to_string(),
+            port: NOWHERE_PORT.to_string(),
+        })
+    })
+}
+
+/// Exempt loopback addresses from the proxy in localhost-only mode, and turn off WebRTC,
+/// which doesn't go through the proxy.
+pub fn install(webview: &webkit2gtk::WebView, isolation: NetworkIsolation) {
+    if isolation == NetworkIsolation::Off {
+        return;
+    }
+    if let Some(settings) = WebViewExt::settings(webview) {
+        settings.set_enable_webrtc(false);
+    }
+    if isolation == NetworkIsolation::LocalhostOnly {
+        if let Some(manager) = webview.website_data_manager() {
+            let proxy = format!("http://{NOWHERE_HOST}:{NOWHERE_PORT}");
+            let mut settings = NetworkProxySettings::new(Some(&proxy), LOOPBACK);
+            manager.set_network_proxy_settings(NetworkProxyMode::Custom, Some(&mut settings));
+        }
+        // The first load may have started before the exemption applied
+        if let Some(uri) = webview.uri() {
+            webview.load_uri(&uri);
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Web notifications forwarded as desktop notifications, with icons, tag-based
+//! replacement and action buttons. Clicks are delivered back to the page.
+
+use gtk::{gio, glib};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+use tao::event_loop::EventLoopProxy;
+use webapps::browser::{QuietHours, QuietHoursAction};
+
+use crate::{UserEvent, lock::Covered};
+
+/// Icons larger than this are not shown.
+const MAX_ICON_BYTES: u64 = 1024 * 1024;
+
+/// Replaces `window.Notification` with a shim that posts to the IPC handler.
+pub const SCRIPT: &str = r#"(function(){
+    var shown = new Map();
+    var nextId = 1;
+    function absolute(url) {
+        try { return url ? new URL(url, location.href).href : ''; } catch (e) { return ''; }
+    }
+    window.Notification = class extends EventTarget {
+        constructor(title, options) {
+            super();
+            options = options || {};
+            var id = nextId++;
+            var data = options.data;
+            var actions = Array.isArray(options.actions) ? options.actions : [];
+            this.title = title || '';
+            this.body = options.body || '';
+            this.tag = options.tag || '';
+            this.data = data;
+            shown.set(id, this);
+            window.ipc.postMessage(JSON.stringify({
+                type: 'notification',
+                id: id,
+                title: this.title,
+                body: this.body,
+                url: data && typeof data.url === 'string' ? absolute(data.url) : '',
+                icon: absolute(options.icon),
+                tag: this.tag,
+                actions: actions
+                    .filter(function(a) { return a && a.action && a.title; })
+                    .map(function(a) { return { action: String(a.action), title: String(a.title) }; })
+            }));
+        }
+        close() {}
+        static get permission() { return 'granted'; }
+        static get maxActions() { return 2; }
+        static requestPermission() { return Promise.resolve('granted'); }
+    };
+    // Called natively when the desktop notification or one of its buttons is clicked
+    window.__webappsNotificationClicked = function(id, action) {
+        var n = shown.get(id);
+        if (!n) return;
+        shown.delete(id);
+        var event = new Event('click', { cancelable: true });
+        event.action = action || '';
+        if (typeof n.onclick === 'function') n.onclick(event);
+        n.dispatchEvent(event);
+    };
+})()"#;
+
+#[derive(Debug, Deserialize)]
+pub struct WebNotification {
+    #[serde(default)]
+    id: u64,
+    #[serde(default = "default_title")]
+    title: String,
+    #[serde(default)]
+    body: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    icon: String,
+    #[serde(default)]
+    tag: String,
+    #[serde(default)]
+    actions: Vec<NotificationAction>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NotificationAction {
+    action: String,
+    title: String,
+}
+
+fn default_title() -> String {
+    "Notification".to_string()
+}
+
+/// Shows forwarded notifications and reports clicks to the event loop.
+#[derive(Clone)]
+pub struct Notifier {
+    app_title: String,
+    proxy: EventLoopProxy<UserEvent>,
+    quiet_hours: Option<QuietHours>,
+    /// Nothing is shown while the app is locked or out of time
+    covered: Covered,
+    /// Desktop notification ids of the last notification shown per tag
+    tags: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl Notifier {
+    pub fn new(
+        app_title: String,
+        proxy: EventLoopProxy<UserEvent>,
+        quiet_hours: Option<QuietHours>,
+        covered: Covered,
+    ) -> Self {
+        Self {
+            app_title,
+            proxy,
+            quiet_hours,
+            covered,
+            tags: Arc::default(),
+        }
+    }
+
+    /// The quiet hours action to apply right now, if quiet hours are in effect.
+    fn quiet_action(&self) -> Option<QuietHoursAction> {
+        let quiet = self.quiet_hours.as_ref()?;
+        let minute = glib::DateTime::now_local()
+            .map(|now| (now.hour() * 60 + now.minute()) as u16)
+            .ok()?;
+        (quiet.covers(minute) || (quiet.while_locked && screen_locked())).then_some(quiet.action)
+    }
+
+    /// Show `notification` from a background thread, since fetching the icon and
+    /// waiting for a click both block.
+    pub fn show(&self, notification: WebNotification) {
+        if self.covered.get() {
+            return;
+        }
+        let notifier = self.clone();
+        std::thread::spawn(move || notifier.show_blocking(notification));
+    }
+
+    fn show_blocking(&self, notification: WebNotification) {
+        let quiet_action = self.quiet_action();
+        if quiet_action == Some(QuietHoursAction::Suppress) {
+            return;
+        }
+
+        let mut desktop = notify_rust::Notification::new();
+        if quiet_action == Some(QuietHoursAction::LowUrgency) {
+            desktop.urgency(notify_rust::Urgency::Low);
+        }
+        desktop
+            .summary(&format!("{} — {}", self.app_title, notification.title))
+            .body(&notification.body)
+            .appname("dev.heppen.webapps")
+            .action("default", &webapps::fl!("notification-open"));
+        for action in notification.actions.iter().take(2) {
+            desktop.action(&action.action, &action.title);
+        }
+        if let Some(icon) = cached_icon(&notification.icon) {
+            desktop.icon(&icon.to_string_lossy());
+        }
+
+        // A notification with the same tag replaces the previous one
+        let tag = (!notification.tag.is_empty()).then_some(notification.tag);
+        if let Some(tag) = &tag {
+            if let Some(previous) = self.tags.lock().ok().and_then(|tags| tags.get(tag).copied()) {
+                desktop.id(previous);
+            }
+        }
+
+        let handle = match desktop.show() {
+            Ok(handle) => handle,
+            Err(e) => {
+                eprintln!("Failed to show notification: {e}");
+                return;
+            }
+        };
+        if let (Some(tag), Ok(mut tags)) = (tag, self.tags.lock()) {
+            tags.insert(tag, handle.id());
+        }
+
+        let id = notification.id;
+        let url = (!notification.url.is_empty()).then_some(notification.url);
+        let proxy = self.proxy.clone();
+        handle.wait_for_action(move |action| {
+            let event = match action {
+                "__closed" => return,
+                "default" => UserEvent::NotificationClicked {
+                    id,
+                    action: None,
+                    url,
+                },
+                action => UserEvent::NotificationClicked {
+                    id,
+                    action: Some(action.to_string()),
+                    url: None,
+                },
+            };
+            let _ = proxy.send_event(event);
+        });
+    }
+}
+
+/// Whether the session's screen saver (lock screen) is active.
+fn screen_locked() -> bool {
+    let Ok(bus) = gio::bus_get_sync(gio::BusType::Session, None::<&gio::Cancellable>) else {
+        return false;
+    };
+    bus.call_sync(
+        Some("org.freedesktop.ScreenSaver"),
+        "/org/freedesktop/ScreenSaver",
+        "org.freedesktop.ScreenSaver",
+        "GetActive",
+        None,
+        None,
+        gio::DBusCallFlags::NONE,
+        500,
+        None::<&gio::Cancellable>,
+    )
+    .ok()
+    .and_then(|reply| reply.get::<(bool,)>())
+    .is_some_and(|(active,)| active)
+}
+
+/// Fetch a notification icon into the cache, reusing an earlier download.
+fn cached_icon(url: &str) -> Option<PathBuf> {
+    let scheme = url.split_once(':')?.0;
+    if !matches!(scheme, "http" | "https" | "data") {
+        return None;
+    }
+
+    let mut hasher = DefaultHasher::new();
+    url.hash(&mut hasher);
+    let dir = dirs::cache_dir()?
+        .join(webapps::APP_ID)
+        .join("notification-icons");
+    let path = dir.join(format!("{:016x}", hasher.finish()));
+    if path.exists() {
+        return Some(path);
+    }
+    std::fs::create_dir_all(&dir).ok()?;
+
+    if scheme == "data" {
+        use base64::Engine;
+        let (meta, payload) = url.split_once(',')?;
+        if !meta.ends_with(";base64") {
+            return None;
+        }
+        let bytes = base64::engine::general_purpose::STANDARD.decode(payload).ok()?;
+        if bytes.len() as u64 > MAX_ICON_BYTES {
+            return None;
+        }
+        std::fs::write(&path, bytes).ok()?;
+    } else {
+        let status = std::process::Command::new("wget")
+            .arg("-q")
+            .arg("--timeout=10")
+            .arg("-O")
+            .arg(&path)
+            .arg(url)
+            .status()
+            .ok()?;
+        let size = std::fs::metadata(&path).map_or(0, |m| m.len());
+        if !status.success() || size == 0 || size > MAX_ICON_BYTES {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+    }
+
+    Some(path)
+}
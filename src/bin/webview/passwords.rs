@@ -0,0 +1,235 @@
+//! Login credential saving and autofill backed by the freedesktop Secret Service.
+
+use gtk::prelude::*;
+use serde::Serialize;
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc};
+use tao::event_loop::EventLoopProxy;
+
+use crate::UserEvent;
+
+/// Schema attribute used to tag items created by web apps.
+const SCHEMA: &str = "dev.heppen.webapps.Password";
+
+/// Detects login forms: asks for stored credentials once a password field shows up
+/// and reports submitted credentials so the user can be offered to save them.
+pub const SCRIPT: &str = r#"(function(){
+    function fields(root) {
+        const password = root.querySelector('input[type="password"]');
+        if (!password) return null;
+        const scope = password.form || document;
+        const candidates = Array.from(scope.querySelectorAll(
+            'input[type="email"], input[type="text"], input[autocomplete="username"], input:not([type])'
+        )).filter(i => i.compareDocumentPosition(password) & Node.DOCUMENT_POSITION_FOLLOWING);
+        return { username: candidates.length ? candidates[candidates.length - 1] : null, password: password };
+    }
+    function setValue(input, value) {
+        input.value = value;
+        input.dispatchEvent(new Event('input', { bubbles: true }));
+        input.dispatchEvent(new Event('change', { bubbles: true }));
+    }
+    window.__webappsCredentials = {
+        fill(origin, credentials) {
+            if (location.origin !== origin || !credentials.length) return;
+            const f = fields(document);
+            if (!f) return;
+            const typed = f.username ? f.username.value : '';
+            const match = credentials.find(c => c.username === typed) || credentials[0];
+            if (f.username && !typed) setValue(f.username, match.username);
+            if (!f.password.value) setValue(f.password, match.password);
+        }
+    };
+    const observer = new MutationObserver(query);
+    function query() {
+        if (!document.querySelector('input[type="password"]')) return;
+        observer.disconnect();
+        window.ipc.postMessage(JSON.stringify({type:'credentials', action:'query'}));
+    }
+    observer.observe(document, { childList: true, subtree: true });
+    document.addEventListener('DOMContentLoaded', query);
+    document.addEventListener('submit', function(e) {
+        const f = fields(e.target);
+        if (!f || !f.password.value) return;
+        window.ipc.postMessage(JSON.stringify({
+            type: 'credentials',
+            action: 'save',
+            username: f.username ? f.username.value : '',
+            password: f.password.value
+        }));
+    }, true);
+})()"#;
+
+/// A login for one origin.
+#[derive(Clone, Serialize)]
+pub struct Credential {
+    #[serde(skip)]
+    pub origin: String,
+    pub username: String,
+    pub password: String,
+}
+
+// Keep passwords out of debug output
+impl std::fmt::Debug for Credential {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Credential")
+            .field("origin", &self.origin)
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Run a keyring operation on its own thread; the Secret Service calls are async
+/// and may block on an unlock prompt.
+fn spawn<F, Fut>(task: F)
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: Future<Output = Result<(), oo7::Error>>,
+{
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start keyring runtime: {e}");
+                return;
+            }
+        };
+        if let Err(e) = runtime.block_on(task()) {
+            eprintln!("Secret Service request failed: {e}");
+        }
+    });
+}
+
+async fn search(app_id: &str, origin: &str) -> Result<Vec<Credential>, oo7::Error> {
+    let keyring = oo7::Keyring::new().await?;
+    keyring.unlock().await?;
+    let attributes = HashMap::from([("xdg:schema", SCHEMA), ("app-id", app_id), ("origin", origin)]);
+
+    let mut credentials = Vec::new();
+    for item in keyring.search_items(&attributes).await? {
+        let username = item
+            .attributes()
+            .await?
+            .get("username")
+            .cloned()
+            .unwrap_or_default();
+        let secret = item.secret().await?;
+        credentials.push(Credential {
+            origin: origin.to_string(),
+            username,
+            password: String::from_utf8_lossy(&secret).into_owned(),
+        });
+    }
+    Ok(credentials)
+}
+
+/// Credential lookups and the "save password?" bar shown after a login form submit.
+pub struct Passwords {
+    app_id: String,
+    proxy: EventLoopProxy<UserEvent>,
+    bar: gtk::InfoBar,
+    label: gtk::Label,
+    pending: Rc<RefCell<Option<Credential>>>,
+}
+
+impl Passwords {
+    /// Create the (hidden) save prompt at the top of `vbox`.
+    pub fn new(
+        vbox: &gtk::Box,
+        app_id: &str,
+        app_title: &str,
+        proxy: EventLoopProxy<UserEvent>,
+    ) -> Self {
+        let bar = gtk::InfoBar::new();
+        bar.set_message_type(gtk::MessageType::Question);
+        bar.set_no_show_all(true);
+        let label = gtk::Label::new(None);
+        label.set_line_wrap(true);
+        label.show();
+        bar.content_area().add(&label);
+        bar.add_button(&webapps::fl!("password-save"), gtk::ResponseType::Accept);
+        bar.add_button(&webapps::fl!("password-not-now"), gtk::ResponseType::Reject);
+        vbox.pack_start(&bar, false, false, 0);
+        vbox.reorder_child(&bar, 0);
+
+        let pending: Rc<RefCell<Option<Credential>>> = Rc::new(RefCell::new(None));
+        {
+            let pending = pending.clone();
+            let app_id = app_id.to_string();
+            let app_title = app_title.to_string();
+            bar.connect_response(move |bar, response| {
+                bar.set_visible(false);
+                let credential = pending.borrow_mut().take();
+                if let (gtk::ResponseType::Accept, Some(credential)) = (response, credential) {
+                    let app_id = app_id.clone();
+                    let label = format!(
+                        "{app_title}: {} @ {}",
+                        credential.username, credential.origin
+                    );
+                    spawn(move || async move {
+                        let keyring = oo7::Keyring::new().await?;
+                        keyring.unlock().await?;
+                        let attributes = HashMap::from([
+                            ("xdg:schema", SCHEMA),
+                            ("app-id", app_id.as_str()),
+                            ("origin", credential.origin.as_str()),
+                            ("username", credential.username.as_str()),
+                        ]);
+                        keyring
+                            .create_item(&label, &attributes, credential.password.as_str(), true)
+                            .await
+                    });
+                }
+            });
+        }
+
+        Self {
+            app_id: app_id.to_string(),
+            proxy,
+            bar,
+            label,
+            pending,
+        }
+    }
+
+    /// Look up stored logins for `origin` and hand them back to the page.
+    pub fn query(&self, origin: String) {
+        let app_id = self.app_id.clone();
+        let proxy = self.proxy.clone();
+        spawn(move || async move {
+            let credentials = search(&app_id, &origin).await?;
+            if !credentials.is_empty() {
+                let _ = proxy.send_event(UserEvent::CredentialsFound(origin, credentials));
+            }
+            Ok(())
+        });
+    }
+
+    /// A login form was submitted; offer to save it unless it's already stored.
+    pub fn submitted(&self, credential: Credential) {
+        let app_id = self.app_id.clone();
+        let proxy = self.proxy.clone();
+        spawn(move || async move {
+            let stored = search(&app_id, &credential.origin).await?;
+            let known = stored.iter().any(|c| {
+                c.username == credential.username && c.password == credential.password
+            });
+            if !known {
+                let _ = proxy.send_event(UserEvent::CredentialsOffer(credential));
+            }
+            Ok(())
+        });
+    }
+
+    /// Show the save prompt for `credential`.
+    pub fn offer(&self, credential: Credential) {
+        self.label.set_text(&webapps::fl!(
+            "password-save-prompt",
+            username = credential.username.as_str(),
+            origin = credential.origin.as_str()
+        ));
+        self.pending.replace(Some(credential));
+        self.bar.set_visible(true);
+    }
+}
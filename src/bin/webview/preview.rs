@@ -0,0 +1,45 @@
+//! Preview windows opened from the editor. Injected scripts can't be swapped in a
+//! running webview, so the preview starts over to show changes.
+
+use std::{path::PathBuf, time::Duration};
+
+use tao::event_loop::EventLoopProxy;
+
+use crate::UserEvent;
+
+/// How often the preview file is checked for changes.
+const POLL: Duration = Duration::from_millis(700);
+
+/// Send [`UserEvent::PreviewChanged`] once the preview at `path` changed and then stayed
+/// the same for a moment, so typing in the editor doesn't restart it on every key.
+pub fn watch(path: PathBuf, proxy: EventLoopProxy<UserEvent>) {
+    std::thread::spawn(move || {
+        let shown = std::fs::read(&path).ok();
+        let mut last = shown.clone();
+        loop {
+            std::thread::sleep(POLL);
+            let current = std::fs::read(&path).ok();
+            if current != shown && current == last {
+                let _ = proxy.send_event(UserEvent::PreviewChanged);
+                return;
+            }
+            last = current;
+        }
+    });
+}
+
+/// Show the changed preview at `path` in a new process, unless the editor closed it.
+pub fn restart(path: &std::path::Path) {
+    if !path.exists() {
+        return;
+    }
+    let spawned = std::env::current_exe().and_then(|exe| {
+        std::process::Command::new(exe)
+            .arg("--preview")
+            .arg(path)
+            .spawn()
+    });
+    if let Err(e) = spawned {
+        eprintln!("Failed to restart the preview: {e}");
+    }
+}
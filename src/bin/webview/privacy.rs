@@ -0,0 +1,64 @@
+//! Do Not Track / Global Privacy Control signals and referrer policy enforcement.
+//!
+//! Both are applied to every request by the web process extension in `web-extension/`;
+//! this side only tells WebKit where to find it and what to enforce.
+
+use std::path::PathBuf;
+
+use gtk::glib::ToVariant as _;
+use webapps::browser::ReferrerPolicy;
+use webkit2gtk::{WebContextExt, WebViewExt};
+
+/// The extension's library, as built by cargo and installed by `just install`.
+const EXTENSION_LIBRARY: &str = "libwebapps_web_extension.so";
+
+/// Expose the privacy signals to scripts, matching the headers.
+pub const DNT_SCRIPT: &str = r#"(function(){
+    Object.defineProperty(Navigator.prototype, 'doNotTrack', { get: () => '1', configurable: true });
+    Object.defineProperty(Navigator.prototype, 'globalPrivacyControl', { get: () => true, configurable: true });
+})()"#;
+
+/// Referrer policy the extension enforces for `policy`.
+fn policy_value(policy: ReferrerPolicy) -> Option<&'static str> {
+    match policy {
+        ReferrerPolicy::Default => None,
+        ReferrerPolicy::TrimCrossOrigin => Some("strict-origin-when-cross-origin"),
+        ReferrerPolicy::SameOriginOnly => Some("same-origin"),
+        ReferrerPolicy::NoReferrer => Some("no-referrer"),
+    }
+}
+
+/// Directory holding the extension: next to the webview in a cargo build,
+/// `lib/dev.heppen.webapps/web-extensions` under the install prefix otherwise.
+pub fn extension_dir() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let bin = exe.parent()?;
+    let installed = bin
+        .parent()?
+        .join("lib")
+        .join(webapps::APP_ID)
+        .join("web-extensions");
+    [bin.to_path_buf(), installed]
+        .into_iter()
+        .find(|dir| dir.join(EXTENSION_LIBRARY).is_file())
+}
+
+/// Load the extension into the web processes of `webview` when the app sends the privacy
+/// signals or restricts referrers. Only takes effect before the first page is loaded.
+pub fn install(webview: &webkit2gtk::WebView, do_not_track: bool, policy: ReferrerPolicy) {
+    let policy = policy_value(policy);
+    if !do_not_track && policy.is_none() {
+        return;
+    }
+    let Some(context) = webview.context() else {
+        return;
+    };
+    let Some(dir) = extension_dir() else {
+        eprintln!("{EXTENSION_LIBRARY} not found, requests go out without privacy headers");
+        return;
+    };
+    context.set_web_extensions_directory(&dir.to_string_lossy());
+    context.set_web_extensions_initialization_user_data(
+        &(do_not_track, policy.unwrap_or_default()).to_variant(),
+    );
+}
@@ -0,0 +1,334 @@
+//! Per-app name resolution: WebKit is pointed at a small proxy on loopback that decides
+//! where each host connects. Host overrides act like a private `/etc/hosts`; other names
+//! go through the app's DNS-over-HTTPS endpoint (RFC 8484) when one is set, so lookups
+//! never reach the system resolver, and through the system resolver otherwise. Only the
+//! app's own user may connect to the proxy.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use base64::Engine as _;
+use gtk::gio;
+use soup::prelude::*;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+use webapps::browser::HostOverride;
+use wry::{ProxyConfig, ProxyEndpoint};
+
+/// Longest request head the proxy accepts.
+const MAX_HEAD: usize = 64 * 1024;
+
+/// Answers are cached for at most this long, whatever their TTL.
+const MAX_TTL: Duration = Duration::from_secs(300);
+
+const TYPE_A: u16 = 1;
+const TYPE_AAAA: u16 = 28;
+
+/// How hosts are turned into addresses.
+struct Resolver {
+    /// Fixed addresses by lowercase host name
+    overrides: HashMap<String, IpAddr>,
+    doh_endpoint: Option<Arc<str>>,
+    /// DoH answers by host name
+    cache: Mutex<HashMap<String, (Vec<IpAddr>, Instant)>>,
+}
+
+/// Wire-format query for `host` (RFC 1035 §4.1), with ID 0 as RFC 8484 recommends.
+fn query(host: &str, qtype: u16) -> Vec<u8> {
+    // Header: ID 0, recursion desired, one question
+    let mut packet = vec![0, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0];
+    for label in host.trim_end_matches('.').split('.') {
+        packet.push(label.len().min(63) as u8);
+        packet.extend_from_slice(&label.as_bytes()[..label.len().min(63)]);
+    }
+    packet.push(0);
+    packet.extend_from_slice(&qtype.to_be_bytes());
+    packet.extend_from_slice(&1u16.to_be_bytes());
+    packet
+}
+
+/// Skip a possibly compressed name, returning the offset after it.
+fn skip_name(packet: &[u8], mut pos: usize) -> Option<usize> {
+    loop {
+        let len = *packet.get(pos)?;
+        if len & 0xc0 == 0xc0 {
+            return Some(pos + 2);
+        }
+        pos += 1;
+        if len == 0 {
+            return Some(pos);
+        }
+        pos += usize::from(len);
+    }
+}
+
+/// Addresses and the shortest TTL from the answer section of a response.
+fn parse_answers(packet: &[u8]) -> Option<(Vec<IpAddr>, u32)> {
+    let read_u16 =
+        |pos: usize| Some(u16::from_be_bytes([*packet.get(pos)?, *packet.get(pos + 1)?]));
+    let questions = read_u16(4)?;
+    let answers = read_u16(6)?;
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = skip_name(packet, pos)? + 4;
+    }
+    let mut addresses = Vec::new();
+    let mut ttl = u32::MAX;
+    for _ in 0..answers {
+        pos = skip_name(packet, pos)?;
+        let rtype = read_u16(pos)?;
+        let record_ttl = u32::from_be_bytes(packet.get(pos + 4..pos + 8)?.try_into().ok()?);
+        let len = usize::from(read_u16(pos + 8)?);
+        let data = packet.get(pos + 10..pos + 10 + len)?;
+        match (rtype, len) {
+            (TYPE_A, 4) => {
+                addresses.push(IpAddr::V4(Ipv4Addr::new(data[0], data[1], data[2], data[3])));
+                ttl = ttl.min(record_ttl);
+            }
+            (TYPE_AAAA, 16) => {
+                let octets: [u8; 16] = data.try_into().ok()?;
+                addresses.push(IpAddr::V6(Ipv6Addr::from(octets)));
+                ttl = ttl.min(record_ttl);
+            }
+            _ => {}
+        }
+        pos += 10 + len;
+    }
+    Some((addresses, ttl))
+}
+
+/// Look `host` up over HTTPS; blocks, so it runs on the blocking thread pool.
+fn resolve_blocking(endpoint: &str, host: &str) -> Result<(Vec<IpAddr>, u32), String> {
+    let session = soup::Session::new();
+    let mut addresses = Vec::new();
+    let mut ttl = u32::MAX;
+    for qtype in [TYPE_A, TYPE_AAAA] {
+        let dns = base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(query(host, qtype));
+        let separator = if endpoint.contains('?') { '&' } else { '?' };
+        let uri = format!("{endpoint}{separator}dns={dns}");
+        let message = soup::Message::new("GET", &uri).map_err(|e| e.to_string())?;
+        if let Some(headers) = message.request_headers() {
+            headers.append("Accept", "application/dns-message");
+        }
+        let body = session
+            .send_and_read(&message, None::<&gio::Cancellable>)
+            .map_err(|e| e.to_string())?;
+        if message.status() != soup::Status::Ok {
+            return Err(format!("resolver answered {:?}", message.status()));
+        }
+        let (found, found_ttl) = parse_answers(&body).ok_or("malformed DNS response")?;
+        addresses.extend(found);
+        ttl = ttl.min(found_ttl);
+    }
+    Ok((addresses, ttl))
+}
+
+async fn resolve(resolver: &Resolver, host: &str, port: u16) -> Option<Vec<IpAddr>> {
+    if let Ok(ip) = host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        return Some(vec![ip]);
+    }
+    let key = host.to_ascii_lowercase();
+    if let Some(ip) = resolver.overrides.get(&key) {
+        return Some(vec![*ip]);
+    }
+    if key == "localhost" {
+        return Some(vec![IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)]);
+    }
+    let Some(endpoint) = &resolver.doh_endpoint else {
+        let addresses = tokio::net::lookup_host((key.as_str(), port)).await.ok()?;
+        return Some(addresses.map(|address| address.ip()).collect());
+    };
+    if let Some((addresses, expires)) = resolver.cache.lock().ok()?.get(&key) {
+        if *expires > Instant::now() {
+            return Some(addresses.clone());
+        }
+    }
+
+    let (endpoint_owned, host_owned) = (endpoint.clone(), key.clone());
+    let result =
+        tokio::task::spawn_blocking(move || resolve_blocking(&endpoint_owned, &host_owned))
+            .await
+            .ok()?;
+    match result {
+        Ok((addresses, ttl)) if !addresses.is_empty() => {
+            let ttl = Duration::from_secs(u64::from(ttl)).min(MAX_TTL);
+            if let Ok(mut cache) = resolver.cache.lock() {
+                cache.insert(key, (addresses.clone(), Instant::now() + ttl));
+            }
+            Some(addresses)
+        }
+        Ok(_) => None,
+        Err(e) => {
+            eprintln!("DNS-over-HTTPS lookup of {host} failed: {e}");
+            None
+        }
+    }
+}
+
+/// Split `host:port`, with IPv6 literals in brackets.
+fn split_authority(authority: &str, default_port: u16) -> Option<(String, u16)> {
+    match authority.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => {
+            Some((host.to_string(), port.parse().ok()?))
+        }
+        _ => Some((authority.to_string(), default_port)),
+    }
+}
+
+async fn connect(resolver: &Resolver, host: &str, port: u16) -> Option<TcpStream> {
+    for ip in resolve(resolver, host, port).await? {
+        if let Ok(stream) = TcpStream::connect(SocketAddr::new(ip, port)).await {
+            return Some(stream);
+        }
+    }
+    None
+}
+
+/// Serve one proxy connection: `CONNECT` tunnels for HTTPS, absolute-form requests for
+/// plain HTTP.
+async fn serve(mut client: TcpStream, resolver: Arc<Resolver>) -> Option<()> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 4096];
+    let end = loop {
+        let read = client.read(&mut buf).await.ok()?;
+        if read == 0 || head.len() > MAX_HEAD {
+            return None;
+        }
+        head.extend_from_slice(&buf[..read]);
+        if let Some(end) = head.windows(4).position(|w| w == b"\r\n\r\n") {
+            break end + 4;
+        }
+    };
+    let text = String::from_utf8_lossy(&head[..end]).into_owned();
+    let mut lines = text.split("\r\n");
+    let mut request_line = lines.next()?.split(' ');
+    let (method, target, version) =
+        (request_line.next()?, request_line.next()?, request_line.next()?);
+
+    if method.eq_ignore_ascii_case("CONNECT") {
+        let (host, port) = split_authority(target, 443)?;
+        let Some(mut upstream) = connect(&resolver, &host, port).await else {
+            let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await;
+            return None;
+        };
+        client
+            .write_all(b"HTTP/1.1 200 Connection Established\r\n\r\n")
+            .await
+            .ok()?;
+        upstream.write_all(&head[end..]).await.ok()?;
+        tokio::io::copy_bidirectional(&mut client, &mut upstream).await.ok()?;
+        return Some(());
+    }
+
+    // Plain HTTP: forward in origin form, one request per connection
+    let url = url::Url::parse(target).ok()?;
+    let host = url.host_str()?.to_string();
+    let port = url.port_or_known_default()?;
+    let Some(mut upstream) = connect(&resolver, &host, port).await else {
+        let _ = client.write_all(b"HTTP/1.1 502 Bad Gateway\r\n\r\n").await;
+        return None;
+    };
+    let path = match url.query() {
+        Some(query) => format!("{}?{query}", url.path()),
+        None => url.path().to_string(),
+    };
+    let mut forwarded = format!("{method} {path} {version}\r\n");
+    for line in lines.filter(|line| !line.is_empty()) {
+        let name = line.split(':').next().unwrap_or_default().to_ascii_lowercase();
+        if name != "connection" && name != "proxy-connection" && name != "keep-alive" {
+            forwarded.push_str(line);
+            forwarded.push_str("\r\n");
+        }
+    }
+    forwarded.push_str("Connection: close\r\n\r\n");
+    upstream.write_all(forwarded.as_bytes()).await.ok()?;
+    upstream.write_all(&head[end..]).await.ok()?;
+    tokio::io::copy_bidirectional(&mut client, &mut upstream).await.ok()?;
+    Some(())
+}
+
+/// Whether the connection from `peer` to the proxy at `local` comes from a process of our
+/// own user, going by the kernel's socket table. Anyone on the machine can reach loopback,
+/// and the proxy must not relay for them.
+async fn own_connection(peer: SocketAddr, local: SocketAddr) -> bool {
+    let (SocketAddr::V4(peer), SocketAddr::V4(local)) = (peer, local) else {
+        return false;
+    };
+    // As the table prints them: the address in memory order, then the port
+    let entry = |address: SocketAddrV4| {
+        let ip = u32::from_ne_bytes(address.ip().octets());
+        format!("{ip:08X}:{:04X}", address.port())
+    };
+    let (peer, local) = (entry(peer), entry(local));
+    // SAFETY: getuid has no preconditions and always succeeds
+    let uid = unsafe { libc::getuid() }.to_string();
+    let Ok(table) = tokio::fs::read_to_string("/proc/net/tcp").await else {
+        return false;
+    };
+    // Columns: slot, local address, remote address, state, queues, timer, retransmits, uid
+    table.lines().skip(1).any(|line| {
+        let fields: Vec<_> = line.split_whitespace().collect();
+        fields.get(1) == Some(&peer.as_str())
+            && fields.get(2) == Some(&local.as_str())
+            && fields.get(7) == Some(&uid.as_str())
+    })
+}
+
+/// Start the resolving proxy and return the proxy WebKit should use.
+pub fn start(overrides: &[HostOverride], doh_endpoint: Option<&str>) -> Option<ProxyConfig> {
+    let listener = std::net::TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.set_nonblocking(true).map(|()| listener))
+        .inspect_err(|e| eprintln!("Failed to start resolving proxy: {e}"))
+        .ok()?;
+    let port = listener.local_addr().ok()?.port();
+    let resolver = Arc::new(Resolver {
+        overrides: overrides
+            .iter()
+            .filter_map(|o| Some((o.host.to_ascii_lowercase(), o.address.parse().ok()?)))
+            .collect(),
+        doh_endpoint: doh_endpoint.map(|endpoint| Arc::from(endpoint.trim())),
+        cache: Mutex::default(),
+    });
+
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start resolving proxy: {e}");
+                return;
+            }
+        };
+        runtime.block_on(async move {
+            let Ok(listener) = TcpListener::from_std(listener) else {
+                return;
+            };
+            while let Ok((client, peer)) = listener.accept().await {
+                let Ok(local) = client.local_addr() else {
+                    continue;
+                };
+                let resolver = resolver.clone();
+                tokio::spawn(async move {
+                    if own_connection(peer, local).await {
+                        serve(client, resolver).await;
+                    } else {
+                        eprintln!("Refusing proxy connection from another user ({peer})");
+                    }
+                });
+            }
+        });
+    });
+
+    Some(ProxyConfig::Http(ProxyEndpoint {
+        host: Ipv4Addr::LOCALHOST.to_string(),
+        port: port.to_string(),
+    }))
+}
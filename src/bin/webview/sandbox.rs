@@ -0,0 +1,284 @@
+//! Opt-in bubblewrap sandbox: the webview re-executes itself inside `bwrap` with an empty
+//! home directory, into which only the app's own profile, config, the download and
+//! pictures folders and a few desktop integration paths are mounted.
+//!
+//! The runtime directory is emptied too, apart from the display and audio sockets, and the
+//! session bus is only reachable through `xdg-dbus-proxy`, filtered down to what the app
+//! needs: portals, notifications, the tray and its own bus name.
+
+use std::{
+    io::Read as _,
+    os::{
+        fd::AsRawFd,
+        unix::{net::UnixStream, process::CommandExt as _},
+    },
+    path::{Path, PathBuf},
+    process::Command,
+    time::Duration,
+};
+
+use webapps::{browser::Browser, fl};
+
+/// Set inside the sandbox so the webview doesn't try to enter it again.
+const SANDBOXED_ENV: &str = "DEV_HEPPEN_WEBAPPS_SANDBOXED";
+
+fn find_program(name: &str) -> Option<PathBuf> {
+    std::env::var_os("PATH")?
+        .to_str()?
+        .split(':')
+        .map(|dir| Path::new(dir).join(name))
+        .find(|path| path.is_file())
+}
+
+fn fail(title: &str, message: String) -> ! {
+    eprintln!("{message}");
+    let _ = notify_rust::Notification::new()
+        .summary(title)
+        .body(&message)
+        .appname("dev.heppen.webapps")
+        .show();
+    std::process::exit(1);
+}
+
+/// Let `fd` survive `exec`, for the processes that have to keep it open.
+fn inheritable(fd: &impl AsRawFd) -> std::io::Result<()> {
+    // SAFETY: only clears the close-on-exec flag of a descriptor owned by the caller
+    if unsafe { libc::fcntl(fd.as_raw_fd(), libc::F_SETFD, 0) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Create `path` if it's missing, as a file can only be mounted once it exists.
+fn touch(path: &Path) -> bool {
+    path.parent()
+        .map_or(Ok(()), std::fs::create_dir_all)
+        .and_then(|()| {
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+        })
+        .is_ok()
+}
+
+/// Mounts inside the otherwise empty home: `(path, writable)`.
+fn home_mounts(browser: &Browser, icon: &str) -> Vec<(PathBuf, bool)> {
+    let app_id = browser.app_id.as_ref();
+    let safe_id = webapps::browser::sanitize_app_id(app_id);
+    let mut mounts = Vec::new();
+
+    // The app's own data: profile, state (bookmarks, usage), settings and navigation log.
+    // An ephemeral profile needs no mount, the sandbox's home is thrown away on exit.
+    for dir in [
+        webapps::profiles_path(&safe_id),
+        webapps::app_state::dir(&safe_id),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if std::fs::create_dir_all(&dir).is_ok() {
+            mounts.push((dir, true));
+        }
+    }
+    if let Some(entry) = webapps::database_path(&format!("{safe_id}.ron")) {
+        mounts.push((entry, false));
+    }
+    // Usage statistics, which the time limit reads back as well
+    let stats = webapps::stats::path(app_id);
+    let log =
+        webapps::audit::log_path(&safe_id).filter(|_| browser.navigation_log.unwrap_or(false));
+    for file in [stats, log].into_iter().flatten() {
+        if touch(&file) {
+            mounts.push((file, true));
+        }
+    }
+    // Where downloads and screenshots are saved
+    for dir in [dirs::download_dir(), dirs::picture_dir()]
+        .into_iter()
+        .flatten()
+    {
+        mounts.push((dir, true));
+    }
+
+    // Desktop theme, fonts and icons, the webview and its web process extension, the app's
+    // icon, certificate and local page
+    let config = dirs::config_dir();
+    let data = dirs::data_dir();
+    let read_only = [
+        config.as_ref().map(|dir| dir.join("cosmic")),
+        config.as_ref().map(|dir| dir.join("gtk-3.0")),
+        config.as_ref().map(|dir| dir.join("fontconfig")),
+        data.as_ref().map(|dir| dir.join("fonts")),
+        data.as_ref().map(|dir| dir.join("icons")),
+        std::env::var_os("XAUTHORITY").map(PathBuf::from),
+        std::env::current_exe().ok(),
+        crate::privacy::extension_dir(),
+        Some(PathBuf::from(icon)),
+        browser.ca_certificate.as_ref().map(PathBuf::from),
+        browser.custom_css_file.clone(),
+        browser.custom_js_file.clone(),
+        browser
+            .url
+            .as_deref()
+            .and_then(|url| url::Url::parse(url).ok())
+            .filter(|url| url.scheme() == "file")
+            .and_then(|url| url.to_file_path().ok())
+            .and_then(|path| path.parent().map(Path::to_path_buf)),
+    ];
+    mounts.extend(read_only.into_iter().flatten().map(|path| (path, false)));
+
+    mounts.retain(|(path, _)| path.exists());
+    mounts
+}
+
+/// Display and audio sockets in the runtime directory.
+fn runtime_sockets(runtime: &Path) -> Vec<PathBuf> {
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").unwrap_or_else(|| "wayland-0".into());
+    [
+        runtime.join(wayland),
+        runtime.join("pulse").join("native"),
+        runtime.join("pipewire-0"),
+    ]
+    .into_iter()
+    .filter(|socket| socket.exists())
+    .collect()
+}
+
+/// What the app may do on the session bus through the proxy.
+fn bus_policy(browser: &Browser) -> Vec<String> {
+    let mut policy = vec![
+        "--filter".to_string(),
+        "--talk=org.freedesktop.portal.*".to_string(),
+        "--talk=org.freedesktop.Notifications".to_string(),
+        "--talk=org.kde.StatusNotifierWatcher".to_string(),
+        "--own=org.kde.StatusNotifierItem-*".to_string(),
+    ];
+    if let Some(name) = webapps::dbus_name(browser.app_id.as_ref()) {
+        policy.push(format!("--own={name}"));
+    }
+    // The keyring holds every other app's secrets as well
+    if browser.password_autofill.unwrap_or(false) {
+        policy.push("--talk=org.freedesktop.secrets".to_string());
+    }
+    policy
+}
+
+/// Start `xdg-dbus-proxy` for the app. Returns its socket and our end of the connection
+/// that keeps it running: the proxy exits once every copy of it is closed.
+fn start_bus_proxy(browser: &Browser, runtime: &Path) -> Option<(PathBuf, UnixStream)> {
+    let proxy = find_program("xdg-dbus-proxy")?;
+    let address = std::env::var("DBUS_SESSION_BUS_ADDRESS").ok()?;
+    let dir = runtime.join(webapps::APP_ID);
+    std::fs::create_dir_all(&dir).ok()?;
+    let socket = dir.join(format!("bus-{}", std::process::id()));
+    let _ = std::fs::remove_file(&socket);
+
+    let (mut ours, theirs) = UnixStream::pair().ok()?;
+    inheritable(&theirs).ok()?;
+    let spawned = Command::new(proxy)
+        .arg(format!("--fd={}", theirs.as_raw_fd()))
+        .arg(address)
+        .arg(&socket)
+        .args(bus_policy(browser))
+        .spawn();
+    drop(theirs);
+    spawned.ok()?;
+
+    // The proxy writes a byte once it is listening
+    ours.set_read_timeout(Some(Duration::from_secs(5))).ok()?;
+    ours.read_exact(&mut [0]).ok()?;
+    ours.set_read_timeout(None).ok()?;
+    inheritable(&ours).ok()?;
+    Some((socket, ours))
+}
+
+/// Re-run this process inside bubblewrap when the app asks for it. Returns only when no
+/// sandbox is needed; the sandboxed process replaces this one otherwise.
+pub fn enter(browser: &Browser, icon: &str) {
+    if !browser.sandbox.unwrap_or(false) || std::env::var_os(SANDBOXED_ENV).is_some() {
+        return;
+    }
+    // The editor hides the option there: Flatpak already confines the app and doesn't
+    // allow nested bubblewrap
+    if webapps::in_flatpak() {
+        eprintln!(
+            "Not sandboxing {}: already running inside Flatpak",
+            browser.app_id.as_ref()
+        );
+        return;
+    }
+    let title = browser.window_title.clone().unwrap_or_default();
+    let Some(bwrap) = find_program("bwrap") else {
+        fail(&title, fl!("sandbox-unavailable"));
+    };
+    let (Some(home), Ok(exe)) = (dirs::home_dir(), std::env::current_exe()) else {
+        fail(&title, fl!("sandbox-unavailable"));
+    };
+    let runtime = dirs::runtime_dir();
+
+    let mut command = Command::new(bwrap);
+    command
+        .args(["--ro-bind", "/", "/", "--dev", "/dev"])
+        .args(["--proc", "/proc", "--unshare-pid", "--unshare-ipc"]);
+    // Hardware acceleration
+    if Path::new("/dev/dri").exists() {
+        command.args(["--dev-bind", "/dev/dri", "/dev/dri"]);
+    }
+    command.arg("--tmpfs").arg(&home);
+
+    // Held until `exec`, which hands the connection keeping the proxy alive to bwrap
+    let bus_proxy = runtime
+        .as_deref()
+        .and_then(|runtime| start_bus_proxy(browser, runtime));
+    if let Some(runtime) = &runtime {
+        command.arg("--tmpfs").arg(runtime);
+        for socket in runtime_sockets(runtime) {
+            command.arg("--bind").arg(&socket).arg(&socket);
+        }
+        match &bus_proxy {
+            Some((socket, keep_alive)) => {
+                let bus = runtime.join("bus");
+                command
+                    .arg("--bind")
+                    .arg(&socket)
+                    .arg(&bus)
+                    .arg("--setenv")
+                    .arg("DBUS_SESSION_BUS_ADDRESS")
+                    .arg(format!("unix:path={}", bus.display()))
+                    .arg("--sync-fd")
+                    .arg(keep_alive.as_raw_fd().to_string());
+            }
+            None => {
+                eprintln!("xdg-dbus-proxy is unavailable, running without the session bus");
+                command.args(["--unsetenv", "DBUS_SESSION_BUS_ADDRESS"]);
+            }
+        }
+    }
+
+    for (path, writable) in home_mounts(browser, icon) {
+        // Read-only paths elsewhere are already visible through the root
+        let hidden = path.starts_with(&home)
+            || runtime
+                .as_ref()
+                .is_some_and(|runtime| path.starts_with(runtime));
+        if !writable && !hidden {
+            continue;
+        }
+        let flag = if writable { "--bind" } else { "--ro-bind" };
+        command.arg(flag).arg(&path).arg(&path);
+    }
+    // Without a network namespace of its own the app can't reach anything, not even loopback
+    if browser.network_isolation == Some(webapps::browser::NetworkIsolation::NoNetwork) {
+        command.arg("--unshare-net");
+    }
+    command
+        .args(["--new-session", "--setenv", SANDBOXED_ENV, "1"])
+        // The accessibility bus isn't proxied
+        .args(["--setenv", "NO_AT_BRIDGE", "1", "--"])
+        .arg(exe)
+        .args(std::env::args_os().skip(1));
+
+    let e = command.exec();
+    fail(&title, fl!("sandbox-failed", error = e.to_string()));
+}
@@ -0,0 +1,41 @@
+//! Default page zoom derived from the display, for apps without a configured zoom.
+//!
+//! GTK 3 only knows integer scale factors, so on a dense panel left at scale 1 (or a
+//! fractional setup GTK rounds down) pages render tiny. The logical DPI after GTK's
+//! own scaling tells how far off 96 DPI content ends up.
+
+use gtk::prelude::*;
+
+const REFERENCE_DPI: f64 = 96.0;
+
+/// Zoom for the monitor the window is on, rounded to 5% steps. Never shrinks pages.
+pub fn default_zoom(window: &gtk::ApplicationWindow) -> f64 {
+    let Some(dpi) = logical_dpi(window) else {
+        return 1.0;
+    };
+    let zoom = (dpi / REFERENCE_DPI * 20.0).round() / 20.0;
+    zoom.clamp(1.0, 2.0)
+}
+
+/// Name of the monitor the window is on, to notice when it moves to another one.
+pub fn current_monitor(window: &gtk::ApplicationWindow) -> Option<String> {
+    let gdk_window = window.window()?;
+    window
+        .display()
+        .monitor_at_window(&gdk_window)
+        .and_then(|monitor| monitor.model())
+        .map(|model| model.to_string())
+}
+
+fn logical_dpi(window: &gtk::ApplicationWindow) -> Option<f64> {
+    let gdk_window = window.window()?;
+    let monitor = window.display().monitor_at_window(&gdk_window)?;
+    let width_mm = monitor.width_mm();
+    if width_mm <= 0 {
+        // Projectors and some virtual outputs report no physical size
+        return None;
+    }
+    // Geometry is in logical (already scaled) pixels
+    let logical_width = f64::from(monitor.geometry().width());
+    Some(logical_width / (f64::from(width_mm) / 25.4))
+}
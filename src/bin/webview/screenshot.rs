@@ -0,0 +1,75 @@
+//! Saves the visible page to the Pictures folder (Ctrl+Shift+S).
+
+use std::path::PathBuf;
+
+use gtk::{gio, glib};
+use webapps::fl;
+use webkit2gtk::{SnapshotOptions, SnapshotRegion, WebViewExt};
+
+fn target_path(app_title: &str) -> PathBuf {
+    let dir = dirs::picture_dir()
+        .or_else(dirs::home_dir)
+        .unwrap_or_else(std::env::temp_dir);
+    let stamp = glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y-%m-%d %H-%M-%S"))
+        .map(|s| s.to_string())
+        .unwrap_or_default();
+    // Titles can contain path separators
+    let name: String = app_title
+        .chars()
+        .map(|c| if c == '/' || c == '\\' { '-' } else { c })
+        .collect();
+    dir.join(format!("{name} {stamp}.png"))
+}
+
+/// Offer to open the saved screenshot; waiting for the click blocks, so it runs on its own thread.
+fn notify(app_title: String, icon: String, path: PathBuf) {
+    std::thread::spawn(move || {
+        let shown = notify_rust::Notification::new()
+            .summary(&format!("{app_title} — {}", fl!("screenshot-saved")))
+            .body(&path.to_string_lossy())
+            .icon(&icon)
+            .appname("dev.heppen.webapps")
+            .action("default", &fl!("notification-open"))
+            .show();
+        match shown {
+            Ok(handle) => handle.wait_for_action(|action| {
+                if action == "default" {
+                    if let Err(e) = open::that_detached(&path) {
+                        eprintln!("Failed to open {}: {e}", path.display());
+                    }
+                }
+            }),
+            Err(e) => eprintln!("Failed to show notification: {e}"),
+        }
+    });
+}
+
+/// Capture the visible part of the page and save it as a PNG.
+pub fn capture(webview: &webkit2gtk::WebView, app_title: &str, icon: &str) {
+    let path = target_path(app_title);
+    let app_title = app_title.to_string();
+    let icon = icon.to_string();
+    webview.snapshot(
+        SnapshotRegion::Visible,
+        SnapshotOptions::NONE,
+        None::<&gio::Cancellable>,
+        move |result| {
+            let saved = result
+                .map_err(|e| e.to_string())
+                .and_then(|surface| {
+                    gtk::cairo::ImageSurface::try_from(surface)
+                        .map_err(|_| "snapshot is not an image".to_string())
+                })
+                .and_then(|image| {
+                    gtk::gdk::pixbuf_get_from_surface(&image, 0, 0, image.width(), image.height())
+                        .ok_or_else(|| "failed to read snapshot pixels".to_string())
+                })
+                .and_then(|pixbuf| pixbuf.savev(&path, "png", &[]).map_err(|e| e.to_string()));
+            match saved {
+                Ok(()) => notify(app_title, icon, path),
+                Err(e) => eprintln!("Failed to save screenshot: {e}"),
+            }
+        },
+    );
+}
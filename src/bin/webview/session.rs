@@ -0,0 +1,108 @@
+//! Scripts capturing each page's URL and scroll state for session restore, and putting it
+//! back.
+
+use webapps::session::Session;
+
+/// Reports the URL and scroll positions (page and inner scroll panes) whenever they settle,
+/// and once more when the page goes away.
+pub const CAPTURE: &str = r#"(function() {
+    if (window.top !== window) return;
+    const scrolled = new Set();
+    function selectorFor(el) {
+        if (el.id) return '#' + CSS.escape(el.id);
+        const parts = [];
+        while (el && el.parentElement && el !== document.body) {
+            const index = Array.prototype.indexOf.call(el.parentElement.children, el) + 1;
+            parts.unshift(el.tagName.toLowerCase() + ':nth-child(' + index + ')');
+            el = el.parentElement;
+        }
+        return ['body'].concat(parts).join(' > ');
+    }
+    function state() {
+        const elements = [];
+        for (const el of scrolled) {
+            if (!el.isConnected || (el.scrollTop === 0 && el.scrollLeft === 0)) continue;
+            elements.push({ selector: selectorFor(el), top: el.scrollTop, left: el.scrollLeft });
+            if (elements.length >= 20) break;
+        }
+        return {
+            type: 'session',
+            url: location.href,
+            scroll_x: window.scrollX,
+            scroll_y: window.scrollY,
+            elements: elements
+        };
+    }
+    // Read natively from the windows a page opened, whose reports can't be told apart
+    window.__webappsSessionState = state;
+    function report() {
+        window.ipc.postMessage(JSON.stringify(state()));
+    }
+    let timer = null;
+    function schedule() {
+        clearTimeout(timer);
+        timer = setTimeout(report, 500);
+    }
+    document.addEventListener('scroll', function(e) {
+        if (e.target instanceof Element) scrolled.add(e.target);
+        schedule();
+    }, true);
+    for (const name of ['pushState', 'replaceState']) {
+        const original = history[name];
+        history[name] = function() {
+            const result = original.apply(this, arguments);
+            schedule();
+            return result;
+        };
+    }
+    window.addEventListener('popstate', schedule);
+    window.addEventListener('hashchange', schedule);
+    window.addEventListener('load', function() { setTimeout(report, 1000); });
+    window.addEventListener('pagehide', report);
+    document.addEventListener('visibilitychange', function() {
+        if (document.visibilityState === 'hidden') report();
+    });
+})()"#;
+
+/// Scrolls each restored page back to where it was, once per launch.
+const RESTORE: &str = r#"(function(pages) {
+    if (window.top !== window) return;
+    const saved = pages.find(function(page) { return page.url === location.href; });
+    if (!saved) return;
+    try {
+        if (sessionStorage.getItem('__webapps_session_restored')) return;
+        sessionStorage.setItem('__webapps_session_restored', '1');
+    } catch (e) {}
+    function restore() {
+        window.scrollTo(saved.scroll_x, saved.scroll_y);
+        for (const entry of saved.elements) {
+            const el = document.querySelector(entry.selector);
+            if (el) {
+                el.scrollTop = entry.top;
+                el.scrollLeft = entry.left;
+            }
+        }
+    }
+    // Late content (images, client-side rendering) can shift the page after load
+    window.addEventListener('load', function() {
+        restore();
+        setTimeout(restore, 500);
+    });
+})(%SESSION%)"#;
+
+/// Restore the scroll state of the app's window and the windows reopened with it.
+pub fn restore_script(session: &Session) -> String {
+    let pages: Vec<_> = std::iter::once(session)
+        .chain(session.windows.iter().map(|window| &window.page))
+        .map(|page| {
+            serde_json::json!({
+                "url": page.url,
+                "scroll_x": page.scroll_x,
+                "scroll_y": page.scroll_y,
+                "elements": page.elements,
+            })
+        })
+        .collect();
+    let data = serde_json::to_string(&pages).unwrap_or_else(|_| "[]".to_string());
+    RESTORE.replace("%SESSION%", &data)
+}
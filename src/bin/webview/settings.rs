@@ -0,0 +1,63 @@
+//! WebKit settings applied from the app configuration.
+
+use webkit2gtk::{SettingsExt, WebContextExt, WebViewExt};
+
+/// Apply the per-app feature toggles to the webview's WebKit settings.
+pub fn apply_features(webview: &webkit2gtk::WebView, features: &webapps::browser::WebFeatures) {
+    let Some(settings) = WebViewExt::settings(webview) else {
+        eprintln!("Failed to access WebKit settings");
+        return;
+    };
+
+    settings.set_enable_javascript(features.javascript);
+    settings.set_enable_webgl(features.webgl);
+    settings.set_enable_smooth_scrolling(features.smooth_scrolling);
+    settings.set_enable_page_cache(features.page_cache);
+}
+
+/// Let local pages load files next to them (scripts, JSON data) while still keeping
+/// them from reading arbitrary origins.
+pub fn allow_local_files(webview: &webkit2gtk::WebView) {
+    let Some(settings) = WebViewExt::settings(webview) else {
+        eprintln!("Failed to access WebKit settings");
+        return;
+    };
+
+    settings.set_allow_file_access_from_file_urls(true);
+    settings.set_allow_universal_access_from_file_urls(false);
+}
+
+/// Turn on spell checking for `languages` (e.g. `en_US`), or the session's locale
+/// when the list is empty.
+pub fn enable_spellcheck(webview: &webkit2gtk::WebView, languages: &[String]) {
+    let Some(context) = webview.context() else {
+        eprintln!("Failed to access WebKit context");
+        return;
+    };
+
+    context.set_spell_checking_enabled(true);
+    if !languages.is_empty() {
+        let languages: Vec<&str> = languages.iter().map(String::as_str).collect();
+        context.set_spell_checking_languages(&languages);
+    }
+}
+
+/// Render without the GPU: no accelerated compositing and no DMA-BUF renderer, for
+/// drivers where WebKit draws a black window. Must run before GTK starts.
+pub fn disable_hardware_acceleration() {
+    // SAFETY: called at the top of main while the process is still single-threaded.
+    unsafe {
+        std::env::set_var("WEBKIT_DISABLE_COMPOSITING_MODE", "1");
+        std::env::set_var("WEBKIT_DISABLE_DMABUF_RENDERER", "1");
+    }
+}
+
+/// Keep WebKit from switching to accelerated compositing.
+pub fn software_rendering(webview: &webkit2gtk::WebView) {
+    let Some(settings) = WebViewExt::settings(webview) else {
+        eprintln!("Failed to access WebKit settings");
+        return;
+    };
+
+    settings.set_hardware_acceleration_policy(webkit2gtk::HardwareAccelerationPolicy::Never);
+}
@@ -0,0 +1,76 @@
+//! Keyboard shortcuts handled by the webview window itself.
+
+use gtk::{gdk, glib, prelude::*};
+use tao::event_loop::EventLoopProxy;
+
+use crate::{UserEvent, lock::Covered};
+
+/// Actions bound to window-level shortcuts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    /// Ctrl+Shift+M
+    ToggleMute,
+    /// Ctrl+L or Ctrl+Shift+C
+    CopyUrl,
+    /// Ctrl+H
+    History,
+    /// Ctrl+D
+    Bookmark,
+    /// Ctrl+Shift+O
+    Bookmarks,
+    /// Ctrl+Shift+S
+    Screenshot,
+    /// Ctrl+Shift+K
+    Cookies,
+}
+
+fn lookup(key: gdk::keys::Key, state: gdk::ModifierType) -> Option<Shortcut> {
+    use gdk::keys::constants as keys;
+
+    let modifiers = state
+        & (gdk::ModifierType::CONTROL_MASK
+            | gdk::ModifierType::SHIFT_MASK
+            | gdk::ModifierType::MOD1_MASK);
+    let ctrl = gdk::ModifierType::CONTROL_MASK;
+    let ctrl_shift = gdk::ModifierType::CONTROL_MASK | gdk::ModifierType::SHIFT_MASK;
+    let key = key.to_lower();
+
+    if modifiers == ctrl_shift && key == keys::m {
+        Some(Shortcut::ToggleMute)
+    } else if (modifiers == ctrl && key == keys::l) || (modifiers == ctrl_shift && key == keys::c) {
+        Some(Shortcut::CopyUrl)
+    } else if modifiers == ctrl && key == keys::h {
+        Some(Shortcut::History)
+    } else if modifiers == ctrl && key == keys::d {
+        Some(Shortcut::Bookmark)
+    } else if modifiers == ctrl_shift && key == keys::o {
+        Some(Shortcut::Bookmarks)
+    } else if modifiers == ctrl_shift && key == keys::s {
+        Some(Shortcut::Screenshot)
+    } else if modifiers == ctrl_shift && key == keys::k {
+        Some(Shortcut::Cookies)
+    } else {
+        None
+    }
+}
+
+/// Intercept shortcuts before the page sees them and forward them to the event loop. While
+/// the app is `covered` they are left to the lock or limit screen.
+pub fn install(
+    window: &gtk::ApplicationWindow,
+    proxy: EventLoopProxy<UserEvent>,
+    covered: Covered,
+) {
+    window.connect_key_press_event(move |_, event| {
+        if covered.get() {
+            return glib::Propagation::Proceed;
+        }
+        match lookup(event.keyval(), event.state()) {
+            Some(shortcut) => {
+                let _ = proxy.send_event(UserEvent::Shortcut(shortcut));
+                glib::Propagation::Stop
+            }
+            None => glib::Propagation::Proceed,
+        }
+    });
+}
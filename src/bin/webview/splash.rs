@@ -0,0 +1,86 @@
+//! Splash screen shown over the webview until the first page load finishes.
+
+use gtk::prelude::*;
+use webkit2gtk::{LoadEvent, WebViewExt};
+
+/// Reveal the page even if the first load never reports completion.
+const SPLASH_TIMEOUT_SECS: u32 = 15;
+
+/// Default splash background when no theme color is configured.
+const DEFAULT_COLOR: (u8, u8, u8) = (0x27, 0x27, 0x27);
+
+/// Splash screen wrapper around the webview container.
+pub struct Splash {
+    stack: gtk::Stack,
+    /// Container the webview should be built into.
+    pub content: gtk::Box,
+}
+
+impl Splash {
+    /// Insert a stack into `vbox` holding the splash page and an empty content box.
+    pub fn new(vbox: &gtk::Box, title: &str, icon_path: &str, color: Option<(u8, u8, u8)>) -> Self {
+        let (r, g, b) = color.unwrap_or(DEFAULT_COLOR);
+        // Pick a readable label color using relative luminance
+        let luminance = 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+        let fg = if luminance > 140.0 { "#000000" } else { "#ffffff" };
+
+        let splash = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        splash.set_widget_name("webapps-splash");
+
+        let inner = gtk::Box::new(gtk::Orientation::Vertical, 16);
+        inner.set_valign(gtk::Align::Center);
+        inner.set_halign(gtk::Align::Center);
+        inner.set_vexpand(true);
+
+        if let Ok(pixbuf) = gtk::gdk_pixbuf::Pixbuf::from_file_at_scale(icon_path, 128, 128, true)
+        {
+            inner.pack_start(&gtk::Image::from_pixbuf(Some(&pixbuf)), false, false, 0);
+        }
+        inner.pack_start(&gtk::Label::new(Some(title)), false, false, 0);
+        splash.pack_start(&inner, true, true, 0);
+
+        let css = format!(
+            "#webapps-splash {{ background-color: #{r:02x}{g:02x}{b:02x}; }} \
+             #webapps-splash label {{ color: {fg}; font-size: 18pt; font-weight: bold; }}"
+        );
+        let provider = gtk::CssProvider::new();
+        if let Err(e) = provider.load_from_data(css.as_bytes()) {
+            eprintln!("Failed to load splash CSS: {e}");
+        }
+        if let Some(screen) = gtk::gdk::Screen::default() {
+            gtk::StyleContext::add_provider_for_screen(
+                &screen,
+                &provider,
+                gtk::STYLE_PROVIDER_PRIORITY_APPLICATION,
+            );
+        }
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+
+        let stack = gtk::Stack::new();
+        stack.set_transition_type(gtk::StackTransitionType::Crossfade);
+        stack.set_transition_duration(200);
+        stack.add_named(&splash, "splash");
+        stack.add_named(&content, "content");
+        vbox.pack_start(&stack, true, true, 0);
+        stack.show_all();
+        stack.set_visible_child_name("splash");
+
+        Self { stack, content }
+    }
+
+    /// Switch to the webview once its first load finishes (or after a timeout).
+    pub fn hide_on_first_load(self, webview: &webkit2gtk::WebView) {
+        let stack = self.stack.clone();
+        webview.connect_load_changed(move |_, event| {
+            if event == LoadEvent::Finished {
+                stack.set_visible_child_name("content");
+            }
+        });
+
+        let stack = self.stack;
+        gtk::glib::timeout_add_seconds_local_once(SPLASH_TIMEOUT_SECS, move || {
+            stack.set_visible_child_name("content");
+        });
+    }
+}
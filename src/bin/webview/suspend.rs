@@ -0,0 +1,66 @@
+//! Memory saver: once the window has been hidden or minimized for a while, the web
+//! process is dropped and the page is loaded again when the window comes back.
+
+use std::{cell::RefCell, rc::Rc};
+
+use gtk::{gdk, glib, prelude::*};
+use webkit2gtk::WebViewExt;
+
+/// How often the window state is checked.
+const TICK_SECS: u32 = 30;
+
+fn hidden(window: &gtk::ApplicationWindow) -> bool {
+    !window.is_visible()
+        || window
+            .window()
+            .is_some_and(|w| w.state().contains(gdk::WindowState::ICONIFIED))
+}
+
+/// Suspend `webview` after `window` has stayed hidden for `minutes`, unless it is
+/// playing audio.
+pub fn install(window: &gtk::ApplicationWindow, webview: &webkit2gtk::WebView, minutes: u32) {
+    // URL to load again on resume, set while suspended
+    let suspended: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+    let resume = {
+        let webview = webview.clone();
+        let suspended = suspended.clone();
+        move || {
+            if let Some(uri) = suspended.borrow_mut().take() {
+                webview.load_uri(&uri);
+            }
+        }
+    };
+    {
+        let resume = resume.clone();
+        window.connect_show(move |_| resume());
+    }
+    window.connect_window_state_event(move |_, event| {
+        if !event
+            .new_window_state()
+            .contains(gdk::WindowState::ICONIFIED)
+        {
+            resume();
+        }
+        glib::Propagation::Proceed
+    });
+
+    let window = window.clone();
+    let webview = webview.clone();
+    let limit = u64::from(minutes) * 60;
+    let mut hidden_secs = 0;
+    glib::timeout_add_seconds_local(TICK_SECS, move || {
+        if !hidden(&window) {
+            hidden_secs = 0;
+            return glib::ControlFlow::Continue;
+        }
+        hidden_secs += u64::from(TICK_SECS);
+        if hidden_secs >= limit && suspended.borrow().is_none() && !webview.is_playing_audio() {
+            if let Some(uri) = webview.uri() {
+                suspended.replace(Some(uri.to_string()));
+                webview.terminate_web_process();
+            }
+        }
+        glib::ControlFlow::Continue
+    });
+}
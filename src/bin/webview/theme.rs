@@ -0,0 +1,41 @@
+//! Exposes the COSMIC desktop theme to pages as CSS custom properties.
+//!
+//! Custom CSS can then follow the desktop, e.g. `a { color: var(--cosmic-accent); }`.
+//! `<html data-cosmic-theme="dark|light">` is set as well for selectors.
+
+use cosmic::cosmic_theme::palette::Srgba;
+
+fn hex(color: Srgba) -> String {
+    let color: Srgba<u8> = color.into_format();
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        color.red, color.green, color.blue, color.alpha
+    )
+}
+
+/// Initialization script defining the variables from the theme active at launch.
+pub fn script() -> String {
+    let theme = cosmic::theme::system_preference();
+    let cosmic = theme.cosmic();
+    let font = cosmic::config::interface_font();
+    let scheme = if cosmic.is_dark { "dark" } else { "light" };
+
+    let variables = serde_json::json!({
+        "--cosmic-accent": hex(cosmic.accent_color()),
+        "--cosmic-on-accent": hex(cosmic.on_accent_color()),
+        "--cosmic-background": hex(cosmic.bg_color()),
+        "--cosmic-on-background": hex(cosmic.on_bg_color()),
+        "--cosmic-color-scheme": scheme,
+        "--cosmic-font-family": format!("\"{}\", sans-serif", font.family),
+    });
+
+    format!(
+        r#"(function(variables, scheme) {{
+    const root = document.documentElement;
+    for (const [name, value] of Object.entries(variables)) {{
+        root.style.setProperty(name, value);
+    }}
+    root.dataset.cosmicTheme = scheme;
+}})({variables}, "{scheme}")"#
+    )
+}
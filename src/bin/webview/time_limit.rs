@@ -0,0 +1,164 @@
+//! Tracks how long the app is in use each day and, with a daily limit set, warns as the
+//! limit nears and covers the app once it is used up.
+
+use std::{cell::Cell, rc::Rc};
+
+use gtk::{gio, glib, prelude::*};
+use webapps::fl;
+use webkit2gtk::WebViewExt;
+
+/// How often active time is counted.
+const TICK_SECS: u32 = 10;
+
+/// Usage is written back to the app's state after this much new activity.
+const SAVE_SECS: u64 = 60;
+
+/// Countdown toasts start this many minutes before the limit.
+const WARN_MINUTES: u64 = 5;
+
+/// Stops anything still playing behind the limit screen.
+const PAUSE_MEDIA: &str = "document.querySelectorAll('audio, video').forEach(m => m.pause());";
+
+/// Today's date in local time, as stored in `AppState::usage_day`.
+pub fn today() -> String {
+    glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y-%m-%d"))
+        .map(|s| s.to_string())
+        .unwrap_or_default()
+}
+
+fn save(app_id: &str, day: &str, seconds: u64) {
+    let saved = webapps::app_state::update(app_id, |state| {
+        state.usage_day = Some(day.to_string());
+        state.usage_seconds = seconds;
+    });
+    if let Err(e) = saved {
+        eprintln!("Failed to save usage time: {e}");
+    }
+}
+
+/// Screen shown in place of the app once the daily limit is used up.
+pub struct LimitScreen {
+    stack: gtk::Stack,
+    /// Container the toolbar, splash screen and webview go into.
+    pub content: gtk::Box,
+}
+
+impl LimitScreen {
+    /// Insert a stack into `vbox` holding the limit page and the content box.
+    pub fn new(
+        vbox: &gtk::Box,
+        title: &str,
+        icon_path: &str,
+        limit_minutes: u32,
+        covered: &crate::lock::Covered,
+    ) -> Self {
+        let page = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        page.set_valign(gtk::Align::Center);
+        page.set_halign(gtk::Align::Center);
+        page.set_vexpand(true);
+
+        if let Ok(pixbuf) = gtk::gdk_pixbuf::Pixbuf::from_file_at_scale(icon_path, 96, 96, true) {
+            page.pack_start(&gtk::Image::from_pixbuf(Some(&pixbuf)), false, false, 0);
+        }
+        page.pack_start(&gtk::Label::new(Some(title)), false, false, 0);
+        page.pack_start(
+            &gtk::Label::new(Some(&fl!("time-limit-reached", minutes = limit_minutes))),
+            false,
+            false,
+            0,
+        );
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        let stack = gtk::Stack::new();
+        stack.set_transition_type(gtk::StackTransitionType::Crossfade);
+        stack.set_transition_duration(200);
+        stack.add_named(&content, "content");
+        stack.add_named(&page, "limit");
+        vbox.pack_start(&stack, true, true, 0);
+        stack.show_all();
+        stack.set_visible_child_name("content");
+        covered.follow(&stack, "limit", crate::lock::LIMIT_SCREEN);
+
+        Self { stack, content }
+    }
+
+    fn set_reached(&self, reached: bool) {
+        self.stack
+            .set_visible_child_name(if reached { "limit" } else { "content" });
+    }
+}
+
+/// Count the time `window` is focused towards today's usage, starting from `used` seconds,
+/// and enforce the daily limit when `limit` is given.
+pub fn track(
+    window: &gtk::ApplicationWindow,
+    webview: &webkit2gtk::WebView,
+    app_id: String,
+    used: u64,
+    limit: Option<(u32, LimitScreen)>,
+) {
+    let mut day = today();
+    let used = Rc::new(Cell::new(used));
+    let mut unsaved = 0;
+    let limit = limit.map(|(minutes, screen)| (u64::from(minutes) * 60, screen));
+
+    let mut check = {
+        let webview = webview.clone();
+        let used = used.clone();
+        let mut reached = false;
+        let mut warned = None;
+        move |(limit_secs, screen): &(u64, LimitScreen)| {
+            let remaining = limit_secs.saturating_sub(used.get());
+            if (remaining == 0) != reached {
+                reached = remaining == 0;
+                screen.set_reached(reached);
+                if reached {
+                    webview.evaluate_javascript(
+                        PAUSE_MEDIA,
+                        None,
+                        None,
+                        None::<&gio::Cancellable>,
+                        |_| {},
+                    );
+                }
+            }
+            if reached {
+                return;
+            }
+            // One toast per remaining minute in the last few
+            let minutes = remaining.div_ceil(60);
+            if minutes <= WARN_MINUTES && warned != Some(minutes) {
+                warned = Some(minutes);
+                crate::toast::show(&webview, &fl!("time-limit-warning", minutes = minutes));
+            }
+        }
+    };
+    if let Some(limit) = &limit {
+        check(limit);
+    }
+
+    let window = window.clone();
+    glib::timeout_add_seconds_local(TICK_SECS, move || {
+        let now = today();
+        if now != day {
+            // A new day starts with a fresh allowance
+            day = now;
+            used.set(0);
+            unsaved = 0;
+            save(&app_id, &day, 0);
+        }
+        if window.is_active() {
+            used.set(used.get() + u64::from(TICK_SECS));
+            unsaved += u64::from(TICK_SECS);
+            if unsaved >= SAVE_SECS {
+                unsaved = 0;
+                save(&app_id, &day, used.get());
+            }
+        }
+        if let Some(limit) = &limit {
+            check(limit);
+        }
+        glib::ControlFlow::Continue
+    });
+}
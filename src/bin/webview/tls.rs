@@ -0,0 +1,209 @@
+//! Interstitial for TLS certificate errors, with per-host exceptions saved to the app state
+//! and an optional CA bundle trusted only by this app.
+
+use gtk::{gio, gio::prelude::*};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+use url::Url;
+use webapps::browser::TlsException;
+use webkit2gtk::{LoadEvent, WebContextExt, WebViewExt};
+
+use crate::error_page::{escape_html, icon_data_uri};
+
+const TEMPLATE: &str = include_str!("../../../resources/pages/tls-error.html");
+
+/// The load currently blocked by the interstitial.
+struct Failure {
+    uri: String,
+    host: String,
+    certificate: gio::TlsCertificate,
+}
+
+/// Load the app's extra CA bundle; certificates signed by it are trusted for this app only.
+pub fn load_ca_bundle(path: &str) -> Vec<gio::TlsCertificate> {
+    match gio::TlsCertificate::list_new_from_file(path) {
+        Ok(certificates) => certificates,
+        Err(e) => {
+            eprintln!("Failed to load CA certificate bundle {path}: {e}");
+            Vec::new()
+        }
+    }
+}
+
+/// Whether `certificate` is valid for `uri` when any of `authorities` is trusted.
+fn signed_by(
+    certificate: &gio::TlsCertificate,
+    uri: &str,
+    authorities: &[gio::TlsCertificate],
+) -> bool {
+    let Ok(identity) = gio::NetworkAddress::parse_uri(uri, 443) else {
+        return false;
+    };
+    authorities
+        .iter()
+        .any(|ca| certificate.verify(Some(&identity), Some(ca)).is_empty())
+}
+
+/// Human-readable reasons for the certificate validation `errors`.
+fn reasons(errors: gio::TlsCertificateFlags) -> Vec<String> {
+    let mut reasons: Vec<String> = [
+        (gio::TlsCertificateFlags::UNKNOWN_CA, webapps::fl!("tls-error-unknown-ca")),
+        (gio::TlsCertificateFlags::BAD_IDENTITY, webapps::fl!("tls-error-bad-identity")),
+        (gio::TlsCertificateFlags::NOT_ACTIVATED, webapps::fl!("tls-error-not-activated")),
+        (gio::TlsCertificateFlags::EXPIRED, webapps::fl!("tls-error-expired")),
+        (gio::TlsCertificateFlags::REVOKED, webapps::fl!("tls-error-revoked")),
+        (gio::TlsCertificateFlags::INSECURE, webapps::fl!("tls-error-insecure")),
+    ]
+    .into_iter()
+    .filter(|(flag, _)| errors.contains(*flag))
+    .map(|(_, reason)| reason)
+    .collect();
+
+    if reasons.is_empty() {
+        reasons.push(webapps::fl!("tls-error-generic"));
+    }
+    reasons
+}
+
+fn render(
+    app_title: &str,
+    icon_uri: &str,
+    host: &str,
+    failing_uri: &str,
+    errors: gio::TlsCertificateFlags,
+) -> String {
+    let reasons: String = reasons(errors)
+        .iter()
+        .map(|reason| format!("<li>{}</li>", escape_html(reason)))
+        .collect();
+
+    TEMPLATE
+        .replace("{{title}}", &escape_html(app_title))
+        .replace("{{icon}}", icon_uri)
+        .replace("{{heading}}", &escape_html(&webapps::fl!("tls-error-heading")))
+        .replace(
+            "{{message}}",
+            &escape_html(&webapps::fl!("tls-error-message", host = host)),
+        )
+        .replace("{{reasons}}", &reasons)
+        .replace("{{details}}", &escape_html(failing_uri))
+        .replace("{{back}}", &escape_html(&webapps::fl!("tls-error-back")))
+        .replace("{{proceed}}", &escape_html(&webapps::fl!("tls-error-proceed")))
+}
+
+/// Shows the interstitial for loads with TLS errors and remembers accepted certificates.
+pub struct TlsInterstitial {
+    webview: webkit2gtk::WebView,
+    app_id: String,
+    failure: Rc<RefCell<Option<Failure>>>,
+}
+
+impl TlsInterstitial {
+    /// Trust previously accepted certificates and intercept new TLS errors.
+    pub fn install(
+        webview: &webkit2gtk::WebView,
+        app_id: &str,
+        app_title: &str,
+        icon_path: &str,
+        exceptions: &[TlsException],
+        authorities: Vec<gio::TlsCertificate>,
+    ) -> Self {
+        if let Some(context) = webview.context() {
+            for exception in exceptions {
+                match gio::TlsCertificate::from_pem(&exception.certificate) {
+                    Ok(certificate) => {
+                        context.allow_tls_certificate_for_host(&certificate, &exception.host);
+                    }
+                    Err(e) => eprintln!("Invalid saved certificate for {}: {e}", exception.host),
+                }
+            }
+        }
+
+        let failure: Rc<RefCell<Option<Failure>>> = Rc::new(RefCell::new(None));
+        let interstitial_pending = Rc::new(Cell::new(false));
+        let app_title = app_title.to_string();
+        let icon_uri = icon_data_uri(icon_path);
+
+        {
+            let failure = failure.clone();
+            let interstitial_pending = interstitial_pending.clone();
+            webview.connect_load_failed_with_tls_errors(move |view, uri, certificate, errors| {
+                let Some(host) = Url::parse(uri)
+                    .ok()
+                    .and_then(|u| u.host_str().map(str::to_string))
+                else {
+                    return false;
+                };
+
+                // Signed by the app's own CA: trust this certificate and retry
+                if signed_by(certificate, uri, &authorities) {
+                    if let Some(context) = view.context() {
+                        context.allow_tls_certificate_for_host(certificate, &host);
+                        view.load_uri(uri);
+                        return true;
+                    }
+                }
+
+                eprintln!("TLS errors loading {uri}: {errors:?}");
+                view.load_alternate_html(
+                    &render(&app_title, &icon_uri, &host, uri, errors),
+                    uri,
+                    None,
+                );
+                failure.replace(Some(Failure {
+                    uri: uri.to_string(),
+                    host,
+                    certificate: certificate.clone(),
+                }));
+                interstitial_pending.set(true);
+                true
+            });
+        }
+
+        {
+            let failure = failure.clone();
+            webview.connect_load_changed(move |_, event| {
+                // The interstitial itself starts a load; anything else leaves it behind
+                if event == LoadEvent::Started && !interstitial_pending.replace(false) {
+                    failure.replace(None);
+                }
+            });
+        }
+
+        Self {
+            webview: webview.clone(),
+            app_id: app_id.to_string(),
+            failure,
+        }
+    }
+
+    /// The user chose to proceed from the interstitial shown for `host`.
+    pub fn proceed(&self, host: &str) {
+        let failure = self.failure.borrow_mut().take();
+        let Some(failure) = failure.filter(|f| f.host == host) else {
+            return;
+        };
+
+        if let Some(context) = self.webview.context() {
+            context.allow_tls_certificate_for_host(&failure.certificate, &failure.host);
+        }
+
+        if let Some(pem) = failure.certificate.certificate_pem() {
+            let exception = TlsException {
+                host: failure.host.clone(),
+                certificate: pem.to_string(),
+            };
+            let result = webapps::app_state::update(&self.app_id, |state| {
+                state.tls_exceptions.retain(|e| e.host != exception.host);
+                state.tls_exceptions.push(exception);
+            });
+            if let Err(e) = result {
+                eprintln!("Failed to save certificate exception: {e}");
+            }
+        }
+
+        self.webview.load_uri(&failure.uri);
+    }
+}
@@ -0,0 +1,26 @@
+//! Short confirmation messages shown over the page.
+
+use gtk::gio;
+use webkit2gtk::WebViewExt;
+
+/// In-page toast; `%MESSAGE%` is replaced with a JSON string.
+const TOAST: &str = r#"(function(message) {
+    const previous = document.getElementById('__webapps_toast');
+    if (previous) previous.remove();
+    const toast = document.createElement('div');
+    toast.id = '__webapps_toast';
+    toast.textContent = message;
+    toast.style.cssText = 'position:fixed;left:50%;bottom:24px;transform:translateX(-50%);' +
+        'z-index:2147483647;padding:8px 16px;border-radius:8px;background:rgba(0,0,0,0.8);' +
+        'color:#fff;font:14px sans-serif;pointer-events:none;transition:opacity 0.3s';
+    document.documentElement.appendChild(toast);
+    setTimeout(() => { toast.style.opacity = '0'; }, 1500);
+    setTimeout(() => toast.remove(), 1800);
+})(%MESSAGE%);"#;
+
+/// Show `message` at the bottom of the page for a moment.
+pub fn show(webview: &webkit2gtk::WebView, message: &str) {
+    let message = serde_json::to_string(message).unwrap_or_default();
+    let script = TOAST.replace("%MESSAGE%", &message);
+    webview.evaluate_javascript(&script, None, None, None::<&gio::Cancellable>, |_| {});
+}
@@ -0,0 +1,104 @@
+//! Slim navigation toolbar rendered above the webview.
+
+use gtk::prelude::*;
+use webkit2gtk::{LoadEvent, WebViewExt};
+
+pub struct Toolbar {
+    back: gtk::Button,
+    forward: gtk::Button,
+    reload: gtk::Button,
+    home: gtk::Button,
+    open_external: gtk::Button,
+    url_label: gtk::Label,
+}
+
+fn icon_button(icon: &str, tooltip: &str) -> gtk::Button {
+    let button = gtk::Button::from_icon_name(Some(icon), gtk::IconSize::SmallToolbar);
+    button.set_relief(gtk::ReliefStyle::None);
+    button.set_tooltip_text(Some(tooltip));
+    button
+}
+
+impl Toolbar {
+    /// Create the toolbar and pack it at the top of `vbox`.
+    pub fn new(vbox: &gtk::Box) -> Self {
+        let bar = gtk::Box::new(gtk::Orientation::Horizontal, 2);
+        bar.set_margin_start(4);
+        bar.set_margin_end(4);
+        bar.set_margin_top(2);
+        bar.set_margin_bottom(2);
+
+        let back = icon_button("go-previous-symbolic", &webapps::fl!("toolbar-back"));
+        let forward = icon_button("go-next-symbolic", &webapps::fl!("toolbar-forward"));
+        let reload = icon_button("view-refresh-symbolic", &webapps::fl!("toolbar-reload"));
+        let home = icon_button("go-home-symbolic", &webapps::fl!("toolbar-home"));
+        let open_external = icon_button("web-browser-symbolic", &webapps::fl!("open-in-browser"));
+
+        let url_label = gtk::Label::new(None);
+        url_label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+        url_label.set_xalign(0.0);
+        url_label.set_selectable(true);
+        url_label.style_context().add_class("dim-label");
+
+        bar.pack_start(&back, false, false, 0);
+        bar.pack_start(&forward, false, false, 0);
+        bar.pack_start(&reload, false, false, 0);
+        bar.pack_start(&home, false, false, 0);
+        bar.pack_start(&url_label, true, true, 6);
+        bar.pack_end(&open_external, false, false, 0);
+
+        vbox.pack_start(&bar, false, false, 0);
+        vbox.pack_start(
+            &gtk::Separator::new(gtk::Orientation::Horizontal),
+            false,
+            false,
+            0,
+        );
+        vbox.show_all();
+
+        Self {
+            back,
+            forward,
+            reload,
+            home,
+            open_external,
+            url_label,
+        }
+    }
+
+    /// Wire the toolbar buttons to `webview`; `home_url` is the app's start page.
+    pub fn connect(self, webview: &webkit2gtk::WebView, home_url: String) {
+        self.back.set_sensitive(false);
+        self.forward.set_sensitive(false);
+
+        let view = webview.clone();
+        self.back.connect_clicked(move |_| view.go_back());
+        let view = webview.clone();
+        self.forward.connect_clicked(move |_| view.go_forward());
+        let view = webview.clone();
+        self.reload.connect_clicked(move |_| view.reload());
+        let view = webview.clone();
+        self.home.connect_clicked(move |_| view.load_uri(&home_url));
+        let view = webview.clone();
+        self.open_external.connect_clicked(move |_| {
+            if let Some(uri) = view.uri() {
+                if let Err(e) = open::that_detached(uri.as_str()) {
+                    eprintln!("Failed to open {uri} in browser: {e}");
+                }
+            }
+        });
+
+        let url_label = self.url_label;
+        webview.connect_uri_notify(move |view| {
+            url_label.set_text(view.uri().as_deref().unwrap_or_default());
+        });
+
+        let (back, forward) = (self.back, self.forward);
+        webview.connect_load_changed(move |view, event| {
+            if event == LoadEvent::Committed || event == LoadEvent::Finished {
+                back.set_sensitive(view.can_go_back());
+                forward.set_sensitive(view.can_go_forward());
+            }
+        });
+    }
+}
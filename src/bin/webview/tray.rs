@@ -0,0 +1,100 @@
+//! Tray icon (StatusNotifierItem) for apps that minimize to background, so a
+//! hidden window can be brought back, reloaded, muted or quit.
+
+use ksni::{MenuItem, TrayMethods, menu::StandardItem};
+use std::path::Path;
+use tao::event_loop::EventLoopProxy;
+use webapps::{control::Command, fl};
+
+use crate::{UserEvent, shortcuts::Shortcut};
+
+struct Tray {
+    id: String,
+    title: String,
+    icon: String,
+    proxy: EventLoopProxy<UserEvent>,
+}
+
+impl Tray {
+    fn send(&self, event: UserEvent) {
+        let _ = self.proxy.send_event(event);
+    }
+
+    fn item(label: String, event: fn() -> UserEvent) -> MenuItem<Self> {
+        StandardItem {
+            label,
+            activate: Box::new(move |tray: &mut Self| tray.send(event())),
+            ..Default::default()
+        }
+        .into()
+    }
+}
+
+impl ksni::Tray for Tray {
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn title(&self) -> String {
+        self.title.clone()
+    }
+
+    // Icons stored as files are looked up through their directory
+    fn icon_name(&self) -> String {
+        match Path::new(&self.icon).file_stem() {
+            Some(stem) if self.icon.starts_with('/') => stem.to_string_lossy().into_owned(),
+            _ => self.icon.clone(),
+        }
+    }
+
+    fn icon_theme_path(&self) -> String {
+        match Path::new(&self.icon).parent() {
+            Some(dir) if self.icon.starts_with('/') => dir.to_string_lossy().into_owned(),
+            _ => String::new(),
+        }
+    }
+
+    fn activate(&mut self, _x: i32, _y: i32) {
+        self.send(UserEvent::ToggleVisibility);
+    }
+
+    fn menu(&self) -> Vec<MenuItem<Self>> {
+        vec![
+            Self::item(fl!("tray-show"), || UserEvent::Control(Command::Show)),
+            Self::item(fl!("tray-reload"), || UserEvent::Control(Command::Reload)),
+            Self::item(fl!("tray-mute"), || UserEvent::Shortcut(Shortcut::ToggleMute)),
+            MenuItem::Separator,
+            Self::item(fl!("tray-quit"), || UserEvent::Control(Command::Quit)),
+        ]
+    }
+}
+
+/// Show the tray icon on a background thread for the rest of the process lifetime.
+pub fn spawn(id: String, title: String, icon: String, proxy: EventLoopProxy<UserEvent>) {
+    std::thread::spawn(move || {
+        let runtime = match tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+        {
+            Ok(runtime) => runtime,
+            Err(e) => {
+                eprintln!("Failed to start tray runtime: {e}");
+                return;
+            }
+        };
+
+        runtime.block_on(async move {
+            let tray = Tray {
+                id,
+                title,
+                icon,
+                proxy,
+            };
+            match tray.spawn().await {
+                // Keep the tray alive until the process exits
+                Ok(_handle) => std::future::pending::<()>().await,
+                Err(e) => eprintln!("Failed to create tray icon: {e}"),
+            }
+        });
+    });
+}
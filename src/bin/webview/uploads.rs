@@ -0,0 +1,152 @@
+//! File uploads (`<input type=file>`) through the xdg file chooser portal, so they
+//! work inside the sandbox and start in the app's configured directory.
+
+use ashpd::desktop::file_chooser::{FileFilter, SelectedFiles};
+use gtk::glib;
+use std::{
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+use tokio::sync::oneshot;
+use walkdir::WalkDir;
+use webkit2gtk::{FileChooserRequestExt, WebViewExt};
+
+/// Reports whether the file input about to open is a directory picker
+/// (`webkitdirectory`), which WebKit doesn't tell the file chooser request.
+pub const SCRIPT: &str = r#"(function(){
+    document.addEventListener('click', function(e) {
+        var input = e.target;
+        if (!(input instanceof HTMLInputElement) || input.type !== 'file') return;
+        window.ipc.postMessage(JSON.stringify({
+            type: 'file-chooser',
+            directory: input.hasAttribute('webkitdirectory')
+        }));
+    }, true);
+})()"#;
+
+struct Options {
+    title: String,
+    multiple: bool,
+    directory: bool,
+    mime_types: Vec<String>,
+    default_dir: Option<PathBuf>,
+}
+
+/// Routes file chooser requests to the portal.
+#[derive(Clone)]
+pub struct Uploads {
+    directory: Arc<AtomicBool>,
+    default_dir: Option<PathBuf>,
+}
+
+impl Uploads {
+    /// `default_dir` may start with `~/` for the home directory.
+    pub fn new(default_dir: Option<&str>) -> Self {
+        let default_dir = default_dir
+            .map(|dir| match (dir.strip_prefix("~/"), dirs::home_dir()) {
+                (Some(rest), Some(home)) => home.join(rest),
+                _ => PathBuf::from(dir),
+            })
+            .filter(|dir| dir.is_dir());
+        Self {
+            directory: Arc::default(),
+            default_dir,
+        }
+    }
+
+    /// Remember whether the next chooser should pick a directory.
+    pub fn set_directory(&self, directory: bool) {
+        self.directory.store(directory, Ordering::Relaxed);
+    }
+
+    pub fn install(&self, webview: &webkit2gtk::WebView) {
+        let uploads = self.clone();
+        webview.connect_run_file_chooser(move |_, request| {
+            let directory = uploads.directory.swap(false, Ordering::Relaxed);
+            let options = Options {
+                title: webapps::fl!("upload-files"),
+                multiple: request.selects_multiple() || directory,
+                directory,
+                mime_types: request
+                    .mime_types()
+                    .into_iter()
+                    .map(|m| m.to_string())
+                    .collect(),
+                default_dir: uploads.default_dir.clone(),
+            };
+
+            // The portal runs on its own runtime; the answer comes back to the main loop
+            let (tx, rx) = oneshot::channel();
+            std::thread::spawn(move || {
+                let files = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime.block_on(choose(options)),
+                    Err(e) => Err(ashpd::Error::IO(e)),
+                };
+                let _ = tx.send(files);
+            });
+
+            let request = request.clone();
+            glib::MainContext::default().spawn_local(async move {
+                match rx.await {
+                    Ok(Ok(files)) if !files.is_empty() => {
+                        let files: Vec<&str> = files.iter().filter_map(|f| f.to_str()).collect();
+                        request.select_files(&files);
+                    }
+                    Ok(Err(e)) => {
+                        eprintln!("File chooser portal failed: {e}");
+                        request.cancel();
+                    }
+                    _ => request.cancel(),
+                }
+            });
+            true
+        });
+    }
+}
+
+/// Ask the portal for files; chosen directories are expanded to the files inside.
+async fn choose(options: Options) -> ashpd::Result<Vec<PathBuf>> {
+    let mut request = SelectedFiles::open_file()
+        .title(options.title.as_str())
+        .modal(true)
+        .multiple(options.multiple)
+        .directory(options.directory)
+        .current_folder(options.default_dir.as_ref())?;
+    if !options.directory && !options.mime_types.is_empty() {
+        let filter = options
+            .mime_types
+            .iter()
+            .fold(FileFilter::new(&webapps::fl!("upload-accepted-files")), |f, m| {
+                f.mimetype(m)
+            });
+        request = request.filter(filter);
+    }
+
+    let selected = match request.send().await?.response() {
+        Ok(selected) => selected,
+        Err(ashpd::Error::Response(ashpd::desktop::ResponseError::Cancelled)) => {
+            return Ok(Vec::new());
+        }
+        Err(e) => return Err(e),
+    };
+
+    let paths = selected.uris().iter().filter_map(|uri| uri.to_file_path().ok());
+    if !options.directory {
+        return Ok(paths.collect());
+    }
+    Ok(paths
+        .flat_map(|dir| {
+            WalkDir::new(dir)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_type().is_file())
+                .map(|e| e.into_path())
+        })
+        .collect())
+}
@@ -0,0 +1,104 @@
+//! The warm side of the renderer pool (see [`webapps::warm`]).
+
+use std::{
+    io::{BufRead as _, BufReader, Write as _},
+    os::unix::net::{UnixListener, UnixStream},
+};
+
+use clap::Parser as _;
+use webapps::{browser::NetworkIsolation, warm};
+
+/// Whether a process that already initialized GTK can run the app. Apps that need the
+/// environment changed before GTK starts, or a re-exec into the sandbox, start cold, as
+/// do private, hidden and preview launches.
+fn eligible(args: &webapps::WebviewArgs) -> bool {
+    if args.private || args.hidden || args.preview.is_some() {
+        return false;
+    }
+    let Some(launcher) = webapps::launcher::WebAppLauncher::from_appid(&args.id) else {
+        return false;
+    };
+    let browser = launcher.browser;
+    let proxy = browser.network_isolation.unwrap_or_default() == NetworkIsolation::Off
+        && browser
+            .proxy_url
+            .as_deref()
+            .is_some_and(|proxy| !proxy.trim().is_empty());
+    !browser.sandbox.unwrap_or(false)
+        && !browser.fingerprint_resistance.unwrap_or(false)
+        && !browser.disable_hardware_acceleration.unwrap_or(false)
+        && !proxy
+}
+
+fn reply(stream: &mut UnixStream, reply: &str) {
+    let _ = stream.write_all(format!("{reply}\n").as_bytes());
+}
+
+/// Initialize GTK and WebKit, then wait for a launch to take over and return its arguments
+/// and startup notification token. Exits when another warm process is already waiting or
+/// a quit request arrives.
+pub fn serve() -> (webapps::WebviewArgs, Option<String>) {
+    let Some(path) = warm::socket_path() else {
+        eprintln!("No runtime directory for the renderer pool");
+        std::process::exit(1);
+    };
+    if UnixStream::connect(&path).is_ok() {
+        std::process::exit(0);
+    }
+    let _ = std::fs::remove_file(&path);
+    if let Some(dir) = path.parent() {
+        let _ = std::fs::create_dir_all(dir);
+    }
+    let listener = match UnixListener::bind(&path) {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("Failed to listen on {}: {e}", path.display());
+            std::process::exit(1);
+        }
+    };
+
+    if let Err(e) = gtk::init() {
+        eprintln!("Failed to initialize GTK: {e}");
+        std::process::exit(1);
+    }
+    // Load the icon theme and WebKit's types now rather than at launch
+    let _ = gtk::IconTheme::default();
+    let _ = webkit2gtk::WebContext::default();
+
+    for mut stream in listener.incoming().filter_map(Result::ok) {
+        let mut line = String::new();
+        if BufReader::new(&stream).read_line(&mut line).is_err() {
+            continue;
+        }
+        let Ok(launch) = serde_json::from_str::<warm::Launch>(&line) else {
+            continue;
+        };
+        if launch.args.first().map(String::as_str) == Some(warm::QUIT) {
+            reply(&mut stream, warm::ACCEPTED);
+            let _ = std::fs::remove_file(&path);
+            std::process::exit(0);
+        }
+        let binary = std::env::args().next().unwrap_or_default();
+        let argv = launch.args.iter().cloned();
+        let args = match webapps::WebviewArgs::try_parse_from(std::iter::once(binary).chain(argv))
+        {
+            Ok(args) if !args.warm && eligible(&args) => args,
+            _ => {
+                reply(&mut stream, warm::DECLINED);
+                continue;
+            }
+        };
+
+        // Free the socket for the replacement before accepting
+        drop(listener);
+        let _ = std::fs::remove_file(&path);
+        reply(&mut stream, warm::ACCEPTED);
+        if let Ok(exe) = std::env::current_exe() {
+            if let Err(e) = std::process::Command::new(exe).arg("--warm").spawn() {
+                eprintln!("Failed to start the next warm renderer: {e}");
+            }
+        }
+        return (args, launch.startup_id().map(str::to_string));
+    }
+    std::process::exit(0);
+}
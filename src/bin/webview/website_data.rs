@@ -0,0 +1,112 @@
+//! The running app's website data: keeping the disk cache under its size limit, and
+//! clearing data when the manager asks for it. Going through WebKit rather than deleting
+//! files leaves it no stale state.
+
+use std::path::{Path, PathBuf};
+
+use gtk::{gio, glib};
+use webapps::ProfileData;
+use webkit2gtk::{
+    CacheModel, WebContextExt, WebViewExt, WebsiteDataManager, WebsiteDataManagerExt,
+    WebsiteDataTypes,
+};
+
+/// How often the disk cache size is checked against the limit.
+const CACHE_CHECK_SECS: u32 = 300;
+
+/// WebKit's HTTP disk cache inside the base cache directory.
+const DISK_CACHE_DIR: &str = "WebKitCache";
+
+/// WebKit's storage for each category of profile data.
+fn data_types(category: ProfileData) -> WebsiteDataTypes {
+    let cache = WebsiteDataTypes::DISK_CACHE | WebsiteDataTypes::MEMORY_CACHE;
+    let local_storage = WebsiteDataTypes::LOCAL_STORAGE | WebsiteDataTypes::SESSION_STORAGE;
+    match category {
+        ProfileData::Cookies => WebsiteDataTypes::COOKIES,
+        ProfileData::Cache => cache,
+        ProfileData::LocalStorage => local_storage,
+        ProfileData::IndexedDb => WebsiteDataTypes::INDEXEDDB_DATABASES,
+        // Kept by the app itself, not by WebKit
+        ProfileData::History => WebsiteDataTypes::empty(),
+        ProfileData::Other => WebsiteDataTypes::ALL.difference(
+            WebsiteDataTypes::COOKIES
+                | cache
+                | local_storage
+                | WebsiteDataTypes::INDEXEDDB_DATABASES,
+        ),
+    }
+}
+
+fn clear_types(manager: &WebsiteDataManager, types: WebsiteDataTypes) {
+    if types.is_empty() {
+        return;
+    }
+    manager.clear(
+        types,
+        glib::TimeSpan(0),
+        None::<&gio::Cancellable>,
+        |result| {
+            if let Err(e) = result {
+                eprintln!("Failed to clear website data: {e}");
+            }
+        },
+    );
+}
+
+/// Clear `category` of the app's data, or all of it. The browsing history lives in
+/// `history_profile`, when the app keeps one.
+pub fn clear(
+    webview: &webkit2gtk::WebView,
+    category: Option<ProfileData>,
+    history_profile: Option<&Path>,
+) {
+    if let Some(manager) = webview.website_data_manager() {
+        clear_types(&manager, category.map_or(WebsiteDataTypes::ALL, data_types));
+    }
+    if matches!(category, None | Some(ProfileData::History)) {
+        if let Some(Err(e)) = history_profile.map(webapps::history::clear) {
+            eprintln!("Failed to clear history: {e}");
+        }
+    }
+}
+
+/// Keep the disk cache under `limit_mb`, with 0 turning it off. WebKit has no size
+/// setting, so the cache is cleared once it has grown past the limit, checked at startup
+/// and then every few minutes.
+pub fn limit_cache(webview: &webkit2gtk::WebView, limit_mb: u32) {
+    if limit_mb == 0 {
+        if let Some(context) = webview.context() {
+            // Leaves WebKit no disk cache capacity
+            context.set_cache_model(CacheModel::DocumentViewer);
+        }
+    }
+    let Some(manager) = webview.website_data_manager() else {
+        return;
+    };
+    // Private apps keep nothing on disk
+    if manager.is_ephemeral() {
+        return;
+    }
+    // Where WebKit actually keeps it, which is not the app's profile for ephemeral ones.
+    // Without a base cache directory WebKit falls back to one named after the program.
+    let Some(dir) = manager
+        .base_cache_directory()
+        .map(|dir| PathBuf::from(dir.as_str()))
+        .or_else(|| Some(dirs::cache_dir()?.join(glib::prgname()?.as_str())))
+        .map(|dir| dir.join(DISK_CACHE_DIR))
+    else {
+        return;
+    };
+
+    let limit = u64::from(limit_mb) * 1024 * 1024;
+    let trim = move || {
+        if webapps::dir_size(&dir) > limit {
+            clear_types(&manager, WebsiteDataTypes::DISK_CACHE);
+        }
+    };
+    trim();
+    glib::timeout_add_seconds_local(CACHE_CHECK_SECS, move || {
+        trim();
+        glib::ControlFlow::Continue
+    });
+}
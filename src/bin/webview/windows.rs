@@ -0,0 +1,150 @@
+//! Windows the app's pages open with `window.open`. Each gets a view related to the app's
+//! own, sharing its web process, settings and scripts, and is tracked so the session can
+//! reopen it.
+
+use std::{cell::RefCell, rc::Rc};
+
+use gtk::{gio, glib, prelude::*};
+use javascriptcore::ValueExt as _;
+use webapps::session::{Session, WindowSession};
+use webkit2gtk::WebViewExt;
+
+/// Size of a window whose page didn't ask for one.
+const DEFAULT_SIZE: (i32, i32) = (800, 600);
+
+/// How often the page state of the open windows is read back.
+const POLL_SECS: u32 = 2;
+
+/// Page state the session capture script keeps in each window.
+const STATE_SCRIPT: &str = "window.__webappsSessionState ? window.__webappsSessionState() : null";
+
+struct Opened {
+    window: gtk::Window,
+    webview: webkit2gtk::WebView,
+    /// Last page state read back, once session restore polls it
+    page: Rc<RefCell<Option<Session>>>,
+}
+
+#[derive(Clone)]
+pub struct Windows {
+    icon: String,
+    opened: Rc<RefCell<Vec<Opened>>>,
+}
+
+impl Windows {
+    /// No windows yet; they show the app's `icon`.
+    pub fn new(icon: &str) -> Self {
+        Self {
+            icon: icon.to_string(),
+            opened: Rc::default(),
+        }
+    }
+
+    /// A hidden window related to `opener`, starting out with `page` as its saved state.
+    fn window(
+        &self,
+        opener: &webkit2gtk::WebView,
+        size: Option<(i32, i32)>,
+        page: Option<Session>,
+    ) -> webkit2gtk::WebView {
+        let webview = webkit2gtk::WebView::with_related_view(opener);
+        let window = gtk::Window::new(gtk::WindowType::Toplevel);
+        let (width, height) = size.unwrap_or(DEFAULT_SIZE);
+        window.set_default_size(width, height);
+        if let Err(e) = window.set_icon_from_file(&self.icon) {
+            eprintln!("Failed to set window icon: {e}");
+        }
+        window.add(&webview);
+
+        let title_window = window.clone();
+        webview.connect_title_notify(move |view| {
+            title_window.set_title(&view.title().unwrap_or_default());
+        });
+        // `window.close()` from the page
+        let close_window = window.clone();
+        webview.connect_close(move |_| close_window.close());
+        let (opened, closed) = (self.opened.clone(), webview.clone());
+        window.connect_destroy(move |_| {
+            opened.borrow_mut().retain(|entry| entry.webview != closed);
+        });
+
+        self.opened.borrow_mut().push(Opened {
+            window,
+            webview: webview.clone(),
+            page: Rc::new(RefCell::new(page)),
+        });
+        webview
+    }
+
+    /// A window for the page `opener` is opening, shown once WebKit has set it up.
+    pub fn open(
+        &self,
+        opener: &webkit2gtk::WebView,
+        size: Option<(i32, i32)>,
+    ) -> webkit2gtk::WebView {
+        let webview = self.window(opener, size, None);
+        webview.connect_ready_to_show(|view| {
+            if let Some(window) = view.toplevel() {
+                window.show_all();
+            }
+        });
+        webview
+    }
+
+    /// Reopen a window saved with the session, related to the app's `webview`.
+    pub fn restore(&self, webview: &webkit2gtk::WebView, saved: &WindowSession) {
+        let restored = self.window(
+            webview,
+            Some((saved.width, saved.height)),
+            Some(saved.page.clone()),
+        );
+        restored.load_uri(&saved.page.url);
+        if let Some(window) = restored.toplevel() {
+            window.show_all();
+        }
+    }
+
+    /// Keep reading back the page state of the open windows, for saving the session.
+    pub fn remember(&self) {
+        let opened = self.opened.clone();
+        glib::timeout_add_seconds_local(POLL_SECS, move || {
+            for entry in opened.borrow().iter() {
+                let page = entry.page.clone();
+                entry.webview.evaluate_javascript(
+                    STATE_SCRIPT,
+                    None,
+                    None,
+                    None::<&gio::Cancellable>,
+                    move |result| {
+                        let state = result
+                            .ok()
+                            .and_then(|value| value.to_json(0))
+                            .and_then(|json| serde_json::from_str::<Session>(&json).ok());
+                        if let Some(state) = state {
+                            *page.borrow_mut() = Some(state);
+                        }
+                    },
+                );
+            }
+            glib::ControlFlow::Continue
+        });
+    }
+
+    /// The windows still open, as saved with the session.
+    pub fn sessions(&self) -> Vec<WindowSession> {
+        self.opened
+            .borrow()
+            .iter()
+            .filter_map(|entry| {
+                let mut page = entry.page.borrow().clone()?;
+                page.windows.clear();
+                let (width, height) = entry.window.size();
+                Some(WindowSession {
+                    page,
+                    width,
+                    height,
+                })
+            })
+            .collect()
+    }
+}
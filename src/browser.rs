@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
+use strum_macros::EnumIter;
 
 /// Maximum length for a sanitized app ID.
 const MAX_APP_ID_LEN: usize = 128;
@@ -20,6 +21,400 @@ pub struct PermissionPolicy {
     pub allow_microphone: bool,
     pub allow_geolocation: bool,
     pub allow_notifications: bool,
+    #[serde(default)]
+    pub allow_screen_sharing: bool,
+}
+
+/// Accuracy requested from the location portal when geolocation is allowed.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum GeolocationAccuracy {
+    Country,
+    #[default]
+    City,
+    Neighborhood,
+    Street,
+    Exact,
+}
+
+/// How much of the Referer header is sent with requests.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum ReferrerPolicy {
+    /// Leave it to the page (WebKit defaults to trimming cross-origin referrers)
+    #[default]
+    Default,
+    /// Always trim cross-origin referrers to the origin
+    TrimCrossOrigin,
+    /// Send referrers to the same origin only
+    SameOriginOnly,
+    /// Never send a referrer
+    NoReferrer,
+}
+
+/// A certificate the user chose to trust for one host despite TLS errors.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct TlsException {
+    pub host: String,
+    /// PEM encoding of the accepted certificate
+    pub certificate: String,
+}
+
+/// A page saved from inside the web app (Ctrl+D).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Bookmark {
+    pub title: String,
+    pub url: String,
+}
+
+/// A host name pinned to an address for one app, like a private `/etc/hosts` entry.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct HostOverride {
+    pub host: String,
+    pub address: String,
+}
+
+impl HostOverride {
+    /// Parse `host=address` pairs separated by commas or whitespace; `None` if any pair
+    /// is malformed or its address isn't an IP address.
+    pub fn parse_list(text: &str) -> Option<Vec<Self>> {
+        text.split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (host, address) = pair.split_once('=')?;
+                let address = address.trim().parse::<std::net::IpAddr>().ok()?;
+                let host = host.trim().trim_end_matches('.').to_lowercase();
+                (!host.is_empty()).then(|| Self {
+                    host,
+                    address: address.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    pub fn format_list(overrides: &[Self]) -> String {
+        overrides
+            .iter()
+            .map(|o| format!("{}={}", o.host, o.address))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Whether pages may start media playback without a user gesture.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum AutoplayPolicy {
+    #[default]
+    Allow,
+    /// Only media without sound (or muted) may autoplay
+    AllowMuted,
+    Block,
+}
+
+/// How the window is sized when the app starts.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum WindowMode {
+    /// Use the configured window size
+    #[default]
+    Normal,
+    Maximized,
+    Fullscreen,
+}
+
+/// What right-clicking a page shows.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum ContextMenu {
+    /// WebKit's own menu
+    #[default]
+    Default,
+    /// WebKit's menu plus app-level actions (open link in browser, copy URL, reload, reader mode)
+    Extended,
+    /// No context menu at all
+    Disabled,
+}
+
+/// Which network destinations the app can reach.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum NetworkIsolation {
+    #[default]
+    Off,
+    /// Only loopback addresses, for local web UIs
+    LocalhostOnly,
+    /// No network at all, for local files
+    NoNetwork,
+}
+
+/// Device emulation presets; each sets the window size, user agent and viewport.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum DevicePreset {
+    #[default]
+    Off,
+    Phone,
+    Tablet,
+    /// Window size and user agent as configured, with a custom pixel ratio
+    Custom,
+}
+
+/// Window size, pixel ratio and user agent a preset stands for.
+pub struct DeviceProfile {
+    pub width: f64,
+    pub height: f64,
+    pub device_pixel_ratio: f64,
+    pub user_agent: &'static str,
+}
+
+impl DevicePreset {
+    pub fn profile(self) -> Option<DeviceProfile> {
+        match self {
+            Self::Phone => Some(DeviceProfile {
+                width: 412.0,
+                height: 915.0,
+                device_pixel_ratio: 2.625,
+                user_agent: crate::MOBILE_UA,
+            }),
+            Self::Tablet => Some(DeviceProfile {
+                width: 820.0,
+                height: 1180.0,
+                device_pixel_ratio: 2.0,
+                user_agent: crate::TABLET_UA,
+            }),
+            Self::Off | Self::Custom => None,
+        }
+    }
+}
+
+/// Emulated device; the window size and user agent live in their own settings.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq)]
+pub struct DeviceEmulation {
+    pub preset: DevicePreset,
+    pub device_pixel_ratio: f64,
+}
+
+/// What happens to forwarded notifications during quiet hours.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum QuietHoursAction {
+    #[default]
+    Suppress,
+    /// Show them, but with low urgency so they don't pop up
+    LowUrgency,
+}
+
+/// When forwarded notifications are held back.
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct QuietHours {
+    /// Daily window in minutes after midnight; wraps past midnight when start > end
+    pub window: Option<(u16, u16)>,
+    pub while_locked: bool,
+    pub action: QuietHoursAction,
+}
+
+impl QuietHours {
+    /// Parse a window written as `HH:MM-HH:MM`.
+    pub fn parse_window(text: &str) -> Option<(u16, u16)> {
+        let minutes = |time: &str| {
+            let (hours, minutes) = time.trim().split_once(':')?;
+            let (hours, minutes) = (hours.parse::<u16>().ok()?, minutes.parse::<u16>().ok()?);
+            (hours < 24 && minutes < 60).then_some(hours * 60 + minutes)
+        };
+        let (start, end) = text.split_once('-')?;
+        Some((minutes(start)?, minutes(end)?))
+    }
+
+    pub fn format_window((start, end): (u16, u16)) -> String {
+        format!(
+            "{:02}:{:02}-{:02}:{:02}",
+            start / 60,
+            start % 60,
+            end / 60,
+            end % 60
+        )
+    }
+
+    /// Whether `minute` (after midnight) falls inside the daily window.
+    pub fn covers(&self, minute: u16) -> bool {
+        match self.window {
+            Some((start, end)) if start <= end => (start..end).contains(&minute),
+            Some((start, end)) => minute >= start || minute < end,
+            None => false,
+        }
+    }
+}
+
+/// Wildcard host rules deciding where the app may navigate.
+///
+/// Patterns like `*.example.com` match the host and all its subdomains; a pattern
+/// containing `/` is matched against `host/path` instead. Deny rules win over allow rules,
+/// and an empty allow list allows every host not denied.
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct NavigationRules {
+    pub allow: Vec<String>,
+    pub deny: Vec<String>,
+}
+
+/// Glob match where `*` stands for any run of characters.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let Some((first, rest)) = pattern.split_once('*') else {
+        return pattern == text;
+    };
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let mut parts: Vec<&str> = rest.split('*').collect();
+    let last = parts.pop().unwrap_or_default();
+    for part in parts {
+        match remaining.find(part) {
+            Some(idx) => remaining = &remaining[idx + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+impl NavigationRules {
+    /// Split a comma- or whitespace-separated list of patterns.
+    pub fn parse_patterns(text: &str) -> Vec<String> {
+        text.split(|c: char| c == ',' || c.is_whitespace())
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.deny.is_empty()
+    }
+
+    fn matches(pattern: &str, host: &str, path: &str) -> bool {
+        if pattern.contains('/') {
+            return wildcard_match(pattern, &format!("{host}{path}"));
+        }
+        // `*.example.com` also covers `example.com` itself
+        wildcard_match(pattern, host)
+            || pattern.strip_prefix("*.").is_some_and(|domain| domain == host)
+    }
+
+    /// Whether the rules let the app load `url`. Only http(s) URLs are subject to them.
+    pub fn allows(&self, url: &str) -> bool {
+        let Ok(url) = url::Url::parse(url) else {
+            return true;
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            return true;
+        }
+        let host = url.host_str().unwrap_or_default().to_lowercase();
+        let path = url.path();
+        if self.deny.iter().any(|p| Self::matches(p, &host, path)) {
+            return false;
+        }
+        self.allow.is_empty() || self.allow.iter().any(|p| Self::matches(p, &host, path))
+    }
+}
+
+/// How a locked app is unlocked.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, Default, PartialEq, Eq, EnumIter)]
+pub enum LockMethod {
+    /// A PIN set for this app
+    #[default]
+    Pin,
+    /// The system password, through polkit
+    System,
+}
+
+/// Authentication required before the app's content is shown.
+///
+/// The PIN only keeps others on a shared session out; it is not encryption of the
+/// app's data.
+#[derive(Debug, Clone, Deserialize, Serialize, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct AppLock {
+    pub method: LockMethod,
+    /// PBKDF2-HMAC-SHA256 of the PIN, as `iterations$salt$hash` with salt and hash in hex
+    pub pin_hash: String,
+    /// Lock again after this many idle minutes (0 never re-locks)
+    pub idle_minutes: u32,
+}
+
+/// PBKDF2 rounds for new PINs. A short PIN has few candidates, so each guess is made
+/// expensive instead.
+const PIN_ITERATIONS: u32 = 600_000;
+
+/// Most rounds a saved hash may ask for, so an edited settings file can't stall the app
+/// on its lock screen.
+const MAX_PIN_ITERATIONS: u32 = 4 * PIN_ITERATIONS;
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    hex.as_bytes()
+        .chunks(2)
+        .map(|pair| {
+            let pair = std::str::from_utf8(pair).ok().filter(|pair| pair.len() == 2)?;
+            u8::from_str_radix(pair, 16).ok()
+        })
+        .collect()
+}
+
+/// PBKDF2 with HMAC-SHA256, producing a single 32-byte block.
+fn pbkdf2_sha256(password: &[u8], salt: &[u8], iterations: u32) -> [u8; 32] {
+    pbkdf2::pbkdf2_hmac_array::<sha2::Sha256, 32>(password, salt, iterations)
+}
+
+/// Compare without stopping at the first difference, so the time taken doesn't reveal
+/// how much of a guess matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+impl AppLock {
+    /// Hash `pin` with a fresh random salt. As slow as `verify_pin`.
+    pub fn hash_pin(pin: &str) -> String {
+        use rand::Rng;
+        let salt: [u8; 16] = rand::rng().random();
+        let hash = pbkdf2_sha256(pin.as_bytes(), &salt, PIN_ITERATIONS);
+        format!("{PIN_ITERATIONS}${}${}", to_hex(&salt), to_hex(&hash))
+    }
+
+    /// Check `pin` against the saved hash. Takes a noticeable moment, so callers keep it
+    /// off the UI thread.
+    pub fn verify_pin(&self, pin: &str) -> bool {
+        let mut parts = self.pin_hash.split('$');
+        let (Some(iterations), Some(salt), Some(hash), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return false;
+        };
+        let (Ok(iterations), Some(salt), Some(hash)) =
+            (iterations.parse::<u32>(), from_hex(salt), from_hex(hash))
+        else {
+            return false;
+        };
+        (1..=MAX_PIN_ITERATIONS).contains(&iterations)
+            && constant_time_eq(&pbkdf2_sha256(pin.as_bytes(), &salt, iterations), &hash)
+    }
+}
+
+/// WebKit engine features that can be toggled per app.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WebFeatures {
+    pub javascript: bool,
+    pub webgl: bool,
+    pub autoplay: AutoplayPolicy,
+    pub smooth_scrolling: bool,
+    pub page_cache: bool,
+}
+
+impl Default for WebFeatures {
+    fn default() -> Self {
+        Self {
+            javascript: true,
+            webgl: true,
+            autoplay: AutoplayPolicy::default(),
+            smooth_scrolling: true,
+            page_cache: true,
+        }
+    }
 }
 
 /// Sanitize an app ID for safe use in filesystem paths and desktop entry filenames.
@@ -54,6 +449,14 @@ pub struct Browser {
     pub try_simulate_mobile: Option<bool>,
     pub custom_css: Option<String>,
     pub custom_js: Option<String>,
+    // Files read again at every launch instead of the saved CSS and JavaScript, which
+    // stay as a fallback
+    pub custom_css_file: Option<PathBuf>,
+    pub custom_js_file: Option<PathBuf>,
+    // Pages the CSS and JavaScript are limited to, as `/mail/*` paths of the app or
+    // patterns like those of the navigation rules; all pages when empty
+    pub custom_css_scope: Option<Vec<String>>,
+    pub custom_js_scope: Option<Vec<String>>,
     pub user_agent: Option<UserAgent>,
     pub permissions: Option<PermissionPolicy>,
     pub url_schemes: Option<Vec<String>>,
@@ -77,6 +480,88 @@ pub struct Browser {
     pub minimize_to_background: Option<bool>,
     // #62: Auto dark mode
     pub auto_dark_mode: Option<bool>,
+    // Auto-reload interval in minutes (0 or None disables it)
+    pub auto_reload_minutes: Option<u32>,
+    // Splash screen shown until the first page load finishes
+    pub splash_screen: Option<bool>,
+    // Theme color (`#rrggbb`) used for the splash screen and window background
+    pub theme_color: Option<String>,
+    // Slim navigation toolbar above the page
+    pub navigation_toolbar: Option<bool>,
+    // Disk cache size limit in MB (0 disables the disk cache)
+    pub cache_limit_mb: Option<u32>,
+    // Fresh throwaway profile per launch, deleted on exit
+    pub ephemeral_profile: Option<bool>,
+    // WebKit feature toggles
+    pub web_features: Option<WebFeatures>,
+    // Location portal accuracy for the Geolocation API
+    pub geolocation_accuracy: Option<GeolocationAccuracy>,
+    // Save and autofill login credentials through the Secret Service
+    pub password_autofill: Option<bool>,
+    // Extra CA bundle (PEM path) trusted only by this app
+    pub ca_certificate: Option<String>,
+    // Send Do Not Track and Global Privacy Control signals
+    pub do_not_track: Option<bool>,
+    // Referer header policy
+    pub referrer_policy: Option<ReferrerPolicy>,
+    // Blur canvas, WebGL, screen and hardware fingerprints
+    pub fingerprint_resistance: Option<bool>,
+    // Start with all page audio muted
+    pub muted: Option<bool>,
+    // Block images and web fonts and disable prefetching
+    pub data_saver: Option<bool>,
+    // Global show/hide shortcut (preferred trigger, e.g. CTRL+ALT+S)
+    pub global_shortcut: Option<String>,
+    // Start with the window hidden (for autostart with minimize to background)
+    pub start_hidden: Option<bool>,
+    // Start the app at login, from an entry in the autostart directory
+    pub autostart: Option<bool>,
+    // Start hidden only when started at login
+    pub autostart_hidden: Option<bool>,
+    // Notification quiet hours
+    pub quiet_hours: Option<QuietHours>,
+    // Spell checking
+    pub spellcheck: Option<bool>,
+    // Spell checking languages (e.g. en_US); empty uses the locale
+    pub spellcheck_languages: Option<Vec<String>>,
+    // Default folder for file uploads
+    pub upload_directory: Option<String>,
+    // Start maximized or fullscreen
+    pub window_mode: Option<WindowMode>,
+    // Window position, relative to the target monitor when one is set
+    pub window_position: Option<(i32, i32)>,
+    // Monitor (connector name, e.g. DP-1) to open on
+    pub monitor: Option<String>,
+    // Show on all workspaces
+    pub sticky: Option<bool>,
+    // Hide from the taskbar and dock
+    pub skip_taskbar: Option<bool>,
+    // Software rendering for GPUs that render WebKit black
+    pub disable_hardware_acceleration: Option<bool>,
+    // Device emulation preset and pixel ratio
+    pub device_emulation: Option<DeviceEmulation>,
+    // Swipe gestures for back/forward (on unless disabled)
+    pub swipe_navigation: Option<bool>,
+    // Right-click menu: default, extended with app actions, or disabled
+    pub context_menu: Option<ContextMenu>,
+    // Wildcard allow/deny rules for navigations and new windows
+    pub navigation_rules: Option<NavigationRules>,
+    // Append every top-level navigation to a per-app log
+    pub navigation_log: Option<bool>,
+    // Require a PIN or the system password before showing the app
+    pub app_lock: Option<AppLock>,
+    // Daily usage limit in minutes (0 or None disables it)
+    pub daily_limit_minutes: Option<u32>,
+    // Run the webview inside bubblewrap with a home holding only its own data
+    pub sandbox: Option<bool>,
+    // Restrict the app to localhost or no network at all
+    pub network_isolation: Option<NetworkIsolation>,
+    // DNS-over-HTTPS resolver (RFC 8484 endpoint) used instead of system DNS
+    pub doh_url: Option<String>,
+    // Host names pinned to addresses for this app only
+    pub host_overrides: Option<Vec<HostOverride>>,
+    // Drop the web process once the window has been hidden this many minutes
+    pub suspend_after_minutes: Option<u32>,
 }
 
 impl Browser {
@@ -84,8 +569,9 @@ impl Browser {
         let safe_id = sanitize_app_id(app_id);
         let mut browser = Self {
             app_id: crate::WebviewArgs {
-                id: safe_id.clone(),
+                id: safe_id,
                 private: false,
+                ..Default::default()
             },
             window_title: None,
             url: None,
@@ -96,6 +582,10 @@ impl Browser {
             try_simulate_mobile: None,
             custom_css: None,
             custom_js: None,
+            custom_css_file: None,
+            custom_js_file: None,
+            custom_css_scope: None,
+            custom_js_scope: None,
             user_agent: None,
             permissions: None,
             url_schemes: None,
@@ -110,33 +600,96 @@ impl Browser {
             last_launched: None,
             minimize_to_background: None,
             auto_dark_mode: None,
+            auto_reload_minutes: None,
+            splash_screen: None,
+            theme_color: None,
+            navigation_toolbar: None,
+            cache_limit_mb: None,
+            ephemeral_profile: None,
+            web_features: None,
+            geolocation_accuracy: None,
+            password_autofill: None,
+            ca_certificate: None,
+            do_not_track: None,
+            referrer_policy: None,
+            fingerprint_resistance: None,
+            muted: None,
+            data_saver: None,
+            global_shortcut: None,
+            start_hidden: None,
+            autostart: None,
+            autostart_hidden: None,
+            quiet_hours: None,
+            spellcheck: None,
+            spellcheck_languages: None,
+            upload_directory: None,
+            window_mode: None,
+            window_position: None,
+            monitor: None,
+            sticky: None,
+            skip_taskbar: None,
+            disable_hardware_acceleration: None,
+            device_emulation: None,
+            swipe_navigation: None,
+            context_menu: None,
+            navigation_rules: None,
+            navigation_log: None,
+            app_lock: None,
+            daily_limit_minutes: None,
+            sandbox: None,
+            network_isolation: None,
+            doh_url: None,
+            host_overrides: None,
+            suspend_after_minutes: None,
         };
 
-        if with_profile {
-            if let Some(xdg_data) = dirs::data_dir() {
-                let path = xdg_data.join(crate::APP_ID).join("profiles").join(&safe_id);
-                browser.profile = Some(path);
-            }
-        };
-
+        browser.set_persistent(with_profile);
         browser
     }
 
+    /// Keep the app's website data in its profile directory, or forget it when the app
+    /// closes.
+    pub fn set_persistent(&mut self, persistent: bool) {
+        self.profile = if persistent {
+            dirs::data_dir().map(|xdg_data| {
+                xdg_data
+                    .join(crate::APP_ID)
+                    .join("profiles")
+                    .join(&self.app_id.id)
+            })
+        } else {
+            None
+        };
+    }
+
     pub fn from_appid(id: &str) -> Option<Self> {
-        let safe_id = sanitize_app_id(id);
-        let db_path = crate::database_path(&format!("{safe_id}.ron"))?;
+        crate::launcher::WebAppLauncher::from_appid(id).map(|launcher| launcher.browser)
+    }
 
-        let content = std::fs::read_to_string(&db_path).ok()?;
+    /// Registered URL schemes, lowercased and stripped of anything that isn't valid
+    /// in a scheme name.
+    pub fn valid_url_schemes(&self) -> Vec<String> {
+        self.url_schemes
+            .iter()
+            .flatten()
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| {
+                s.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && s
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+            })
+            .collect()
+    }
 
-        // Same 64KB safety limit used in launcher::installed_webapps()
-        const MAX_RON_SIZE: usize = 64 * 1024;
-        if content.len() > MAX_RON_SIZE {
-            tracing::warn!("RON file too large: {}", db_path.display());
-            return None;
-        }
+    /// The CSS to inject: the linked file if it can be read, or the saved CSS.
+    pub fn load_custom_css(&self) -> Option<String> {
+        read_linked(self.custom_css_file.as_deref()).or_else(|| self.custom_css.clone())
+    }
 
-        let launcher: crate::launcher::WebAppLauncher = ron::from_str(&content).ok()?;
-        Some(launcher.browser)
+    /// The JavaScript to inject: the linked file if it can be read, or the saved script.
+    pub fn load_custom_js(&self) -> Option<String> {
+        read_linked(self.custom_js_file.as_deref()).or_else(|| self.custom_js.clone())
     }
 
     pub fn get_exec(&self) -> String {
@@ -157,3 +710,136 @@ impl Browser {
         }
     }
 }
+
+/// Contents of a linked CSS or JavaScript file.
+fn read_linked(file: Option<&std::path::Path>) -> Option<String> {
+    let file = file?;
+    match std::fs::read_to_string(file) {
+        Ok(code) => Some(code),
+        Err(e) => {
+            tracing::warn!("Failed to read {}: {e}", file.display());
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_hours_window() {
+        assert_eq!(QuietHours::parse_window("22:00-07:30"), Some((1320, 450)));
+        assert_eq!(
+            QuietHours::parse_window(" 9:05 - 17:00 "),
+            Some((545, 1020))
+        );
+        assert_eq!(QuietHours::parse_window("24:00-07:00"), None);
+        assert_eq!(QuietHours::parse_window("22:60-07:00"), None);
+        assert_eq!(QuietHours::parse_window("22:00"), None);
+        assert_eq!(QuietHours::format_window((1320, 450)), "22:00-07:30");
+    }
+
+    #[test]
+    fn quiet_hours_wrap_past_midnight() {
+        let quiet = QuietHours {
+            window: QuietHours::parse_window("22:00-07:00"),
+            ..QuietHours::default()
+        };
+        assert!(quiet.covers(22 * 60));
+        assert!(quiet.covers(23 * 60 + 59));
+        assert!(quiet.covers(0));
+        assert!(quiet.covers(6 * 60 + 59));
+        assert!(!quiet.covers(7 * 60));
+        assert!(!quiet.covers(12 * 60));
+        assert!(!quiet.covers(21 * 60 + 59));
+    }
+
+    #[test]
+    fn quiet_hours_same_day() {
+        let quiet = QuietHours {
+            window: Some((9 * 60, 17 * 60)),
+            ..QuietHours::default()
+        };
+        assert!(quiet.covers(9 * 60));
+        assert!(!quiet.covers(17 * 60));
+        assert!(!quiet.covers(0));
+        assert!(!QuietHours::default().covers(0));
+    }
+
+    #[test]
+    fn wildcards() {
+        assert!(wildcard_match("*.example.com", "mail.example.com"));
+        assert!(wildcard_match("*.example.com", "a.b.example.com"));
+        assert!(!wildcard_match("*.example.com", "example.com"));
+        assert!(!wildcard_match("*.example.com", "badexample.com"));
+        assert!(wildcard_match(
+            "example.com/docs/*",
+            "example.com/docs/intro"
+        ));
+        assert!(wildcard_match("a*b*c", "abc"));
+        assert!(!wildcard_match("a*bc", "abc.bc.x"));
+        assert!(wildcard_match("exact.org", "exact.org"));
+    }
+
+    #[test]
+    fn subdomain_pattern_covers_the_domain() {
+        let rules = NavigationRules {
+            allow: vec!["*.example.com".to_string()],
+            deny: Vec::new(),
+        };
+        assert!(rules.allows("https://example.com/"));
+        assert!(rules.allows("https://www.example.com/page"));
+        assert!(!rules.allows("https://notexample.com/"));
+        assert!(!rules.allows("https://example.org/"));
+        // Only http(s) is subject to the rules
+        assert!(rules.allows("about:blank"));
+    }
+
+    #[test]
+    fn deny_wins_over_allow() {
+        let rules = NavigationRules {
+            allow: NavigationRules::parse_patterns("*.example.com, example.org"),
+            deny: NavigationRules::parse_patterns("ads.example.com example.com/private/*"),
+        };
+        assert!(rules.allows("https://www.example.com/"));
+        assert!(!rules.allows("https://ads.example.com/banner"));
+        assert!(!rules.allows("https://example.com/private/page"));
+        assert!(rules.allows("https://example.com/public"));
+        assert!(rules.allows("https://EXAMPLE.org/"));
+
+        let deny_only = NavigationRules {
+            allow: Vec::new(),
+            deny: vec!["*.tracker.net".to_string()],
+        };
+        assert!(deny_only.allows("https://anything.com/"));
+        assert!(!deny_only.allows("https://tracker.net/"));
+    }
+
+    #[test]
+    fn host_overrides() {
+        let parsed = HostOverride::parse_list("API.example.com.=10.0.0.1, db.local=::1\n").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                HostOverride {
+                    host: "api.example.com".to_string(),
+                    address: "10.0.0.1".to_string(),
+                },
+                HostOverride {
+                    host: "db.local".to_string(),
+                    address: "::1".to_string(),
+                },
+            ]
+        );
+        assert_eq!(
+            HostOverride::format_list(&parsed),
+            "api.example.com=10.0.0.1, db.local=::1"
+        );
+        assert_eq!(HostOverride::parse_list(""), Some(Vec::new()));
+        assert_eq!(HostOverride::parse_list("example.com"), None);
+        assert_eq!(HostOverride::parse_list("example.com=not-an-ip"), None);
+        assert_eq!(HostOverride::parse_list("=10.0.0.1"), None);
+        assert_eq!(HostOverride::parse_list("a.com=10.0.0.1 b.com=bad"), None);
+    }
+}
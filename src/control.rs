@@ -0,0 +1,60 @@
+//! Control channel for running web apps.
+//!
+//! Each webview that owns its bus name (see [`crate::dbus_name`]) also serves the
+//! `dev.heppen.webapps.Control` interface, so the manager and scripts can drive it:
+//!
+//! ```text
+//! busctl --user call dev.heppen.webapps.<AppId> /dev/heppen/webapps/<AppId> \
+//!     dev.heppen.webapps.Control Navigate s https://example.com
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+pub const INTERFACE: &str = "dev.heppen.webapps.Control";
+
+/// Commands a running web app accepts.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Command {
+    Reload,
+    Navigate(String),
+    Show,
+    Hide,
+    SetZoom(f64),
+    /// Clear one category of the app's website data, or all of it
+    ClearData(Option<crate::ProfileData>),
+    Quit,
+}
+
+/// Send `command` to the running instance of `app_id`.
+pub async fn send(app_id: &str, command: &Command) -> zbus::Result<()> {
+    let name = crate::dbus_name(app_id)
+        .ok_or_else(|| zbus::Error::Failure(format!("'{app_id}' has no bus name")))?;
+    let path = crate::dbus_object_path(&name);
+    let connection = zbus::Connection::session().await?;
+    let (name, path) = (Some(name.as_str()), path.as_str());
+
+    match command {
+        Command::Reload => connection.call_method(name, path, Some(INTERFACE), "Reload", &()).await,
+        Command::Navigate(url) => {
+            connection
+                .call_method(name, path, Some(INTERFACE), "Navigate", &(url.as_str(),))
+                .await
+        }
+        Command::Show => connection.call_method(name, path, Some(INTERFACE), "Show", &()).await,
+        Command::Hide => connection.call_method(name, path, Some(INTERFACE), "Hide", &()).await,
+        Command::SetZoom(level) => {
+            connection
+                .call_method(name, path, Some(INTERFACE), "SetZoom", &(*level,))
+                .await
+        }
+        Command::ClearData(category) => {
+            // The interface takes the category's name, empty for everything
+            let category = category.map(|c| format!("{c:?}")).unwrap_or_default();
+            connection
+                .call_method(name, path, Some(INTERFACE), "ClearData", &(category.as_str(),))
+                .await
+        }
+        Command::Quit => connection.call_method(name, path, Some(INTERFACE), "Quit", &()).await,
+    }?;
+    Ok(())
+}
@@ -0,0 +1,109 @@
+//! Cookies stored in an app's persistent profile.
+//!
+//! WebKit keeps them in a `cookies` text jar using the Netscape `cookies.txt` format, one
+//! cookie per line: domain, subdomain flag, path, secure flag, expiry, name and value,
+//! tab-separated. HttpOnly cookies carry a `#HttpOnly_` prefix on the domain.
+
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "cookies";
+
+const HTTP_ONLY_PREFIX: &str = "#HttpOnly_";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cookie {
+    pub domain: String,
+    pub path: String,
+    pub name: String,
+    pub value: String,
+    pub secure: bool,
+    pub http_only: bool,
+    /// Unix timestamp, 0 for a session cookie
+    pub expires: u64,
+}
+
+impl Cookie {
+    fn parse(line: &str) -> Option<Self> {
+        let (line, http_only) = match line.strip_prefix(HTTP_ONLY_PREFIX) {
+            Some(rest) => (rest, true),
+            None if line.starts_with('#') => return None,
+            None => (line, false),
+        };
+        let mut fields = line.split('\t');
+        let domain = fields.next()?.to_string();
+        let _subdomains = fields.next()?;
+        let path = fields.next()?.to_string();
+        let secure = fields.next()? == "TRUE";
+        let expires = fields.next()?.parse().unwrap_or(0);
+        let name = fields.next()?.to_string();
+        let value = fields.next().unwrap_or_default().to_string();
+        Some(Self {
+            domain,
+            path,
+            name,
+            value,
+            secure,
+            http_only,
+            expires,
+        })
+    }
+
+    /// Whether this is the same cookie, which is identified by domain, path and name.
+    fn is(&self, other: &Cookie) -> bool {
+        self.domain == other.domain && self.path == other.path && self.name == other.name
+    }
+}
+
+pub fn path(profile: &Path) -> PathBuf {
+    profile.join(FILE_NAME)
+}
+
+/// All cookies in the profile, sorted by domain and name.
+pub fn load(profile: &Path) -> Vec<Cookie> {
+    let Ok(content) = std::fs::read_to_string(path(profile)) else {
+        return Vec::new();
+    };
+    let mut cookies: Vec<Cookie> = content.lines().filter_map(Cookie::parse).collect();
+    cookies.sort_by(|a, b| {
+        a.domain
+            .trim_start_matches('.')
+            .cmp(b.domain.trim_start_matches('.'))
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    cookies
+}
+
+/// Remove one cookie from the jar. A running app keeps it in memory until it exits.
+pub fn delete(profile: &Path, cookie: &Cookie) -> Result<(), std::io::Error> {
+    let file = path(profile);
+    let content = match std::fs::read_to_string(&file) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(e),
+    };
+    let kept: String = content
+        .lines()
+        .filter(|line| !Cookie::parse(line).is_some_and(|c| c.is(cookie)))
+        .map(|line| format!("{line}\n"))
+        .collect();
+    let tmp = profile.join(format!("{FILE_NAME}.tmp"));
+    std::fs::write(&tmp, kept)?;
+    std::fs::rename(tmp, file)
+}
+
+/// Write the profile's cookies to `target` as a `cookies.txt` file other tools can import.
+/// Returns how many cookies were exported.
+pub fn export(profile: &Path, target: &Path) -> Result<usize, std::io::Error> {
+    let content = std::fs::read_to_string(path(profile)).unwrap_or_default();
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| Cookie::parse(line).is_some())
+        .collect();
+    let mut out = String::from("# Netscape HTTP Cookie File\n\n");
+    for line in &lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    std::fs::write(target, out)?;
+    Ok(lines.len())
+}
@@ -0,0 +1,125 @@
+//! Downloaded favicons by origin, with the validators needed to ask the server whether
+//! they changed.
+//!
+//! Recently checked favicons are reused without a request, older ones are revalidated,
+//! and a cached favicon is used when the network is unavailable. Icon downloads run
+//! concurrently, so changes are made under a lock on the reloaded cache.
+
+use std::{collections::HashMap, os::fd::AsRawFd as _, path::PathBuf, time::UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{APP_ID, http::Validators};
+
+/// Favicons checked within this many seconds are used without asking the server.
+const FRESH_SECS: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CachedFavicon {
+    /// The saved icon file
+    pub path: String,
+    /// URL the icon was downloaded from, which the validators belong to
+    pub source: String,
+    pub validators: Validators,
+    /// Unix timestamp of the last download or revalidation
+    pub checked: u64,
+}
+
+impl CachedFavicon {
+    pub fn exists(&self) -> bool {
+        std::path::Path::new(&self.path).is_file()
+    }
+
+    pub fn is_fresh(&self) -> bool {
+        now().saturating_sub(self.checked) < FRESH_SECS
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FaviconCache {
+    pub entries: HashMap<String, CachedFavicon>,
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_ID).join("favicons.ron"))
+}
+
+/// Cache key: scheme, host and port of `url`.
+pub fn origin(url: &url::Url) -> String {
+    url.origin().ascii_serialization()
+}
+
+impl FaviconCache {
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| ron::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Reload the cache, apply `change` and save it if that returns `true`, holding a lock
+    /// so concurrent updates don't drop each other's entries.
+    fn update(change: impl FnOnce(&mut Self) -> bool) -> std::io::Result<()> {
+        let path = path().ok_or_else(|| std::io::Error::other("no cache directory"))?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        // Held until the new cache is in place; closing the file releases it
+        let lock = std::fs::File::create(path.with_extension("lock"))?;
+        // SAFETY: `flock` only takes the descriptor, which `lock` keeps open
+        if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX) } == -1 {
+            return Err(std::io::Error::last_os_error());
+        }
+
+        let mut cache = Self::load();
+        if !change(&mut cache) {
+            return Ok(());
+        }
+        let serialized = ron::ser::to_string_pretty(&cache, ron::ser::PrettyConfig::default())
+            .map_err(std::io::Error::other)?;
+        let tmp = path.with_extension("ron.tmp");
+        std::fs::write(&tmp, serialized)?;
+        std::fs::rename(tmp, path)
+    }
+
+    /// The cached favicon of `origin`, if its file is still there.
+    pub fn get(&self, origin: &str) -> Option<&CachedFavicon> {
+        self.entries.get(origin).filter(|entry| entry.exists())
+    }
+
+    /// Record a download or revalidation of `origin`'s favicon and save the cache.
+    pub fn store(origin: &str, path: String, source: String, validators: Validators) {
+        let result = Self::update(|cache| {
+            cache.entries.insert(
+                origin.to_string(),
+                CachedFavicon {
+                    path,
+                    source,
+                    validators,
+                    checked: now(),
+                },
+            );
+            true
+        });
+        if let Err(e) = result {
+            tracing::warn!("Failed to save the favicon cache: {e}");
+        }
+    }
+
+    /// Forget `origin`'s favicon, so the next download starts from scratch.
+    pub fn remove(origin: &str) {
+        if let Err(e) = Self::update(|cache| cache.entries.remove(origin).is_some()) {
+            tracing::warn!("Failed to save the favicon cache: {e}");
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Opt-in refresh of the favicons of installed apps, run when the manager starts, and
+//! re-downloading the icons of apps on request.
+//!
+//! Only apps whose icon is the favicon downloaded for their site are refreshed, so icons
+//! the user picked or edited are left alone. Apps whose favicon changed are installed
+//! again, and the change is recorded in a log the manager shows.
+
+use std::{
+    path::{Path, PathBuf},
+    time::UNIX_EPOCH,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{APP_ID, favicon_cache, http, launcher::WebAppLauncher};
+
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Changes kept in the log.
+const MAX_CHANGES: usize = 50;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum Schedule {
+    #[default]
+    Off,
+    /// Every time the manager starts
+    OnLaunch,
+    /// When the manager starts and the last refresh is a week old
+    Weekly,
+}
+
+impl Schedule {
+    pub fn is_due(self, log: &RefreshLog) -> bool {
+        match self {
+            Schedule::Off => false,
+            Schedule::OnLaunch => true,
+            Schedule::Weekly => now().saturating_sub(log.last_run) >= WEEK_SECS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct FaviconChange {
+    pub app_id: String,
+    pub name: String,
+    /// Unix timestamp of the refresh that found it
+    pub time: u64,
+}
+
+/// Outcome of re-downloading the icon of an app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refetch {
+    /// The app got a new icon and was installed again
+    Updated,
+    Unchanged,
+    /// The app has no site, or none of its icons could be downloaded
+    NoIcon,
+    Failed(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RefreshLog {
+    /// Unix timestamp of the last refresh
+    pub last_run: u64,
+    /// Newest first
+    pub changes: Vec<FaviconChange>,
+}
+
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn path() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("favicon-refresh.ron"))
+}
+
+impl RefreshLog {
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| ron::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(&self) -> std::io::Result<()> {
+        let path = path().ok_or_else(|| std::io::Error::other("no data directory"))?;
+        let serialized = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(std::io::Error::other)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(path, serialized)
+    }
+
+    /// Add `changes` to the log and save it, also marking a scheduled refresh as done.
+    fn record(changes: &[FaviconChange], scheduled: bool) {
+        let mut log = Self::load();
+        if scheduled {
+            log.last_run = now();
+        }
+        log.changes.splice(0..0, changes.iter().cloned());
+        log.changes.truncate(MAX_CHANGES);
+        if let Err(e) = log.save() {
+            tracing::warn!("Failed to save the favicon refresh log: {e}");
+        }
+    }
+}
+
+/// Whether `app` still uses the favicon downloaded for its site.
+fn uses_downloaded_favicon(app: &WebAppLauncher, url: &url::Url) -> bool {
+    let (Some(domain), Some(icons)) = (url.host_str(), crate::icons_location()) else {
+        return false;
+    };
+    let domain = crate::sanitize_domain_for_filename(domain);
+    ["png", "svg"].iter().any(|extension| {
+        icons.join(format!("favicon-{domain}.{extension}")) == Path::new(&app.icon)
+    })
+}
+
+/// Download the favicons of the installed apps again and reinstall the apps whose icon
+/// changed. Returns the changes, which are also added to the log.
+pub async fn run() -> Vec<FaviconChange> {
+    let cancel = http::Cancel::default();
+    let mut changes = Vec::new();
+    for mut app in crate::launcher::installed_webapps_async().await {
+        let Some(url) = app.browser.url.clone() else {
+            continue;
+        };
+        let Ok(parsed) = url::Url::parse(&url) else {
+            continue;
+        };
+        if !uses_downloaded_favicon(&app, &parsed) {
+            continue;
+        }
+
+        let before = tokio::fs::read(&app.icon).await.ok();
+        let Some(path) = crate::download_favicon(&url, &cancel).await else {
+            continue;
+        };
+        match apply(&mut app, path, before).await {
+            Ok(Some(change)) => changes.push(change),
+            Ok(None) => {}
+            Err(e) => tracing::warn!("Failed to refresh the icon of {}: {e}", app.name),
+        }
+    }
+
+    RefreshLog::record(&changes, true);
+    changes
+}
+
+/// Download the favicon of `app`'s site again, skipping the cached one, and make it the
+/// app's icon. Unlike [`run`], this also replaces icons the user picked.
+pub async fn refetch(mut app: WebAppLauncher, cancel: &http::Cancel) -> Refetch {
+    let Some(url) = app.browser.url.clone() else {
+        return Refetch::NoIcon;
+    };
+    let Ok(parsed) = url::Url::parse(&url) else {
+        return Refetch::NoIcon;
+    };
+    favicon_cache::FaviconCache::remove(&favicon_cache::origin(&parsed));
+
+    let before = tokio::fs::read(&app.icon).await.ok();
+    let Some(path) = crate::download_favicon(&url, cancel).await else {
+        return Refetch::NoIcon;
+    };
+    match apply(&mut app, path, before).await {
+        Ok(Some(change)) => {
+            RefreshLog::record(&[change], false);
+            Refetch::Updated
+        }
+        Ok(None) => Refetch::Unchanged,
+        Err(e) => Refetch::Failed(e),
+    }
+}
+
+/// Make the downloaded icon at `path` the icon of `app` and install it again, unless it is
+/// the icon the app already had, whose data was `before`.
+async fn apply(
+    app: &mut WebAppLauncher,
+    path: String,
+    before: Option<Vec<u8>>,
+) -> Result<Option<FaviconChange>, String> {
+    if path == app.icon && tokio::fs::read(&path).await.ok() == before {
+        return Ok(None);
+    }
+
+    app.icon = path;
+    app.save().map_err(|e| e.to_string())?;
+    if let Err(e) = app.create().await {
+        tracing::warn!("Failed to reinstall {} with its new icon: {e}", app.name);
+    }
+    Ok(Some(FaviconChange {
+        app_id: app.browser.app_id.as_ref().to_string(),
+        name: app.name.clone(),
+        time: now(),
+    }))
+}
@@ -0,0 +1,63 @@
+//! App icons installed into the user's hicolor icon theme at several sizes, so docks and
+//! window switchers pick a crisp one instead of scaling a single file.
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+    APP_ID,
+    icon_edit::{self, IconEdit},
+};
+
+/// Sizes installed for every app icon.
+pub const SIZES: [u32; 5] = [32, 64, 128, 256, 512];
+
+/// Always under the real home directory: inside Flatpak the XDG data dir points into the
+/// sandbox, where the desktop would never see it.
+fn theme_dir() -> Option<PathBuf> {
+    Some(dirs::home_dir()?.join(".local/share/icons/hicolor"))
+}
+
+/// Icon name of an app, the same as its desktop file id.
+pub fn icon_name(app_id: &str) -> String {
+    format!("{APP_ID}.{app_id}")
+}
+
+/// Install the icon at `source` as `name` at every size in [`SIZES`], and also as a
+/// scalable icon if it is an SVG. Returns the largest PNG.
+pub fn install(source: &Path, name: &str) -> std::io::Result<Vec<u8>> {
+    let dir = theme_dir().ok_or_else(|| std::io::Error::other("no home directory"))?;
+    let mut largest = None;
+    for size in SIZES {
+        let png = icon_edit::render(source, &IconEdit::default(), size)
+            .ok_or_else(|| std::io::Error::other("the icon can't be decoded"))?;
+        let apps = dir.join(format!("{size}x{size}")).join("apps");
+        std::fs::create_dir_all(&apps)?;
+        std::fs::write(apps.join(format!("{name}.png")), &png)?;
+        largest = Some(png);
+    }
+    if crate::is_svg(&source.to_string_lossy()) {
+        let apps = dir.join("scalable").join("apps");
+        std::fs::create_dir_all(&apps)?;
+        std::fs::copy(source, apps.join(format!("{name}.svg")))?;
+    }
+    largest.ok_or_else(|| std::io::Error::other("no icon sizes"))
+}
+
+/// Remove the icons installed as `name`.
+pub fn uninstall(name: &str) {
+    let Some(dir) = theme_dir() else {
+        return;
+    };
+    let files = SIZES
+        .iter()
+        .map(|size| dir.join(format!("{size}x{size}/apps/{name}.png")))
+        .chain(std::iter::once(dir.join(format!("scalable/apps/{name}.svg"))));
+    for file in files {
+        match std::fs::remove_file(&file) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                tracing::warn!("Failed to remove the icon {}: {e}", file.display());
+            }
+            _ => {}
+        }
+    }
+}
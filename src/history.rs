@@ -0,0 +1,71 @@
+//! Per-app browsing history, stored as `history.ron` inside the app's persistent profile.
+//!
+//! Keeping it in the profile directory means clearing the app's data also clears its history.
+
+use serde::{Deserialize, Serialize};
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+pub const FILE_NAME: &str = "history.ron";
+
+/// Oldest entries are dropped once the history grows past this.
+const MAX_ENTRIES: usize = 1000;
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct HistoryEntry {
+    pub url: String,
+    pub title: String,
+    /// Unix timestamp of the visit
+    pub visited: u64,
+}
+
+fn path(profile: &Path) -> PathBuf {
+    profile.join(FILE_NAME)
+}
+
+/// Visits recorded in `profile`, oldest first.
+pub fn load(profile: &Path) -> Vec<HistoryEntry> {
+    std::fs::read_to_string(path(profile))
+        .ok()
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Append a visit; reloading the page that is already newest only refreshes its entry.
+pub fn record(profile: &Path, url: &str, title: &str) -> Result<(), std::io::Error> {
+    let mut entries = load(profile);
+    let visited = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default();
+
+    if entries.last().is_some_and(|last| last.url == url) {
+        entries.pop();
+    }
+    entries.push(HistoryEntry {
+        url: url.to_string(),
+        title: title.to_string(),
+        visited,
+    });
+    if entries.len() > MAX_ENTRIES {
+        entries.drain(..entries.len() - MAX_ENTRIES);
+    }
+
+    let serialized = ron::ser::to_string_pretty(&entries, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    std::fs::create_dir_all(profile)?;
+    // Replaced in one step, so the manager never reads a half-written file
+    let tmp = path(profile).with_extension("ron.tmp");
+    std::fs::write(&tmp, serialized)?;
+    std::fs::rename(tmp, path(profile))
+}
+
+/// Forget every recorded visit.
+pub fn clear(profile: &Path) -> Result<(), std::io::Error> {
+    match std::fs::remove_file(path(profile)) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,305 @@
+//! Downloads for favicons, page titles and thumbnails.
+//!
+//! Requests go through `wget` like the rest of the manager, with a timeout, a redirect
+//! limit and a size cap, and are retried with backoff when the network fails. A
+//! [`Cancel`] handle stops a request early, e.g. when the user edits the URL again.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{io::AsyncReadExt as _, sync::Notify};
+
+/// wget's exit status for network failures, the only ones worth retrying.
+const WGET_NETWORK_FAILURE: i32 = 4;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Options {
+    /// Seconds for DNS, connect and each read
+    pub timeout_secs: u32,
+    pub max_redirects: u32,
+    /// Larger responses are rejected
+    pub max_size: usize,
+    /// Attempts after the first one
+    pub retries: u32,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            timeout_secs: 10,
+            max_redirects: 5,
+            max_size: 2 * 1024 * 1024,
+            retries: 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Cancelled,
+    TooLarge,
+    Failed(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Cancelled => write!(f, "cancelled"),
+            Error::TooLarge => write!(f, "response too large"),
+            Error::Failed(reason) => write!(f, "{reason}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Stops the requests it was passed to. Cloning shares the handle.
+#[derive(Debug, Clone, Default)]
+pub struct Cancel(Arc<(AtomicBool, Notify)>);
+
+impl Cancel {
+    pub fn cancel(&self) {
+        self.0.0.store(true, Ordering::SeqCst);
+        self.0.1.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.0.load(Ordering::SeqCst)
+    }
+
+    async fn cancelled(&self) {
+        // Created before the check, so a cancel in between still wakes it
+        let notified = self.0.1.notified();
+        if !self.is_cancelled() {
+            notified.await;
+        }
+    }
+}
+
+/// Validators from an earlier response, for a conditional request.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Response {
+    /// Status of the final response, after redirects
+    pub status: u16,
+    pub body: Vec<u8>,
+    pub validators: Validators,
+}
+
+impl Response {
+    pub fn not_modified(&self) -> bool {
+        self.status == 304
+    }
+}
+
+/// Answer to a [`head`] request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Head {
+    /// Status of the final response, after redirects
+    pub status: u16,
+    /// Where the redirects led, or the requested URL without any
+    pub url: String,
+}
+
+impl Head {
+    /// Whether the site answered. Some servers refuse HEAD requests but serve pages.
+    pub fn is_ok(&self) -> bool {
+        self.status < 400 || self.status == 405
+    }
+}
+
+/// Status and validators of the last response in wget's `--server-response` output.
+fn parse_headers(output: &str) -> (u16, Validators) {
+    let mut status = 0;
+    let mut validators = Validators::default();
+    for line in output.lines().map(str::trim) {
+        if let Some(rest) = line.strip_prefix("HTTP/") {
+            // A new response after a redirect starts over
+            status = rest
+                .split_whitespace()
+                .nth(1)
+                .and_then(|code| code.parse().ok())
+                .unwrap_or(0);
+            validators = Validators::default();
+        } else if let Some((name, value)) = line.split_once(':') {
+            let value = Some(value.trim().to_string());
+            match name.to_ascii_lowercase().as_str() {
+                "etag" => validators.etag = value,
+                "last-modified" => validators.last_modified = value,
+                _ => {}
+            }
+        }
+    }
+    (status, validators)
+}
+
+/// The URL that the redirects in wget's `--server-response` output lead to from `url`.
+fn redirect_target(url: &str, output: &str) -> String {
+    let mut target = url.to_string();
+    for line in output.lines().map(str::trim) {
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("location") {
+                // Locations may be relative to the URL they redirect from
+                if let Ok(next) = url::Url::parse(&target).and_then(|base| base.join(value.trim()))
+                {
+                    target = next.to_string();
+                }
+            }
+        }
+    }
+    target
+}
+
+/// One wget run. `Ok(None)` means a network failure worth retrying.
+async fn attempt(
+    url: &str,
+    options: &Options,
+    validators: Option<&Validators>,
+) -> Result<Option<Response>, Error> {
+    let mut command = tokio::process::Command::new("wget");
+    command
+        .arg("-q")
+        .arg("--server-response")
+        .arg("-O")
+        .arg("-")
+        .arg(format!("--timeout={}", options.timeout_secs))
+        .arg(format!("--max-redirect={}", options.max_redirects))
+        .arg("--tries=1");
+    if let Some(validators) = validators {
+        if let Some(etag) = &validators.etag {
+            command.arg(format!("--header=If-None-Match: {etag}"));
+        }
+        if let Some(last_modified) = &validators.last_modified {
+            command.arg(format!("--header=If-Modified-Since: {last_modified}"));
+        }
+    }
+    let mut child = command
+        .arg(url)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| Error::Failed(format!("failed to run wget: {e}")))?;
+
+    let (mut body, mut headers) = (Vec::new(), String::new());
+    let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+    let read_body = async {
+        match stdout {
+            Some(stdout) => {
+                stdout
+                    .take(options.max_size as u64 + 1)
+                    .read_to_end(&mut body)
+                    .await
+            }
+            None => Ok(0),
+        }
+    };
+    let read_headers = async {
+        match stderr {
+            Some(mut stderr) => stderr.read_to_string(&mut headers).await,
+            None => Ok(0),
+        }
+    };
+    let (read_body, _) = tokio::join!(read_body, read_headers);
+    read_body.map_err(|e| Error::Failed(e.to_string()))?;
+    if body.len() > options.max_size {
+        return Err(Error::TooLarge);
+    }
+
+    let exit = child
+        .wait()
+        .await
+        .map_err(|e| Error::Failed(e.to_string()))?;
+    let (status, validators) = parse_headers(&headers);
+    let response = Response {
+        status,
+        body,
+        validators,
+    };
+    match exit.code() {
+        Some(0) if !response.body.is_empty() => Ok(Some(response)),
+        Some(0) => Err(Error::Failed("empty response".to_string())),
+        Some(WGET_NETWORK_FAILURE) => Ok(None),
+        // wget reports "304 Not Modified" as a server error
+        _ if response.not_modified() => Ok(Some(response)),
+        _ => Err(Error::Failed(format!("wget exited with {exit}"))),
+    }
+}
+
+/// Fetch `url`, retrying network failures with doubling delays.
+pub async fn get(url: &str, options: &Options, cancel: &Cancel) -> Result<Vec<u8>, Error> {
+    Ok(fetch(url, options, None, cancel).await?.body)
+}
+
+/// Like [`get`], but sends `validators` so an unchanged resource comes back as
+/// "304 Not Modified" without a body.
+pub async fn fetch(
+    url: &str,
+    options: &Options,
+    validators: Option<&Validators>,
+    cancel: &Cancel,
+) -> Result<Response, Error> {
+    let request = async {
+        let mut delay = Duration::from_millis(500);
+        for retry in 0..=options.retries {
+            if let Some(response) = attempt(url, options, validators).await? {
+                return Ok(response);
+            }
+            if retry < options.retries {
+                tracing::debug!("Network failure fetching {url}, retrying in {delay:?}");
+                tokio::time::sleep(delay).await;
+                delay *= 2;
+            }
+        }
+        Err(Error::Failed("network unreachable".to_string()))
+    };
+    tokio::select! {
+        result = request => result,
+        () = cancel.cancelled() => Err(Error::Cancelled),
+    }
+}
+
+/// Check that `url` can be reached with a HEAD request, following redirects. Server
+/// errors are returned as a [`Head`] with their status; only a missing answer fails.
+pub async fn head(url: &str, options: &Options, cancel: &Cancel) -> Result<Head, Error> {
+    let request = async {
+        let output = tokio::process::Command::new("wget")
+            .arg("-q")
+            .arg("--spider")
+            .arg("--server-response")
+            .arg(format!("--timeout={}", options.timeout_secs))
+            .arg(format!("--max-redirect={}", options.max_redirects))
+            .arg("--tries=1")
+            .arg(url)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| Error::Failed(format!("failed to run wget: {e}")))?;
+        let headers = String::from_utf8_lossy(&output.stderr);
+        match parse_headers(&headers) {
+            (0, _) if output.status.code() == Some(WGET_NETWORK_FAILURE) => {
+                Err(Error::Failed("network unreachable".to_string()))
+            }
+            (0, _) => Err(Error::Failed("no response".to_string())),
+            (status, _) => Ok(Head {
+                status,
+                url: redirect_target(url, &headers),
+            }),
+        }
+    };
+    tokio::select! {
+        result = request => result,
+        () = cancel.cancelled() => Err(Error::Cancelled),
+    }
+}
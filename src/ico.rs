@@ -0,0 +1,273 @@
+//! Multi-resolution ICO files, which many sites ship as their only favicon.
+//!
+//! Every frame is either an embedded PNG or a headerless BMP. The largest frame is picked
+//! and converted to PNG, rather than using whichever frame a decoder returns first.
+
+use std::io::Cursor;
+
+use cosmic::iced_winit::graphics::image::image_rs::{ImageFormat, Rgba, RgbaImage};
+
+const PNG_SIGNATURE: &[u8] = &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A];
+
+/// BMP frames larger than this are rejected; the format itself stops at 256.
+const MAX_BMP_SIZE: usize = 1024;
+
+struct Frame<'a> {
+    width: u32,
+    height: u32,
+    bits: u16,
+    data: &'a [u8],
+}
+
+fn u16_at(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn u32_at(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+pub fn is_ico(data: &[u8]) -> bool {
+    data.starts_with(&[0x00, 0x00, 0x01, 0x00])
+}
+
+/// The frames listed in the directory whose data is inside the file.
+fn frames(data: &[u8]) -> Vec<Frame<'_>> {
+    let count = u16_at(data, 4).unwrap_or(0) as usize;
+    (0..count)
+        .filter_map(|i| {
+            let entry = data.get(6 + i * 16..6 + (i + 1) * 16)?;
+            let size = u32_at(entry, 8)? as usize;
+            let offset = u32_at(entry, 12)? as usize;
+            let frame = data.get(offset..offset.checked_add(size)?)?;
+            if frame.starts_with(PNG_SIGNATURE) {
+                // The IHDR chunk has the real size, the directory can't go past 256
+                let width = u32::from_be_bytes(frame.get(16..20)?.try_into().ok()?);
+                let height = u32::from_be_bytes(frame.get(20..24)?.try_into().ok()?);
+                Some(Frame {
+                    width,
+                    height,
+                    bits: 32,
+                    data: frame,
+                })
+            } else {
+                // 0 in the directory means 256
+                let side = |byte: u8| if byte == 0 { 256 } else { u32::from(byte) };
+                Some(Frame {
+                    width: side(entry[0]),
+                    height: side(entry[1]),
+                    bits: u16_at(frame, 14)?,
+                    data: frame,
+                })
+            }
+        })
+        .collect()
+}
+
+/// Decode a BMP frame: pixel rows stored bottom-up, followed by a 1-bit transparency mask.
+fn decode_bmp(data: &[u8]) -> Option<RgbaImage> {
+    let header = u32_at(data, 0)? as usize;
+    let width = u32_at(data, 4)? as usize;
+    // The height covers both the pixels and the mask
+    let height = u32_at(data, 8)? as usize / 2;
+    if width == 0 || height == 0 || width > MAX_BMP_SIZE || height > MAX_BMP_SIZE {
+        return None;
+    }
+    let bits = u16_at(data, 14)? as usize;
+    let compression = u32_at(data, 16)?;
+    // Uncompressed, or bit fields that are the usual BGRA masks in practice
+    if compression != 0 && compression != 3 {
+        return None;
+    }
+    let colors = match bits {
+        1 | 4 | 8 => match u32_at(data, 32)? {
+            0 => 1 << bits,
+            used => used as usize,
+        },
+        24 | 32 => 0,
+        _ => return None,
+    };
+    // A plain info header is followed by the three bit field masks
+    let masks = if compression == 3 && header == 40 {
+        12
+    } else {
+        0
+    };
+    let palette_start = header + masks;
+    let palette = data.get(palette_start..palette_start + colors * 4)?;
+
+    let stride = (width * bits).div_ceil(32) * 4;
+    let mask_stride = width.div_ceil(32) * 4;
+    let pixels_start = palette_start + colors * 4;
+    let mask_start = pixels_start + stride * height;
+    let pixels = data.get(pixels_start..mask_start)?;
+    // Some encoders leave the mask out of 32-bit frames
+    let mask = data.get(mask_start..mask_start + mask_stride * height);
+
+    let mut image = RgbaImage::new(width as u32, height as u32);
+    let mut has_alpha = false;
+    for y in 0..height {
+        let row = &pixels[(height - 1 - y) * stride..][..stride];
+        for x in 0..width {
+            let [b, g, r, a] = match bits {
+                32 => [row[x * 4], row[x * 4 + 1], row[x * 4 + 2], row[x * 4 + 3]],
+                24 => [row[x * 3], row[x * 3 + 1], row[x * 3 + 2], 255],
+                _ => {
+                    let bit = x * bits;
+                    let shift = 8 - bits - bit % 8;
+                    let index = ((row[bit / 8] >> shift) & (u8::MAX >> (8 - bits))) as usize;
+                    let color = palette.get(index * 4..index * 4 + 3)?;
+                    [color[0], color[1], color[2], 255]
+                }
+            };
+            has_alpha |= bits == 32 && a != 0;
+            image.put_pixel(x as u32, y as u32, Rgba([r, g, b, a]));
+        }
+    }
+
+    // Without an alpha channel, set bits in the mask mark transparent pixels
+    if !has_alpha {
+        if let Some(mask) = mask {
+            for y in 0..height {
+                let row = &mask[(height - 1 - y) * mask_stride..][..mask_stride];
+                for x in 0..width {
+                    let transparent = (row[x / 8] >> (7 - x % 8)) & 1 == 1;
+                    image.get_pixel_mut(x as u32, y as u32).0[3] =
+                        if transparent { 0 } else { 255 };
+                }
+            }
+        }
+    }
+    Some(image)
+}
+
+/// The largest frame of an ICO file as PNG data, with its smaller side in pixels.
+pub fn to_png(data: &[u8]) -> Option<(Vec<u8>, u32)> {
+    if !is_ico(data) {
+        return None;
+    }
+    let frames = frames(data);
+    let frame = frames
+        .iter()
+        .max_by_key(|frame| (frame.width.min(frame.height), frame.bits))?;
+    let size = frame.width.min(frame.height);
+    if frame.data.starts_with(PNG_SIGNATURE) {
+        return Some((frame.data.to_vec(), size));
+    }
+
+    let image = decode_bmp(frame.data)?;
+    let mut png = Vec::new();
+    image
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .ok()?;
+    Some((png, size))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An ICO file holding `frames`, each listed with its directory width and height.
+    fn ico(frames: &[(u8, u8, &[u8])]) -> Vec<u8> {
+        let mut data = vec![0, 0, 1, 0];
+        data.extend_from_slice(&(frames.len() as u16).to_le_bytes());
+        let mut offset = 6 + frames.len() * 16;
+        for (width, height, frame) in frames {
+            data.extend_from_slice(&[*width, *height, 0, 0, 1, 0, 32, 0]);
+            data.extend_from_slice(&(frame.len() as u32).to_le_bytes());
+            data.extend_from_slice(&(offset as u32).to_le_bytes());
+            offset += frame.len();
+        }
+        for (_, _, frame) in frames {
+            data.extend_from_slice(frame);
+        }
+        data
+    }
+
+    /// The start of a PNG: signature and IHDR chunk, which is all the directory reads.
+    fn png(width: u32, height: u32) -> Vec<u8> {
+        let mut data = PNG_SIGNATURE.to_vec();
+        data.extend_from_slice(&13u32.to_be_bytes());
+        data.extend_from_slice(b"IHDR");
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&[8, 6, 0, 0, 0]);
+        data
+    }
+
+    /// A 1×1 32-bit BMP frame with an empty transparency mask.
+    fn bmp() -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&40u32.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&1u16.to_le_bytes());
+        data.extend_from_slice(&32u16.to_le_bytes());
+        data.extend_from_slice(&[0; 24]);
+        data.extend_from_slice(&[0x10, 0x20, 0x30, 0xFF]);
+        data.extend_from_slice(&[0; 4]);
+        data
+    }
+
+    #[test]
+    fn png_frame_is_returned_as_is() {
+        let frame = png(48, 32);
+        let (data, size) = to_png(&ico(&[(48, 32, &frame)])).unwrap();
+        assert_eq!(data, frame);
+        assert_eq!(size, 32);
+    }
+
+    #[test]
+    fn png_size_comes_from_its_header() {
+        // The directory says 0 (256) for a 512 px PNG; the BMP is smaller either way
+        let large = png(512, 512);
+        let (data, size) = to_png(&ico(&[(1, 1, &bmp()), (0, 0, &large)])).unwrap();
+        assert_eq!(data, large);
+        assert_eq!(size, 512);
+    }
+
+    #[test]
+    fn bmp_frame_is_converted() {
+        let (data, size) = to_png(&ico(&[(1, 1, &bmp())])).unwrap();
+        assert!(data.starts_with(PNG_SIGNATURE));
+        assert_eq!(size, 1);
+    }
+
+    #[test]
+    fn truncated_directory() {
+        let mut data = ico(&[(48, 48, &png(48, 48))]);
+        // Claims three frames, but only one entry follows
+        data[4] = 3;
+        assert_eq!(frames(&data).len(), 1);
+        data.truncate(6 + 8);
+        assert!(frames(&data).is_empty());
+        assert!(to_png(&data).is_none());
+    }
+
+    #[test]
+    fn frame_past_the_end_is_skipped() {
+        let frame = png(48, 48);
+        let mut data = ico(&[(48, 48, &frame)]);
+        data[6 + 8..6 + 12].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(to_png(&data).is_none());
+        data[6 + 8..6 + 12].copy_from_slice(&(frame.len() as u32 + 1).to_le_bytes());
+        assert!(to_png(&data).is_none());
+    }
+
+    #[test]
+    fn oversized_bmp_is_rejected() {
+        let mut frame = bmp();
+        frame[4..8].copy_from_slice(&(MAX_BMP_SIZE as u32 + 1).to_le_bytes());
+        assert!(decode_bmp(&frame).is_none());
+        assert!(to_png(&ico(&[(0, 0, &frame)])).is_none());
+    }
+
+    #[test]
+    fn not_an_ico() {
+        assert!(to_png(&png(16, 16)).is_none());
+    }
+}
@@ -0,0 +1,150 @@
+//! Post-processing for app icons: make them square by cropping or padding, fill a
+//! background behind transparent logos and round the corners.
+
+use std::{
+    hash::{Hash as _, Hasher as _},
+    io::Cursor,
+    path::Path,
+};
+
+use cosmic::iced_winit::graphics::image::image_rs::{
+    self as image, ImageFormat, Rgba, RgbaImage, imageops,
+};
+
+/// Side of processed icons in pixels.
+pub const OUTPUT_SIZE: u32 = 256;
+
+/// Largest padding on each side, in percent of the side.
+pub const MAX_PADDING: u32 = 40;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Fit {
+    /// Scale the whole icon into the square, leaving transparent bars
+    #[default]
+    Pad,
+    /// Cut the longer side down to a centered square
+    Crop,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct IconEdit {
+    pub fit: Fit,
+    /// Space around the icon on each side, in percent of the side
+    pub padding: u32,
+    /// Filled behind the icon, for logos with a transparent background
+    pub background: Option<(u8, u8, u8)>,
+    /// Corner radius in percent of the side; 50 makes a circle
+    pub corner_radius: u32,
+}
+
+/// Decode the icon at `path`, rendering SVG icons `size` pixels wide.
+fn load(path: &Path, size: u32) -> Option<RgbaImage> {
+    let data = std::fs::read(path).ok()?;
+    if !crate::is_svg(&path.to_string_lossy()) {
+        let data = crate::ico::to_png(&data).map_or(data, |(png, _)| png);
+        return Some(image::load_from_memory(&data).ok()?.to_rgba8());
+    }
+
+    let tree = resvg::usvg::Tree::from_data(&data, &resvg::usvg::Options::default()).ok()?;
+    let tree_size = tree.size();
+    let scale = size as f32 / tree_size.width().max(tree_size.height());
+    let width = ((tree_size.width() * scale).round() as u32).max(1);
+    let height = ((tree_size.height() * scale).round() as u32).max(1);
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+    resvg::render(
+        &tree,
+        resvg::tiny_skia::Transform::from_scale(scale, scale),
+        &mut pixmap.as_mut(),
+    );
+    // tiny-skia keeps premultiplied alpha
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    RgbaImage::from_raw(width, height, pixels)
+}
+
+/// Make the pixels outside corners of `radius` transparent, smoothing the edge.
+fn round_corners(image: &mut RgbaImage, radius: u32) {
+    if radius == 0 {
+        return;
+    }
+    let (width, height) = image.dimensions();
+    let radius = radius as f32;
+    for (x, y, pixel) in image.enumerate_pixels_mut() {
+        let (x, y) = (x as f32 + 0.5, y as f32 + 0.5);
+        // The nearest point of the square shrunk by the radius; inside it the distance is 0
+        let nearest_x = x.clamp(radius, width as f32 - radius);
+        let nearest_y = y.clamp(radius, height as f32 - radius);
+        let distance = ((x - nearest_x).powi(2) + (y - nearest_y).powi(2)).sqrt();
+        let coverage = (radius - distance + 0.5).clamp(0.0, 1.0);
+        pixel[3] = (f32::from(pixel[3]) * coverage).round() as u8;
+    }
+}
+
+/// Apply `edit` to the icon at `path`, returning a square PNG `size` pixels wide.
+pub fn render(path: &Path, edit: &IconEdit, size: u32) -> Option<Vec<u8>> {
+    let mut source = load(path, size)?;
+    if edit.fit == Fit::Crop {
+        let (width, height) = source.dimensions();
+        let side = width.min(height);
+        source = imageops::crop_imm(&source, (width - side) / 2, (height - side) / 2, side, side)
+            .to_image();
+    }
+
+    // Scale the longer side to the space inside the padding
+    let inner = size - size * edit.padding.min(MAX_PADDING) * 2 / 100;
+    let (width, height) = source.dimensions();
+    let scale = inner as f32 / width.max(height) as f32;
+    let width = ((width as f32 * scale).round() as u32).max(1);
+    let height = ((height as f32 * scale).round() as u32).max(1);
+    let scaled = imageops::resize(&source, width, height, imageops::FilterType::Lanczos3);
+
+    let background = match edit.background {
+        Some((r, g, b)) => Rgba([r, g, b, 255]),
+        None => Rgba([0, 0, 0, 0]),
+    };
+    let mut icon = RgbaImage::from_pixel(size, size, background);
+    imageops::overlay(
+        &mut icon,
+        &scaled,
+        i64::from((size - width) / 2),
+        i64::from((size - height) / 2),
+    );
+    round_corners(&mut icon, size * edit.corner_radius.min(50) / 100);
+
+    let mut png = Vec::new();
+    icon.write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .ok()?;
+    Some(png)
+}
+
+/// Apply `edit` to the icon at `source` and save the result with the other icons, keeping
+/// the original. Returns the path of the processed icon.
+pub async fn save(source: String, edit: IconEdit) -> Option<String> {
+    // Named after the original and the edit, so apps sharing an icon don't overwrite
+    // each other's version
+    let mut hasher = std::hash::DefaultHasher::new();
+    (&source, &edit).hash(&mut hasher);
+    let stem = Path::new(&source)
+        .file_stem()?
+        .to_string_lossy()
+        .to_string();
+    let path = crate::icons_location()?.join(format!("{stem}-{:016x}.png", hasher.finish()));
+
+    let png = tokio::task::spawn_blocking(move || render(Path::new(&source), &edit, OUTPUT_SIZE))
+        .await
+        .ok()??;
+    if let Some(dir) = path.parent() {
+        tokio::fs::create_dir_all(dir).await.ok()?;
+    }
+    if let Err(e) = tokio::fs::write(&path, png).await {
+        tracing::error!("Failed to save the edited icon: {e}");
+        return None;
+    }
+    Some(path.to_string_lossy().to_string())
+}
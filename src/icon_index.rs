@@ -0,0 +1,134 @@
+//! Persisted index of the icons the picker can offer, so searching doesn't walk and decode
+//! every icon theme each time.
+//!
+//! Kept in the cache directory and refreshed in the background; entries of files that
+//! haven't changed since the last refresh are reused without reading them again.
+
+use std::{collections::HashMap, path::PathBuf, time::UNIX_EPOCH};
+
+use cosmic::iced_winit::graphics::image::image_rs::ImageReader;
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::{APP_ID, ICON_SIZE, MAX_ICON_RESULTS, MAX_ICON_SEARCH_DEPTH};
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IconEntry {
+    pub path: String,
+    /// Lowercase file name, matched against the search text
+    pub name: String,
+    /// Smaller side in pixels
+    pub size: u32,
+    /// Modification time of the file (Unix seconds)
+    pub modified: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct IconIndex {
+    pub entries: Vec<IconEntry>,
+}
+
+fn path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_ID).join("icon-index.ron"))
+}
+
+/// Smaller side of the image at `path`, read from the header or the SVG size.
+fn icon_size(path: &std::path::Path) -> Option<u32> {
+    if crate::is_svg(&path.to_string_lossy()) {
+        let buffer = std::fs::read_to_string(path).ok()?;
+        let tree = usvg::Tree::from_str(&buffer, &usvg::Options::default()).ok()?;
+        let size = tree.size();
+        Some(size.width().min(size.height()) as u32)
+    } else {
+        let (width, height) = ImageReader::open(path)
+            .ok()?
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()?;
+        Some(width.min(height))
+    }
+}
+
+impl IconIndex {
+    /// The index saved by the last refresh, or an empty one.
+    pub fn load() -> Self {
+        path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|content| ron::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> std::io::Result<()> {
+        let path = path().ok_or_else(|| std::io::Error::other("no cache directory"))?;
+        let serialized = ron::to_string(self).map_err(std::io::Error::other)?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let tmp = path.with_extension("ron.tmp");
+        std::fs::write(&tmp, serialized)?;
+        std::fs::rename(tmp, path)
+    }
+
+    /// Walk the icon directory again, reading only new or changed files.
+    pub fn refresh(&self) -> Self {
+        let Some(root) = crate::icons_location() else {
+            return Self::default();
+        };
+        let previous: HashMap<&str, &IconEntry> = self
+            .entries
+            .iter()
+            .map(|entry| (entry.path.as_str(), entry))
+            .collect();
+
+        let entries = WalkDir::new(root)
+            .max_depth(MAX_ICON_SEARCH_DEPTH)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| {
+                let path = e.path().to_str()?.to_string();
+                let extension = e.path().extension()?.to_str()?.to_lowercase();
+                if extension != "svg" && extension != "png" {
+                    return None;
+                }
+                let modified = e
+                    .metadata()
+                    .ok()?
+                    .modified()
+                    .ok()?
+                    .duration_since(UNIX_EPOCH)
+                    .ok()?
+                    .as_secs();
+                if let Some(entry) = previous.get(path.as_str()) {
+                    if entry.modified == modified {
+                        return Some((*entry).clone());
+                    }
+                }
+                Some(IconEntry {
+                    size: icon_size(e.path())?,
+                    name: e.file_name().to_str()?.to_lowercase(),
+                    path,
+                    modified,
+                })
+            })
+            .collect();
+        Self { entries }
+    }
+
+    /// Paths of icons large enough to use whose file name contains `query`.
+    pub fn search(&self, query: &str) -> Vec<String> {
+        let query = query.trim().to_lowercase();
+        self.entries
+            .iter()
+            .filter(|entry| entry.size >= ICON_SIZE && entry.name.contains(&query))
+            .take(MAX_ICON_RESULTS)
+            .map(|entry| entry.path.clone())
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
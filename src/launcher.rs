@@ -4,13 +4,45 @@ use ashpd::desktop::{
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::HashMap,
     fs::{self},
     io::Read,
+    path::{Path, PathBuf},
+    sync::{LazyLock, Mutex},
+    time::SystemTime,
 };
 use tokio::fs::remove_file;
 
 use crate::APP_ID;
 
+/// Session service file that lets D-Bus start a web app by its bus name.
+///
+/// Always under the real home directory: inside Flatpak the XDG data dir points into
+/// the sandbox, where the session bus would never see it.
+fn dbus_service_path(bus_name: &str) -> Option<PathBuf> {
+    Some(
+        dirs::home_dir()?
+            .join(".local/share/dbus-1/services")
+            .join(format!("{bus_name}.service")),
+    )
+}
+
+/// Command D-Bus runs to activate a web app. dbus-daemon does not search `PATH`.
+fn dbus_service_exec(app_id: &str) -> String {
+    let binary = format!("{APP_ID}.webview");
+    if Path::new("/.flatpak-info").exists() {
+        return format!("/usr/bin/flatpak run --command={binary} {APP_ID} {app_id}");
+    }
+    let path = std::env::var_os("PATH")
+        .and_then(|paths| {
+            std::env::split_paths(&paths)
+                .map(|dir| dir.join(&binary))
+                .find(|candidate| candidate.is_file())
+        })
+        .unwrap_or_else(|| PathBuf::from(&binary));
+    format!("{} {app_id}", path.display())
+}
+
 /// Sanitize a string for use in a desktop entry field.
 /// Strips newlines, carriage returns, tabs, backslashes, semicolons,
 /// and all ASCII control characters to prevent key injection and value manipulation.
@@ -31,7 +63,7 @@ pub fn webapplauncher_is_valid(
     category: &crate::Category,
 ) -> bool {
     if let Some(url) = url {
-        if crate::url_valid(url)
+        if crate::app_url_valid(url)
             && !name.is_empty()
             && !icon.is_empty()
             && !url.is_empty()
@@ -47,43 +79,137 @@ pub fn webapplauncher_is_valid(
 /// Maximum size for a single RON database file (64 KB).
 const MAX_RON_FILE_SIZE: u64 = 64 * 1024;
 
-pub fn installed_webapps() -> Vec<WebAppLauncher> {
-    let mut webapps = Vec::new();
+/// Parsed database files, keyed by path and valid while modification time and size match.
+static LAUNCHER_CACHE: LazyLock<Mutex<HashMap<PathBuf, CachedLauncher>>> =
+    LazyLock::new(Default::default);
 
-    if let Some(data_dir) = dirs::data_dir() {
-        if let Ok(entries) = fs::read_dir(data_dir.join(APP_ID).join("database")) {
-            for entry in entries {
-                if let Ok(entry) = entry {
-                    let metadata = match entry.metadata() {
-                        Ok(m) => m,
-                        Err(_) => continue,
-                    };
-
-                    if metadata.len() > MAX_RON_FILE_SIZE {
-                        tracing::warn!(
-                            "Skipping oversized file {:?} ({} bytes)",
-                            entry.path(),
-                            metadata.len()
-                        );
-                        continue;
-                    }
+struct CachedLauncher {
+    modified: Option<SystemTime>,
+    len: u64,
+    launcher: Option<WebAppLauncher>,
+}
 
-                    let mut content = String::new();
+fn database_dir() -> Option<PathBuf> {
+    Some(dirs::data_dir()?.join(APP_ID).join("database"))
+}
 
-                    if let Ok(mut f) = std::fs::File::open(entry.path()) {
-                        if let Err(e) = f.read_to_string(&mut content) {
-                            tracing::warn!("Failed to read {:?}: {e}", entry.path());
-                            continue;
-                        }
-                        if let Ok(launcher) = ron::from_str::<WebAppLauncher>(&content) {
-                            webapps.push(launcher);
-                        }
-                    }
-                }
-            }
+/// The cached parse of `path`, if the file hasn't changed since.
+fn cached_launcher(path: &Path, metadata: &fs::Metadata) -> Option<Option<WebAppLauncher>> {
+    let cache = LAUNCHER_CACHE.lock().ok()?;
+    let cached = cache.get(path)?;
+    (cached.modified == metadata.modified().ok() && cached.len == metadata.len())
+        .then(|| cached.launcher.clone())
+}
+
+fn parse_launcher(path: &Path, content: &str) -> Option<WebAppLauncher> {
+    match ron::from_str::<WebAppLauncher>(content) {
+        Ok(launcher) => Some(launcher),
+        Err(e) => {
+            tracing::warn!("Failed to parse {path:?}: {e}");
+            None
         }
     }
+}
+
+/// Load `path` from the cache or the disk. Returns `None` for files to skip.
+fn load_launcher(path: PathBuf, metadata: &fs::Metadata) -> Option<WebAppLauncher> {
+    if metadata.len() > MAX_RON_FILE_SIZE {
+        tracing::warn!("Skipping oversized file {path:?} ({} bytes)", metadata.len());
+        return None;
+    }
+    if let Some(launcher) = cached_launcher(&path, metadata) {
+        return launcher;
+    }
+    let mut content = String::new();
+    if let Err(e) = fs::File::open(&path).and_then(|mut f| f.read_to_string(&mut content)) {
+        tracing::warn!("Failed to read {path:?}: {e}");
+        return None;
+    }
+    let launcher = parse_launcher(&path, &content);
+    store_launcher(path, metadata, launcher.clone());
+    launcher
+}
+
+fn store_launcher(path: PathBuf, metadata: &fs::Metadata, launcher: Option<WebAppLauncher>) {
+    if let Ok(mut cache) = LAUNCHER_CACHE.lock() {
+        cache.insert(
+            path,
+            CachedLauncher {
+                modified: metadata.modified().ok(),
+                len: metadata.len(),
+                launcher,
+            },
+        );
+    }
+}
+
+/// Drop cache entries for files that no longer exist.
+fn prune_cache(present: &[PathBuf]) {
+    if let Ok(mut cache) = LAUNCHER_CACHE.lock() {
+        cache.retain(|path, _| present.contains(path));
+    }
+}
 
+pub fn installed_webapps() -> Vec<WebAppLauncher> {
+    let Some(entries) = database_dir().and_then(|dir| fs::read_dir(dir).ok()) else {
+        return Vec::new();
+    };
+    let mut present = Vec::new();
+    let mut webapps = Vec::new();
+    for entry in entries.filter_map(Result::ok) {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        present.push(entry.path());
+        webapps.extend(load_launcher(entry.path(), &metadata));
+    }
+    prune_cache(&present);
+    webapps
+}
+
+/// Like [`installed_webapps`], but reads changed files concurrently.
+pub async fn installed_webapps_async() -> Vec<WebAppLauncher> {
+    let Some(dir) = database_dir() else {
+        return Vec::new();
+    };
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return Vec::new();
+    };
+    let mut present = Vec::new();
+    let mut webapps = Vec::new();
+    let mut reads = tokio::task::JoinSet::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let Ok(metadata) = entry.metadata().await else {
+            continue;
+        };
+        let path = entry.path();
+        present.push(path.clone());
+        if metadata.len() > MAX_RON_FILE_SIZE {
+            tracing::warn!("Skipping oversized file {path:?} ({} bytes)", metadata.len());
+            continue;
+        }
+        if let Some(launcher) = cached_launcher(&path, &metadata) {
+            webapps.extend(launcher);
+            continue;
+        }
+        reads.spawn(async move {
+            match tokio::fs::read_to_string(&path).await {
+                Ok(content) => {
+                    let launcher = parse_launcher(&path, &content);
+                    store_launcher(path, &metadata, launcher.clone());
+                    launcher
+                }
+                Err(e) => {
+                    tracing::warn!("Failed to read {path:?}: {e}");
+                    None
+                }
+            }
+        });
+    }
+    while let Some(result) = reads.join_next().await {
+        webapps.extend(result.ok().flatten());
+    }
+    prune_cache(&present);
     webapps
 }
 
@@ -93,9 +219,148 @@ pub struct WebAppLauncher {
     pub name: String,
     pub icon: String,
     pub category: crate::Category,
+    /// Name of the user's own category the app is in, kept within `category` in menus
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub custom_category: Option<String>,
+    /// Free-form labels to organize the apps by, finer than the category
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+/// Split comma-separated tags, dropping empty ones and those already in `existing`,
+/// ignoring case.
+pub fn parse_tags(text: &str, existing: &[String]) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in text.split(',').map(str::trim).filter(|tag| !tag.is_empty()) {
+        let known = existing
+            .iter()
+            .chain(tags.iter())
+            .any(|other| other.eq_ignore_ascii_case(tag));
+        if !known {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+/// What deleting an app removes along with its launcher and settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Purge {
+    /// The persistent profile directory with the app's website data
+    pub profile: bool,
+    /// The icons installed for menus, and the app's icon file unless another app uses it
+    pub icons: bool,
+}
+
+impl Default for Purge {
+    fn default() -> Self {
+        Self {
+            profile: true,
+            icons: true,
+        }
+    }
 }
 
 impl WebAppLauncher {
+    /// Name of the category shown in the manager.
+    pub fn category_name(&self) -> String {
+        self.custom_category
+            .clone()
+            .unwrap_or_else(|| self.category.name())
+    }
+
+    /// Write the app to the database.
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let location = crate::database_path(&format!("{}.ron", self.browser.app_id.as_ref()))
+            .ok_or("no database directory")?;
+        let content = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        let tmp = location.with_extension("ron.tmp");
+        fs::write(&tmp, content)?;
+        fs::rename(tmp, location)?;
+        Ok(())
+    }
+
+    /// Give the app the id `new_id`, which names its desktop file and window class. The
+    /// profile, state, usage statistics and navigation log move along, and the app is
+    /// installed under the new id before the old one is removed. Returns the renamed app.
+    pub async fn rename(&self, new_id: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let old_id = self.browser.app_id.id.clone();
+        let new_id = crate::browser::sanitize_app_id(new_id);
+        if new_id.is_empty() || new_id == old_id {
+            return Err("invalid app id".into());
+        }
+        let exists = crate::database_path(&format!("{new_id}.ron")).is_some_and(|p| p.exists());
+        if exists {
+            return Err(format!("an app with the id {new_id} already exists").into());
+        }
+
+        let mut renamed = self.clone();
+        renamed.browser.app_id.id = new_id.clone();
+        renamed.browser.set_persistent(self.browser.profile.is_some());
+
+        let rotated_log =
+            |id: &str| crate::audit::log_path(id).map(|path| path.with_extension("log.1"));
+        let data: [fn(&str) -> Option<PathBuf>; 5] = [
+            crate::profiles_path,
+            crate::app_state::dir,
+            crate::stats::path,
+            crate::audit::log_path,
+            rotated_log,
+        ];
+        let mut moved = Vec::new();
+        for path in data {
+            let (Some(from), Some(to)) = (path(&old_id), path(&new_id)) else {
+                continue;
+            };
+            if !from.exists() {
+                continue;
+            }
+            if let Err(e) = fs::rename(&from, &to) {
+                restore(&moved);
+                return Err(e.into());
+            }
+            moved.push((from, to));
+        }
+
+        // Errors as strings, as they are kept across the install
+        let saved = renamed.save().map_err(|e| e.to_string());
+        let installed = match saved {
+            Ok(()) => renamed.create().await.map_err(|e| e.to_string()),
+            Err(e) => Err(e),
+        };
+        if let Err(e) = installed {
+            restore(&moved);
+            if let Some(path) = crate::database_path(&format!("{new_id}.ron")) {
+                let _ = fs::remove_file(path);
+            }
+            return Err(e.into());
+        }
+
+        self.delete(Purge {
+            profile: false,
+            icons: false,
+        })
+        .await?;
+        crate::hicolor::uninstall(&crate::hicolor::icon_name(&old_id));
+        Ok(renamed)
+    }
+
+    /// Load a single installed web app from the database by its app ID.
+    pub fn from_appid(id: &str) -> Option<Self> {
+        let safe_id = crate::browser::sanitize_app_id(id);
+        let db_path = crate::database_path(&format!("{safe_id}.ron"))?;
+
+        let content = std::fs::read_to_string(&db_path).ok()?;
+
+        // Same 64KB safety limit used in installed_webapps()
+        if content.len() as u64 > MAX_RON_FILE_SIZE {
+            tracing::warn!("RON file too large: {}", db_path.display());
+            return None;
+        }
+
+        ron::from_str(&content).ok()
+    }
+
     pub async fn create(&self) -> Result<(), Box<dyn std::error::Error>> {
         let safe_name = sanitize_desktop_field(&self.name);
         let safe_wm_class = sanitize_desktop_field(&self.browser.app_id.id);
@@ -108,9 +373,35 @@ impl WebAppLauncher {
         desktop_entry.push_str("Type=Application\n");
         desktop_entry.push_str(&format!("Name={safe_name}\n"));
         desktop_entry.push_str("Comment=Quick WebApp\n");
-        desktop_entry.push_str(&format!("Exec={safe_exec}\n"));
+        let schemes = self.browser.valid_url_schemes();
+        if schemes.is_empty() {
+            desktop_entry.push_str(&format!("Exec={safe_exec}\n"));
+        } else {
+            desktop_entry.push_str(&format!("Exec={safe_exec} %u\n"));
+            let mime_types: String = schemes
+                .iter()
+                .map(|s| format!("x-scheme-handler/{s};"))
+                .collect();
+            desktop_entry.push_str(&format!("MimeType={mime_types}\n"));
+        }
         desktop_entry.push_str(&format!("StartupWMClass={safe_wm_class}\n"));
         desktop_entry.push_str(&format!("Categories={}\n", self.category.as_ref()));
+        let bus_name = crate::dbus_name(&self.browser.app_id.id);
+        if let Some(service_path) = bus_name.as_deref().and_then(dbus_service_path) {
+            let service = format!(
+                "[D-BUS Service]\nName={}\nExec={}\n",
+                bus_name.as_deref().unwrap_or_default(),
+                sanitize_desktop_field(&dbus_service_exec(&self.browser.app_id.id))
+            );
+            let written = service_path
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|()| fs::write(&service_path, service));
+            match written {
+                Ok(()) => desktop_entry.push_str("DBusActivatable=true\n"),
+                Err(e) => tracing::warn!("Failed to write D-Bus service file: {e}"),
+            }
+        }
         desktop_entry.push_str("Actions=new-window;new-private-window;open-in-browser;\n");
         desktop_entry.push_str("\n[Desktop Action new-window]\n");
         desktop_entry.push_str("Name=New Window\n");
@@ -129,7 +420,25 @@ impl WebAppLauncher {
 
         let proxy = DynamicLauncherProxy::new().await?;
 
-        let buffer = std::fs::read(&self.icon)?;
+        // Install the icon at every size; the portal gets the largest one
+        let icon_name = crate::hicolor::icon_name(&self.browser.app_id.id);
+        let source = PathBuf::from(&self.icon);
+        let installed =
+            tokio::task::spawn_blocking(move || crate::hicolor::install(&source, &icon_name))
+                .await?;
+        let buffer = match installed {
+            Ok(png) => png,
+            Err(e) => {
+                tracing::warn!("Failed to install the icon sizes: {e}");
+                let mut buffer = std::fs::read(&self.icon)?;
+                // Install the largest frame of an ICO file, not whichever one the portal
+                // decodes
+                if let Some((png, _)) = crate::ico::to_png(&buffer) {
+                    buffer = png;
+                }
+                buffer
+            }
+        };
 
         let icon = Icon::Bytes(buffer);
         let response = proxy
@@ -149,10 +458,27 @@ impl WebAppLauncher {
             )
             .await?;
 
+        if let Err(e) = crate::autostart::update(&self.browser, &safe_name) {
+            tracing::warn!("Failed to update the autostart entry: {e}");
+        }
+
         Ok(())
     }
 
-    pub async fn delete(&self) -> Result<(), Box<dyn std::error::Error>> {
+    /// The app's icon when it was copied into the icons directory and no other app uses
+    /// it, unlike icons from the icon theme or elsewhere on disk.
+    pub fn own_icon_file(&self) -> Option<PathBuf> {
+        let path = Path::new(&self.icon);
+        if !path.starts_with(crate::icons_location()?) || !path.is_file() {
+            return None;
+        }
+        let shared = installed_webapps().iter().any(|app| {
+            app.browser.app_id.id != self.browser.app_id.id && app.icon == self.icon
+        });
+        (!shared).then(|| path.to_path_buf())
+    }
+
+    pub async fn delete(&self, purge: Purge) -> Result<(), Box<dyn std::error::Error>> {
         let proxy = DynamicLauncherProxy::new().await?;
 
         proxy
@@ -163,12 +489,48 @@ impl WebAppLauncher {
             remove_file(path).await?;
         }
 
-        self.browser.delete();
+        if let Some(path) =
+            crate::dbus_name(&self.browser.app_id.id).and_then(|name| dbus_service_path(&name))
+        {
+            if path.exists() {
+                remove_file(path).await?;
+            }
+        }
+
+        if let Err(e) = crate::autostart::disable(&self.browser.app_id.id) {
+            tracing::warn!("Failed to remove the autostart entry: {e}");
+        }
+
+        if purge.icons {
+            crate::hicolor::uninstall(&crate::hicolor::icon_name(&self.browser.app_id.id));
+            if let Some(path) = self.own_icon_file() {
+                remove_file(path).await?;
+            }
+        }
+
+        if purge.profile {
+            self.browser.delete();
+        }
+        if let Err(e) = crate::stats::clear(self.browser.app_id.as_ref()) {
+            tracing::warn!("Failed to delete usage statistics: {e}");
+        }
+        if let Err(e) = crate::app_state::clear(self.browser.app_id.as_ref()) {
+            tracing::warn!("Failed to delete the app's state: {e}");
+        }
 
         Ok(())
     }
 }
 
+/// Move the data of an app back after renaming it failed.
+fn restore(moved: &[(PathBuf, PathBuf)]) {
+    for (from, to) in moved.iter().rev() {
+        if let Err(e) = fs::rename(to, from) {
+            tracing::error!("Failed to move {} back: {e}", to.display());
+        }
+    }
+}
+
 /// Export all installed web apps to a RON file.
 pub fn export_all(path: &std::path::Path) -> Result<(), Box<dyn std::error::Error>> {
     let apps = installed_webapps();
@@ -194,11 +556,12 @@ fn validate_imported_app(mut app: WebAppLauncher) -> Option<WebAppLauncher> {
     app.browser.app_id = crate::WebviewArgs {
         id: safe_id,
         private: false,
+        ..Default::default()
     };
 
-    // Validate URL is http/https
+    // Validate URL is http/https or a local file
     if let Some(ref url) = app.browser.url {
-        if !crate::url_valid(url) {
+        if !crate::app_url_valid(url) {
             tracing::warn!("Rejecting imported app '{}': invalid URL", app.name);
             return None;
         }
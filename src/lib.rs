@@ -6,8 +6,8 @@ use std::{
     fmt::Display,
     fs::{self, create_dir_all},
     io::{Cursor, Read},
-    os::unix::fs::PermissionsExt as _,
-    path::PathBuf,
+    os::{fd::AsRawFd as _, unix::fs::PermissionsExt as _},
+    path::{Path, PathBuf},
 };
 use tokio::{fs::File, io::AsyncWriteExt as _, process::Child};
 
@@ -16,9 +16,28 @@ use strum_macros::EnumIter;
 use url::Url;
 use walkdir::WalkDir;
 
+pub mod app_state;
+pub mod audit;
+pub mod autostart;
 pub mod browser;
+pub mod control;
+pub mod cookies;
+pub mod favicon_cache;
+pub mod favicon_refresh;
+pub mod hicolor;
+pub mod history;
+pub mod http;
+pub mod ico;
+pub mod icon_edit;
+pub mod icon_index;
 pub mod launcher;
 pub mod localize;
+pub mod preview;
+pub mod process;
+pub mod session;
+pub mod site_styles;
+pub mod stats;
+pub mod warm;
 
 pub const DEFAULT_WINDOW_WIDTH: WindowWidth = 800.0;
 pub const DEFAULT_WINDOW_HEIGHT: WindowHeight = 600.0;
@@ -29,6 +48,7 @@ pub const APP_ID: &str = "dev.heppen.webapps";
 pub const APP_ICON: &[u8] =
     include_bytes!("../resources/icons/hicolor/256x256/apps/dev.heppen.webapps.png");
 pub const MOBILE_UA: &str = "Mozilla/5.0 (Android 16; Mobile; rv:68.0) Gecko/68.0 Firefox/142.0";
+pub const TABLET_UA: &str = "Mozilla/5.0 (Android 16; Tablet; rv:68.0) Gecko/68.0 Firefox/142.0";
 
 pub fn url_valid(url: &str) -> bool {
     if let Ok(parsed) = Url::parse(url) {
@@ -38,6 +58,126 @@ pub fn url_valid(url: &str) -> bool {
     }
 }
 
+/// Well-known D-Bus name owned by a running web app, e.g. `dev.heppen.webapps.Slack1234`.
+/// Returns `None` if the app id can't form a valid bus name element.
+pub fn dbus_name(app_id: &str) -> Option<String> {
+    let valid_element = |element: &str| {
+        element
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '-')
+            && element
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    };
+    if app_id.split('.').all(valid_element) {
+        Some(format!("{APP_ID}.{app_id}"))
+    } else {
+        None
+    }
+}
+
+/// Object path matching a D-Bus name from [`dbus_name`].
+pub fn dbus_object_path(name: &str) -> String {
+    format!("/{}", name.replace('.', "/").replace('-', "_"))
+}
+
+/// Whether `url` can be wrapped as a web app: http(s) sites and local `file://` pages.
+pub fn app_url_valid(url: &str) -> bool {
+    url_valid(url)
+        || Url::parse(url)
+            .is_ok_and(|parsed| parsed.scheme() == "file" && parsed.to_file_path().is_ok())
+}
+
+/// Whether `url` points at this machine: a `file://` page or a loopback host.
+pub fn is_local_url(url: &str) -> bool {
+    let Ok(parsed) = Url::parse(url) else {
+        return false;
+    };
+    if parsed.scheme() == "file" {
+        return true;
+    }
+    match parsed.host() {
+        Some(url::Host::Domain(domain)) => domain == "localhost" || domain.ends_with(".localhost"),
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+/// Map a URI opened through one of the app's registered `schemes` onto the app's
+/// own site: `slack://client/T123` becomes `<origin of base>/client/T123`.
+/// Web URIs are returned unchanged; anything else, including a URI that would land on
+/// another host, yields `None`.
+pub fn scheme_uri_to_url(uri: &str, schemes: &[String], base: &str) -> Option<String> {
+    let parsed = Url::parse(uri).ok()?;
+    if matches!(parsed.scheme(), "http" | "https" | "file") {
+        return Some(uri.to_string());
+    }
+    if !schemes.iter().any(|s| s.eq_ignore_ascii_case(parsed.scheme())) {
+        return None;
+    }
+
+    // Joining treats `\` like `/`, and a leftover `/\host` would point at another host
+    let rest = uri[parsed.scheme().len() + 1..].trim_start_matches(['/', '\\']);
+    let base = Url::parse(base).ok()?;
+    let joined = base.join(&format!("/{rest}")).ok()?;
+    (joined.host() == base.host()).then_some(joined.into())
+}
+
+/// Turn what users type for local apps into a URL: absolute paths become `file://`
+/// URLs and scheme-less `localhost:3000` style addresses get `http://`.
+pub fn normalize_app_url(input: &str) -> String {
+    let input = input.trim();
+    if input.starts_with('/') {
+        if let Ok(url) = Url::from_file_path(input) {
+            return url.to_string();
+        }
+    }
+    let host = input.split(['/', ':']).next().unwrap_or_default();
+    if !input.contains("://") && (host == "localhost" || host == "127.0.0.1") {
+        return format!("http://{input}");
+    }
+    input.to_string()
+}
+
+/// Whether two app URLs open the same page, regardless of the scheme, a leading `www.`
+/// and a trailing slash.
+pub fn same_app_url(a: &str, b: &str) -> bool {
+    fn key(url: &str) -> Option<String> {
+        let url = Url::parse(&normalize_app_url(url)).ok()?;
+        let host = url.host_str()?;
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        let port = url.port().map(|port| format!(":{port}")).unwrap_or_default();
+        let query = url.query().map(|query| format!("?{query}")).unwrap_or_default();
+        Some(format!("{host}{port}{}{query}", url.path().trim_end_matches('/')))
+    }
+    match (key(a), key(b)) {
+        (Some(a), Some(b)) => a == b,
+        _ => normalize_app_url(a) == normalize_app_url(b),
+    }
+}
+
+/// Parse a `#rrggbb` (or `#rgb`) hex color into its RGB components.
+pub fn parse_hex_color(color: &str) -> Option<(u8, u8, u8)> {
+    let hex = color.trim().strip_prefix('#')?;
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    match hex.len() {
+        6 => Some((
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        )),
+        3 => {
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 1], 16).ok().map(|v| v * 17);
+            Some((channel(0)?, channel(1)?, channel(2)?))
+        }
+        _ => None,
+    }
+}
+
 pub fn is_svg(path: &str) -> bool {
     if !url_valid(path) {
         let pb = PathBuf::from(path);
@@ -49,6 +189,11 @@ pub fn is_svg(path: &str) -> bool {
     false
 }
 
+/// Whether this process runs inside Flatpak.
+pub fn in_flatpak() -> bool {
+    Path::new("/.flatpak-info").exists()
+}
+
 pub fn themes_path(theme_file: &str) -> Option<PathBuf> {
     if let Some(xdg_data) = dirs::data_dir() {
         let path = xdg_data.join(APP_ID).join("themes");
@@ -91,6 +236,63 @@ pub fn profiles_path(app_id: &str) -> Option<PathBuf> {
     None
 }
 
+/// Lock file inside each ephemeral profile, held by the launch using the profile.
+const EPHEMERAL_LOCK: &str = ".lock";
+
+/// A throwaway profile directory for one launch of a web app, kept locked for as long as
+/// this value lives.
+pub struct EphemeralProfile {
+    pub path: PathBuf,
+    _lock: fs::File,
+}
+
+/// Open the lock file in `dir` and try to take it without waiting.
+fn lock_ephemeral_dir(dir: &Path) -> std::io::Result<fs::File> {
+    let lock = fs::File::create(dir.join(EPHEMERAL_LOCK))?;
+    // SAFETY: `flock` only takes the descriptor, which `lock` keeps open
+    if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(lock)
+}
+
+/// Create a fresh throwaway profile directory for one launch of a web app.
+/// Directories left behind by previous launches whose lock is no longer held are removed.
+pub fn ephemeral_profile_path(app_id: &str) -> Option<EphemeralProfile> {
+    let root = dirs::cache_dir()?.join(APP_ID).join("ephemeral");
+
+    // Clean up leftovers from crashed launches. Directories still being set up are hidden
+    // until they are locked.
+    if let Ok(entries) = fs::read_dir(&root) {
+        for entry in entries.filter_map(Result::ok) {
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            if let Ok(_lock) = lock_ephemeral_dir(&entry.path()) {
+                let _ = fs::remove_dir_all(entry.path());
+            }
+        }
+    }
+
+    let name = format!("{app_id}-{}", std::process::id());
+    let staging = root.join(format!(".{name}"));
+    let path = root.join(name);
+    let profile = create_dir_all(&staging)
+        .and_then(|()| lock_ephemeral_dir(&staging))
+        .and_then(|lock| {
+            fs::rename(&staging, &path)?;
+            Ok(EphemeralProfile { path, _lock: lock })
+        });
+    match profile {
+        Ok(profile) => Some(profile),
+        Err(e) => {
+            tracing::error!("Failed to create ephemeral profile directory: {e}");
+            let _ = fs::remove_dir_all(staging);
+            None
+        }
+    }
+}
+
 /// Validate that downloaded bytes look like a real image (PNG, JPEG, GIF, or ICO).
 fn is_valid_image_bytes(data: &[u8]) -> bool {
     if data.len() < 4 {
@@ -115,9 +317,14 @@ fn is_valid_image_bytes(data: &[u8]) -> bool {
     false
 }
 
+/// Validate that downloaded bytes are an SVG document that can be rendered.
+fn is_valid_svg_bytes(data: &[u8]) -> bool {
+    usvg::Tree::from_data(data, &usvg::Options::default()).is_ok()
+}
+
 /// Sanitize a domain string for safe use in filenames.
 /// Only allows alphanumeric, dots, and hyphens.
-fn sanitize_domain_for_filename(domain: &str) -> String {
+pub(crate) fn sanitize_domain_for_filename(domain: &str) -> String {
     domain
         .chars()
         .filter(|c| c.is_alphanumeric() || *c == '.' || *c == '-')
@@ -162,32 +369,196 @@ fn extract_meta_content(html: &str, property: &str) -> Option<String> {
     None
 }
 
-/// Fetch basic site metadata (title) from a URL.
-/// Returns the page title if found.
-pub async fn fetch_site_title(url_str: &str) -> Option<String> {
-    if !url_valid(url_str) {
-        return None;
+/// Value of attribute `name` in an HTML start tag, unquoted and decoded.
+fn html_attribute(tag: &str, name: &str) -> Option<String> {
+    let lower = tag.to_ascii_lowercase();
+    let mut search = 0;
+    while let Some(found) = lower[search..].find(name) {
+        let start = search + found;
+        search = start + name.len();
+        // Only whole attribute names, not e.g. "data-href"
+        let preceded = lower[..start].ends_with(|c: char| c.is_ascii_whitespace());
+        let rest = lower[search..].trim_start();
+        if !preceded || !rest.starts_with('=') {
+            continue;
+        }
+        let value_start = tag.len() - rest[1..].trim_start().len();
+        let value = &tag[value_start..];
+        let value = match value.chars().next() {
+            Some(quote @ ('"' | '\'')) => value[1..].split(quote).next()?,
+            _ => value
+                .split(|c: char| c.is_ascii_whitespace() || c == '>')
+                .next()?,
+        };
+        return Some(html_decode_basic(value.trim()));
     }
+    None
+}
+
+/// An icon declared by a page's `<link>` or `<meta>` tags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PageIcon {
+    url: url::Url,
+    svg: bool,
+    /// Declared or conventional size in pixels
+    size: Option<u32>,
+}
+
+/// The start tags named `name` in `html`, without the closing `>`.
+fn html_tags<'a>(html: &'a str, name: &str) -> Vec<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    lower
+        .match_indices(&format!("<{name}"))
+        .filter_map(|(start, _)| Some(&html[start..start + lower[start..].find('>')?]))
+        .collect()
+}
+
+/// The largest width in a size list like "16x16 32x32".
+fn largest_size(sizes: &str) -> Option<u32> {
+    sizes
+        .split_whitespace()
+        .filter_map(|size| {
+            let size = size.to_ascii_lowercase();
+            let (width, _) = size.split_once('x')?;
+            width.parse::<u32>().ok()
+        })
+        .max()
+}
+
+/// Turn declared icons into [`PageIcon`]s: relative URLs resolved against `base`, only
+/// http(s) ones, each once.
+fn resolve_icons(
+    icons: impl IntoIterator<Item = (String, bool, Option<u32>)>,
+    base: &url::Url,
+) -> Vec<PageIcon> {
+    let mut seen = HashSet::new();
+    icons
+        .into_iter()
+        .filter_map(|(href, svg, size)| {
+            let url = base.join(&href).ok()?;
+            if !matches!(url.scheme(), "http" | "https") || !seen.insert(url.clone()) {
+                return None;
+            }
+            let svg = svg || url.path().to_ascii_lowercase().ends_with(".svg");
+            Some(PageIcon { url, svg, size })
+        })
+        .collect()
+}
+
+/// Icons declared by `html`, resolved against `base`: SVG icons first, then by size.
+fn page_icons(html: &str, base: &url::Url) -> Vec<PageIcon> {
+    let links = html_tags(html, "link").into_iter().filter_map(|tag| {
+        let rel = html_attribute(tag, "rel")?.to_ascii_lowercase();
+        // "mask-icon" is a single-color Safari pinned tab icon, not the site's icon
+        let apple = rel
+            .split_whitespace()
+            .any(|rel| rel.starts_with("apple-touch-icon"));
+        if !apple && !rel.split_whitespace().any(|rel| rel == "icon") {
+            return None;
+        }
+        let svg = html_attribute(tag, "type")
+            .is_some_and(|mime| mime.eq_ignore_ascii_case("image/svg+xml"));
+        let size = html_attribute(tag, "sizes")
+            .and_then(|sizes| largest_size(&sizes))
+            // Apple's touch icons are 180×180 unless they say otherwise
+            .or(apple.then_some(180));
+        Some((html_attribute(tag, "href")?, svg, size))
+    });
+    let metas = html_tags(html, "meta").into_iter().filter_map(|tag| {
+        let name = html_attribute(tag, "property")
+            .or_else(|| html_attribute(tag, "name"))?
+            .to_ascii_lowercase();
+        let size = match name.as_str() {
+            "msapplication-tileimage" => Some(144),
+            // Social previews have no fixed size, so they are tried last
+            "og:image" => None,
+            _ => return None,
+        };
+        Some((html_attribute(tag, "content")?, false, size))
+    });
+
+    let mut icons = resolve_icons(links.chain(metas), base);
+    icons.sort_by_key(|icon| (!icon.svg, std::cmp::Reverse(icon.size)));
+    icons
+}
+
+/// URL of the web app manifest linked by `html`.
+fn manifest_url(html: &str, base: &url::Url) -> Option<url::Url> {
+    html_tags(html, "link").into_iter().find_map(|tag| {
+        let rel = html_attribute(tag, "rel")?.to_ascii_lowercase();
+        if !rel.split_whitespace().any(|rel| rel == "manifest") {
+            return None;
+        }
+        base.join(&html_attribute(tag, "href")?).ok()
+    })
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WebManifest {
+    icons: Vec<WebManifestIcon>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct WebManifestIcon {
+    src: String,
+    sizes: String,
+    #[serde(rename = "type")]
+    mime: String,
+}
+
+/// Icons listed in a web app manifest, with relative URLs resolved against the manifest's.
+fn manifest_icons(manifest: &[u8], base: &url::Url) -> Vec<PageIcon> {
+    let Ok(manifest) = serde_json::from_slice::<WebManifest>(manifest) else {
+        return Vec::new();
+    };
+    let icons = manifest.icons.into_iter().map(|icon| {
+        let svg = icon.mime.eq_ignore_ascii_case("image/svg+xml");
+        (icon.src, svg, largest_size(&icon.sizes))
+    });
+    resolve_icons(icons, base)
+}
 
-    let response = tokio::process::Command::new("wget")
-        .arg("-q")
-        .arg("-O")
-        .arg("-")
-        .arg("--timeout=10")
-        .arg("--max-redirect=3")
-        .arg(url_str)
-        .output()
-        .await
+/// A downloaded raster icon with its width and height; ICO files become their largest
+/// frame.
+fn raster_icon(body: Vec<u8>) -> Option<(Vec<u8>, u32, u32)> {
+    let body = match ico::to_png(&body) {
+        Some((png, _)) => png,
+        None => body,
+    };
+    if !is_valid_image_bytes(&body) {
+        return None;
+    }
+    let (width, height) = ImageReader::new(Cursor::new(&body))
+        .with_guessed_format()
+        .ok()?
+        .into_dimensions()
         .ok()?;
+    Some((body, width, height))
+}
 
-    if !response.status.success() || response.stdout.is_empty() {
+/// Fetch basic site metadata (title) from a URL.
+/// Returns the page title if found.
+pub async fn fetch_site_title(url_str: &str, cancel: &http::Cancel) -> Option<String> {
+    if !url_valid(url_str) {
         return None;
     }
 
+    let options = http::Options {
+        max_redirects: 3,
+        ..Default::default()
+    };
+    let body = match http::get(url_str, &options, cancel).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::debug!("Failed to fetch the title of {url_str}: {e}");
+            return None;
+        }
+    };
+
     // Limit to first 64KB to avoid processing huge pages
-    let html = String::from_utf8_lossy(
-        &response.stdout[..response.stdout.len().min(64 * 1024)]
-    );
+    let html = String::from_utf8_lossy(&body[..body.len().min(64 * 1024)]);
 
     // Try og:title first
     if let Some(title) = extract_meta_content(&html, "og:title") {
@@ -210,9 +581,37 @@ pub async fn fetch_site_title(url_str: &str) -> Option<String> {
     None
 }
 
+/// The start of the page at `url_str`, where its icons are declared.
+async fn page_head(url_str: &str, cancel: &http::Cancel) -> Result<String, http::Error> {
+    let options = http::Options {
+        max_redirects: 3,
+        ..Default::default()
+    };
+    let body = http::get(url_str, &options, cancel).await?;
+    // Limit to first 64KB to avoid processing huge pages
+    Ok(String::from_utf8_lossy(&body[..body.len().min(64 * 1024)]).into_owned())
+}
+
+/// Where [`download_favicon`] looks for an icon, in order of preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FaviconSource {
+    /// An `image/svg+xml` icon declared by the page
+    Svg,
+    /// A raster icon declared by the page
+    Page,
+    /// The site's own `/favicon.ico`
+    Ico,
+    /// Google's favicon service
+    Service,
+}
+
 /// Download a favicon for the given URL and save it to the icons directory.
-/// Returns the path to the saved favicon file on success.
-pub async fn download_favicon(url_str: &str) -> Option<String> {
+/// An SVG icon declared by the page is saved as SVG. Otherwise the largest raster icon
+/// the page declares (icons, Apple touch icons, tile and Open Graph images), the largest
+/// frame of the site's favicon.ico or a PNG from the favicon service is saved.
+/// Returns the path to the saved favicon file on success. Favicons are cached by origin
+/// and revalidated with the server, and the cached one is used when offline.
+pub async fn download_favicon(url_str: &str, cancel: &http::Cancel) -> Option<String> {
     let parsed = url::Url::parse(url_str).ok()?;
     let domain = parsed.host_str()?;
 
@@ -225,51 +624,261 @@ pub async fn download_favicon(url_str: &str) -> Option<String> {
         return None;
     }
 
-    let favicon_url = format!("https://www.google.com/s2/favicons?domain={domain}&sz=128");
-
-    let response = tokio::process::Command::new("wget")
-        .arg("-q")
-        .arg("-O")
-        .arg("-")
-        .arg("--timeout=10")
-        .arg(&favicon_url)
-        .output()
-        .await
-        .ok()?;
+    let origin = favicon_cache::origin(&parsed);
+    let cached = favicon_cache::FaviconCache::load().get(&origin).cloned();
+    if let Some(cached) = cached.as_ref().filter(|cached| cached.is_fresh()) {
+        return Some(cached.path.clone());
+    }
 
-    if !response.status.success() || response.stdout.is_empty() {
-        return None;
+    // Prefer the page's own icons, SVG ones stay sharp at any size
+    let icons = match page_head(url_str, cancel).await {
+        Ok(html) => page_icons(&html, &parsed),
+        Err(http::Error::Cancelled) => return None,
+        Err(e) => {
+            tracing::debug!("Failed to fetch {url_str} for its icons: {e}");
+            Vec::new()
+        }
+    };
+    let mut sources: Vec<(String, FaviconSource)> = icons
+        .into_iter()
+        .map(|icon| {
+            let kind = if icon.svg {
+                FaviconSource::Svg
+            } else {
+                FaviconSource::Page
+            };
+            (icon.url.to_string(), kind)
+        })
+        .collect();
+    if let Ok(ico) = parsed.join("/favicon.ico") {
+        sources.push((ico.to_string(), FaviconSource::Ico));
     }
+    sources.push((
+        format!("https://www.google.com/s2/favicons?domain={domain}&sz=128"),
+        FaviconSource::Service,
+    ));
 
     // Reject excessively large responses (max 2 MB for a favicon)
-    const MAX_FAVICON_SIZE: usize = 2 * 1024 * 1024;
-    if response.stdout.len() > MAX_FAVICON_SIZE {
-        tracing::warn!(
-            "Favicon response too large: {} bytes",
-            response.stdout.len()
-        );
-        return None;
+    let options = http::Options {
+        max_size: 2 * 1024 * 1024,
+        ..Default::default()
+    };
+    let safe_domain = sanitize_domain_for_filename(domain);
+    for (source, kind) in sources {
+        // Validators only apply to the icon they were sent with
+        let cached_source = cached.as_ref().filter(|cached| cached.source == source);
+        let validators = cached_source.map(|cached| &cached.validators);
+        let response = match http::fetch(&source, &options, validators, cancel).await {
+            Ok(response) => response,
+            Err(http::Error::Cancelled) => return None,
+            Err(e) => {
+                tracing::warn!("Failed to download favicon for {domain} from {source}: {e}");
+                continue;
+            }
+        };
+        if let Some(cached) = cached_source.filter(|_| response.not_modified()) {
+            let (path, validators) = (cached.path.clone(), cached.validators.clone());
+            favicon_cache::FaviconCache::store(&origin, path.clone(), source, validators);
+            return Some(path);
+        }
+
+        // Validate the response is actually an image, and store ICO files as their
+        // largest frame
+        let body = match kind {
+            FaviconSource::Svg => Some(response.body).filter(|body| is_valid_svg_bytes(body)),
+            // Small or far from square images make blurry or distorted app icons, the
+            // favicon service does better
+            FaviconSource::Page | FaviconSource::Ico => raster_icon(response.body)
+                .filter(|(_, width, height)| {
+                    let (short, long) = (*width.min(height), *width.max(height));
+                    short >= ICON_SIZE && long * 4 <= short * 5
+                })
+                .map(|(data, ..)| data),
+            FaviconSource::Service if ico::is_ico(&response.body) => {
+                ico::to_png(&response.body).map(|(png, _)| png)
+            }
+            FaviconSource::Service => Some(response.body).filter(|body| is_valid_image_bytes(body)),
+        };
+        let Some(body) = body else {
+            tracing::warn!("Favicon response from {source} is not a usable image");
+            continue;
+        };
+
+        let icons_dir = icons_location()?;
+        if let Err(e) = tokio::fs::create_dir_all(&icons_dir).await {
+            tracing::error!("Failed to create icons directory: {e}");
+            return None;
+        }
+
+        let extension = if kind == FaviconSource::Svg {
+            "svg"
+        } else {
+            "png"
+        };
+        let favicon_path = icons_dir.join(format!("favicon-{safe_domain}.{extension}"));
+        tokio::fs::write(&favicon_path, &body).await.ok()?;
+
+        let path = favicon_path.to_string_lossy().to_string();
+        favicon_cache::FaviconCache::store(&origin, path.clone(), source, response.validators);
+        return Some(path);
     }
 
-    // Validate the response is actually an image
-    if !is_valid_image_bytes(&response.stdout) {
-        tracing::warn!("Favicon response is not a valid image format");
-        return None;
+    // Offline: the last download is better than nothing
+    cached.map(|cached| cached.path)
+}
+
+/// An icon a site offers, downloaded so the user can pick one.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IconCandidate {
+    pub icon: Icon,
+    /// Where it was downloaded from
+    pub source: String,
+    /// Width and height of raster icons, `None` for SVG
+    pub size: Option<(u32, u32)>,
+}
+
+fn icon_candidates_dir() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_ID).join("icon-candidates"))
+}
+
+/// Download every icon the site at `url_str` offers, in parallel: the icons its page and
+/// web app manifest declare, its favicon.ico, and the Google and DuckDuckGo favicon
+/// services. Icons too small for an app icon and duplicates are left out; the rest are
+/// sorted with SVG icons first, then the largest.
+pub async fn icon_candidates(url_str: &str, cancel: &http::Cancel) -> Vec<IconCandidate> {
+    let Ok(parsed) = url::Url::parse(url_str) else {
+        return Vec::new();
+    };
+    let Some(domain) = parsed.host_str().map(sanitize_domain_for_filename) else {
+        return Vec::new();
+    };
+    let Some(dir) = icon_candidates_dir() else {
+        return Vec::new();
+    };
+    // The candidates of the previous search are no longer offered
+    let _ = tokio::fs::remove_dir_all(&dir).await;
+    if let Err(e) = tokio::fs::create_dir_all(&dir).await {
+        tracing::error!("Failed to create the icon candidates directory: {e}");
+        return Vec::new();
+    }
+
+    let mut icons = Vec::new();
+    match page_head(url_str, cancel).await {
+        Ok(html) => {
+            icons.extend(page_icons(&html, &parsed));
+            if let Some(manifest) = manifest_url(&html, &parsed) {
+                match http::get(manifest.as_str(), &http::Options::default(), cancel).await {
+                    Ok(body) => icons.extend(manifest_icons(&body, &manifest)),
+                    Err(http::Error::Cancelled) => return Vec::new(),
+                    Err(e) => tracing::debug!("Failed to fetch the manifest {manifest}: {e}"),
+                }
+            }
+        }
+        Err(http::Error::Cancelled) => return Vec::new(),
+        Err(e) => tracing::debug!("Failed to fetch {url_str} for its icons: {e}"),
+    }
+    let fallbacks = [
+        parsed.join("/favicon.ico").ok(),
+        url::Url::parse(&format!(
+            "https://www.google.com/s2/favicons?domain={domain}&sz=256"
+        ))
+        .ok(),
+        url::Url::parse(&format!("https://icons.duckduckgo.com/ip3/{domain}.ico")).ok(),
+    ];
+    icons.extend(fallbacks.into_iter().flatten().map(|url| PageIcon {
+        url,
+        svg: false,
+        size: None,
+    }));
+    let mut seen = HashSet::new();
+    icons.retain(|icon| seen.insert(icon.url.clone()));
+
+    // Reject excessively large responses (max 2 MB for an icon)
+    let options = http::Options {
+        max_size: 2 * 1024 * 1024,
+        ..Default::default()
+    };
+    let mut downloads = tokio::task::JoinSet::new();
+    for icon in icons {
+        let (cancel, dir, domain) = (cancel.clone(), dir.clone(), domain.clone());
+        downloads.spawn(async move {
+            let body = match http::get(icon.url.as_str(), &options, &cancel).await {
+                Ok(body) => body,
+                Err(e) => {
+                    tracing::debug!("Failed to download the icon {}: {e}", icon.url);
+                    return None;
+                }
+            };
+            let (data, size) = if icon.svg {
+                (Some(body).filter(|body| is_valid_svg_bytes(body))?, None)
+            } else {
+                let (data, width, height) = raster_icon(body)?;
+                if width.min(height) < ICON_SIZE {
+                    return None;
+                }
+                (data, Some((width, height)))
+            };
+
+            // Named by content, so services returning the same icon are noticed
+            let mut hasher = std::hash::DefaultHasher::new();
+            std::hash::Hash::hash(&data, &mut hasher);
+            let hash = std::hash::Hasher::finish(&hasher);
+            let extension = if icon.svg { "svg" } else { "png" };
+            let path = dir.join(format!("favicon-{domain}-{hash:016x}.{extension}"));
+            tokio::fs::write(&path, &data).await.ok()?;
+
+            let handle = if icon.svg {
+                IconType::Svg(widget::svg::Handle::from_path(&path))
+            } else {
+                IconType::Raster(iced_core::image::Handle::from_bytes(data))
+            };
+            Some(IconCandidate {
+                icon: Icon::new(handle, path.to_string_lossy().to_string(), true),
+                source: icon.url.to_string(),
+                size,
+            })
+        });
     }
 
+    let mut paths = HashSet::new();
+    let mut candidates = Vec::new();
+    while let Some(result) = downloads.join_next().await {
+        if let Ok(Some(candidate)) = result {
+            if paths.insert(candidate.icon.path.clone()) {
+                candidates.push(candidate);
+            }
+        }
+    }
+    if cancel.is_cancelled() {
+        return Vec::new();
+    }
+    candidates.sort_by_key(|candidate| {
+        let side = candidate.size.map(|(width, height)| width.min(height));
+        (side.is_some(), std::cmp::Reverse(side))
+    });
+    candidates
+}
+
+/// Copy a chosen candidate to the icons directory, so the next search doesn't remove it.
+/// Returns the icon with its new path.
+pub async fn save_icon_candidate(candidate: IconCandidate) -> Option<Icon> {
     let icons_dir = icons_location()?;
     if let Err(e) = tokio::fs::create_dir_all(&icons_dir).await {
         tracing::error!("Failed to create icons directory: {e}");
         return None;
     }
-
-    let safe_domain = sanitize_domain_for_filename(domain);
-    let favicon_path = icons_dir.join(format!("favicon-{safe_domain}.png"));
-    tokio::fs::write(&favicon_path, &response.stdout)
-        .await
-        .ok()?;
-
-    Some(favicon_path.to_string_lossy().to_string())
+    let source = PathBuf::from(&candidate.icon.path);
+    let path = icons_dir.join(source.file_name()?);
+    if let Err(e) = tokio::fs::copy(&source, &path).await {
+        tracing::error!("Failed to save the chosen icon: {e}");
+        return None;
+    }
+    let icon = match candidate.icon.icon {
+        // SVG handles read their file when drawn
+        IconType::Svg(_) => IconType::Svg(widget::svg::Handle::from_path(&path)),
+        raster => raster,
+    };
+    Some(Icon::new(icon, path.to_string_lossy().to_string(), true))
 }
 
 /// Get the path for a cached thumbnail file.
@@ -290,8 +899,9 @@ pub fn thumbnails_path(filename: &str) -> Option<PathBuf> {
 
 /// Download a website thumbnail via thum.io and cache it.
 /// Returns the file path on success. Cached thumbnails are reused if less than 24 hours old.
-pub async fn download_thumbnail(url_str: &str) -> Option<String> {
-    if !url_valid(url_str) {
+pub async fn download_thumbnail(url_str: &str, cancel: &http::Cancel) -> Option<String> {
+    // The thumbnail service can't reach pages on this machine
+    if !url_valid(url_str) || is_local_url(url_str) {
         return None;
     }
 
@@ -315,36 +925,26 @@ pub async fn download_thumbnail(url_str: &str) -> Option<String> {
 
     let thumb_url = format!("https://image.thum.io/get/width/600/{url_str}");
 
-    let response = tokio::process::Command::new("wget")
-        .arg("-q")
-        .arg("-O")
-        .arg("-")
-        .arg("--timeout=15")
-        .arg(&thumb_url)
-        .output()
-        .await
-        .ok()?;
-
-    if !response.status.success() || response.stdout.is_empty() {
-        return None;
-    }
-
     // Max 5 MB for a thumbnail
-    const MAX_THUMB_SIZE: usize = 5 * 1024 * 1024;
-    if response.stdout.len() > MAX_THUMB_SIZE {
-        tracing::warn!(
-            "Thumbnail response too large: {} bytes",
-            response.stdout.len()
-        );
-        return None;
-    }
+    let options = http::Options {
+        timeout_secs: 15,
+        max_size: 5 * 1024 * 1024,
+        ..Default::default()
+    };
+    let body = match http::get(&thumb_url, &options, cancel).await {
+        Ok(body) => body,
+        Err(e) => {
+            tracing::warn!("Failed to download thumbnail for {domain}: {e}");
+            return None;
+        }
+    };
 
-    if !is_valid_image_bytes(&response.stdout) {
+    if !is_valid_image_bytes(&body) {
         tracing::warn!("Thumbnail response is not a valid image format");
         return None;
     }
 
-    tokio::fs::write(&thumb_path, &response.stdout).await.ok()?;
+    tokio::fs::write(&thumb_path, &body).await.ok()?;
 
     Some(thumb_path.to_string_lossy().to_string())
 }
@@ -429,9 +1029,9 @@ pub async fn execute_script(script: String) -> Result<Child, std::io::Error> {
 }
 
 /// Maximum directory depth when searching for icons.
-const MAX_ICON_SEARCH_DEPTH: usize = 8;
+pub const MAX_ICON_SEARCH_DEPTH: usize = 8;
 /// Maximum number of icon results to return.
-const MAX_ICON_RESULTS: usize = 200;
+pub const MAX_ICON_RESULTS: usize = 200;
 
 pub async fn find_icon(path: PathBuf, icon_name: String) -> Vec<String> {
     tokio::task::spawn_blocking(move || {
@@ -511,6 +1111,9 @@ pub async fn image_handle(path: String) -> Option<Icon> {
                     tracing::warn!("Failed to read icon file {:?}: {e}", rp);
                     return None;
                 }
+                if let Some((png, _)) = ico::to_png(&data) {
+                    data = png;
+                }
 
                 // Validate image dimensions in the blocking thread
                 let image_reader = ImageReader::new(Cursor::new(&data))
@@ -628,6 +1231,14 @@ impl Category {
     }
 }
 
+/// A category made by the user. Menus show its apps under the XDG category `xdg`, as
+/// they only know those.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CustomCategory {
+    pub name: String,
+    pub xdg: Category,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum IconType {
     Raster(widget::image::Handle),
@@ -675,9 +1286,52 @@ impl Default for WindowSize {
 pub struct WebviewArgs {
     #[arg(default_value = "")]
     pub id: String,
+    /// URI to open, e.g. from a registered URL scheme
+    #[arg()]
+    #[serde(skip)]
+    pub uri: Option<String>,
     #[arg(long, default_value_t = false)]
     #[serde(default)]
     pub private: bool,
+    /// Start with the window hidden
+    #[arg(long, default_value_t = false)]
+    #[serde(skip)]
+    pub hidden: bool,
+    /// Open URL in the running instance
+    #[arg(long, value_name = "URL", group = "remote")]
+    #[serde(skip)]
+    pub navigate: Option<String>,
+    /// Reload the running instance
+    #[arg(long, default_value_t = false, group = "remote")]
+    #[serde(skip)]
+    pub reload: bool,
+    /// Quit the running instance
+    #[arg(long, default_value_t = false, group = "remote")]
+    #[serde(skip)]
+    pub quit: bool,
+    /// Initialize and wait in the background for a launch to take over
+    #[arg(long, default_value_t = false)]
+    #[serde(skip)]
+    pub warm: bool,
+    /// Preview the app saved in FILE, starting over whenever it changes
+    #[arg(long, value_name = "FILE")]
+    #[serde(skip)]
+    pub preview: Option<PathBuf>,
+}
+
+impl WebviewArgs {
+    /// Command for an already-running instance, if one was requested.
+    pub fn remote_command(&self) -> Option<control::Command> {
+        if let Some(url) = &self.navigate {
+            Some(control::Command::Navigate(url.clone()))
+        } else if self.reload {
+            Some(control::Command::Reload)
+        } else if self.quit {
+            Some(control::Command::Quit)
+        } else {
+            None
+        }
+    }
 }
 
 impl AsRef<str> for WebviewArgs {
@@ -692,9 +1346,29 @@ impl IntoIterator for WebviewArgs {
 
     fn into_iter(self) -> Self::IntoIter {
         let mut args = vec![self.id];
+        if let Some(uri) = self.uri {
+            args.push(uri);
+        }
         if self.private {
             args.push("--private".to_string());
         }
+        if self.hidden {
+            args.push("--hidden".to_string());
+        }
+        if let Some(url) = self.navigate {
+            args.push("--navigate".to_string());
+            args.push(url);
+        }
+        if self.reload {
+            args.push("--reload".to_string());
+        }
+        if self.quit {
+            args.push("--quit".to_string());
+        }
+        if let Some(path) = self.preview {
+            args.push("--preview".to_string());
+            args.push(path.to_string_lossy().to_string());
+        }
         args.into_iter()
     }
 }
@@ -705,10 +1379,12 @@ pub fn profile_size(app_id: &str) -> u64 {
     let Some(path) = profiles_path(app_id) else {
         return 0;
     };
-    if !path.exists() {
-        return 0;
-    }
-    WalkDir::new(&path)
+    dir_size(&path)
+}
+
+/// Total size of the files under `path`, or 0 if it doesn't exist.
+pub fn dir_size(path: &Path) -> u64 {
+    WalkDir::new(path)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter_map(|e| e.metadata().ok())
@@ -717,6 +1393,89 @@ pub fn profile_size(app_id: &str) -> u64 {
         .sum()
 }
 
+/// Categories of website data stored in a persistent profile directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumIter, Deserialize, Serialize)]
+pub enum ProfileData {
+    Cookies,
+    Cache,
+    LocalStorage,
+    IndexedDb,
+    History,
+    Other,
+}
+
+impl ProfileData {
+    /// Classify a file inside a profile directory by the WebKit storage it belongs to.
+    fn classify(relative: &std::path::Path) -> Self {
+        let lower = relative.to_string_lossy().to_lowercase();
+        if relative == std::path::Path::new(history::FILE_NAME) {
+            ProfileData::History
+        } else if lower.contains("cookie") {
+            ProfileData::Cookies
+        } else if lower.contains("indexeddb") {
+            ProfileData::IndexedDb
+        } else if lower.contains("localstorage") {
+            ProfileData::LocalStorage
+        } else if relative
+            .components()
+            .any(|c| c.as_os_str().eq_ignore_ascii_case("webkitcache"))
+        {
+            // Only WebKit's HTTP disk cache: sites' CacheStorage and service workers are
+            // offline data and belong with the rest
+            ProfileData::Cache
+        } else {
+            ProfileData::Other
+        }
+    }
+}
+
+/// Files in a web app's profile directory, paired with their data category.
+fn profile_files(app_id: &str) -> Vec<(PathBuf, ProfileData, u64)> {
+    let Some(root) = profiles_path(app_id) else {
+        return Vec::new();
+    };
+    WalkDir::new(&root)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            if !meta.is_file() {
+                return None;
+            }
+            let relative = e.path().strip_prefix(&root).ok()?;
+            let category = ProfileData::classify(relative);
+            Some((e.path().to_path_buf(), category, meta.len()))
+        })
+        .collect()
+}
+
+/// Calculate the disk usage of a web app's profile per data category.
+/// Every category is present in the result, in declaration order.
+pub fn profile_usage(app_id: &str) -> Vec<(ProfileData, u64)> {
+    let files = profile_files(app_id);
+    ProfileData::iter()
+        .map(|category| {
+            let size = files
+                .iter()
+                .filter(|(_, c, _)| *c == category)
+                .map(|(_, _, len)| len)
+                .sum();
+            (category, size)
+        })
+        .collect()
+}
+
+/// Remove one category of website data from a web app's profile directory.
+pub fn clear_profile_category(app_id: &str, category: ProfileData) -> Result<(), std::io::Error> {
+    for (path, _, _) in profile_files(app_id)
+        .into_iter()
+        .filter(|(_, c, _)| *c == category)
+    {
+        std::fs::remove_file(&path)?;
+    }
+    Ok(())
+}
+
 /// Format bytes as a human-readable string.
 pub fn format_bytes(bytes: u64) -> String {
     if bytes < 1024 {
@@ -730,37 +1489,23 @@ pub fn format_bytes(bytes: u64) -> String {
     }
 }
 
-/// Scan `/proc/*/cmdline` to find running webview processes and extract their app_id arguments.
-/// Returns a set of app_id strings for currently running webview instances.
-pub fn running_webview_app_ids() -> HashSet<String> {
-    let mut ids = HashSet::new();
-    let Ok(entries) = fs::read_dir("/proc") else {
-        return ids;
-    };
-
-    for entry in entries.filter_map(Result::ok) {
-        let path = entry.path().join("cmdline");
-        if let Ok(data) = fs::read(&path) {
-            // cmdline is NUL-separated
-            let args: Vec<&[u8]> = data.split(|&b| b == 0).collect();
-            if let Some(exe) = args.first() {
-                let exe_str = String::from_utf8_lossy(exe);
-                if exe_str.ends_with("dev-heppen-webapps-webview")
-                    || exe_str.ends_with("dev.heppen.webapps.webview")
-                {
-                    // The app_id is the first argument after the binary name
-                    if let Some(app_id_bytes) = args.get(1) {
-                        let app_id = String::from_utf8_lossy(app_id_bytes);
-                        if !app_id.is_empty() && !app_id.starts_with('-') {
-                            ids.insert(app_id.to_string());
-                        }
-                    }
-                }
-            }
-        }
+/// Format a duration in seconds as hours and minutes, e.g. `2h 5m`.
+pub fn format_duration(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    match (hours, minutes) {
+        (0, 0) => "<1m".to_string(),
+        (0, m) => format!("{m}m"),
+        (h, m) => format!("{h}h {m}m"),
     }
+}
 
-    ids
+/// Find running webview processes in `/proc` and extract their app_id arguments.
+/// Returns a set of app_id strings for currently running webview instances.
+pub fn running_webview_app_ids() -> HashSet<String> {
+    process::running_webviews()
+        .into_iter()
+        .map(|process| process.app_id)
+        .collect()
 }
 
 /// Format a Unix timestamp as a human-readable date/time string.
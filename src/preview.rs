@@ -0,0 +1,70 @@
+//! Previews of an app as configured in the editor, before it is saved.
+//!
+//! The editor writes the app to a file that a webview started with `--preview` shows.
+//! The webview starts over whenever the file changes, and closes when it is removed.
+
+use std::path::{Path, PathBuf};
+
+use crate::{APP_ID, browser::Browser, launcher::WebAppLauncher};
+
+/// Window size of previews, smaller than most apps so it fits beside the manager.
+pub const WINDOW_SIZE: (f64, f64) = (480.0, 720.0);
+
+pub fn path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_ID).join("preview.ron"))
+}
+
+/// Write `launcher` for the preview, leaving the file alone if it is unchanged so the
+/// preview doesn't start over.
+pub fn write(launcher: &WebAppLauncher) -> std::io::Result<PathBuf> {
+    let path = path().ok_or_else(|| std::io::Error::other("no cache directory"))?;
+    let serialized = ron::ser::to_string_pretty(launcher, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    if std::fs::read_to_string(&path).is_ok_and(|current| current == serialized) {
+        return Ok(path);
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("ron.tmp");
+    std::fs::write(&tmp, serialized)?;
+    std::fs::rename(tmp, &path)?;
+    Ok(path)
+}
+
+/// Close the preview.
+pub fn remove() {
+    if let Some(path) = path() {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+pub fn read(path: &Path) -> Option<WebAppLauncher> {
+    let content = std::fs::read_to_string(path).ok()?;
+    ron::from_str(&content)
+        .inspect_err(|e| tracing::warn!("Invalid preview {}: {e}", path.display()))
+        .ok()
+}
+
+/// Turn off what would leave traces of the preview or get in its way: it runs without
+/// the app's profile, history or session, and can't be locked, limited or hidden.
+pub fn prepare(browser: &mut Browser) {
+    browser.private_mode = Some(true);
+    browser.profile = None;
+    browser.ephemeral_profile = None;
+    browser.restore_session = None;
+    browser.navigation_log = None;
+    browser.app_lock = None;
+    browser.daily_limit_minutes = None;
+    browser.start_hidden = None;
+    browser.autostart = None;
+    browser.minimize_to_background = None;
+    browser.global_shortcut = None;
+    browser.window_mode = None;
+    browser.window_position = None;
+    browser.sticky = None;
+    browser.skip_taskbar = None;
+    browser.window_size = Some(crate::WindowSize(WINDOW_SIZE.0, WINDOW_SIZE.1));
+    let title = browser.window_title.clone().unwrap_or_default();
+    browser.window_title = Some(crate::fl!("preview-window-title", title = title));
+}
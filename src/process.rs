@@ -0,0 +1,135 @@
+//! Resource usage of running webviews, read from `/proc`.
+//!
+//! WebKit renders and fetches in helper processes started by the webview, so memory and
+//! CPU time are summed over the webview and all of its descendants.
+
+use std::{collections::HashMap, fs};
+
+/// Kernel clock ticks per second for `/proc/<pid>/stat` times (`USER_HZ`, 100 on Linux).
+pub const TICKS_PER_SEC: u64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WebviewProcess {
+    pub pid: u32,
+    pub app_id: String,
+    /// Resident memory in bytes
+    pub memory: u64,
+    /// User and system CPU time in clock ticks since the process started
+    pub cpu_ticks: u64,
+}
+
+/// Parent pid and CPU ticks from `/proc/<pid>/stat`.
+fn stat(pid: u32) -> Option<(u32, u64)> {
+    let data = fs::read_to_string(format!("/proc/{pid}/stat")).ok()?;
+    // The command name may contain spaces and parentheses, the fields follow the last ')'
+    let fields: Vec<&str> = data.get(data.rfind(')')? + 2..)?.split(' ').collect();
+    let ppid = fields.get(1)?.parse().ok()?;
+    let utime: u64 = fields.get(11)?.parse().ok()?;
+    let stime: u64 = fields.get(12)?.parse().ok()?;
+    Some((ppid, utime + stime))
+}
+
+/// Resident memory in bytes from `/proc/<pid>/status`.
+fn resident(pid: u32) -> u64 {
+    fs::read_to_string(format!("/proc/{pid}/status"))
+        .ok()
+        .and_then(|status| {
+            let value = status.lines().find_map(|line| line.strip_prefix("VmRSS:"))?;
+            value.trim_end().trim_end_matches("kB").trim().parse::<u64>().ok()
+        })
+        .map(|kb| kb * 1024)
+        .unwrap_or(0)
+}
+
+/// The arguments of a webview process, if `pid` is one.
+fn webview_args(pid: u32) -> Option<Vec<String>> {
+    let data = fs::read(format!("/proc/{pid}/cmdline")).ok()?;
+    // cmdline is NUL-separated
+    let mut args = data.split(|&b| b == 0);
+    let exe = String::from_utf8_lossy(args.next()?);
+    if !exe.ends_with("dev-heppen-webapps-webview") && !exe.ends_with("dev.heppen.webapps.webview")
+    {
+        return None;
+    }
+    Some(
+        args.map(|arg| String::from_utf8_lossy(arg).to_string())
+            .collect(),
+    )
+}
+
+/// The app id of a webview process, if `pid` is one.
+fn webview_app_id(pid: u32) -> Option<String> {
+    // The app_id is the first argument after the binary name
+    let app_id = webview_args(pid)?.into_iter().next()?;
+    (!app_id.is_empty() && !app_id.starts_with('-')).then_some(app_id)
+}
+
+/// Whether a preview opened from the editor is showing.
+pub fn preview_running() -> bool {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return false;
+    };
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .filter_map(webview_args)
+        .any(|args| args.iter().any(|arg| arg == "--preview"))
+}
+
+/// Every running webview with the resources used by its process tree.
+pub fn running_webviews() -> Vec<WebviewProcess> {
+    let Ok(entries) = fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let pids: Vec<u32> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().to_str()?.parse().ok())
+        .collect();
+
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    let mut ticks: HashMap<u32, u64> = HashMap::new();
+    for &pid in &pids {
+        if let Some((ppid, cpu)) = stat(pid) {
+            children.entry(ppid).or_default().push(pid);
+            ticks.insert(pid, cpu);
+        }
+    }
+
+    let mut webviews: Vec<WebviewProcess> = pids
+        .iter()
+        .filter_map(|&pid| {
+            let app_id = webview_app_id(pid)?;
+            let mut process = WebviewProcess {
+                pid,
+                app_id,
+                memory: 0,
+                cpu_ticks: 0,
+            };
+            let mut pending = vec![pid];
+            while let Some(pid) = pending.pop() {
+                process.memory += resident(pid);
+                process.cpu_ticks += ticks.get(&pid).copied().unwrap_or(0);
+                if let Some(kids) = children.get(&pid) {
+                    pending.extend(kids);
+                }
+            }
+            Some(process)
+        })
+        .collect();
+    webviews.sort_by(|a, b| a.app_id.cmp(&b.app_id));
+    webviews
+}
+
+/// Forcefully end a webview that no longer responds to `Quit`.
+pub async fn kill(pid: u32) -> std::io::Result<()> {
+    let status = tokio::process::Command::new("kill")
+        .arg("-KILL")
+        .arg(pid.to_string())
+        .status()
+        .await?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!("kill exited with {status}")))
+    }
+}
@@ -0,0 +1,65 @@
+//! Session state saved when a web app exits and restored on the next launch.
+//!
+//! The session is the page and scroll state of the app's window, plus the windows its
+//! pages opened with `window.open` and still had open, with their size. The back/forward
+//! history is not saved.
+//!
+//! Stored as `session.ron` in the app's persistent profile. The file is replaced
+//! atomically, so a crash mid-write leaves the previous session intact.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+pub const FILE_NAME: &str = "session.ron";
+
+/// Scroll offset of a scrollable element (text area, scrolling pane) inside the page.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct ElementScroll {
+    /// CSS selector locating the element
+    pub selector: String,
+    pub top: f64,
+    pub left: f64,
+}
+
+/// The page open in the app's window and how far it was scrolled.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Session {
+    pub url: String,
+    #[serde(default)]
+    pub scroll_x: f64,
+    #[serde(default)]
+    pub scroll_y: f64,
+    #[serde(default)]
+    pub elements: Vec<ElementScroll>,
+    /// Other windows the app had open, in the order they were opened
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<WindowSession>,
+}
+
+/// A window opened by one of the app's pages.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct WindowSession {
+    /// Its page; never has windows of its own
+    pub page: Session,
+    pub width: i32,
+    pub height: i32,
+}
+
+fn path(profile: &Path) -> PathBuf {
+    profile.join(FILE_NAME)
+}
+
+pub fn load(profile: &Path) -> Option<Session> {
+    let content = std::fs::read_to_string(path(profile)).ok()?;
+    ron::from_str(&content).ok()
+}
+
+/// Write `session` to a temporary file and move it into place.
+pub fn save(profile: &Path, session: &Session) -> Result<(), std::io::Error> {
+    let serialized = ron::ser::to_string_pretty(session, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    std::fs::create_dir_all(profile)?;
+    let tmp = profile.join(format!("{FILE_NAME}.tmp"));
+    std::fs::write(&tmp, serialized)?;
+    std::fs::rename(tmp, path(profile))
+}
@@ -0,0 +1,96 @@
+//! Ready-made custom CSS for popular sites, applied to an app from the editor.
+//!
+//! The gallery ships with the app and can be updated from the repository; the last
+//! fetched copy is kept in the cache and used instead of the shipped one.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{APP_ID, http};
+
+const BUILTIN: &str = include_str!("../resources/styles/gallery.ron");
+
+const GALLERY_URL: &str = concat!(
+    "https://raw.githubusercontent.com/olafkfreund/cosmic-ext-web-apps/",
+    "main/resources/styles/gallery.ron"
+);
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct SiteStyle {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    /// Sites it is made for, including their subdomains; empty for any site
+    pub hosts: Vec<String>,
+    pub css: String,
+}
+
+impl SiteStyle {
+    /// Whether the style is made for the site at `url`, or for any site.
+    pub fn suits(&self, url: &str) -> bool {
+        if self.hosts.is_empty() {
+            return true;
+        }
+        let Some(host) = url::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+        else {
+            return false;
+        };
+        self.hosts
+            .iter()
+            .any(|site| host == *site || host.ends_with(&format!(".{site}")))
+    }
+
+    /// The CSS with a comment naming the style, so it can be found in the app's CSS.
+    pub fn snippet(&self) -> String {
+        format!("/* {} */\n{}\n", self.name, self.css.trim())
+    }
+}
+
+fn path() -> Option<PathBuf> {
+    Some(dirs::cache_dir()?.join(APP_ID).join("site-styles.ron"))
+}
+
+fn parse(content: &str) -> Option<Vec<SiteStyle>> {
+    ron::from_str(content)
+        .inspect_err(|e| tracing::warn!("Invalid style gallery: {e}"))
+        .ok()
+}
+
+/// The last fetched gallery, or the one shipped with the app.
+pub fn load() -> Vec<SiteStyle> {
+    path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| parse(&content))
+        .or_else(|| parse(BUILTIN))
+        .unwrap_or_default()
+}
+
+/// Download the current gallery and keep it for [`load`].
+pub async fn fetch(cancel: &http::Cancel) -> Result<Vec<SiteStyle>, http::Error> {
+    let options = http::Options {
+        max_size: 1024 * 1024,
+        ..Default::default()
+    };
+    let response = http::fetch(GALLERY_URL, &options, None, cancel).await?;
+    if response.status != 200 {
+        return Err(http::Error::Failed(format!("HTTP {}", response.status)));
+    }
+    let content = String::from_utf8(response.body)
+        .map_err(|_| http::Error::Failed("the gallery isn't UTF-8".to_string()))?;
+    let styles = parse(&content)
+        .ok_or_else(|| http::Error::Failed("the gallery can't be read".to_string()))?;
+
+    if let Some(path) = path() {
+        if let Some(dir) = path.parent() {
+            let _ = tokio::fs::create_dir_all(dir).await;
+        }
+        if let Err(e) = tokio::fs::write(&path, content).await {
+            tracing::warn!("Failed to save the style gallery: {e}");
+        }
+    }
+    Ok(styles)
+}
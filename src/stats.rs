@@ -0,0 +1,109 @@
+//! Usage statistics recorded by the webview: launches, time the window was open and
+//! focused, and per-day totals.
+//!
+//! Kept in `stats/<app_id>.ron` next to the app database rather than in the profile, so
+//! clearing website data leaves them alone.
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Days of per-day totals kept.
+const MAX_DAYS: usize = 90;
+
+/// Totals for one local calendar day.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DayUsage {
+    pub sessions: u32,
+    /// Seconds the app was running
+    pub open_secs: u64,
+    /// Seconds its window had focus
+    pub focus_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct UsageStats {
+    pub sessions: u64,
+    pub first_used: Option<u64>,
+    pub last_used: Option<u64>,
+    pub open_secs: u64,
+    pub focus_secs: u64,
+    /// Length of the most recent session in seconds
+    pub last_session_secs: u64,
+    /// Per-day totals by local date (`YYYY-MM-DD`)
+    pub days: BTreeMap<String, DayUsage>,
+}
+
+/// One run of the webview, from launch to exit.
+#[derive(Debug, Clone, Copy)]
+pub struct Session {
+    /// Unix timestamp of the launch
+    pub started: u64,
+    pub open_secs: u64,
+    pub focus_secs: u64,
+}
+
+pub fn path(app_id: &str) -> Option<PathBuf> {
+    let safe_id = crate::browser::sanitize_app_id(app_id);
+    Some(
+        dirs::data_dir()?
+            .join(crate::APP_ID)
+            .join("stats")
+            .join(format!("{safe_id}.ron")),
+    )
+}
+
+pub fn load(app_id: &str) -> UsageStats {
+    path(app_id)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| ron::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Add a finished session, counted towards `day` (local `YYYY-MM-DD`).
+pub fn record(app_id: &str, day: &str, session: Session) -> Result<(), std::io::Error> {
+    let Some(path) = path(app_id) else {
+        return Ok(());
+    };
+    let mut stats = load(app_id);
+    stats.sessions += 1;
+    stats.first_used.get_or_insert(session.started);
+    stats.last_used = Some(session.started);
+    stats.open_secs += session.open_secs;
+    stats.focus_secs += session.focus_secs;
+    stats.last_session_secs = session.open_secs;
+
+    let today = stats.days.entry(day.to_string()).or_default();
+    today.sessions += 1;
+    today.open_secs += session.open_secs;
+    today.focus_secs += session.focus_secs;
+    while stats.days.len() > MAX_DAYS {
+        stats.days.pop_first();
+    }
+
+    let serialized = ron::ser::to_string_pretty(&stats, ron::ser::PrettyConfig::default())
+        .map_err(std::io::Error::other)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("ron.tmp");
+    std::fs::write(&tmp, &serialized)?;
+    match std::fs::rename(&tmp, &path) {
+        // The sandbox mounts the file itself, which can't be replaced, only rewritten
+        Err(e) if e.kind() == std::io::ErrorKind::ResourceBusy => {
+            let _ = std::fs::remove_file(tmp);
+            std::fs::write(path, serialized)
+        }
+        result => result,
+    }
+}
+
+/// Remove the app's statistics, e.g. when the app is deleted.
+pub fn clear(app_id: &str) -> Result<(), std::io::Error> {
+    match path(app_id).map(std::fs::remove_file) {
+        Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,131 @@
+//! Warm renderer pool: a webview process that has already initialized GTK and WebKit
+//! waits in the background, and launches hand their arguments to it instead of paying the
+//! cold start. After taking over one app it starts a fresh warm process.
+//!
+//! The pool is enabled by an autostart entry, so it is running after login too.
+
+use std::{
+    collections::HashMap,
+    io::{BufRead as _, BufReader, Write as _},
+    os::unix::net::UnixStream,
+    path::PathBuf,
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{APP_ID, in_flatpak};
+
+/// Reply of a warm process that took over the launch.
+pub const ACCEPTED: &str = "ok";
+/// Reply of a warm process that can't run this app; it starts cold instead.
+pub const DECLINED: &str = "cold";
+/// Request asking the warm process to exit.
+pub const QUIT: &str = "quit";
+
+/// Variables the launcher sets for this one launch, which the warm process needs to hand
+/// focus to the new window.
+const LAUNCH_ENV: [&str; 2] = ["XDG_ACTIVATION_TOKEN", "DESKTOP_STARTUP_ID"];
+
+/// A launch handed to the warm process: the webview arguments and its launch environment.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Launch {
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+}
+
+impl Launch {
+    /// Token for activating the launch's window, from either startup notification protocol.
+    pub fn startup_id(&self) -> Option<&str> {
+        LAUNCH_ENV
+            .iter()
+            .find_map(|name| self.env.get(*name))
+            .map(String::as_str)
+    }
+}
+
+/// Socket the warm process listens on.
+pub fn socket_path() -> Option<PathBuf> {
+    let runtime = dirs::runtime_dir()?;
+    // Inside Flatpak only the app's own runtime directory is shared between instances
+    let dir = if in_flatpak() {
+        runtime.join("app").join(APP_ID)
+    } else {
+        runtime.join(APP_ID)
+    };
+    Some(dir.join("warm.sock"))
+}
+
+fn autostart_path() -> Option<PathBuf> {
+    crate::autostart::entry_path("warm")
+}
+
+pub fn is_enabled() -> bool {
+    autostart_path().is_some_and(|path| path.exists())
+}
+
+/// Start the pool now and at every login.
+pub fn enable() -> std::io::Result<()> {
+    let path = autostart_path().ok_or_else(|| std::io::Error::other("no config directory"))?;
+    let binary = format!("{APP_ID}.webview");
+    let exec = if in_flatpak() {
+        format!("/usr/bin/flatpak run --command={binary} {APP_ID} --warm")
+    } else {
+        format!("{binary} --warm")
+    };
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(
+        &path,
+        format!(
+            "[Desktop Entry]\nType=Application\nName=Web Apps renderer pool\nExec={exec}\n\
+             NoDisplay=true\nX-GNOME-Autostart-enabled=true\n"
+        ),
+    )?;
+    std::process::Command::new(binary).arg("--warm").spawn()?;
+    Ok(())
+}
+
+/// Remove the autostart entry and stop the running warm process.
+pub fn disable() -> std::io::Result<()> {
+    if let Some(path) = autostart_path() {
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+            _ => {}
+        }
+    }
+    let _ = request(&Launch {
+        args: vec![QUIT.to_string()],
+        ..Launch::default()
+    });
+    Ok(())
+}
+
+/// Send one request line to the warm process and wait for its reply.
+fn request(launch: &Launch) -> std::io::Result<String> {
+    let path = socket_path().ok_or_else(|| std::io::Error::other("no runtime directory"))?;
+    let mut stream = UnixStream::connect(path)?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    let payload = serde_json::to_string(launch).map_err(std::io::Error::other)?;
+    stream.write_all(payload.as_bytes())?;
+    stream.write_all(b"\n")?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply.trim().to_string())
+}
+
+/// Hand the webview arguments to a warm process, along with the launch environment.
+/// Returns `true` if it took over the launch.
+pub fn hand_off(args: &[String]) -> bool {
+    let env = LAUNCH_ENV
+        .iter()
+        .filter_map(|name| Some((name.to_string(), std::env::var(name).ok()?)))
+        .collect();
+    let launch = Launch {
+        args: args.to_vec(),
+        env,
+    };
+    request(&launch).is_ok_and(|reply| reply == ACCEPTED)
+}
@@ -0,0 +1,113 @@
+//! Web process extension for the webview. WebKit loads it into every web process, where it
+//! sees each request before it goes out: subresources, navigations and redirects alike.
+//! The UI process has no such hook, so this is where the Do Not Track and Global Privacy
+//! Control headers are added and the app's referrer policy is enforced.
+//!
+//! The webview passes its settings as the initialization data, a `(bs)` tuple: whether to
+//! send the privacy signals, and the referrer policy, empty to leave it to the page.
+
+use std::rc::Rc;
+
+use glib::{prelude::*, translate::from_glib_none};
+
+#[link(name = "webkit2gtk-4.1")]
+unsafe extern "C" {
+    fn webkit_uri_request_get_http_headers(
+        request: *mut glib::gobject_ffi::GObject,
+    ) -> *mut soup::ffi::SoupMessageHeaders;
+}
+
+struct Settings {
+    do_not_track: bool,
+    referrer_policy: String,
+}
+
+/// The `Referer` a request to `target` may carry under `policy`, or none at all.
+fn allowed_referrer(policy: &str, referrer: &str, target: &str) -> Option<String> {
+    let from = url::Url::parse(referrer).ok();
+    let to = url::Url::parse(target).ok();
+    let same_origin = from
+        .as_ref()
+        .zip(to.as_ref())
+        .is_some_and(|(from, to)| from.origin() == to.origin());
+    match policy {
+        "no-referrer" => None,
+        "same-origin" => same_origin.then(|| referrer.to_string()),
+        "strict-origin-when-cross-origin" if !same_origin => {
+            let (from, to) = (from?, to?);
+            // Nothing leaks from HTTPS to plain HTTP
+            if from.scheme() == "https" && to.scheme() != "https" {
+                return None;
+            }
+            Some(format!("{}/", from.origin().ascii_serialization()))
+        }
+        _ => Some(referrer.to_string()),
+    }
+}
+
+fn send_request(settings: &Settings, request: &glib::Object) {
+    // SAFETY: `request` is the WebKitURIRequest about to be sent, which owns its headers
+    let headers = unsafe { webkit_uri_request_get_http_headers(request.as_ptr()) };
+    if headers.is_null() {
+        return;
+    }
+    // SAFETY: checked above; the borrowed headers get a reference of their own
+    let headers: soup::MessageHeaders = unsafe { from_glib_none(headers) };
+
+    if settings.do_not_track {
+        headers.replace("DNT", "1");
+        headers.replace("Sec-GPC", "1");
+    }
+    let (Some(referrer), Some(target)) = (
+        headers.one("Referer"),
+        request.property::<Option<String>>("uri"),
+    ) else {
+        return;
+    };
+    match allowed_referrer(&settings.referrer_policy, &referrer, &target) {
+        Some(allowed) if allowed != referrer.as_str() => headers.replace("Referer", &allowed),
+        Some(_) => {}
+        None => headers.remove("Referer"),
+    }
+}
+
+/// Entry point WebKit calls once it has loaded the extension into a new web process.
+///
+/// # Safety
+///
+/// `extension` must be the process's WebKitWebExtension and `user_data` a valid variant,
+/// as WebKit passes them.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn webkit_web_extension_initialize_with_user_data(
+    extension: *mut glib::gobject_ffi::GObject,
+    user_data: *const glib::ffi::GVariant,
+) {
+    // SAFETY: both come from WebKit, see above
+    let (extension, user_data): (glib::Object, glib::Variant) = unsafe {
+        (
+            from_glib_none(extension),
+            from_glib_none(user_data.cast_mut()),
+        )
+    };
+    let Some((do_not_track, referrer_policy)) = user_data.get::<(bool, String)>() else {
+        eprintln!("Web extension: unexpected settings {user_data}");
+        return;
+    };
+    let settings = Rc::new(Settings {
+        do_not_track,
+        referrer_policy,
+    });
+
+    extension.connect_local("page-created", false, move |values| {
+        let page = values.get(1)?.get::<glib::Object>().ok()?;
+        let settings = settings.clone();
+        page.connect_local("send-request", false, move |values| {
+            if let Some(request) = values.get(1).and_then(|v| v.get::<glib::Object>().ok()) {
+                send_request(&settings, &request);
+            }
+            // Let the request go ahead
+            Some(false.to_value())
+        });
+        None
+    });
+}